@@ -0,0 +1,295 @@
+use crate::ast::format::{escape, Formatter, PrettyPrint};
+
+use super::{
+    Address, Call, CallArgs, Closure, ClosureParam, Data, Enum, Format, FormatItem, Lambda,
+    LeftCall, Map, Number, Primitive, Printf, PtrAccess, Slice, StrSlice, Struct, Tuple, Union,
+    Variable, Vector,
+};
+
+impl PrettyPrint for Data {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Data::Primitive(value) => value.pretty(f),
+            Data::Slice(value) => value.pretty(f),
+            Data::StrSlice(value) => value.pretty(f),
+            Data::Vec(value) => value.pretty(f),
+            Data::Closure(value) => value.pretty(f),
+            Data::Lambda(value) => value.pretty(f),
+            Data::Tuple(value) => value.pretty(f),
+            Data::Address(value) => value.pretty(f),
+            Data::PtrAccess(value) => value.pretty(f),
+            Data::Variable(value) => value.pretty(f),
+            Data::Call(value) => value.pretty(f),
+            Data::Printf(value) => value.pretty(f),
+            Data::Format(value) => value.pretty(f),
+            Data::Unit => f.write("unit"),
+            Data::Map(value) => value.pretty(f),
+            Data::Struct(value) => value.pretty(f),
+            Data::Union(value) => value.pretty(f),
+            Data::Enum(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for Variable {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&self.name);
+    }
+}
+
+impl PrettyPrint for Primitive {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Primitive::Number(value) => value.pretty(f),
+            Primitive::Bool(true) => f.write("true"),
+            Primitive::Bool(false) => f.write("false"),
+            Primitive::Char(value) => {
+                f.write(&format!("'{}'", escape(&value.to_string(), '\'', false)))
+            }
+        }
+    }
+}
+
+impl PrettyPrint for Number {
+    /*
+     * @desc Print a number with the suffix of its type, a signed number is written as the
+     * unsigned number of the same width the parser converts back
+     */
+    fn pretty(&self, f: &mut Formatter) {
+        let literal = match self {
+            Number::U8(value) => format!("{}u8", value),
+            Number::U16(value) => format!("{}u16", value),
+            Number::U32(value) => format!("{}u32", value),
+            Number::U64(value) => format!("{}u64", value),
+            Number::U128(value) => format!("{}u128", value),
+            Number::I8(value) => format!("{}i8", *value as u8),
+            Number::I16(value) => format!("{}i16", *value as u16),
+            Number::I32(value) => format!("{}i32", *value as u32),
+            Number::I64(value) => format!("{}i64", *value as u64),
+            Number::I128(value) => format!("{}i128", *value as u128),
+            Number::F64(value) => format!("{:?}", value),
+            Number::Unresolved(value) => format!("{}", *value as u64),
+        };
+        f.write(&literal);
+    }
+}
+
+impl PrettyPrint for Slice {
+    fn pretty(&self, f: &mut Formatter) {
+        f.list("[", &self.value, "]", true, |f, item| item.pretty(f));
+    }
+}
+
+impl PrettyPrint for StrSlice {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&format!("\"{}\"", escape(&self.value, '"', false)));
+    }
+}
+
+impl PrettyPrint for Vector {
+    fn pretty(&self, f: &mut Formatter) {
+        f.list("vec[", &self.value, "]", true, |f, item| item.pretty(f));
+    }
+}
+
+impl PrettyPrint for Tuple {
+    fn pretty(&self, f: &mut Formatter) {
+        // a single item needs its comma to be told apart from a parenthesized expression
+        if let [item] = self.value.as_slice() {
+            f.write("(");
+            item.pretty(f);
+            f.write(",)");
+            return;
+        }
+        f.list("(", &self.value, ")", true, |f, item| item.pretty(f));
+    }
+}
+
+impl PrettyPrint for ClosureParam {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            ClosureParam::Full(var) => var.pretty(f),
+            ClosureParam::Minimal(name) => f.write(name),
+        }
+    }
+}
+
+impl PrettyPrint for Closure {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("move ");
+        f.list("(", &self.params, ")", false, |f, param| param.pretty(f));
+        f.write(" -> ");
+        self.block.pretty(f);
+    }
+}
+
+impl PrettyPrint for Lambda {
+    fn pretty(&self, f: &mut Formatter) {
+        f.list("(", &self.params, ")", false, |f, param| param.pretty(f));
+        f.write(" -> ");
+        self.block.pretty(f);
+    }
+}
+
+impl PrettyPrint for Address {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("&");
+        self.value.pretty(f);
+    }
+}
+
+impl PrettyPrint for PtrAccess {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("*");
+        self.value.pretty(f);
+    }
+}
+
+impl PrettyPrint for Struct {
+    fn pretty(&self, f: &mut Formatter) {
+        self.path.pretty(f);
+        f.write(&self.id);
+        f.list(" { ", &self.fields, " }", true, |f, (name, value)| {
+            f.write(name);
+            f.write(" : ");
+            value.pretty(f);
+        });
+    }
+}
+
+impl PrettyPrint for Union {
+    fn pretty(&self, f: &mut Formatter) {
+        self.path.pretty(f);
+        f.write(&self.typename);
+        f.write("::");
+        f.write(&self.variant);
+        f.list(" { ", &self.fields, " }", true, |f, (name, value)| {
+            f.write(name);
+            f.write(" : ");
+            value.pretty(f);
+        });
+    }
+}
+
+impl PrettyPrint for Enum {
+    fn pretty(&self, f: &mut Formatter) {
+        self.path.pretty(f);
+        f.write(&self.typename);
+        f.write("::");
+        f.write(&self.name);
+    }
+}
+
+impl PrettyPrint for Map {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("map");
+        f.list(" { ", &self.fields, " }", true, |f, (key, value)| {
+            key.pretty(f);
+            f.write(" : ");
+            value.pretty(f);
+        });
+    }
+}
+
+impl PrettyPrint for CallArgs {
+    fn pretty(&self, f: &mut Formatter) {
+        f.list("(", &self.args, ")", false, |f, arg| arg.pretty(f));
+    }
+}
+
+impl PrettyPrint for Call {
+    fn pretty(&self, f: &mut Formatter) {
+        match &self.path {
+            LeftCall::VarCall(call) => call.path.pretty(f),
+            LeftCall::ExternCall(call) => call.path.pretty(f),
+            // the core and dynamic calls are rewritten from variable calls and method calls
+            // during the resolution, only parsed trees are printed
+            LeftCall::CoreCall(_) | LeftCall::DynamicCall(_) => {
+                unreachable!("a resolved call is never printed back to source")
+            }
+        }
+        self.args.pretty(f);
+    }
+}
+
+/*
+ * @desc Print the string of a printf or a format, the expressions are written between braces
+ */
+fn pretty_fstring(f: &mut Formatter, items: &[FormatItem]) {
+    f.write("\"");
+    for item in items {
+        match item {
+            FormatItem::Str(value) => f.write(&escape(value, '"', true)),
+            FormatItem::Expr(value) => {
+                f.write("{");
+                value.pretty(f);
+                f.write("}");
+            }
+        }
+    }
+    f.write("\"");
+}
+
+impl PrettyPrint for Printf {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("printf(");
+        pretty_fstring(f, &self.args);
+        f.write(")");
+    }
+}
+
+impl PrettyPrint for Format {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("format(");
+        pretty_fstring(f, &self.args);
+        f.write(")");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{expressions::Expression, TryParse};
+
+    use super::*;
+
+    fn round_trip(source: &str) -> String {
+        let expr = Expression::parse(source.into())
+            .expect("Parsing should have succeeded")
+            .1;
+        let printed = expr.to_source();
+        let reparsed = Expression::parse(printed.as_str().into())
+            .expect("Parsing should have succeeded")
+            .1;
+        assert_eq!(expr, reparsed);
+        printed
+    }
+
+    #[test]
+    fn valid_format_data() {
+        assert_eq!(round_trip("255i8"), "255i8");
+        assert_eq!(round_trip("'\\n'"), "'\\n'");
+        assert_eq!(round_trip("\"a\\\"b\""), "\"a\\\"b\"");
+        assert_eq!(round_trip("( 1 , )"), "(1,)");
+        assert_eq!(round_trip("vec[1,2,3]"), "vec[1, 2, 3]");
+        assert_eq!(round_trip("map{\"a\":1u8}"), "map { \"a\" : 1u8 }");
+        assert_eq!(round_trip("Point{x:1,y:2}"), "Point { x : 1, y : 2 }");
+        assert_eq!(round_trip("Color::Red"), "Color::Red");
+        assert_eq!(
+            round_trip("Shape::Circle{radius:5}"),
+            "Shape::Circle { radius : 5 }"
+        );
+        assert_eq!(
+            round_trip("move (u,v:u64)->u+v"),
+            "move (u, v : u64) -> u + v"
+        );
+        assert_eq!(round_trip("&x"), "&x");
+    }
+
+    #[test]
+    fn valid_format_long_list() {
+        let items: Vec<String> = (0..30).map(|i| format!("{}", 1000 + i)).collect();
+        let printed = round_trip(&format!("vec[{}]", items.join(",")));
+        assert!(printed.starts_with("vec[\n    1000,\n"), "{}", printed);
+        assert!(printed.ends_with("    1029,\n]"), "{}", printed);
+    }
+}
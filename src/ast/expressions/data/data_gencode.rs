@@ -27,9 +27,9 @@ use crate::{
 };
 
 use super::{
-    Address, Call, Closure, ClosureReprData, CoreCall, Data, Enum, ExternCall, Format, Lambda, Map,
-    Number, Primitive, Printf, PtrAccess, Slice, StrSlice, Struct, Tuple, Union, VarCall, Variable,
-    Vector,
+    Address, Call, Closure, ClosureReprData, CoreCall, Data, DynamicCall, Enum, ExternCall, Format,
+    Lambda, Map, Number, Primitive, Printf, PtrAccess, Slice, StrSlice, Struct, Tuple, Union,
+    VarCall, Variable, Vector,
 };
 
 impl GenerateCode for Data {
@@ -456,8 +456,10 @@ impl GenerateCode for Closure {
             label: Some(store_label),
         }));
         instructions.push_label_by_id(repr_data.closure_label, "closure".to_string());
-        self.block
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        instructions.in_function(repr_data.closure_label, |instructions| {
+            self.block
+                .gencode::<E>(scope_manager, scope_id, instructions, context)
+        })?;
 
         instructions.push_label_by_id(store_label, "store_closure".to_string());
 
@@ -481,8 +483,10 @@ impl GenerateCode for Lambda {
             label: Some(store_label),
         }));
         instructions.push_label_by_id(lambda_label, "lambda".to_string());
-        self.block
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        instructions.in_function(lambda_label, |instructions| {
+            self.block
+                .gencode::<E>(scope_manager, scope_id, instructions, context)
+        })?;
 
         instructions.push_label_by_id(store_label, "store_lambda".to_string());
 
@@ -738,6 +742,10 @@ impl GenerateCode for Call {
                 let Some(param_size) = self.args.size else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
+                if let Some(instance) = scope_manager.take_pending_fn_instance_by_id(*id) {
+                    // instance of a generic function resolved after the generation of its definition
+                    instance.gencode::<E>(scope_manager, None, instructions, context)?;
+                }
                 let Ok(crate::semantic::scope::scope::VariableInfo { address, .. }) =
                     scope_manager.find_var_by_id(*id)
                 else {
@@ -793,6 +801,20 @@ impl GenerateCode for Call {
                 }
                 path.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
+            super::LeftCall::DynamicCall(DynamicCall { method, .. }) => {
+                let Some(param_size) = self.args.size else {
+                    return Err(CodeGenerationError::UnresolvedError);
+                };
+                // the interface value pushes the address of the data followed by the vtable index
+                for arg in self.args.args.iter() {
+                    arg.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                }
+                instructions.push(Asm::Call(crate::vm::asm::branch::Call::Dynamic {
+                    method: *method,
+                    param_size,
+                }));
+                Ok(())
+            }
         }
     }
 }
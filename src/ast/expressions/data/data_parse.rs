@@ -501,7 +501,11 @@ impl TryParse for CallArgs {
                 separated_list0(wst(lexem::COMA), Expression::parse),
                 wst(lexem::PAR_C),
             ),
-            |args| CallArgs { args, size: None },
+            |args| CallArgs {
+                args,
+                size: None,
+                resolved_receiver: false,
+            },
         )(input)
     }
 }
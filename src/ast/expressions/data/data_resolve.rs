@@ -5,20 +5,24 @@ use super::{
     LeftCall, Map, Number, Primitive, Printf, PtrAccess, Slice, StrSlice, Struct, Tuple, Union,
     Variable, Vector,
 };
-use crate::ast::expressions::data::{CoreCall, ExternCall, VarCall};
+use crate::ast::expressions::data::{CoreCall, DynamicCall, ExternCall, VarCall};
 use crate::ast::expressions::{Atomic, CompletePath, Expression, Path};
 use crate::ast::statements::block::BlockCommonApi;
-use crate::semantic::scope::scope::{ScopeState, VariableInfo};
+use crate::ast::statements::definition::{FnDef, TypeDef};
+use crate::semantic::scope::scope::{MethodInfo, ScopeState, VariableInfo};
 use crate::semantic::scope::static_types::{
     AddrType, ClosureType, FunctionType, LambdaType, MapType, NumberType, PrimitiveType, SliceType,
     StrSliceType, StringType, TupleType, VecType, POINTER_SIZE,
 };
 use crate::semantic::scope::user_types::UserType;
+use crate::semantic::symbols::Symbol;
 use crate::semantic::ResolveCore;
 use crate::semantic::{
     scope::static_types::StaticType, CompatibleWith, EType, Resolve, SemanticError, TypeOf,
 };
-use crate::semantic::{Desugar, Info, ResolveFromStruct, ResolveNumber, SizeOf};
+use crate::semantic::{
+    ConstEval, Desugar, Info, NamedType, ResolveFromStruct, ResolveNumber, SizeOf,
+};
 use crate::vm::allocator::align;
 use crate::vm::asm::branch::Label;
 use crate::vm::core::{Core, PathFinder};
@@ -48,12 +52,46 @@ impl Resolve for Data {
             Data::Tuple(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::Address(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::PtrAccess(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
-            Data::Variable(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            Data::Variable(value) => {
+                // variables shadow the constants of the same name
+                if scope_manager
+                    .find_var_by_name(&value.name, None, scope_id)
+                    .is_err()
+                {
+                    if let Some(constant) =
+                        scope_manager.find_constant_by_name(None, &value.name, scope_id)
+                    {
+                        *self = constant;
+                        return self.resolve::<E>(scope_manager, scope_id, context, extra);
+                    }
+                }
+                value.resolve::<E>(scope_manager, scope_id, context, extra)
+            }
             Data::Unit => Ok(()),
             Data::Map(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::Struct(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::Union(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
-            Data::Enum(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
+            Data::Enum(value) => {
+                // Module::CONSTANT is parsed as an enum value
+                let type_path = match &value.path {
+                    Path::Segment(vec) => Some(vec.as_slice()),
+                    Path::Empty => None,
+                };
+                if scope_manager
+                    .find_type_by_name(type_path, &value.typename, scope_id)
+                    .is_err()
+                {
+                    let mut path = type_path.map(<[String]>::to_vec).unwrap_or_default();
+                    path.push(value.typename.clone());
+                    if let Some(constant) =
+                        scope_manager.find_constant_by_name(Some(&path), &value.name, scope_id)
+                    {
+                        *self = constant;
+                        return self.resolve::<E>(scope_manager, scope_id, context, extra);
+                    }
+                }
+                value.resolve::<E>(scope_manager, scope_id, context, &mut ())
+            }
             Data::StrSlice(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::Call(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
             Data::Printf(value) => value.resolve::<E>(scope_manager, scope_id, context, &mut ()),
@@ -62,6 +100,37 @@ impl Resolve for Data {
     }
 }
 
+impl ConstEval for Data {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        Ok(match self {
+            Data::Primitive(Primitive::Number(Number::Unresolved(_))) => None,
+            Data::Primitive(_) | Data::Unit => Some(self.clone()),
+            Data::StrSlice(StrSlice {
+                value, metadata, ..
+            }) => Some(Data::StrSlice(StrSlice {
+                value: value.clone(),
+                address: None,
+                metadata: metadata.clone(),
+            })),
+            Data::Tuple(Tuple { value, metadata }) => {
+                let mut items = Vec::with_capacity(value.len());
+                for item in value.iter() {
+                    let Some(item) = item.const_eval()? else {
+                        return Ok(None);
+                    };
+                    items.push(Expression::Atomic(Atomic::Data(item)));
+                }
+                Some(Data::Tuple(Tuple {
+                    value: items,
+                    metadata: metadata.clone(),
+                }))
+            }
+            Data::Enum(Enum { value: Some(_), .. }) => Some(self.clone()),
+            _ => None,
+        })
+    }
+}
+
 impl ResolveNumber for Data {
     fn is_unresolved_number(&self) -> bool {
         match self {
@@ -93,7 +162,7 @@ impl ResolveFromStruct for Data {
                 value.resolve_from_struct::<E>(scope_manager, scope_id, struct_id)
             }
             Data::Call(value) => value.resolve_from_struct::<E>(scope_manager, scope_id, struct_id),
-            _ => Err(SemanticError::UnknownField),
+            _ => Err(SemanticError::ExpectedFieldName),
         }
     }
 }
@@ -141,6 +210,8 @@ impl Resolve for Variable {
         Self: Sized,
     {
         let var = scope_manager.find_var_by_name(&self.name, None, scope_id)?;
+        scope_manager.signal_variable_use(var.id);
+        scope_manager.signal_reference(self.metadata.span, Symbol::Variable(var.id));
 
         if let Some(scope_id) = scope_id {
             scope_manager.signal_variable_access(&var, scope_id);
@@ -194,7 +265,7 @@ impl ResolveFromStruct for Variable {
             offset += align(field_type.size_of())
         }
         if !found {
-            return Err(SemanticError::UnknownField);
+            return Err(SemanticError::UnknownField(self.name.clone()));
         }
         Ok(())
     }
@@ -226,76 +297,49 @@ impl ResolveNumber for Primitive {
         let Primitive::Number(Number::Unresolved(value)) = *self else {
             return Ok(());
         };
+        // the literal does not fit in the expected number type
+        let mismatch = || SemanticError::MismatchedTypes {
+            expected: NamedType {
+                etype: EType::Static(StaticType::Primitive(PrimitiveType::Number(to))),
+                name: format!("{:?}", to).to_lowercase(),
+            },
+            found: NamedType {
+                etype: EType::Static(StaticType::Primitive(PrimitiveType::Number(
+                    NumberType::I64,
+                ))),
+                name: value.to_string(),
+            },
+        };
         match to {
             NumberType::U8 => {
-                *self = Primitive::Number(Number::U8(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::U8((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::U16 => {
-                *self = Primitive::Number(Number::U16(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::U16((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::U32 => {
-                *self = Primitive::Number(Number::U32(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::U32((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::U64 => {
-                *self = Primitive::Number(Number::U64(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::U64((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::U128 => {
-                *self = Primitive::Number(Number::U128(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::U128((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::I8 => {
-                *self = Primitive::Number(Number::I8(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::I8((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::I16 => {
-                *self = Primitive::Number(Number::I16(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::I16((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::I32 => {
-                *self = Primitive::Number(Number::I32(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::I32((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::I64 => {
-                *self = Primitive::Number(Number::I64(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::I64((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::I128 => {
-                *self = Primitive::Number(Number::I128(
-                    (value)
-                        .try_into()
-                        .map_err(|_| SemanticError::IncompatibleTypes)?,
-                ))
+                *self = Primitive::Number(Number::I128((value).try_into().map_err(|_| mismatch())?))
             }
             NumberType::F64 => *self = Primitive::Number(Number::F64(value as f64)),
         }
@@ -325,11 +369,19 @@ impl Resolve for Primitive {
             if let PrimitiveType::Number(number_type) = primitive_type {
                 self.resolve_number(*number_type)?;
             }
-            if self.type_of(scope_manager, scope_id)? != *context {
-                return Err(SemanticError::IncompatibleTypes);
+            let found = self.type_of(scope_manager, scope_id)?;
+            if found != *context {
+                return Err(SemanticError::MismatchedTypes {
+                    expected: NamedType::new(context, scope_manager, scope_id),
+                    found: NamedType::new(&found, scope_manager, scope_id),
+                });
             }
-        } else {
-            return Err(SemanticError::IncompatibleTypes);
+        } else if let Some(context) = context {
+            let found = self.type_of(scope_manager, scope_id)?;
+            return Err(SemanticError::MismatchedTypes {
+                expected: NamedType::new(context, scope_manager, scope_id),
+                found: NamedType::new(&found, scope_manager, scope_id),
+            });
         }
         Ok(())
     }
@@ -354,7 +406,17 @@ impl Resolve for Slice {
                     let item_type = item_type.as_ref().clone();
                     let sitem_type = &Some(item_type.clone());
                     if self.value.len() > *size {
-                        return Err(SemanticError::IncompatibleTypes);
+                        let found = e_static!(StaticType::Slice(SliceType {
+                            size: self.value.len(),
+                            item_type: Box::new(item_type.clone()),
+                        }));
+                        return Err(SemanticError::mismatched(
+                            rcontext,
+                            &found,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(self.metadata.span));
                     }
                     for value in &mut self.value {
                         let _ =
@@ -379,7 +441,13 @@ impl Resolve for Slice {
                     };
                     Ok(())
                 }
-                _ => Err(SemanticError::IncompatibleTypes),
+                _ => Err(SemanticError::unexpected_kind(
+                    rcontext,
+                    "an array",
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.metadata.span)),
             },
             None => {
                 if self.value.is_empty() {
@@ -453,7 +521,13 @@ impl Resolve for StrSlice {
     {
         if let Some(context) = context {
             if EType::Static(StaticType::StrSlice(StrSliceType {})) != *context {
-                return Err(SemanticError::IncompatibleTypes);
+                return Err(SemanticError::mismatched(
+                    context,
+                    &EType::Static(StaticType::StrSlice(StrSliceType {})),
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.metadata.span));
             }
         }
         self.address = scope_manager.register_data(POINTER_SIZE + self.value.len(), scope_id)?;
@@ -503,7 +577,13 @@ impl Resolve for Vector {
 
                     Ok(())
                 }
-                _ => Err(SemanticError::IncompatibleTypes),
+                _ => Err(SemanticError::unexpected_kind(
+                    rcontext,
+                    "a vector",
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.metadata.span)),
             },
             None => {
                 if self.value.is_empty() {
@@ -572,7 +652,13 @@ impl Resolve for Tuple {
             Some(rcontext) => match rcontext {
                 EType::Static(StaticType::Tuple(TupleType(values_type))) => {
                     if values_type.len() != self.value.len() {
-                        return Err(SemanticError::IncompatibleTypes);
+                        return Err(SemanticError::unexpected_kind(
+                            rcontext,
+                            &format!("a tuple of {} values", self.value.len()),
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(self.metadata.span));
                     }
                     for (value, value_type) in self.value.iter_mut().zip(values_type) {
                         let _ = value.resolve::<E>(
@@ -597,7 +683,13 @@ impl Resolve for Tuple {
 
                     Ok(())
                 }
-                _ => Err(SemanticError::IncompatibleTypes),
+                _ => Err(SemanticError::unexpected_kind(
+                    rcontext,
+                    "a tuple",
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.metadata.span)),
             },
             None => {
                 let mut values_type = Vec::new();
@@ -654,7 +746,13 @@ impl Resolve for Closure {
             Some(context) => {
                 let EType::Static(StaticType::Closure(ClosureType { params, ret })) = context
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::unexpected_kind(
+                        context,
+                        "a closure",
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.metadata.span));
                 };
 
                 // Register parameters, add as first parameter the function pointer
@@ -797,7 +895,13 @@ impl Resolve for Lambda {
         match context {
             Some(context) => {
                 let EType::Static(StaticType::Lambda(LambdaType { params, ret })) = context else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::unexpected_kind(
+                        context,
+                        "a lambda",
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.metadata.span));
                 };
                 // Register parameters, add as first parameter the function pointer
                 if let Some(name) = &self.name {
@@ -881,9 +985,10 @@ impl Resolve for Address {
     {
         match self.value.as_ref() {
             Atomic::Data(_) => {}
-            Atomic::UnaryOperation(_) => return Err(SemanticError::IncompatibleTypes),
+            Atomic::UnaryOperation(_) | Atomic::ExprFlow(_) => {
+                return Err(SemanticError::ExpectedLeftExpression.at(self.metadata.span))
+            }
             Atomic::Paren(_) => {}
-            Atomic::ExprFlow(_) => return Err(SemanticError::IncompatibleTypes),
         }
         let _ = self
             .value
@@ -986,16 +1091,34 @@ impl Resolve for Struct {
     where
         Self: Sized,
     {
-        let UserType::Struct(semantic::scope::user_types::Struct { fields, .. }) = scope_manager
-            .find_type_by_name(
-                match &self.path {
-                    crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
-                    crate::ast::expressions::Path::Empty => None,
-                },
-                &self.id,
+        let mut resolved_fields = Vec::new();
+        if self.path == Path::Empty
+            && scope_manager.find_generic_type(&self.id).is_some()
+            && scope_manager
+                .find_type_by_name(None, &self.id, scope_id)
+                .is_err()
+        {
+            // literal of a generic struct : refer to the instance inferred from the fields
+            (self.id, resolved_fields) = TypeDef::instantiate_from_fields::<E>(
+                scope_manager,
                 scope_id,
-            )?
-            .def
+                &self.id,
+                None,
+                &mut self.fields,
+                context,
+            )?;
+        }
+        let user_type = scope_manager.find_type_by_name(
+            match &self.path {
+                crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
+                crate::ast::expressions::Path::Empty => None,
+            },
+            &self.id,
+            scope_id,
+        )?;
+        scope_manager.signal_type_use(user_type.id);
+        scope_manager.signal_reference(self.metadata.span, Symbol::Type(user_type.id));
+        let UserType::Struct(semantic::scope::user_types::Struct { fields, .. }) = user_type.def
         else {
             return Err(SemanticError::ExpectedStruct);
         };
@@ -1006,20 +1129,30 @@ impl Resolve for Struct {
 
         for (ref field_name, expr) in &mut self.fields {
             let Some((_, field_type)) = fields.iter().find(|(n, _)| *n == *field_name) else {
-                return Err(SemanticError::UnknownField);
+                return Err(SemanticError::UnknownField(field_name.clone()));
             };
 
-            let _ = expr.resolve::<E>(
-                scope_manager,
-                scope_id,
-                &Some(field_type.clone()),
-                &mut None,
-            )?;
-            let _ = field_type.compatible_with(
-                &expr.type_of(scope_manager, scope_id)?,
-                &scope_manager,
-                scope_id,
-            )?;
+            let res = if resolved_fields.contains(field_name) {
+                Ok(())
+            } else {
+                expr.resolve::<E>(
+                    scope_manager,
+                    scope_id,
+                    &Some(field_type.clone()),
+                    &mut None,
+                )
+            }
+            .and_then(|_| expr.type_of(scope_manager, scope_id))
+                .and_then(|expr_type| {
+                    field_type.compatible_with(&expr_type, &scope_manager, scope_id)
+                });
+            if let Err(err) = res {
+                return Err(SemanticError::InvalidField {
+                    field: field_name.clone(),
+                    error: Box::new(err.into_inner()),
+                }
+                .at(expr.span()));
+            }
         }
 
         self.metadata.info = crate::semantic::Info::Resolved {
@@ -1099,16 +1232,34 @@ impl Resolve for Union {
     where
         Self: Sized,
     {
-        let UserType::Union(semantic::scope::user_types::Union { variants, .. }) = scope_manager
-            .find_type_by_name(
-                match &self.path {
-                    crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
-                    crate::ast::expressions::Path::Empty => None,
-                },
-                &self.typename,
+        let mut resolved_fields = Vec::new();
+        if self.path == Path::Empty
+            && scope_manager.find_generic_type(&self.typename).is_some()
+            && scope_manager
+                .find_type_by_name(None, &self.typename, scope_id)
+                .is_err()
+        {
+            // literal of a generic union : refer to the instance inferred from the fields
+            (self.typename, resolved_fields) = TypeDef::instantiate_from_fields::<E>(
+                scope_manager,
                 scope_id,
-            )?
-            .def
+                &self.typename,
+                Some(&self.variant),
+                &mut self.fields,
+                context,
+            )?;
+        }
+        let user_type = scope_manager.find_type_by_name(
+            match &self.path {
+                crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
+                crate::ast::expressions::Path::Empty => None,
+            },
+            &self.typename,
+            scope_id,
+        )?;
+        scope_manager.signal_type_use(user_type.id);
+        scope_manager.signal_reference(self.metadata.span, Symbol::Type(user_type.id));
+        let UserType::Union(semantic::scope::user_types::Union { variants, .. }) = user_type.def
         else {
             return Err(SemanticError::ExpectedStruct);
         };
@@ -1116,7 +1267,7 @@ impl Resolve for Union {
         let Some((_, semantic::scope::user_types::Struct { fields, .. })) =
             variants.iter().find(|(n, _)| *n == self.variant)
         else {
-            return Err(SemanticError::UnknownField);
+            return Err(SemanticError::UnknownField(self.variant.clone()));
         };
 
         if fields.len() != self.fields.len() {
@@ -1125,20 +1276,30 @@ impl Resolve for Union {
 
         for (ref field_name, expr) in &mut self.fields {
             let Some((_, field_type)) = fields.iter().find(|(n, _)| *n == *field_name) else {
-                return Err(SemanticError::UnknownField);
+                return Err(SemanticError::UnknownField(field_name.clone()));
             };
 
-            let _ = expr.resolve::<E>(
-                scope_manager,
-                scope_id,
-                &Some(field_type.clone()),
-                &mut None,
-            )?;
-            let _ = field_type.compatible_with(
-                &expr.type_of(scope_manager, scope_id)?,
-                &scope_manager,
-                scope_id,
-            )?;
+            let res = if resolved_fields.contains(field_name) {
+                Ok(())
+            } else {
+                expr.resolve::<E>(
+                    scope_manager,
+                    scope_id,
+                    &Some(field_type.clone()),
+                    &mut None,
+                )
+            }
+            .and_then(|_| expr.type_of(scope_manager, scope_id))
+                .and_then(|expr_type| {
+                    field_type.compatible_with(&expr_type, &scope_manager, scope_id)
+                });
+            if let Err(err) = res {
+                return Err(SemanticError::InvalidField {
+                    field: field_name.clone(),
+                    error: Box::new(err.into_inner()),
+                }
+                .at(expr.span()));
+            }
         }
 
         self.metadata.info = crate::semantic::Info::Resolved {
@@ -1178,21 +1339,21 @@ impl Resolve for Enum {
     where
         Self: Sized,
     {
-        let UserType::Enum(semantic::scope::user_types::Enum { values, .. }) = scope_manager
-            .find_type_by_name(
-                match &self.path {
-                    crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
-                    crate::ast::expressions::Path::Empty => None,
-                },
-                &self.typename,
-                scope_id,
-            )?
-            .def
-        else {
+        let user_type = scope_manager.find_type_by_name(
+            match &self.path {
+                crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
+                crate::ast::expressions::Path::Empty => None,
+            },
+            &self.typename,
+            scope_id,
+        )?;
+        scope_manager.signal_type_use(user_type.id);
+        scope_manager.signal_reference(self.metadata.span, Symbol::Type(user_type.id));
+        let UserType::Enum(semantic::scope::user_types::Enum { values, .. }) = user_type.def else {
             return Err(SemanticError::ExpectedStruct);
         };
         let Some((_, value)) = values.iter().find(|(n, _)| **n == *self.name) else {
-            return Err(SemanticError::UnknownField);
+            return Err(SemanticError::UnknownField(self.name.clone()));
         };
         let _ = self.value.insert(*value);
 
@@ -1256,7 +1417,12 @@ impl Resolve for Map {
                     }
                     Ok(())
                 }
-                _ => Err(SemanticError::IncompatibleTypes),
+                _ => {
+                    Err(
+                        SemanticError::unexpected_kind(rcontext, "a map", scope_manager, scope_id)
+                            .at(self.metadata.span),
+                    )
+                }
             },
             None => {
                 let mut current_key_type: Option<EType> = None;
@@ -1346,15 +1512,53 @@ impl Resolve for Call {
                 id,
                 is_closure,
             }) => {
+                if let Path::Segment(vec) = path {
+                    if let (Err(_), [type_name]) = (
+                        scope_manager.find_var_by_name(name, Some(vec.as_slice()), scope_id),
+                        vec.as_slice(),
+                    ) {
+                        // call to a method through its type : Type::method(args)
+                        if let Ok(user_type) =
+                            scope_manager.find_type_by_name(None, type_name, scope_id)
+                        {
+                            scope_manager.signal_type_use(user_type.id);
+                            let Some(MethodInfo { function, .. }) =
+                                scope_manager.find_method(user_type.id, name).cloned()
+                            else {
+                                return Err(SemanticError::UnknownMethod(name.clone()));
+                            };
+                            *name = function;
+                            *path = Path::Empty;
+                        }
+                    }
+                }
+                let mut are_args_resolved = false;
                 let function = match path {
                     Path::Segment(vec) => {
                         scope_manager.find_var_by_name(name, Some(vec.as_slice()), scope_id)?
                     }
-                    Path::Empty => {
-                        let function = scope_manager.find_var_by_name(name, None, scope_id)?;
-                        function
-                    }
+                    Path::Empty => match scope_manager.find_var_by_name(name, None, scope_id) {
+                        Ok(function) => function,
+                        Err(err) => {
+                            if scope_manager.find_generic_fn(name).is_none() {
+                                return Err(err);
+                            }
+                            // call to a generic function : the arguments are resolved during the inference of the type parameters
+                            *name = FnDef::instantiate_from_call::<E>(
+                                scope_manager,
+                                scope_id,
+                                name,
+                                &mut self.args.args,
+                                context,
+                            )?;
+                            are_args_resolved = true;
+                            scope_manager.find_var_by_name(name, None, None)?
+                        }
+                    },
                 };
+                scope_manager.signal_variable_use(function.id);
+                // the callee has no location of its own, the call refers to it
+                scope_manager.signal_reference(self.metadata.span, Symbol::Variable(function.id));
                 if let Some(scope_id) = scope_id {
                     scope_manager.signal_variable_access(&function, scope_id);
                 }
@@ -1376,19 +1580,47 @@ impl Resolve for Call {
                 };
 
                 if params.len() != self.args.args.len() {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: params.len(),
+                        found: self.args.args.len(),
+                    });
                 }
 
+                let call_span = self.metadata.span;
                 let mut params_size = 0;
-                for (arg, param) in self.args.args.iter_mut().zip(params) {
-                    let _ =
-                        arg.resolve::<E>(scope_manager, scope_id, &Some(param.clone()), &mut None)?;
+                let resolved_receiver = self.args.resolved_receiver;
+                for (position, (arg, param)) in self.args.args.iter_mut().zip(params).enumerate() {
+                    let res = if are_args_resolved || (resolved_receiver && position == 0) {
+                        arg.type_of(scope_manager, scope_id).and_then(|arg_type| {
+                            param.compatible_with(&arg_type, &scope_manager, scope_id)
+                        })
+                    } else {
+                        arg.resolve::<E>(scope_manager, scope_id, &Some(param.clone()), &mut None)
+                            .and_then(|_| arg.type_of(scope_manager, scope_id))
+                            .and_then(|arg_type| {
+                                param.compatible_with(&arg_type, &scope_manager, scope_id)
+                            })
+                    };
+                    if let Err(err) = res {
+                        return Err(SemanticError::InvalidArgument {
+                            position: position + 1,
+                            error: Box::new(err.into_inner()),
+                        }
+                        .at(arg.span())
+                        .with_note(call_span, "in this call"));
+                    }
                     params_size += param.size_of();
                 }
                 let _ = self.args.size.insert(params_size);
 
                 if context.is_some() && *context.as_ref().unwrap() != **return_type {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched(
+                        context.as_ref().unwrap(),
+                        return_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.metadata.span));
                 }
                 self.metadata.info = Info::Resolved {
                     context: context.clone(),
@@ -1424,6 +1656,7 @@ impl Resolve for Call {
                     scope_id,
                     context.as_ref(),
                     &mut self.args.args,
+                    self.args.resolved_receiver,
                 )?;
 
                 self.metadata.info = Info::Resolved {
@@ -1433,6 +1666,63 @@ impl Resolve for Call {
 
                 Ok(())
             }
+            LeftCall::DynamicCall(DynamicCall {
+                interface_id,
+                method,
+            }) => {
+                let Ok(UserType::Interface(interface)) =
+                    scope_manager.find_type_by_id(*interface_id, scope_id)
+                else {
+                    return Err(SemanticError::ExpectedInterface);
+                };
+                let Some((_, FunctionType { params, ret })) = interface.methods.get(*method) else {
+                    return Err(SemanticError::UnknownMethod(method.to_string()));
+                };
+
+                if params.len() + 1 != self.args.args.len() {
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: params.len(),
+                        found: self.args.args.len() - 1,
+                    });
+                }
+
+                let call_span = self.metadata.span;
+                let mut args = self.args.args.iter_mut();
+                if let Some(receiver) = args.next() {
+                    if !self.args.resolved_receiver {
+                        let _ = receiver.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                    }
+                }
+                // the function implementing the method receives the address of the data
+                let mut params_size = POINTER_SIZE;
+                for (position, (arg, param)) in args.zip(params).enumerate() {
+                    let res = arg
+                        .resolve::<E>(scope_manager, scope_id, &Some(param.clone()), &mut None)
+                        .and_then(|_| arg.type_of(scope_manager, scope_id))
+                        .and_then(|arg_type| {
+                            param.compatible_with(&arg_type, &scope_manager, scope_id)
+                        });
+                    if let Err(err) = res {
+                        return Err(SemanticError::InvalidArgument {
+                            position: position + 1,
+                            error: Box::new(err.into_inner()),
+                        }
+                        .at(arg.span())
+                        .with_note(call_span, "in this call"));
+                    }
+                    params_size += param.size_of();
+                }
+                let _ = self.args.size.insert(params_size);
+
+                if let Some(context) = context {
+                    let _ = context.compatible_with(ret.as_ref(), &scope_manager, scope_id)?;
+                }
+                self.metadata.info = Info::Resolved {
+                    context: context.clone(),
+                    signature: Some(ret.as_ref().clone()),
+                };
+                Ok(())
+            }
         }
     }
 }
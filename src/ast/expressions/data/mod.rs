@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap};
 
 use ulid::Ulid;
 
 use crate::ast::statements::block::{ClosureBlock, LambdaBlock};
 use crate::semantic::SizeOf;
 use crate::vm::allocator::MemoryAddress;
+use crate::vm::asm::math_operation::MathOperator;
 use crate::vm::core::Core;
 use crate::{
     ast::{statements::declaration::TypedVar, utils::strings::ID},
@@ -17,6 +18,7 @@ use crate::{
 
 use super::{Atomic, CompletePath, Expression, Path};
 
+pub mod data_format;
 pub mod data_gencode;
 pub mod data_parse;
 pub mod data_resolve;
@@ -191,6 +193,8 @@ pub struct Map {
 pub struct CallArgs {
     pub args: Vec<Expression>,
     pub size: Option<usize>,
+    // the first argument is the receiver of a method call, resolved when the method was found
+    pub resolved_receiver: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -223,6 +227,7 @@ pub enum LeftCall {
     VarCall(VarCall),
     ExternCall(ExternCall),
     CoreCall(CoreCall),
+    DynamicCall(DynamicCall),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -242,6 +247,13 @@ pub struct CoreCall {
     pub path: Core,
 }
 
+// call to a method of an interface, the first argument is the interface value
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicCall {
+    pub interface_id: u64,
+    pub method: usize,
+}
+
 impl Data {
     pub fn metadata(&self) -> Option<&Metadata> {
         match self {
@@ -334,6 +346,26 @@ impl Data {
     }
 }
 
+impl Closure {
+    pub fn new(params: Vec<ClosureParam>, block: ClosureBlock) -> Self {
+        Self {
+            params,
+            block,
+            repr_data: None,
+            name: None,
+            id: None,
+            metadata: Metadata::default(),
+        }
+    }
+
+    /*
+     * @desc Label of the generated body of the closure, known once resolved
+     */
+    pub fn label(&self) -> Option<Ulid> {
+        self.repr_data.as_ref().map(|repr_data| repr_data.closure_label)
+    }
+}
+
 impl Primitive {
     pub fn size_of(&self) -> usize {
         match self {
@@ -418,3 +450,104 @@ impl Primitive {
         }
     }
 }
+
+macro_rules! fold_integer {
+    ($left:expr, $right:expr, $operator:expr) => {
+        match $operator {
+            MathOperator::Mult => $left.checked_mul($right),
+            MathOperator::Div => $left.checked_div($right),
+            MathOperator::Mod => $left.checked_rem($right),
+            MathOperator::Add => $left.checked_add($right),
+            MathOperator::Sub => $left.checked_sub($right),
+            MathOperator::BitAnd => Some($left & $right),
+            MathOperator::BitOr => Some($left | $right),
+            MathOperator::BitXor => Some($left ^ $right),
+            MathOperator::ShiftLeft => u32::try_from($right)
+                .ok()
+                .and_then(|right| $left.checked_shl(right)),
+            MathOperator::ShiftRight => u32::try_from($right)
+                .ok()
+                .and_then(|right| $left.checked_shr(right)),
+        }
+    };
+}
+
+impl Number {
+    /*
+     * Compile time evaluation of a math operation between two numbers of the same type.
+     * Returns None for anything that would fail or differ at runtime (overflow, division by zero)
+     */
+    pub fn fold(self, other: Number, operator: MathOperator) -> Option<Number> {
+        match (self, other) {
+            (Number::U8(left), Number::U8(right)) => {
+                fold_integer!(left, right, operator).map(Number::U8)
+            }
+            (Number::U16(left), Number::U16(right)) => {
+                fold_integer!(left, right, operator).map(Number::U16)
+            }
+            (Number::U32(left), Number::U32(right)) => {
+                fold_integer!(left, right, operator).map(Number::U32)
+            }
+            (Number::U64(left), Number::U64(right)) => {
+                fold_integer!(left, right, operator).map(Number::U64)
+            }
+            (Number::U128(left), Number::U128(right)) => {
+                fold_integer!(left, right, operator).map(Number::U128)
+            }
+            (Number::I8(left), Number::I8(right)) => {
+                fold_integer!(left, right, operator).map(Number::I8)
+            }
+            (Number::I16(left), Number::I16(right)) => {
+                fold_integer!(left, right, operator).map(Number::I16)
+            }
+            (Number::I32(left), Number::I32(right)) => {
+                fold_integer!(left, right, operator).map(Number::I32)
+            }
+            (Number::I64(left), Number::I64(right)) => {
+                fold_integer!(left, right, operator).map(Number::I64)
+            }
+            (Number::I128(left), Number::I128(right)) => {
+                fold_integer!(left, right, operator).map(Number::I128)
+            }
+            (Number::F64(left), Number::F64(right)) => match operator {
+                MathOperator::Mult => Some(left * right),
+                MathOperator::Div => (right != 0.0).then(|| left / right),
+                MathOperator::Mod => Some(left % right),
+                MathOperator::Add => Some(left + right),
+                MathOperator::Sub => Some(left - right),
+                _ => None,
+            }
+            .map(Number::F64),
+            _ => None,
+        }
+    }
+
+    pub fn compare(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::U8(left), Number::U8(right)) => left.partial_cmp(right),
+            (Number::U16(left), Number::U16(right)) => left.partial_cmp(right),
+            (Number::U32(left), Number::U32(right)) => left.partial_cmp(right),
+            (Number::U64(left), Number::U64(right)) => left.partial_cmp(right),
+            (Number::U128(left), Number::U128(right)) => left.partial_cmp(right),
+            (Number::I8(left), Number::I8(right)) => left.partial_cmp(right),
+            (Number::I16(left), Number::I16(right)) => left.partial_cmp(right),
+            (Number::I32(left), Number::I32(right)) => left.partial_cmp(right),
+            (Number::I64(left), Number::I64(right)) => left.partial_cmp(right),
+            (Number::I128(left), Number::I128(right)) => left.partial_cmp(right),
+            (Number::F64(left), Number::F64(right)) => left.partial_cmp(right),
+            _ => None,
+        }
+    }
+
+    pub fn negate(self) -> Option<Number> {
+        match self {
+            Number::I8(value) => value.checked_neg().map(Number::I8),
+            Number::I16(value) => value.checked_neg().map(Number::I16),
+            Number::I32(value) => value.checked_neg().map(Number::I32),
+            Number::I64(value) => value.checked_neg().map(Number::I64),
+            Number::I128(value) => value.checked_neg().map(Number::I128),
+            Number::F64(value) => Some(Number::F64(-value)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::Debug;
+
+use crate::{
+    ast::{
+        expressions::Expression,
+        format::{Formatter, PrettyPrint},
+        statements::block::BlockCommonApi,
+        TryParse,
+    },
+    semantic::Resolve,
+    vm::GenerateCode,
+};
+
+use super::{
+    Cases, EnumCase, ExprFlow, IfExpr, MatchExpr, PrimitiveCase, StringCase, TryExpr, UnionCase,
+    UnionPattern,
+};
+
+/*
+ * @desc Print a match with one arm per line, each arm followed by a comma
+ */
+pub(crate) fn pretty_match<
+    B: TryParse + Resolve + GenerateCode + BlockCommonApi + Clone + Debug + PartialEq + PrettyPrint,
+    C: TryParse + Resolve + GenerateCode + BlockCommonApi + Clone + Debug + PartialEq + PrettyPrint,
+    D: PrettyPrint,
+>(
+    f: &mut Formatter,
+    expr: &Expression,
+    cases: &Cases<B, C>,
+    else_branch: Option<&D>,
+) {
+    f.write("match ");
+    expr.pretty(f);
+    f.write(" {");
+    f.newline();
+    f.indent();
+    match cases {
+        Cases::Primitive { cases } => {
+            for case in cases {
+                case.pretty(f);
+            }
+        }
+        Cases::String { cases } => {
+            for case in cases {
+                case.pretty(f);
+            }
+        }
+        Cases::Enum { cases } => {
+            for case in cases {
+                case.pretty(f);
+            }
+        }
+        Cases::Union { cases } => {
+            for case in cases {
+                case.pretty(f);
+            }
+        }
+    }
+    if let Some(else_branch) = else_branch {
+        f.write("else => ");
+        else_branch.pretty(f);
+        f.write(",");
+        f.newline();
+    }
+    f.dedent();
+    f.write("}");
+}
+
+/*
+ * @desc Print an arm of a match on its own line
+ */
+fn pretty_case<T>(
+    f: &mut Formatter,
+    patterns: &[T],
+    mut print: impl FnMut(&mut Formatter, &T),
+    block: &impl PrettyPrint,
+) {
+    f.write("case ");
+    for (index, pattern) in patterns.iter().enumerate() {
+        if index > 0 {
+            f.write(" | ");
+        }
+        print(f, pattern);
+    }
+    f.write(" => ");
+    block.pretty(f);
+    f.write(",");
+    f.newline();
+}
+
+impl<
+        B: TryParse
+            + Resolve
+            + GenerateCode
+            + BlockCommonApi
+            + Clone
+            + Debug
+            + PartialEq
+            + PrettyPrint,
+    > PrettyPrint for PrimitiveCase<B>
+{
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_case(
+            f,
+            &self.patterns,
+            |f, pattern| pattern.pretty(f),
+            &self.block,
+        );
+    }
+}
+
+impl<
+        B: TryParse
+            + Resolve
+            + GenerateCode
+            + BlockCommonApi
+            + Clone
+            + Debug
+            + PartialEq
+            + PrettyPrint,
+    > PrettyPrint for StringCase<B>
+{
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_case(
+            f,
+            &self.patterns,
+            |f, pattern| pattern.pretty(f),
+            &self.block,
+        );
+    }
+}
+
+impl<
+        B: TryParse
+            + Resolve
+            + GenerateCode
+            + BlockCommonApi
+            + Clone
+            + Debug
+            + PartialEq
+            + PrettyPrint,
+    > PrettyPrint for EnumCase<B>
+{
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_case(
+            f,
+            &self.patterns,
+            |f, (pattern, _)| pattern.pretty(f),
+            &self.block,
+        );
+    }
+}
+
+impl<
+        B: TryParse
+            + Resolve
+            + GenerateCode
+            + BlockCommonApi
+            + Clone
+            + Debug
+            + PartialEq
+            + PrettyPrint,
+    > PrettyPrint for UnionCase<B>
+{
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_case(
+            f,
+            &[&self.pattern],
+            |f, pattern| pattern.pretty(f),
+            &self.block,
+        );
+    }
+}
+
+impl PrettyPrint for UnionPattern {
+    fn pretty(&self, f: &mut Formatter) {
+        self.path.pretty(f);
+        f.write(&self.typename);
+        f.write("::");
+        f.write(&self.variant);
+        f.list(" { ", &self.vars_names, " }", false, |f, name| {
+            f.write(name)
+        });
+    }
+}
+
+impl PrettyPrint for ExprFlow {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            ExprFlow::If(value) => value.pretty(f),
+            ExprFlow::Match(value) => value.pretty(f),
+            ExprFlow::Try(value) => value.pretty(f),
+            ExprFlow::SizeOf(value, _) => {
+                f.write("size_of(");
+                value.pretty(f);
+                f.write(")");
+            }
+        }
+    }
+}
+
+impl PrettyPrint for IfExpr {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("if ");
+        self.condition.pretty(f);
+        f.write(" then ");
+        self.then_branch.pretty(f);
+        f.write(" else ");
+        self.else_branch.pretty(f);
+    }
+}
+
+impl PrettyPrint for MatchExpr {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_match(f, &self.expr, &self.cases, self.else_branch.as_ref());
+    }
+}
+
+impl PrettyPrint for TryExpr {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("try ");
+        self.try_branch.pretty(f);
+        if let Some(else_branch) = &self.else_branch {
+            f.write(" else ");
+            if let Some(name) = &self.error_name {
+                f.write(name);
+                if let Some(message) = &self.message_name {
+                    f.write(", ");
+                    f.write(message);
+                }
+                f.write(" ");
+            }
+            else_branch.pretty(f);
+        }
+    }
+}
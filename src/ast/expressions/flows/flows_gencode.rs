@@ -7,10 +7,10 @@ use crate::ast::TryParse;
 use crate::semantic::scope::scope::{ScopeState, VariableInfo};
 use crate::semantic::scope::static_types::POINTER_SIZE;
 use crate::semantic::Resolve;
-use crate::vm::asm::branch::{BranchTry, CloseFrame, Return};
+use crate::vm::asm::branch::BranchTry;
 use crate::vm::asm::mem::Mem;
 use crate::vm::asm::operation::{Equal, Operation, StrEqual};
-use crate::vm::core::ERROR_VALUE;
+use crate::vm::runtime::RAISED_ERROR_CODE;
 use crate::vm::{CodeGenerationContext, CodeGenerationError, GenerateCode};
 use crate::{
     semantic::{SizeOf, TypeOf},
@@ -415,18 +415,26 @@ impl GenerateCode for TryExpr {
             instructions,
             &CodeGenerationContext::default(),
         )?;
-        // (1)
 
         if self.pop_last_err {
             let next = Label::gen();
             /* Pop the error */
             instructions.push(Asm::If(BranchIf { else_label: next }));
-            instructions.push(Asm::Pop(return_size)); // discard error value
+            instructions.push(Asm::Pop(return_size + self.err_payload_size)); // discard error value
+            instructions.push(Asm::Data(Data::Serialized {
+                data: RAISED_ERROR_CODE.to_le_bytes().into(),
+            }));
+            if self.message_id.is_some() {
+                instructions.push(Asm::Try(BranchTry::Message));
+            }
             instructions.push(Asm::Goto(Goto {
                 label: Some(else_label),
             }));
             instructions.push_label_by_id(next, "else".to_string().into());
-        } else {
+            if self.err_payload_size > 0 {
+                // discard the payload of the error
+                instructions.push(Asm::Pop(self.err_payload_size));
+            }
         }
 
         instructions.push(Asm::Try(BranchTry::EndTry));
@@ -434,54 +442,46 @@ impl GenerateCode for TryExpr {
         instructions.push(Asm::Goto(Goto {
             label: Some(end_label),
         }));
+        // the stack is restored by the error handler which pushes the code of the caught error
         instructions.push_label_by_id(recover_else_label, "recover_else".to_string().into());
+        if self.message_id.is_some() {
+            instructions.push(Asm::Try(BranchTry::Message));
+        }
 
-        if self.pop_last_err {
-            if let Some(inner_scope) = self.try_branch.scope {
-                match scope_manager.scope_states.get(&inner_scope) {
-                    Some(ScopeState::IIFE) => {
-                        // Push dummy data that will be returned
-                        let mut dummy_data = vec![0; return_size];
-                        dummy_data.push(ERROR_VALUE);
-
-                        instructions.push(Asm::Data(Data::Serialized {
-                            data: dummy_data.into(),
-                        }));
-                        // Once return the cursor will go back to (1)
-                        instructions.push(Asm::Return(Return { size: return_size }));
-                    }
-                    Some(ScopeState::Inline) => {
-                        instructions.push(Asm::Goto(Goto {
-                            label: Some(else_label),
-                        }));
-                    }
-                    _ => return Err(CodeGenerationError::UnresolvedError),
-                }
-            } else {
-                return Err(CodeGenerationError::UnresolvedError);
-            }
-        } else {
-            if let Some(inner_scope) = self.try_branch.scope {
-                match scope_manager.scope_states.get(&inner_scope) {
-                    Some(ScopeState::IIFE) => {
-                        // Once return the cursor will go back to (1)
-                        instructions.push(Asm::CloseFrame(CloseFrame));
-                    }
-                    Some(ScopeState::Inline) => {
-                        instructions.push(Asm::Goto(Goto {
-                            label: Some(else_label),
+        instructions.push_label_by_id(else_label, "else".to_string().into());
+        instructions.push(Asm::Try(BranchTry::EndTry));
+
+        match (self.error_id, &self.else_branch) {
+            (Some(id), Some(block)) => {
+                let Some(inner_scope) = block.scope() else {
+                    return Err(CodeGenerationError::UnresolvedError);
+                };
+                if ScopeState::IIFE
+                    == *scope_manager
+                        .scope_states
+                        .get(&inner_scope)
+                        .unwrap_or(&ScopeState::Inline)
+                {
+                    // IIFE : the code and the message are left on the stack to be picked up by the IIFE
+                } else {
+                    for id in self.message_id.into_iter().chain([id]) {
+                        let Some(VariableInfo { address, ctype, .. }) =
+                            scope_manager.find_var_by_id(id).ok()
+                        else {
+                            return Err(CodeGenerationError::UnresolvedError);
+                        };
+                        instructions.push(Asm::Mem(Mem::Store {
+                            size: ctype.size_of(),
+                            address: (*address)
+                                .try_into()
+                                .map_err(|_| CodeGenerationError::UnresolvedError)?,
                         }));
                     }
-                    _ => return Err(CodeGenerationError::UnresolvedError),
                 }
-            } else {
-                return Err(CodeGenerationError::UnresolvedError);
             }
+            _ => instructions.push(Asm::Pop(POINTER_SIZE)),
         }
 
-        instructions.push_label_by_id(else_label, "else".to_string().into());
-        instructions.push(Asm::Try(BranchTry::EndTry));
-
         if let Some(block) = &self.else_branch {
             block.gencode::<E>(scope_manager, scope_id, instructions, context)?;
         }
@@ -749,4 +749,113 @@ mod tests {
             assert_fn,
         );
     }
+
+    #[test]
+    fn valid_try_bound_error() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 4);
+            let res = test_extract_variable::<i64>("res5", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 5);
+            true
+        }
+
+        test_statements(
+            r##"
+        let arr = [1,2,3];
+        let zero = 0;
+
+        let res1 = try { arr[4] } else err {
+            let code = match err {
+                case RuntimeError::IndexOutOfBound => { 1 },
+                else => { 0 }
+            };
+            code
+        };
+
+        let res2 = try { 10 / zero } else err {
+            let code = match err {
+                case RuntimeError::MathError => { 2 },
+                else => { 0 }
+            };
+            code
+        };
+
+        let res3 = try { (5,Err()) } else err {
+            let code = match err {
+                case RuntimeError::Raised => { 3 },
+                else => { 0 }
+            };
+            code
+        };
+
+        let res4 = try {
+            let x = 2;
+            arr[4]
+        } else err {
+            let code = match err {
+                case RuntimeError::IndexOutOfBound => { 4 },
+                else => { 0 }
+            };
+            code
+        };
+        let res5 = try { arr[2] + 2 } else err { 0 };
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_try_message() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 14);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 100);
+            true
+        }
+
+        test_statements(
+            r##"
+        let negative = "negative value";
+        fn check(x:i64) -> i64 {
+            if x < 0 {
+                raise negative;
+            }
+            return x;
+        }
+
+        let res1 = try { check(-1) } else err, message { len(message) as i64 };
+        let res2 = try { (5,Err()) } else err, message { len(message) as i64 + 100 };
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
 }
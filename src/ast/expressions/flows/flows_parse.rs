@@ -286,18 +286,33 @@ impl TryParse for TryExpr {
      * @desc Parse try expression
      *
      * @grammar
-     * Try_Expr := try { Expr } else { Expr}
+     * Try_Expr := try { Expr } else ( ID ( , ID | Λ ) | Λ ) { Expr}
      */
     fn parse(input: Span) -> PResult<Self> {
         map(
             pair(
                 preceded(wst_closed(lexem::TRY), ExprBlock::parse),
-                opt(preceded(wst_closed(lexem::ELSE), ExprBlock::parse)),
+                opt(preceded(
+                    wst_closed(lexem::ELSE),
+                    pair(
+                        opt(pair(parse_id, opt(preceded(wst(lexem::COMA), parse_id)))),
+                        ExprBlock::parse,
+                    ),
+                )),
             ),
             |(try_branch, else_branch)| TryExpr {
                 try_branch,
-                else_branch,
+                error_name: else_branch
+                    .as_ref()
+                    .and_then(|(names, _)| names.as_ref().map(|(name, _)| name.clone())),
+                message_name: else_branch
+                    .as_ref()
+                    .and_then(|(names, _)| names.as_ref().and_then(|(_, name)| name.clone())),
+                else_branch: else_branch.map(|(_, block)| block),
+                error_id: None,
+                message_id: None,
                 pop_last_err: false,
+                err_payload_size: 0,
                 metadata: Metadata::default(),
             },
         )(input)
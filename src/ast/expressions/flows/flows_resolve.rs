@@ -7,7 +7,9 @@ use crate::ast::statements::block::BlockCommonApi;
 use crate::ast::TryParse;
 use crate::p_num;
 use crate::semantic::scope::scope::ScopeState;
-use crate::semantic::scope::static_types::{PrimitiveType, TupleType, POINTER_SIZE};
+use crate::semantic::scope::static_types::{
+    ErrorType, PrimitiveType, StringType, TupleType, POINTER_SIZE,
+};
 use crate::semantic::scope::user_types::{Enum, Struct, Union};
 use crate::semantic::{
     scope::{static_types::StaticType, user_types::UserType},
@@ -177,18 +179,26 @@ impl Resolve for UnionPattern {
     where
         Self: Sized,
     {
-        let UserType::Union(union_type @ Union { .. }) = scope_manager
-            .find_type_by_name(
-                match &self.path {
-                    crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
-                    crate::ast::expressions::Path::Empty => None,
-                },
-                &self.typename,
+        let user_type = scope_manager.find_type_by_name(
+            match &self.path {
+                crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
+                crate::ast::expressions::Path::Empty => None,
+            },
+            &self.typename,
+            scope_id,
+        )?;
+        scope_manager.signal_type_use(user_type.id);
+        let UserType::Union(union_type @ Union { .. }) = user_type.def else {
+            let found = EType::User {
+                id: user_type.id,
+                size: user_type.def.size_of(),
+            };
+            return Err(SemanticError::mismatched_kind(
+                "a union",
+                &found,
+                scope_manager,
                 scope_id,
-            )?
-            .def
-        else {
-            return Err(SemanticError::IncompatibleTypes);
+            ));
         };
 
         let union_size = union_type.size_of();
@@ -430,7 +440,13 @@ impl Resolve for MatchExpr {
         match &mut self.cases {
             super::Cases::Primitive { cases } => {
                 let EType::Static(StaticType::Primitive(_)) = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a primitive value",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 let mut current_case_type: Option<EType> = None;
                 for case in cases {
@@ -455,7 +471,15 @@ impl Resolve for MatchExpr {
                 match expr_type {
                     EType::Static(StaticType::StrSlice(_))
                     | EType::Static(StaticType::String(_)) => {}
-                    _ => return Err(SemanticError::IncompatibleTypes),
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a string",
+                            &expr_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(self.expr.span()));
+                    }
                 }
 
                 let mut current_case_type: Option<EType> = None;
@@ -479,12 +503,24 @@ impl Resolve for MatchExpr {
             }
             super::Cases::Enum { cases } => {
                 let EType::User { id, size } = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "an enum",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 let UserType::Enum(Enum { id, values }) =
                     scope_manager.find_type_by_id(id, scope_id)?.clone()
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "an enum",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
 
                 let mut current_case_type: Option<EType> = None;
@@ -525,12 +561,24 @@ impl Resolve for MatchExpr {
             }
             super::Cases::Union { cases } => {
                 let EType::User { id, size } = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a union",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 let UserType::Union(Union { id, variants }) =
                     scope_manager.find_type_by_id(id, scope_id)?.clone()
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a union",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
 
                 let mut current_case_type: Option<EType> = None;
@@ -685,6 +733,40 @@ impl Resolve for TryExpr {
             .resolve::<E>(scope_manager, scope_id, context, &mut ())?;
 
         if let Some(block) = &mut self.else_branch {
+            if let Some(error_name) = &self.error_name {
+                // bind the caught error in the else block
+                let inner_scope = block.init_from_parent(scope_manager, scope_id)?;
+                let error_type = scope_manager.runtime_error_type()?;
+                let message_type = EType::Static(StaticType::String(StringType()));
+                if scope_manager.is_scope_global(Some(inner_scope)) {
+                    scope_manager
+                        .scope_states
+                        .insert(inner_scope, ScopeState::IIFE);
+                    // the block is an IIFE
+                    let id =
+                        scope_manager.register_parameter(error_name, error_type, Some(inner_scope))?;
+                    let _ = self.error_id.insert(id);
+                    if let Some(message_name) = &self.message_name {
+                        let id = scope_manager.register_parameter(
+                            message_name,
+                            message_type,
+                            Some(inner_scope),
+                        )?;
+                        let _ = self.message_id.insert(id);
+                    }
+                } else {
+                    scope_manager
+                        .scope_states
+                        .insert(inner_scope, ScopeState::Inline);
+                    let id = scope_manager.register_var(error_name, error_type, Some(inner_scope))?;
+                    let _ = self.error_id.insert(id);
+                    if let Some(message_name) = &self.message_name {
+                        let id =
+                            scope_manager.register_var(message_name, message_type, Some(inner_scope))?;
+                        let _ = self.message_id.insert(id);
+                    }
+                }
+            }
             block.resolve::<E>(scope_manager, scope_id, context, &mut ())?;
         }
 
@@ -697,9 +779,17 @@ impl Resolve for TryExpr {
             .unwrap_or(Ok(EType::Static(StaticType::Unit)))?;
 
         if let EType::Static(StaticType::Tuple(TupleType(tuple_type))) = &mut try_branch_type {
-            if let Some(EType::Static(StaticType::Error)) = tuple_type.last() {
-                self.pop_last_err = true;
-                tuple_type.pop();
+            match tuple_type.last() {
+                Some(EType::Static(StaticType::Error)) => {
+                    self.pop_last_err = true;
+                    tuple_type.pop();
+                }
+                Some(EType::Static(StaticType::TypedError(ErrorType(payload)))) => {
+                    self.pop_last_err = true;
+                    self.err_payload_size = payload.size_of();
+                    tuple_type.pop();
+                }
+                _ => {}
             }
             if tuple_type.len() == 1 {
                 try_branch_type = tuple_type[0].clone();
@@ -707,8 +797,18 @@ impl Resolve for TryExpr {
         } else if let EType::Static(StaticType::Error) = try_branch_type {
             self.pop_last_err = true;
             try_branch_type = EType::Static(StaticType::Unit);
+        } else if let EType::Static(StaticType::TypedError(ErrorType(payload))) = &try_branch_type {
+            self.pop_last_err = true;
+            self.err_payload_size = payload.size_of();
+            try_branch_type = EType::Static(StaticType::Unit);
         } else if self.else_branch.is_none() && EType::Static(StaticType::Unit) != try_branch_type {
-            return Err(SemanticError::IncompatibleTypes);
+            return Err(SemanticError::mismatched_kind(
+                "an error or no value",
+                &try_branch_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.metadata.span));
         }
 
         let _ = try_branch_type.compatible_with(&else_branch_type, &scope_manager, scope_id)?;
@@ -1,3 +1,4 @@
+pub mod flows_format;
 pub mod flows_gencode;
 pub mod flows_parse;
 pub mod flows_resolve;
@@ -103,7 +104,12 @@ pub enum Cases<
 pub struct TryExpr {
     try_branch: ExprBlock,
     else_branch: Option<ExprBlock>,
+    error_name: Option<ID>,
+    error_id: Option<u64>,
+    message_name: Option<ID>,
+    message_id: Option<u64>,
     pop_last_err: bool,
+    err_payload_size: usize,
     metadata: Metadata,
 }
 
@@ -9,8 +9,9 @@ use operation::ExprCall;
 use crate::{
     ast::{
         expressions::operation::LogicalOr,
+        format::{Formatter, PrettyPrint},
         utils::{
-            io::{PResult, Span},
+            io::{spanned, HasSpan, PResult, SourceSpan, Span},
             lexem,
             strings::{eater, wst},
         },
@@ -18,14 +19,14 @@ use crate::{
     semantic::{EType, Metadata, Resolve, ResolveFromStruct, SemanticError, TypeOf},
 };
 use crate::{
-    semantic::{Desugar, ResolveNumber},
+    semantic::{ConstEval, Desugar, ResolveNumber},
     vm::GenerateCode,
 };
 
 use self::operation::{
     operation_parse::TryParseOperation, Addition, BitwiseAnd, BitwiseOR, BitwiseXOR, Cast,
-    Comparaison, Equation, FieldAccess, ListAccess, LogicalAnd, Product, Shift, Substraction,
-    TupleAccess, UnaryOperation,
+    Comparaison, Equation, FieldAccess, ListAccess, LogicalAnd, Membership, Product, Propagate,
+    Range, Shift, Substraction, TupleAccess, UnaryOperation,
 };
 
 use super::{
@@ -48,10 +49,13 @@ pub enum Expression {
     BitwiseXOR(operation::BitwiseXOR),
     BitwiseOR(operation::BitwiseOR),
     Cast(operation::Cast),
+    Propagate(operation::Propagate),
     Comparaison(operation::Comparaison),
     Equation(operation::Equation),
     LogicalAnd(operation::LogicalAnd),
     LogicalOr(operation::LogicalOr),
+    Range(operation::Range),
+    Membership(operation::Membership),
     FieldAccess(operation::FieldAccess),
     ListAccess(operation::ListAccess),
     TupleAccess(operation::TupleAccess),
@@ -131,7 +135,7 @@ impl TryParse for Atomic {
      */
     fn parse(input: Span) -> PResult<Self> {
         squash(
-            alt((
+            spanned(alt((
                 map(
                     delimited(wst(lexem::PAR_O), Expression::parse, wst(lexem::PAR_C)),
                     |value| Atomic::Paren(Box::new(value)),
@@ -139,7 +143,7 @@ impl TryParse for Atomic {
                 map(flows::ExprFlow::parse, Atomic::ExprFlow),
                 map(data::Data::parse, Atomic::Data),
                 map(operation::UnaryOperation::parse, Atomic::UnaryOperation),
-            )),
+            ))),
             "Expected a valid expression",
         )(input)
     }
@@ -186,7 +190,7 @@ impl ResolveFromStruct for Atomic {
             Atomic::Paren(value) => {
                 value.resolve_from_struct::<E>(scope_manager, scope_id, struct_id)
             }
-            _ => Err(SemanticError::UnknownField),
+            _ => Err(SemanticError::ExpectedFieldName),
         }
     }
 }
@@ -238,9 +242,10 @@ impl TryParse for Expression {
      * @desc Parse an expression
      *
      * @grammar
-     * Expr := AndOp Or AndOp | AndOp
+     * Expr := OrOp .. OrOp | OrOp ..= OrOp | OrOp
+     * OrOp := AndOp Or AndOp | AndOp
      * AndOp := CompOp And CompOp | CompOp
-     * CompOp := BOr (< |<= | >= | >| == | != | in ) BOr | BOr
+     * CompOp := BOr (< |<= | >= | >| == | != ) BOr | BOr in Collection | BOr
      * BOr := XOr \| XOr  | XOr
      * XOr := BAnd ^ BAnd | BAnd
      * BAnd := Shift & Shift | Shift
@@ -249,7 +254,7 @@ impl TryParse for Expression {
      * HighM := Atom (* | / | % ) Atom | Atom
      */
     fn parse(input: Span) -> PResult<Self> {
-        eater::ws::<_, Expression>(LogicalOr::parse)(input)
+        eater::ws::<_, Expression>(spanned(Range::parse))(input)
     }
 }
 
@@ -267,7 +272,8 @@ impl Resolve for Expression {
     where
         Self: Sized,
     {
-        match self {
+        let span = self.span();
+        let res = match self {
             Expression::Product(value) => {
                 value.resolve::<E>(scope_manager, scope_id, context, &mut ())
             }
@@ -301,13 +307,27 @@ impl Resolve for Expression {
             Expression::LogicalOr(value) => {
                 value.resolve::<E>(scope_manager, scope_id, context, &mut ())
             }
+            Expression::Range(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, &mut ())
+            }
+            Expression::Membership(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, &mut ())
+            }
             Expression::Atomic(value) => {
                 value.resolve::<E>(scope_manager, scope_id, context, extra)
             }
             Expression::Cast(value) => {
                 value.resolve::<E>(scope_manager, scope_id, context, &mut ())
             }
+            Expression::Propagate(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, &mut ())
+            }
             Expression::FieldAccess(value) => {
+                if let Some(mut call) = value.resolve_method::<E>(scope_manager, scope_id)? {
+                    let _ = call.resolve::<E>(scope_manager, scope_id, context, extra)?;
+                    *self = call;
+                    return Ok(());
+                }
                 value.resolve::<E>(scope_manager, scope_id, context, extra)
             }
             Expression::ListAccess(value) => {
@@ -319,7 +339,11 @@ impl Resolve for Expression {
             Expression::ExprCall(value) => {
                 value.resolve::<E>(scope_manager, scope_id, context, extra)
             }
+        };
+        if let (Ok(_), Some(signature)) = (&res, self.signature()) {
+            scope_manager.signal_signature(span, &signature);
         }
+        res.map_err(|err| err.at(span))
     }
 }
 
@@ -334,10 +358,13 @@ impl ResolveNumber for Expression {
             Expression::BitwiseXOR(bitwise_xor) => bitwise_xor.is_unresolved_number(),
             Expression::BitwiseOR(bitwise_or) => bitwise_or.is_unresolved_number(),
             Expression::Cast(cast) => cast.is_unresolved_number(),
+            Expression::Propagate(_) => false,
             Expression::Comparaison(comparaison) => comparaison.is_unresolved_number(),
             Expression::Equation(equation) => equation.is_unresolved_number(),
             Expression::LogicalAnd(logical_and) => logical_and.is_unresolved_number(),
             Expression::LogicalOr(logical_or) => logical_or.is_unresolved_number(),
+            Expression::Range(range) => range.is_unresolved_number(),
+            Expression::Membership(membership) => membership.is_unresolved_number(),
             Expression::FieldAccess(_) => false,
             Expression::ListAccess(_) => false,
             Expression::TupleAccess(_) => false,
@@ -359,10 +386,13 @@ impl ResolveNumber for Expression {
             Expression::BitwiseXOR(bitwise_xor) => bitwise_xor.resolve_number(to),
             Expression::BitwiseOR(bitwise_or) => bitwise_or.resolve_number(to),
             Expression::Cast(cast) => cast.resolve_number(to),
+            Expression::Propagate(_) => Ok(()),
             Expression::Comparaison(comparaison) => comparaison.resolve_number(to),
             Expression::Equation(equation) => equation.resolve_number(to),
             Expression::LogicalAnd(logical_and) => logical_and.resolve_number(to),
             Expression::LogicalOr(logical_or) => logical_or.resolve_number(to),
+            Expression::Range(range) => range.resolve_number(to),
+            Expression::Membership(membership) => membership.resolve_number(to),
             Expression::FieldAccess(_) => Ok(()),
             Expression::ListAccess(_) => Ok(()),
             Expression::TupleAccess(_) => Ok(()),
@@ -395,6 +425,38 @@ impl ResolveNumber for Atomic {
     }
 }
 
+impl ConstEval for Atomic {
+    fn const_eval(&self) -> Result<Option<data::Data>, SemanticError> {
+        match self {
+            Atomic::Data(data) => data.const_eval(),
+            Atomic::UnaryOperation(unary_operation) => unary_operation.const_eval(),
+            Atomic::Paren(expression) => expression.const_eval(),
+            Atomic::ExprFlow(_) => Ok(None),
+        }
+    }
+}
+
+impl ConstEval for Expression {
+    fn const_eval(&self) -> Result<Option<data::Data>, SemanticError> {
+        match self {
+            Expression::Product(product) => product.const_eval(),
+            Expression::Addition(addition) => addition.const_eval(),
+            Expression::Substraction(substraction) => substraction.const_eval(),
+            Expression::Shift(shift) => shift.const_eval(),
+            Expression::BitwiseAnd(bitwise_and) => bitwise_and.const_eval(),
+            Expression::BitwiseXOR(bitwise_xor) => bitwise_xor.const_eval(),
+            Expression::BitwiseOR(bitwise_or) => bitwise_or.const_eval(),
+            Expression::Comparaison(comparaison) => comparaison.const_eval(),
+            Expression::Equation(equation) => equation.const_eval(),
+            Expression::LogicalAnd(logical_and) => logical_and.const_eval(),
+            Expression::LogicalOr(logical_or) => logical_or.const_eval(),
+            Expression::TupleAccess(tuple_access) => tuple_access.const_eval(),
+            Expression::Atomic(atomic) => atomic.const_eval(),
+            _ => Ok(None),
+        }
+    }
+}
+
 impl ResolveFromStruct for Expression {
     fn resolve_from_struct<E: crate::vm::external::Engine>(
         &mut self,
@@ -415,7 +477,7 @@ impl ResolveFromStruct for Expression {
             Expression::Atomic(value) => {
                 value.resolve_from_struct::<E>(scope_manager, scope_id, struct_id)
             }
-            _ => Err(SemanticError::UnknownField),
+            _ => Err(SemanticError::ExpectedFieldName),
         }
     }
 }
@@ -435,10 +497,13 @@ impl Desugar<Expression> for Expression {
             Expression::BitwiseXOR(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::BitwiseOR(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::Cast(value) => value.desugar::<E>(scope_manager, scope_id)?,
+            Expression::Propagate(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::Comparaison(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::Equation(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::LogicalAnd(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::LogicalOr(value) => value.desugar::<E>(scope_manager, scope_id)?,
+            Expression::Range(value) => value.desugar::<E>(scope_manager, scope_id)?,
+            Expression::Membership(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::FieldAccess(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::ListAccess(value) => value.desugar::<E>(scope_manager, scope_id)?,
             Expression::TupleAccess(value) => value.desugar::<E>(scope_manager, scope_id)?,
@@ -474,9 +539,12 @@ impl TypeOf for Expression {
             Expression::Comparaison(value) => value.type_of(&scope_manager, scope_id),
             Expression::LogicalAnd(value) => value.type_of(&scope_manager, scope_id),
             Expression::LogicalOr(value) => value.type_of(&scope_manager, scope_id),
+            Expression::Range(value) => value.type_of(&scope_manager, scope_id),
+            Expression::Membership(value) => value.type_of(&scope_manager, scope_id),
             Expression::Equation(value) => value.type_of(&scope_manager, scope_id),
             Expression::Atomic(value) => value.type_of(&scope_manager, scope_id),
             Expression::Cast(value) => value.type_of(&scope_manager, scope_id),
+            Expression::Propagate(value) => value.type_of(&scope_manager, scope_id),
             Expression::FieldAccess(value) => value.type_of(&scope_manager, scope_id),
             Expression::ListAccess(value) => value.type_of(&scope_manager, scope_id),
             Expression::TupleAccess(value) => value.type_of(&scope_manager, scope_id),
@@ -492,6 +560,100 @@ impl GenerateCode for Expression {
         scope_id: Option<u128>,
         instructions: &mut crate::vm::program::Program<E>,
         context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        // the constant operations are folded once, from the root of the expression
+        if self.is_foldable() {
+            let mut folded = self.clone();
+            folded.fold();
+            return folded.gencode_folded::<E>(scope_manager, scope_id, instructions, context);
+        }
+        self.gencode_folded::<E>(scope_manager, scope_id, instructions, context)
+    }
+}
+
+impl Expression {
+    fn is_foldable(&self) -> bool {
+        matches!(
+            self,
+            Expression::Product(_)
+                | Expression::Addition(_)
+                | Expression::Substraction(_)
+                | Expression::Shift(_)
+                | Expression::BitwiseAnd(_)
+                | Expression::BitwiseXOR(_)
+                | Expression::BitwiseOR(_)
+                | Expression::Comparaison(_)
+                | Expression::Equation(_)
+                | Expression::LogicalAnd(_)
+                | Expression::LogicalOr(_)
+                | Expression::TupleAccess(_)
+                | Expression::Atomic(Atomic::UnaryOperation(_) | Atomic::Paren(_))
+        )
+    }
+
+    /*
+     * @desc Fold the constant operations into a single push, from the operands up
+     * The operations failing on constants are left to the runtime
+     */
+    fn fold(&mut self) {
+        let operands = match self {
+            Expression::Product(
+                Product::Mult { left, right, .. }
+                | Product::Div { left, right, .. }
+                | Product::Mod { left, right, .. },
+            )
+            | Expression::Addition(Addition { left, right, .. })
+            | Expression::Substraction(Substraction { left, right, .. })
+            | Expression::Shift(
+                Shift::Left { left, right, .. } | Shift::Right { left, right, .. },
+            )
+            | Expression::BitwiseAnd(BitwiseAnd { left, right, .. })
+            | Expression::BitwiseXOR(BitwiseXOR { left, right, .. })
+            | Expression::BitwiseOR(BitwiseOR { left, right, .. })
+            | Expression::Comparaison(
+                Comparaison::Less { left, right, .. }
+                | Comparaison::LessEqual { left, right, .. }
+                | Comparaison::Greater { left, right, .. }
+                | Comparaison::GreaterEqual { left, right, .. },
+            )
+            | Expression::Equation(
+                Equation::Equal { left, right, .. } | Equation::NotEqual { left, right, .. },
+            )
+            | Expression::LogicalAnd(LogicalAnd { left, right, .. })
+            | Expression::LogicalOr(LogicalOr { left, right, .. }) => vec![left, right],
+            Expression::TupleAccess(TupleAccess { var: value, .. })
+            | Expression::Atomic(
+                Atomic::Paren(value)
+                | Atomic::UnaryOperation(
+                    UnaryOperation::Minus { value, .. } | UnaryOperation::Not { value, .. },
+                ),
+            ) => vec![value],
+            _ => return,
+        };
+
+        // an operation is folded once all of its operands are
+        let mut constant = true;
+        for operand in operands {
+            operand.fold();
+            constant &= matches!(**operand, Expression::Atomic(Atomic::Data(_)));
+        }
+        if !constant {
+            return;
+        }
+        if let Ok(Some(value @ data::Data::Primitive(_))) = self.const_eval() {
+            *self = Expression::Atomic(Atomic::Data(value));
+        }
+    }
+
+    /*
+     * @desc Generate an expression whose constant operations are already folded
+     */
+    fn gencode_folded<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
         match self {
             Expression::Product(value) => {
@@ -518,6 +680,9 @@ impl GenerateCode for Expression {
             Expression::Cast(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
+            Expression::Propagate(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
             Expression::Comparaison(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
@@ -530,6 +695,12 @@ impl GenerateCode for Expression {
             Expression::LogicalOr(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
+            Expression::Range(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+            Expression::Membership(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
             Expression::Atomic(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
@@ -565,7 +736,7 @@ impl GenerateCode for Atomic {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
             Atomic::Paren(value) => {
-                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+                value.gencode_folded::<E>(scope_manager, scope_id, instructions, context)
             }
             Atomic::ExprFlow(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
@@ -588,6 +759,7 @@ impl Expression {
             Expression::BitwiseXOR(BitwiseXOR { metadata, .. }) => Some(metadata),
             Expression::BitwiseOR(BitwiseOR { metadata, .. }) => Some(metadata),
             Expression::Cast(Cast { metadata, .. }) => Some(metadata),
+            Expression::Propagate(Propagate { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::Greater { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::GreaterEqual { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::Less { metadata, .. }) => Some(metadata),
@@ -596,6 +768,8 @@ impl Expression {
             Expression::Equation(Equation::NotEqual { metadata, .. }) => Some(metadata),
             Expression::LogicalAnd(LogicalAnd { metadata, .. }) => Some(metadata),
             Expression::LogicalOr(LogicalOr { metadata, .. }) => Some(metadata),
+            Expression::Range(Range { metadata, .. }) => Some(metadata),
+            Expression::Membership(Membership { metadata, .. }) => Some(metadata),
             Expression::Atomic(value) => value.metadata(),
             Expression::FieldAccess(FieldAccess { metadata, .. }) => Some(metadata),
             Expression::ListAccess(ListAccess { metadata, .. }) => Some(metadata),
@@ -617,6 +791,7 @@ impl Expression {
             Expression::BitwiseXOR(BitwiseXOR { metadata, .. }) => Some(metadata),
             Expression::BitwiseOR(BitwiseOR { metadata, .. }) => Some(metadata),
             Expression::Cast(Cast { metadata, .. }) => Some(metadata),
+            Expression::Propagate(Propagate { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::Greater { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::GreaterEqual { metadata, .. }) => Some(metadata),
             Expression::Comparaison(Comparaison::Less { metadata, .. }) => Some(metadata),
@@ -625,6 +800,8 @@ impl Expression {
             Expression::Equation(Equation::NotEqual { metadata, .. }) => Some(metadata),
             Expression::LogicalAnd(LogicalAnd { metadata, .. }) => Some(metadata),
             Expression::LogicalOr(LogicalOr { metadata, .. }) => Some(metadata),
+            Expression::Range(Range { metadata, .. }) => Some(metadata),
+            Expression::Membership(Membership { metadata, .. }) => Some(metadata),
             Expression::Atomic(value) => value.metadata_mut(),
             Expression::FieldAccess(FieldAccess { metadata, .. }) => Some(metadata),
             Expression::ListAccess(ListAccess { metadata, .. }) => Some(metadata),
@@ -633,6 +810,12 @@ impl Expression {
         }
     }
 
+    pub fn span(&self) -> SourceSpan {
+        self.metadata()
+            .map(|metadata| metadata.span)
+            .unwrap_or_default()
+    }
+
     pub fn signature(&self) -> Option<EType> {
         match self {
             Expression::Product(Product::Div { metadata, .. }) => metadata.signature(),
@@ -646,6 +829,7 @@ impl Expression {
             Expression::BitwiseXOR(BitwiseXOR { metadata, .. }) => metadata.signature(),
             Expression::BitwiseOR(BitwiseOR { metadata, .. }) => metadata.signature(),
             Expression::Cast(Cast { metadata, .. }) => metadata.signature(),
+            Expression::Propagate(Propagate { metadata, .. }) => metadata.signature(),
             Expression::Comparaison(Comparaison::Greater { metadata, .. }) => metadata.signature(),
             Expression::Comparaison(Comparaison::GreaterEqual { metadata, .. }) => {
                 metadata.signature()
@@ -658,6 +842,8 @@ impl Expression {
             Expression::Equation(Equation::NotEqual { metadata, .. }) => metadata.signature(),
             Expression::LogicalAnd(LogicalAnd { metadata, .. }) => metadata.signature(),
             Expression::LogicalOr(LogicalOr { metadata, .. }) => metadata.signature(),
+            Expression::Range(Range { metadata, .. }) => metadata.signature(),
+            Expression::Membership(Membership { metadata, .. }) => metadata.signature(),
             Expression::Atomic(value) => value.signature(),
             Expression::FieldAccess(FieldAccess { metadata, .. }) => metadata.signature(),
             Expression::ListAccess(ListAccess { metadata, .. }) => metadata.signature(),
@@ -667,6 +853,27 @@ impl Expression {
     }
 }
 
+impl HasSpan for Expression {
+    fn set_span(&mut self, span: SourceSpan) {
+        if let Some(metadata) = self.metadata_mut() {
+            // the innermost parser sets the location of a node
+            if !metadata.span.is_known() {
+                metadata.span = span;
+            }
+        }
+    }
+}
+
+impl HasSpan for Atomic {
+    fn set_span(&mut self, span: SourceSpan) {
+        if let Some(metadata) = self.metadata_mut() {
+            if !metadata.span.is_known() {
+                metadata.span = span;
+            }
+        }
+    }
+}
+
 impl Atomic {
     pub fn metadata(&self) -> Option<&Metadata> {
         match self {
@@ -701,3 +908,63 @@ impl Atomic {
         }
     }
 }
+
+impl PrettyPrint for Expression {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Expression::Product(value) => value.pretty(f),
+            Expression::Addition(value) => value.pretty(f),
+            Expression::Substraction(value) => value.pretty(f),
+            Expression::Shift(value) => value.pretty(f),
+            Expression::BitwiseAnd(value) => value.pretty(f),
+            Expression::BitwiseXOR(value) => value.pretty(f),
+            Expression::BitwiseOR(value) => value.pretty(f),
+            Expression::Cast(value) => value.pretty(f),
+            Expression::Propagate(value) => value.pretty(f),
+            Expression::Comparaison(value) => value.pretty(f),
+            Expression::Equation(value) => value.pretty(f),
+            Expression::LogicalAnd(value) => value.pretty(f),
+            Expression::LogicalOr(value) => value.pretty(f),
+            Expression::Range(value) => value.pretty(f),
+            Expression::Membership(value) => value.pretty(f),
+            Expression::FieldAccess(value) => value.pretty(f),
+            Expression::ListAccess(value) => value.pretty(f),
+            Expression::TupleAccess(value) => value.pretty(f),
+            Expression::ExprCall(value) => value.pretty(f),
+            Expression::Atomic(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for Atomic {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Atomic::Data(value) => value.pretty(f),
+            Atomic::UnaryOperation(value) => value.pretty(f),
+            Atomic::Paren(value) => {
+                f.write("(");
+                value.pretty(f);
+                f.write(")");
+            }
+            Atomic::ExprFlow(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for Path {
+    fn pretty(&self, f: &mut Formatter) {
+        if let Path::Segment(segments) = self {
+            for segment in segments {
+                f.write(segment);
+                f.write("::");
+            }
+        }
+    }
+}
+
+impl PrettyPrint for CompletePath {
+    fn pretty(&self, f: &mut Formatter) {
+        self.path.pretty(f);
+        f.write(&self.name);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{ast::types::Type, semantic::Metadata};
+use crate::{
+    ast::types::Type,
+    semantic::{EType, Metadata},
+};
 
 use super::{data::CallArgs, Atomic, Expression};
+pub mod operation_format;
 pub mod operation_gencode;
 pub mod operation_parse;
 pub mod operation_resolve;
@@ -93,20 +97,20 @@ pub enum Shift {
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct BitwiseAnd {
-    left: Box<Expression>,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
     pub metadata: Metadata,
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct BitwiseXOR {
-    left: Box<Expression>,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
     pub metadata: Metadata,
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct BitwiseOR {
-    left: Box<Expression>,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
     pub metadata: Metadata,
 }
 
@@ -117,6 +121,13 @@ pub struct Cast {
     pub metadata: Metadata,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Propagate {
+    value: Box<Expression>,
+    return_type: Option<EType>, // return type of the enclosing function, closure or lambda
+    pub metadata: Metadata,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Comparaison {
     Less {
@@ -157,13 +168,28 @@ pub enum Equation {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogicalAnd {
-    left: Box<Expression>,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
     pub metadata: Metadata,
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogicalOr {
-    left: Box<Expression>,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    pub metadata: Metadata,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    lower: Box<Expression>,
+    upper: Box<Expression>,
+    inclusive: bool,
+    pub metadata: Metadata,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Membership {
+    item: Box<Expression>,
+    collection: Box<Expression>,
     pub metadata: Metadata,
 }
@@ -0,0 +1,212 @@
+use crate::ast::{
+    expressions::Expression,
+    format::{Formatter, PrettyPrint},
+};
+
+use super::{
+    Addition, BitwiseAnd, BitwiseOR, BitwiseXOR, Cast, Comparaison, Equation, ExprCall,
+    FieldAccess, ListAccess, LogicalAnd, LogicalOr, Membership, Product, Propagate, Range, Shift,
+    Substraction, TupleAccess, UnaryOperation,
+};
+
+/*
+ * @desc Print a binary operation, the parenthesis of the source are nodes of the tree
+ */
+fn pretty_binary(f: &mut Formatter, left: &Expression, operator: &str, right: &Expression) {
+    left.pretty(f);
+    f.write(" ");
+    f.write(operator);
+    f.write(" ");
+    right.pretty(f);
+}
+
+impl PrettyPrint for UnaryOperation {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            UnaryOperation::Minus { value, .. } => {
+                f.write("-");
+                value.pretty(f);
+            }
+            UnaryOperation::Not { value, .. } => {
+                f.write("!");
+                value.pretty(f);
+            }
+        }
+    }
+}
+
+impl PrettyPrint for FieldAccess {
+    fn pretty(&self, f: &mut Formatter) {
+        self.var.pretty(f);
+        f.write(".");
+        self.field.pretty(f);
+    }
+}
+
+impl PrettyPrint for TupleAccess {
+    fn pretty(&self, f: &mut Formatter) {
+        self.var.pretty(f);
+        f.write(&format!(".{}", self.index));
+    }
+}
+
+impl PrettyPrint for ListAccess {
+    fn pretty(&self, f: &mut Formatter) {
+        self.var.pretty(f);
+        f.write("[");
+        self.index.pretty(f);
+        f.write("]");
+    }
+}
+
+impl PrettyPrint for ExprCall {
+    fn pretty(&self, f: &mut Formatter) {
+        self.var.pretty(f);
+        self.args.pretty(f);
+    }
+}
+
+impl PrettyPrint for Product {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Product::Mult { left, right, .. } => pretty_binary(f, left, "*", right),
+            Product::Div { left, right, .. } => pretty_binary(f, left, "/", right),
+            Product::Mod { left, right, .. } => pretty_binary(f, left, "%", right),
+        }
+    }
+}
+
+impl PrettyPrint for Addition {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "+", &self.right);
+    }
+}
+
+impl PrettyPrint for Substraction {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "-", &self.right);
+    }
+}
+
+impl PrettyPrint for Shift {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Shift::Left { left, right, .. } => pretty_binary(f, left, "<<", right),
+            Shift::Right { left, right, .. } => pretty_binary(f, left, ">>", right),
+        }
+    }
+}
+
+impl PrettyPrint for BitwiseAnd {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "&", &self.right);
+    }
+}
+
+impl PrettyPrint for BitwiseXOR {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "^", &self.right);
+    }
+}
+
+impl PrettyPrint for BitwiseOR {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "|", &self.right);
+    }
+}
+
+impl PrettyPrint for Cast {
+    fn pretty(&self, f: &mut Formatter) {
+        self.left.pretty(f);
+        f.write(" as ");
+        self.right.pretty(f);
+    }
+}
+
+impl PrettyPrint for Propagate {
+    fn pretty(&self, f: &mut Formatter) {
+        self.value.pretty(f);
+        f.write("?");
+    }
+}
+
+impl PrettyPrint for Comparaison {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Comparaison::Less { left, right, .. } => pretty_binary(f, left, "<", right),
+            Comparaison::LessEqual { left, right, .. } => pretty_binary(f, left, "<=", right),
+            Comparaison::Greater { left, right, .. } => pretty_binary(f, left, ">", right),
+            Comparaison::GreaterEqual { left, right, .. } => pretty_binary(f, left, ">=", right),
+        }
+    }
+}
+
+impl PrettyPrint for Equation {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Equation::Equal { left, right, .. } => pretty_binary(f, left, "==", right),
+            Equation::NotEqual { left, right, .. } => pretty_binary(f, left, "!=", right),
+        }
+    }
+}
+
+impl PrettyPrint for LogicalAnd {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "and", &self.right);
+    }
+}
+
+impl PrettyPrint for LogicalOr {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.left, "or", &self.right);
+    }
+}
+
+impl PrettyPrint for Range {
+    fn pretty(&self, f: &mut Formatter) {
+        self.lower.pretty(f);
+        f.write(if self.inclusive { "..=" } else { ".." });
+        self.upper.pretty(f);
+    }
+}
+
+impl PrettyPrint for Membership {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_binary(f, &self.item, "in", &self.collection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::TryParse;
+
+    use super::*;
+
+    fn round_trip(source: &str) -> String {
+        let expr = Expression::parse(source.into())
+            .expect("Parsing should have succeeded")
+            .1;
+        let printed = expr.to_source();
+        let reparsed = Expression::parse(printed.as_str().into())
+            .expect("Parsing should have succeeded")
+            .1;
+        assert_eq!(expr, reparsed);
+        printed
+    }
+
+    #[test]
+    fn valid_format_operations() {
+        assert_eq!(round_trip("1+2*  3"), "1 + 2 * 3");
+        assert_eq!(round_trip("(1+2)*3"), "(1 + 2) * 3");
+        assert_eq!(
+            round_trip("a>2 and !( b==3 ) or c"),
+            "a > 2 and !(b == 3) or c"
+        );
+        assert_eq!(round_trip("x as u64 << 2"), "x as u64 << 2");
+        assert_eq!(round_trip("3 in 0..10"), "3 in 0..10");
+        assert_eq!(round_trip("1..=10"), "1..=10");
+        assert_eq!(round_trip("p.x . y[0].1"), "p.x.y[0].1");
+        assert_eq!(round_trip("s.f( 1,2 ).g()"), "s.f(1, 2).g()");
+        assert_eq!(round_trip("f(1)?"), "f(1)?");
+    }
+}
@@ -1,7 +1,19 @@
 use crate::ast::expressions::locate::Locatable;
-use crate::semantic::scope::static_types::{SliceType, POINTER_SIZE};
-use crate::vm::asm::alloc::Access;
-use crate::vm::asm::locate::{LocateIndex, LocateOffset};
+use crate::ast::expressions::{data::Data, Atomic};
+use crate::semantic::scope::static_types::{
+    AddrType, MapType, RangeType, SliceType, VecType, POINTER_SIZE,
+};
+use crate::semantic::scope::user_types::UserType;
+use crate::vm::allocator::vtable::VTable;
+use crate::vm::asm::alloc::{Access, Alloc};
+use crate::vm::asm::branch::{BranchIf, Goto, Label};
+use crate::vm::asm::locate::{LocateIndex, LocateOffset, LocateOffsetFromStackPointer};
+use crate::vm::asm::mem::Mem;
+use crate::vm::core::{
+    iter::{IterAsm, Iterable},
+    map::{DerefHashing, MapAsm},
+    CoreAsm,
+};
 use crate::vm::{CodeGenerationError, GenerateCode};
 use crate::{
     semantic::{scope::static_types::StaticType, EType, SizeOf, TypeOf},
@@ -15,7 +27,7 @@ use crate::{
     },
 };
 
-use super::{ExprCall, FieldAccess, ListAccess, TupleAccess};
+use super::{ExprCall, FieldAccess, ListAccess, Propagate, TupleAccess};
 
 impl GenerateCode for super::UnaryOperation {
     fn gencode<E: crate::vm::external::Engine>(
@@ -30,7 +42,8 @@ impl GenerateCode for super::UnaryOperation {
                 let Some(value_type) = value.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = value.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    value.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Minus(Minus {
@@ -40,7 +53,8 @@ impl GenerateCode for super::UnaryOperation {
                 Ok(())
             }
             super::UnaryOperation::Not { value, metadata: _ } => {
-                let _ = value.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    value.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Not(Not()),
@@ -166,12 +180,40 @@ impl GenerateCode for FieldAccess {
                 // the address was pushed on the stack
                 self.field
                     .runtime_access(scope_manager, scope_id, instructions)?;
+
+                if is_temporary(&self.var) {
+                    // the accessed value sits above the temporary it was read from
+                    // move it down to the base of the temporary and discard the rest
+                    let Some(var_type) = self.var.signature() else {
+                        return Err(CodeGenerationError::UnresolvedError);
+                    };
+                    let Some(field_type) = self.metadata.signature() else {
+                        return Err(CodeGenerationError::UnresolvedError);
+                    };
+                    let var_size = var_type.size_of();
+                    let field_size = field_type.size_of();
+                    instructions.push(Asm::OffsetSP(LocateOffsetFromStackPointer {
+                        offset: var_size + field_size,
+                    }));
+                    instructions.push(Asm::Mem(Mem::Take { size: field_size }));
+                    instructions.push(Asm::Pop(var_size - field_size + POINTER_SIZE));
+                }
             }
         }
         Ok(())
     }
 }
 
+fn is_temporary(expr: &super::Expression) -> bool {
+    match expr {
+        super::Expression::ExprCall(_) => true,
+        super::Expression::Propagate(_) => true,
+        super::Expression::Atomic(Atomic::Data(Data::Call(_))) => true,
+        super::Expression::Atomic(Atomic::Paren(value)) => is_temporary(value),
+        _ => false,
+    }
+}
+
 impl GenerateCode for ExprCall {
     fn gencode<E: crate::vm::external::Engine>(
         &self,
@@ -232,8 +274,9 @@ impl GenerateCode for super::Product {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Mult(Mult {
@@ -255,8 +298,9 @@ impl GenerateCode for super::Product {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Div(Division {
@@ -278,8 +322,9 @@ impl GenerateCode for super::Product {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Mod(Mod {
@@ -310,10 +355,10 @@ impl GenerateCode for super::Addition {
         };
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::Addition(Addition {
@@ -342,10 +387,10 @@ impl GenerateCode for super::Substraction {
         };
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::Substraction(Substraction {
@@ -378,8 +423,9 @@ impl GenerateCode for super::Shift {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::ShiftLeft(ShiftLeft {
@@ -401,8 +447,9 @@ impl GenerateCode for super::Shift {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::ShiftRight(ShiftRight {
@@ -433,10 +480,10 @@ impl GenerateCode for super::BitwiseAnd {
         };
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::BitwiseAnd(BitwiseAnd {
@@ -465,10 +512,10 @@ impl GenerateCode for super::BitwiseXOR {
         };
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::BitwiseXOR(BitwiseXOR {
@@ -497,10 +544,10 @@ impl GenerateCode for super::BitwiseOR {
         };
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::BitwiseOR(BitwiseOR {
@@ -531,6 +578,34 @@ impl GenerateCode for super::Cast {
             .left
             .gencode::<E>(scope_manager, scope_id, instructions, context)?;
 
+        if let EType::User { id, .. } = right_type {
+            if let Ok(UserType::Interface(_)) = scope_manager.find_type_by_id(id, scope_id) {
+                let (type_id, owned) = match left_type {
+                    EType::User { id: type_id, .. } if type_id == id => {
+                        // already an interface value
+                        return Ok(());
+                    }
+                    EType::User { id: type_id, size } => {
+                        // the value is moved to the heap, the interface value owns it until freed
+                        instructions.push(Asm::Alloc(Alloc::Heap { size }));
+                        instructions.push(Asm::Mem(Mem::Take { size }));
+                        (type_id, VTable::OWNED)
+                    }
+                    // an interface value cast from an address borrows the data
+                    EType::Static(StaticType::Address(AddrType(sub_type))) => match *sub_type {
+                        EType::User { id: type_id, .. } => (type_id, 0),
+                        _ => return Err(CodeGenerationError::UnresolvedError),
+                    },
+                    _ => return Err(CodeGenerationError::UnresolvedError),
+                };
+                let vtable_idx = instructions.vtable.index(id, type_id) | owned;
+                instructions.push(Asm::Data(crate::vm::asm::data::Data::Serialized {
+                    data: vtable_idx.to_le_bytes().into(),
+                }));
+                return Ok(());
+            }
+        }
+
         let op_left_type: Result<OpPrimitive, CodeGenerationError> = left_type.try_into();
         let op_right_type: Result<OpPrimitive, CodeGenerationError> = right_type.try_into();
 
@@ -548,6 +623,76 @@ impl GenerateCode for super::Cast {
     }
 }
 
+impl GenerateCode for Propagate {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let Some(value_type) = self.value.signature() else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let Some((_, payload)) = Propagate::split_error(&value_type) else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let Some((return_values, return_payload)) = self
+            .return_type
+            .as_ref()
+            .and_then(|return_type| Propagate::split_error(return_type))
+        else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let Some(return_label) = context.return_label else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let payload_size = payload.map(|payload| payload.size_of()).unwrap_or(0);
+
+        let _ = self
+            .value
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+
+        let ok_label = Label::gen();
+        instructions.push(Asm::If(BranchIf {
+            else_label: ok_label,
+        }));
+
+        // the error is returned along zeroed values
+        let values_size: usize = return_values.iter().map(|value| value.size_of()).sum();
+        if values_size > 0 {
+            instructions.push(Asm::Data(crate::vm::asm::data::Data::Serialized {
+                data: vec![0; values_size].into(),
+            }));
+        }
+        if let Some(return_payload) = return_payload {
+            if payload_size > 0 {
+                // copy the payload of the error on top of the zeroed values
+                instructions.push(Asm::OffsetSP(LocateOffsetFromStackPointer {
+                    offset: values_size + payload_size,
+                }));
+                instructions.push(Asm::Access(Access::Runtime {
+                    size: Some(payload_size),
+                }));
+            } else {
+                instructions.push(Asm::Data(crate::vm::asm::data::Data::Serialized {
+                    data: vec![0; return_payload.size_of()].into(),
+                }));
+            }
+        }
+        instructions.push(Asm::Core(CoreAsm::Error));
+        instructions.push(Asm::Goto(Goto {
+            label: Some(return_label),
+        }));
+
+        instructions.push_label_by_id(ok_label, "ok".to_string().into());
+        if payload_size > 0 {
+            instructions.push(Asm::Pop(payload_size));
+        }
+        Ok(())
+    }
+}
+
 impl GenerateCode for super::Comparaison {
     fn gencode<E: crate::vm::external::Engine>(
         &self,
@@ -568,8 +713,9 @@ impl GenerateCode for super::Comparaison {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Less(Less {
@@ -591,8 +737,9 @@ impl GenerateCode for super::Comparaison {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::LessEqual(LessEqual {
@@ -614,8 +761,9 @@ impl GenerateCode for super::Comparaison {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Greater(Greater {
@@ -637,8 +785,9 @@ impl GenerateCode for super::Comparaison {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::GreaterEqual(GreaterEqual {
@@ -673,8 +822,9 @@ impl GenerateCode for super::Equation {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::Equal(Equal {
@@ -696,8 +846,9 @@ impl GenerateCode for super::Equation {
                 let Some(right_type) = right.signature() else {
                     return Err(CodeGenerationError::UnresolvedError);
                 };
-                let _ = left.gencode::<E>(scope_manager, scope_id, instructions, context)?;
-                let _ = right.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ = left.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
+                let _ =
+                    right.gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
                 instructions.push(Asm::Operation(Operation {
                     kind: OperationKind::NotEqual(NotEqual {
@@ -722,10 +873,10 @@ impl GenerateCode for super::LogicalAnd {
     ) -> Result<(), crate::vm::CodeGenerationError> {
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::LogicalAnd(LogicalAnd()),
@@ -745,10 +896,10 @@ impl GenerateCode for super::LogicalOr {
     ) -> Result<(), crate::vm::CodeGenerationError> {
         let _ = self
             .left
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
         let _ = self
             .right
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            .gencode_folded::<E>(scope_manager, scope_id, instructions, context)?;
 
         instructions.push(Asm::Operation(Operation {
             kind: OperationKind::LogicalOr(LogicalOr()),
@@ -758,9 +909,85 @@ impl GenerateCode for super::LogicalOr {
     }
 }
 
+impl GenerateCode for super::Range {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        // a range is its two bounds laid out one after the other
+        let _ = self
+            .lower
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        let _ = self
+            .upper
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        Ok(())
+    }
+}
+
+impl GenerateCode for super::Membership {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let (Some(collection_type), Some(item_type)) =
+            (self.collection.signature(), self.item.signature())
+        else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let ref_access = DerefHashing::from(&item_type);
+
+        let _ = self
+            .collection
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        let _ = self
+            .item
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+
+        let iterable = match collection_type {
+            EType::Static(StaticType::Map(MapType {
+                keys_type,
+                values_type,
+            })) => {
+                instructions.push(Asm::Core(CoreAsm::Map(MapAsm::Contains {
+                    item_size: values_type.size_of(),
+                    key_size: keys_type.size_of(),
+                    ref_access,
+                })));
+                return Ok(());
+            }
+            EType::Static(StaticType::Range(RangeType { num, inclusive })) => {
+                Iterable::Range { num, inclusive }
+            }
+            EType::Static(StaticType::Vec(VecType(item_type))) => Iterable::Vec {
+                item_size: item_type.size_of(),
+            },
+            EType::Static(StaticType::Slice(SliceType { size, item_type })) => Iterable::Slice {
+                len: size,
+                item_size: item_type.size_of(),
+            },
+            EType::Static(StaticType::String(_)) => Iterable::String,
+            EType::Static(StaticType::StrSlice(_)) => Iterable::StrSlice,
+            _ => return Err(CodeGenerationError::UnresolvedError),
+        };
+        instructions.push(Asm::Core(CoreAsm::Iter(IterAsm::Contains {
+            iterable,
+            ref_access,
+        })));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use crate::vm::core::{ERROR_VALUE, OK_VALUE};
     use crate::{test_extract_variable, test_statements};
 
     #[test]
@@ -1473,4 +1700,220 @@ mod tests {
             assert_fn,
         );
     }
+
+    #[test]
+    fn valid_propagate() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 13);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 0);
+            let res = test_extract_variable::<u64>("err2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 7);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 42);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 9);
+            let res = test_extract_variable::<u8>("err5", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, ERROR_VALUE);
+            let res = test_extract_variable::<u8>("err6", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, OK_VALUE);
+            true
+        }
+
+        test_statements(
+            r##"
+        fn check(x : i64) -> (i64, Error[u64]) {
+            if x < 0 {
+                return (0, Err(7));
+            }
+            return (x * 2, Ok());
+        }
+
+        fn twice(x : i64) -> (i64, Error[u64]) {
+            let y = check(x)?;
+            let z = check(y)?;
+            return (z + 1, Ok());
+        }
+
+        fn valid(x : i64) -> Error {
+            let _ = check(x)?;
+            return Ok();
+        }
+
+        let (res1, err1) = twice(3);
+        let (res2, err2) = twice(-1);
+        let res3 = try { twice(-1) } else { 42 };
+        let res4 = try { twice(2) } else { 42 };
+        let err5 = valid(-1);
+        let err6 = valid(1);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_membership() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let expected = [
+                ("res1", 1u8),
+                ("res2", 0),
+                ("res3", 1),
+                ("res4", 0),
+                ("res5", 1),
+                ("res6", 0),
+                ("res7", 1),
+                ("res8", 0),
+                ("res9", 1),
+                ("res10", 1),
+                ("res11", 0),
+                ("res12", 1),
+                ("res13", 0),
+                ("res14", 1),
+                ("res15", 0),
+                ("res16", 1),
+            ];
+            for (name, value) in expected {
+                let res = test_extract_variable::<u8>(name, scope_manager, stack, heap)
+                    .expect("Deserialization should have succeeded");
+                assert_eq!(res, value, "{}", name);
+            }
+            true
+        }
+
+        test_statements(
+            r##"
+        let values = vec[1,2,3];
+        let res1 = 2 in values;
+        let res2 = 4 in values;
+        let res3 = 3 in [1,2,3];
+        let res4 = 0 in [1,2,3];
+
+        let hmap : Map[u64]u64 = map {
+            101 : 5,
+            102 : 6,
+        };
+        let res5 = 102 in hmap;
+        let res6 = 103 in hmap;
+
+        let text = string("Hello World");
+        let res7 = 'W' in text;
+        let res8 = 'w' in text;
+        let res9 = "lo W" in text;
+        let res10 = string("World") in "Hello World";
+        let res11 = "worlds" in text;
+
+        let res12 = 5 in 0..10;
+        let res13 = 10 in 0..10;
+        let res14 = 10 in 0..=10;
+        let res15 = -1 in 0..10;
+        let res16 = string("b") in vec[string("a"), string("b")];
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_constant_folding() {
+        use crate::{
+            ast::{statements::Statement, TryParse},
+            semantic::{scope::scope::ScopeManager, Resolve},
+            vm::{
+                asm::Asm,
+                external::test::NoopEngine,
+                program::{Instruction, Program},
+                CodeGenerationContext, GenerateCode,
+            },
+        };
+
+        fn count_operations(input: &str) -> usize {
+            let mut scope_manager = ScopeManager::default();
+            let mut program = Program::<NoopEngine>::default();
+            for statement in input.split_inclusive(';') {
+                let mut statement = Statement::parse(statement.into()).unwrap().1;
+                statement
+                    .resolve::<NoopEngine>(&mut scope_manager, None, &None, &mut ())
+                    .expect("Resolution should have succeeded");
+                statement
+                    .gencode::<NoopEngine>(
+                        &mut scope_manager,
+                        None,
+                        &mut program,
+                        &CodeGenerationContext::default(),
+                    )
+                    .expect("Code generation should have succeeded");
+            }
+            program
+                .instructions
+                .iter()
+                .filter(|instruction| matches!(instruction, Instruction::Asm(Asm::Operation(_))))
+                .count()
+        }
+
+        assert_eq!(count_operations("let x = (2 + 3) * 4 - 1 << 2;"), 0);
+        assert_eq!(count_operations("let x = 2 < 3 and !(4 == 5);"), 0);
+        assert_eq!(count_operations("let x = 2; let y = x * (3 + 4);"), 1);
+        assert_eq!(
+            count_operations("let x = 2; let y = -(x + 1) * (3 + -(4 * 2)) == (2 + 1);"),
+            4
+        );
+        // failing operations are left to the runtime
+        assert_eq!(count_operations("let x = 255u8 + 1u8;"), 1);
+        assert_eq!(count_operations("let x = 1 / 0;"), 1);
+
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, ((2 + 3) * 4 - 1) << 2);
+            let res = test_extract_variable::<i8>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, -(7 % 4) ^ 1);
+            let res = test_extract_variable::<u8>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 12 * (3 + 4));
+            true
+        }
+
+        test_statements(
+            r##"
+        let res1 = ((2 + 3) * 4 - 1) << 2;
+        let res2 : i8 = -(7 % 4) ^ 1;
+        let res3 = 2.5 * 2.0 >= 5.0 or false;
+        let x = 12;
+        let res4 = x * (3 + 4);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
 }
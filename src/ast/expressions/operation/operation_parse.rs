@@ -12,7 +12,7 @@ use crate::{
         expressions::data::CallArgs,
         types::Type,
         utils::{
-            io::{PResult, Span},
+            io::{spanned, PResult, Span},
             lexem,
             strings::{wst, wst_closed},
         },
@@ -23,8 +23,8 @@ use crate::{
 
 use super::{
     Addition, Atomic, BitwiseAnd, BitwiseOR, BitwiseXOR, Cast, Comparaison, Equation, ExprCall,
-    Expression, FieldAccess, ListAccess, LogicalAnd, LogicalOr, Product, Shift, Substraction,
-    TupleAccess, UnaryOperation,
+    Expression, FieldAccess, ListAccess, LogicalAnd, LogicalOr, Membership, Product, Propagate,
+    Range, Shift, Substraction, TupleAccess, UnaryOperation,
 };
 
 impl TryParse for UnaryOperation {
@@ -106,7 +106,7 @@ impl TryParseOperation for ExprCall {
     where
         Self: Sized,
     {
-        let (remainder, left) = FieldAccess::parse_atomic(input)?;
+        let (remainder, left) = spanned(FieldAccess::parse_atomic)(input)?;
         let (_, peeked) = opt(peek(wst(lexem::RANGE_SEP)))(remainder)?;
         if peeked.is_some() {
             return Ok((remainder, left));
@@ -133,7 +133,7 @@ impl TryParseOperation for TupleAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = ExprCall::parse(input)?;
+        let (remainder, left) = spanned(ExprCall::parse)(input)?;
         let (_, peeked_result) = opt(peek(preceded(
             wst(lexem::DOT),
             take_while_m_n(1, usize::MAX, |c: char| c.is_digit(10)),
@@ -172,7 +172,7 @@ impl TryParseAtomicOperation for TupleAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = ExprCall::parse_atomic(input)?;
+        let (remainder, left) = spanned(ExprCall::parse_atomic)(input)?;
         let (_, peeked_result) = opt(peek(preceded(
             wst(lexem::DOT),
             take_while_m_n(1, usize::MAX, |c: char| c.is_digit(10)),
@@ -211,7 +211,7 @@ impl TryParseAtomicOperation for ListAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = TupleAccess::parse_atomic(input)?;
+        let (remainder, left) = spanned(TupleAccess::parse_atomic)(input)?;
         let (remainder, index) = opt(delimited(
             wst(lexem::SQ_BRA_O),
             cut(Expression::parse.context("Invalid list accessing expression")),
@@ -238,7 +238,7 @@ impl TryParseOperation for ListAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = TupleAccess::parse(input)?;
+        let (remainder, left) = spanned(TupleAccess::parse)(input)?;
         let (remainder, index) = opt(delimited(
             wst(lexem::SQ_BRA_O),
             cut(Expression::parse.context("Invalid list accessing expression")),
@@ -265,7 +265,7 @@ impl TryParseOperation for FieldAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = ListAccess::parse(input)?;
+        let (remainder, left) = spanned(ListAccess::parse)(input)?;
         let (_, peeked) = opt(peek(wst(lexem::RANGE_SEP)))(remainder)?;
         if peeked.is_some() {
             return Ok((remainder, left));
@@ -293,8 +293,9 @@ impl TryParseOperation for FieldAccess {
                     }),
                 ));
             }
-            let (remainder, right) =
-                cut(FieldAccess::parse.context("Invalid field accessing expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(FieldAccess::parse).context("Invalid field accessing expression")
+            )(remainder)?;
             Ok((
                 remainder,
                 Expression::FieldAccess(FieldAccess {
@@ -314,7 +315,7 @@ impl TryParseAtomicOperation for FieldAccess {
     where
         Self: Sized,
     {
-        let (remainder, left) = ListAccess::parse_atomic(input)?;
+        let (remainder, left) = spanned(ListAccess::parse_atomic)(input)?;
         let (_, peeked) = opt(peek(wst(lexem::RANGE_SEP)))(remainder)?;
         if peeked.is_some() {
             return Ok((remainder, left));
@@ -342,8 +343,9 @@ impl TryParseAtomicOperation for FieldAccess {
                     }),
                 ));
             }
-            let (remainder, right) =
-                cut(FieldAccess::parse.context("Invalid field accessing expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(FieldAccess::parse).context("Invalid field accessing expression")
+            )(remainder)?;
             Ok((
                 remainder,
                 Expression::FieldAccess(FieldAccess {
@@ -372,7 +374,7 @@ impl TryParseOperation for Product {
      * HighM := Atom (* | / | % ) Atom | Atom
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = Cast::parse(input)?;
+        let (remainder, left) = spanned(Cast::parse)(input)?;
         let (remainder, op) = opt(alt((
             value(HighOrdMathOPERATOR::Mult, wst(lexem::MULT)),
             value(HighOrdMathOPERATOR::Div, wst(lexem::DIV)),
@@ -380,8 +382,9 @@ impl TryParseOperation for Product {
         )))(remainder)?;
 
         if let Some(op) = op {
-            let (remainder, right) =
-                cut(Product::parse.context("Invalid product (*,/,%) expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(Product::parse).context("Invalid product (*,/,%) expression")
+            )(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -424,15 +427,16 @@ impl TryParseOperation for Addition {
      * LowM := HighM + HighM | HighM
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = Product::parse(input)?;
+        let (remainder, left) = spanned(Product::parse)(input)?;
         let (remainder, op) = opt(alt((
             value(LowOrdMathOPERATOR::Add, wst(lexem::ADD)),
             value(LowOrdMathOPERATOR::Minus, wst(lexem::MINUS)),
         )))(remainder)?;
 
         if let Some(op) = op {
-            let (remainder, right) =
-                cut(Addition::parse.context("Invalid addition (+,-) expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(Addition::parse).context("Invalid addition (+,-) expression")
+            )(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -469,15 +473,16 @@ impl TryParseOperation for Shift {
      * Shift := LowM (<<|>>) LowM | LowM
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = Addition::parse(input)?;
+        let (remainder, left) = spanned(Addition::parse)(input)?;
         let (remainder, op) = opt(alt((
             value(ShiftOPERATOR::Left, wst_closed(lexem::SHL)),
             value(ShiftOPERATOR::Right, wst_closed(lexem::SHR)),
         )))(remainder)?;
 
         if let Some(op) = op {
-            let (remainder, right) =
-                cut(Shift::parse.context("Invalid shift ( >> , << ) expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(Shift::parse).context("Invalid shift ( >> , << ) expression")
+            )(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -509,12 +514,13 @@ impl TryParseOperation for BitwiseAnd {
      * BAnd := Shift & Shift | Shift
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = Shift::parse(input)?;
+        let (remainder, left) = spanned(Shift::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::BAND))(remainder)?;
 
         if let Some(_op) = op {
-            let (remainder, right) =
-                cut(BitwiseAnd::parse.context("Invalid bitwise and expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(BitwiseAnd::parse).context("Invalid bitwise and expression")
+            )(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -539,12 +545,13 @@ impl TryParseOperation for BitwiseXOR {
      * XOr := BAnd ^ BAnd | BAnd
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = BitwiseAnd::parse(input)?;
+        let (remainder, left) = spanned(BitwiseAnd::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::XOR))(remainder)?;
 
         if let Some(_op) = op {
-            let (remainder, right) =
-                cut(BitwiseXOR::parse.context("Invalid bitwise xor expression"))(remainder)?;
+            let (remainder, right) = cut(
+                spanned(BitwiseXOR::parse).context("Invalid bitwise xor expression")
+            )(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -569,12 +576,12 @@ impl TryParseOperation for BitwiseOR {
      * BOr := XOr \| XOr  | XOr
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = BitwiseXOR::parse(input)?;
+        let (remainder, left) = spanned(BitwiseXOR::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::BOR))(remainder)?;
 
         if let Some(_op) = op {
             let (remainder, right) =
-                cut(BitwiseOR::parse.context("Invalid bitwise or expression"))(remainder)?;
+                cut(spanned(BitwiseOR::parse).context("Invalid bitwise or expression"))(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -596,10 +603,10 @@ impl TryParseOperation for Cast {
      * @desc Parse bitwise xor operation
      *
      * @grammar
-     * Cast := Atom as Type | Atom
+     * Cast := Propagate as Type | Propagate
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = FieldAccess::parse(input)?;
+        let (remainder, left) = spanned(Propagate::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::AS))(remainder)?;
 
         if let Some(_op) = op {
@@ -619,6 +626,32 @@ impl TryParseOperation for Cast {
     }
 }
 
+impl TryParseOperation for Propagate {
+    /*
+     * @desc Parse error propagation operation
+     *
+     * @grammar
+     * Propagate := Atom ? | Atom
+     */
+    fn parse(input: Span) -> PResult<Expression> {
+        let (remainder, value) = spanned(FieldAccess::parse)(input)?;
+        let (remainder, op) = opt(wst(lexem::PROPAGATE))(remainder)?;
+
+        if let Some(_op) = op {
+            Ok((
+                remainder,
+                Expression::Propagate(Propagate {
+                    value: value.into(),
+                    return_type: None,
+                    metadata: Metadata::default(),
+                }),
+            ))
+        } else {
+            Ok((remainder, value))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum ComparaisonOPERATOR {
     Less,
@@ -627,16 +660,17 @@ enum ComparaisonOPERATOR {
     GreaterEqual,
     Equal,
     NotEqual,
+    In,
 }
 impl TryParseOperation for Comparaison {
     /*
      * @desc Parse comparaison operation
      *
      * @grammar
-     * CompOp := BOr (< |<= | >= | > | == | != | in ) BOr | BOr
+     * CompOp := BOr (< |<= | >= | > | == | != ) BOr | BOr in Collection | BOr
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = BitwiseOR::parse(input)?;
+        let (remainder, left) = spanned(BitwiseOR::parse)(input)?;
         let (remainder, op) = opt(alt((
             value(ComparaisonOPERATOR::LessEqual, wst(lexem::ELE)),
             value(ComparaisonOPERATOR::Less, wst(lexem::LE)),
@@ -644,13 +678,20 @@ impl TryParseOperation for Comparaison {
             value(ComparaisonOPERATOR::Greater, wst(lexem::GE)),
             value(ComparaisonOPERATOR::Equal, wst(lexem::EQ)),
             value(ComparaisonOPERATOR::NotEqual, wst(lexem::NEQ)),
+            value(ComparaisonOPERATOR::In, wst_closed(lexem::IN)),
         )))(remainder)?;
 
         if let Some(op) = op {
-            let (remainder, right) = cut(BitwiseOR::parse
-                .context("Invalid comparaison ( == , != , <=, >=, <, >) expression"))(
-                remainder
-            )?;
+            let (remainder, right) = match op {
+                ComparaisonOPERATOR::In => cut(spanned(Membership::parse_collection)
+                    .context("Invalid membership ( in ) expression"))(
+                    remainder
+                )?,
+                _ => cut(spanned(BitwiseOR::parse)
+                    .context("Invalid comparaison ( == , != , <=, >=, <, >) expression"))(
+                    remainder,
+                )?,
+            };
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -690,6 +731,11 @@ impl TryParseOperation for Comparaison {
                         right,
                         metadata: Metadata::default(),
                     }),
+                    ComparaisonOPERATOR::In => Expression::Membership(Membership {
+                        item: left,
+                        collection: right,
+                        metadata: Metadata::default(),
+                    }),
                 },
             ))
         } else {
@@ -706,12 +752,12 @@ impl TryParseOperation for LogicalAnd {
      * AndOp := CompOp And CompOp | CompOp
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = Comparaison::parse(input)?;
+        let (remainder, left) = spanned(Comparaison::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::AND))(remainder)?;
 
         if let Some(_op) = op {
             let (remainder, right) =
-                cut(LogicalAnd::parse.context("Invalid and expression"))(remainder)?;
+                cut(spanned(LogicalAnd::parse).context("Invalid and expression"))(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -736,12 +782,12 @@ impl TryParseOperation for LogicalOr {
      * Expr := AndOp Or AndOp | AndOp
      */
     fn parse(input: Span) -> PResult<Expression> {
-        let (remainder, left) = LogicalAnd::parse(input)?;
+        let (remainder, left) = spanned(LogicalAnd::parse)(input)?;
         let (remainder, op) = opt(wst_closed(lexem::OR))(remainder)?;
 
         if let Some(_op) = op {
             let (remainder, right) =
-                cut(LogicalOr::parse.context("Invalid or expression"))(remainder)?;
+                cut(spanned(LogicalOr::parse).context("Invalid or expression"))(remainder)?;
             let left = Box::new(left);
             let right = Box::new(right);
             Ok((
@@ -758,6 +804,70 @@ impl TryParseOperation for LogicalOr {
     }
 }
 
+impl Membership {
+    /*
+     * @desc Parse the collection of a membership test, a range being accepted without parenthesis
+     *
+     * @grammar
+     * Collection := BOr .. BOr | BOr ..= BOr | BOr
+     */
+    fn parse_collection(input: Span) -> PResult<Expression> {
+        let (remainder, left) = spanned(BitwiseOR::parse)(input)?;
+        let (remainder, op) = opt(alt((
+            value(true, wst(lexem::RANGE_INCL_SEP)),
+            value(false, wst(lexem::RANGE_SEP)),
+        )))(remainder)?;
+
+        if let Some(inclusive) = op {
+            let (remainder, right) =
+                cut(spanned(BitwiseOR::parse).context("Invalid range expression"))(remainder)?;
+            Ok((
+                remainder,
+                Expression::Range(Range {
+                    lower: Box::new(left),
+                    upper: Box::new(right),
+                    inclusive,
+                    metadata: Metadata::default(),
+                }),
+            ))
+        } else {
+            Ok((remainder, left))
+        }
+    }
+}
+
+impl TryParseOperation for Range {
+    /*
+     * @desc Parse range
+     *
+     * @grammar
+     * Range := OrOp .. OrOp | OrOp ..= OrOp | OrOp
+     */
+    fn parse(input: Span) -> PResult<Expression> {
+        let (remainder, left) = spanned(LogicalOr::parse)(input)?;
+        let (remainder, op) = opt(alt((
+            value(true, wst(lexem::RANGE_INCL_SEP)),
+            value(false, wst(lexem::RANGE_SEP)),
+        )))(remainder)?;
+
+        if let Some(inclusive) = op {
+            let (remainder, right) =
+                cut(spanned(LogicalOr::parse).context("Invalid range expression"))(remainder)?;
+            Ok((
+                remainder,
+                Expression::Range(Range {
+                    lower: Box::new(left),
+                    upper: Box::new(right),
+                    inclusive,
+                    metadata: Metadata::default(),
+                }),
+            ))
+        } else {
+            Ok((remainder, left))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1130,4 +1240,57 @@ mod tests {
             value
         );
     }
+
+    #[test]
+    fn valid_range() {
+        let res = Range::parse("0..10".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Expression::Range(Range {
+                lower: Box::new(Expression::Atomic(Atomic::Data(Data::Primitive(v_num!(
+                    Unresolved, 0
+                ))))),
+                upper: Box::new(Expression::Atomic(Atomic::Data(Data::Primitive(v_num!(
+                    Unresolved, 10
+                ))))),
+                inclusive: false,
+                metadata: Metadata::default()
+            }),
+            value
+        );
+
+        let res = Range::parse("1 ..= x + 1".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert!(matches!(
+            value,
+            Expression::Range(Range {
+                inclusive: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn valid_membership() {
+        let res = Comparaison::parse("x in values".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert!(matches!(value, Expression::Membership(_)));
+
+        let res = Comparaison::parse("x in 0..=10".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        let Expression::Membership(Membership { collection, .. }) = value else {
+            panic!("Expected a membership expression");
+        };
+        assert!(matches!(
+            *collection,
+            Expression::Range(Range {
+                inclusive: true,
+                ..
+            })
+        ));
+    }
 }
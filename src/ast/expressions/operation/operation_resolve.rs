@@ -1,18 +1,30 @@
 use super::{
     Addition, BitwiseAnd, BitwiseOR, BitwiseXOR, Cast, Comparaison, Equation, ExprCall,
-    FieldAccess, ListAccess, LogicalAnd, LogicalOr, Product, Shift, Substraction, TupleAccess,
-    UnaryOperation,
+    FieldAccess, ListAccess, LogicalAnd, LogicalOr, Membership, Product, Propagate, Range, Shift,
+    Substraction, TupleAccess, UnaryOperation,
 };
 
-use crate::ast::expressions::{Atomic, Expression};
+use std::cmp::Ordering;
+
+use crate::ast::expressions::data::{
+    Address, Call, CallArgs, CoreCall, Data, DynamicCall, LeftCall, Number, Primitive, PtrAccess,
+    VarCall,
+};
+use crate::ast::expressions::{Atomic, CompletePath, Expression, Path};
 use crate::p_num;
+use crate::semantic::scope::scope::{MethodInfo, MethodReceiver};
 use crate::semantic::scope::static_types::{
-    ClosureType, FunctionType, LambdaType, NumberType, PrimitiveType, SliceType, StaticType,
-    TupleType, VecType,
+    AddrType, ClosureType, FunctionType, LambdaType, MapType, NumberType, PrimitiveType, RangeType,
+    SliceType, StaticType, TupleType, VecType,
 };
 use crate::semantic::scope::user_types::{Struct, UserType};
-use crate::semantic::{Desugar, EType, Resolve, ResolveCore, ResolveNumber, SemanticError, TypeOf};
-use crate::semantic::{Info, ResolveFromStruct, SizeOf};
+use crate::semantic::{
+    CompatibleWith, ConstEval, Desugar, EType, Resolve, ResolveCore, ResolveNumber, SemanticError,
+    TypeOf,
+};
+use crate::semantic::{Info, Metadata, ResolveFromStruct, SizeOf};
+use crate::vm::asm::math_operation::MathOperator;
+use crate::vm::core::Core;
 
 impl Resolve for UnaryOperation {
     type Output = ();
@@ -67,7 +79,13 @@ impl Resolve for UnaryOperation {
                 let _ = value.resolve::<E>(scope_manager, scope_id, context, &mut None)?;
                 let value_type = value.type_of(&scope_manager, scope_id)?;
                 let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) = value_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched(
+                        &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                        &value_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(value.span()));
                 };
 
                 metadata.info = Info::Resolved {
@@ -332,6 +350,25 @@ impl Resolve for FieldAccess {
             .var
             .resolve::<E>(scope_manager, scope_id, context, extra)?;
 
+        let var_type = self.var.type_of(&scope_manager, scope_id)?;
+        if let EType::Static(StaticType::Address(AddrType(sub_type))) = &var_type {
+            if let EType::User { .. } = sub_type.as_ref() {
+                // field access through an address : the address is dereferenced
+                self.var = Box::new(Expression::Atomic(Atomic::Data(Data::PtrAccess(
+                    PtrAccess {
+                        value: Box::new(Atomic::Paren(self.var.clone())),
+                        metadata: Metadata {
+                            info: Info::Resolved {
+                                context: None,
+                                signature: Some(sub_type.as_ref().clone()),
+                            },
+                            ..Metadata::default()
+                        },
+                    },
+                ))));
+            }
+        }
+
         let EType::User { id, .. } = self.var.type_of(&scope_manager, scope_id)? else {
             return Err(SemanticError::ExpectedStruct);
         };
@@ -351,6 +388,235 @@ impl Resolve for FieldAccess {
     }
 }
 
+impl FieldAccess {
+    pub fn is_method_call(&self) -> bool {
+        has_method_call(&self.field)
+    }
+
+    /*
+     * @desc a.b.method() is parsed as a.(b.method()) : rotate the chain so that a.b is the receiver
+     */
+    fn rotate_method_chain(&mut self) {
+        while let Expression::FieldAccess(FieldAccess { var, field, .. }) = self.field.as_ref() {
+            if !has_method_call(var) && !has_method_call(field) {
+                break;
+            }
+            let (var, field) = (var.clone(), field.clone());
+            self.var = Box::new(Expression::FieldAccess(FieldAccess {
+                var: self.var.clone(),
+                field: var,
+                metadata: Metadata::default(),
+            }));
+            self.field = field;
+        }
+    }
+
+    /*
+     * @desc Rewrite a method call into a call to the function implementing the method
+     * Methods of user types are found in their implementations, methods of builtin types in the core library
+     * The receiver is passed as the first argument, either by value or by address
+     * Returns None if the field access is not a method call
+     */
+    pub fn resolve_method<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Expression>, SemanticError> {
+        self.rotate_method_chain();
+
+        let Expression::Atomic(Atomic::Data(Data::Call(Call {
+            path:
+                LeftCall::VarCall(VarCall {
+                    path:
+                        CompletePath {
+                            path: Path::Empty,
+                            name,
+                        },
+                    ..
+                }),
+            args,
+            ..
+        }))) = self.field.as_ref()
+        else {
+            return Ok(None);
+        };
+
+        // the receiver is resolved once to find its type, the call takes it resolved
+        let _ = self
+            .var
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+        let receiver_type = self.var.type_of(&scope_manager, scope_id)?;
+        let deref = |signature: &EType| {
+            Expression::Atomic(Atomic::Data(Data::PtrAccess(PtrAccess {
+                value: Box::new(Atomic::Paren(self.var.clone())),
+                metadata: resolved_metadata(signature),
+            })))
+        };
+        let (type_id, is_address) = match &receiver_type {
+            EType::User { id, .. } => (*id, false),
+            EType::Static(StaticType::Address(AddrType(sub_type))) => match sub_type.as_ref() {
+                EType::User { id, .. } => (*id, true),
+                EType::Static(
+                    collection @ (StaticType::Vec(_)
+                    | StaticType::Map(_)
+                    | StaticType::String(_)
+                    | StaticType::StrSlice(_)),
+                ) => {
+                    // the core functions take the collection itself : the address is dereferenced
+                    return core_method_call(collection, name, deref(sub_type), args).map(Some);
+                }
+                _ => {
+                    return core_method_call(
+                        &StaticType::Address(AddrType(sub_type.clone())),
+                        name,
+                        *self.var.clone(),
+                        args,
+                    )
+                    .map(Some);
+                }
+            },
+            EType::Static(static_type) => {
+                return core_method_call(static_type, name, *self.var.clone(), args).map(Some);
+            }
+        };
+        let value_type = match &receiver_type {
+            EType::Static(StaticType::Address(AddrType(sub_type))) => sub_type.as_ref().clone(),
+            receiver_type => receiver_type.clone(),
+        };
+        if let Ok(UserType::Interface(interface)) = scope_manager.find_type_by_id(type_id, scope_id)
+        {
+            // call to a method of an interface : the function is found in the vtable at runtime
+            let Some((method, _)) = interface.find_method(name) else {
+                return Err(SemanticError::UnknownMethod(name.clone()));
+            };
+            let receiver = if is_address {
+                deref(&value_type)
+            } else {
+                *self.var.clone()
+            };
+            let mut call_args = Vec::with_capacity(args.args.len() + 1);
+            call_args.push(receiver);
+            call_args.extend(args.args.iter().cloned());
+
+            return Ok(Some(Expression::Atomic(Atomic::Data(Data::Call(Call {
+                path: LeftCall::DynamicCall(DynamicCall {
+                    interface_id: type_id,
+                    method,
+                }),
+                args: CallArgs {
+                    args: call_args,
+                    size: None,
+                    resolved_receiver: true,
+                },
+                metadata: Metadata::default(),
+            })))));
+        }
+
+        let Some(MethodInfo { function, receiver }) =
+            scope_manager.find_method(type_id, name).cloned()
+        else {
+            return Err(SemanticError::UnknownMethod(name.clone()));
+        };
+
+        let receiver = match (receiver, is_address) {
+            (MethodReceiver::Value, false) | (MethodReceiver::Address, true) => *self.var.clone(),
+            (MethodReceiver::Value, true) => deref(&value_type),
+            (MethodReceiver::Address, false) => {
+                Expression::Atomic(Atomic::Data(Data::Address(Address {
+                    value: Box::new(Atomic::Paren(self.var.clone())),
+                    metadata: resolved_metadata(&EType::Static(StaticType::Address(AddrType(
+                        Box::new(value_type),
+                    )))),
+                })))
+            }
+            (MethodReceiver::None, _) => return Err(SemanticError::UnknownMethod(name.clone())),
+        };
+
+        let mut call_args = Vec::with_capacity(args.args.len() + 1);
+        call_args.push(receiver);
+        call_args.extend(args.args.iter().cloned());
+
+        Ok(Some(Expression::Atomic(Atomic::Data(Data::Call(Call {
+            path: LeftCall::VarCall(VarCall {
+                path: CompletePath {
+                    path: Path::Empty,
+                    name: function,
+                },
+                id: None,
+                is_closure: false,
+            }),
+            args: CallArgs {
+                args: call_args,
+                size: None,
+                resolved_receiver: true,
+            },
+            metadata: Metadata::default(),
+        })))))
+    }
+}
+
+/*
+ * @desc Metadata of a node wrapping the receiver of a method call, resolved along with the receiver
+ */
+fn resolved_metadata(signature: &EType) -> Metadata {
+    Metadata {
+        info: Info::Resolved {
+            context: None,
+            signature: Some(signature.clone()),
+        },
+        ..Metadata::default()
+    }
+}
+
+/*
+ * @desc Rewrite a method call on a builtin type into a call to the matching core function
+ * The receiver is passed as the first argument
+ */
+fn core_method_call(
+    receiver_type: &StaticType,
+    name: &str,
+    receiver: Expression,
+    args: &CallArgs,
+) -> Result<Expression, SemanticError> {
+    let Some(core) = Core::find_method(receiver_type, name) else {
+        return Err(SemanticError::UnknownMethod(name.to_string()));
+    };
+
+    let mut call_args = Vec::with_capacity(args.args.len() + 1);
+    call_args.push(receiver);
+    call_args.extend(args.args.iter().cloned());
+
+    Ok(Expression::Atomic(Atomic::Data(Data::Call(Call {
+        path: LeftCall::CoreCall(CoreCall { path: core }),
+        args: CallArgs {
+            args: call_args,
+            size: None,
+            resolved_receiver: true,
+        },
+        metadata: Metadata::default(),
+    }))))
+}
+
+fn has_method_call(expr: &Expression) -> bool {
+    match expr {
+        Expression::FieldAccess(FieldAccess { var, field, .. }) => {
+            has_method_call(var) || has_method_call(field)
+        }
+        Expression::Atomic(Atomic::Data(Data::Call(Call {
+            path:
+                LeftCall::VarCall(VarCall {
+                    path:
+                        CompletePath {
+                            path: Path::Empty, ..
+                        },
+                    ..
+                }),
+            ..
+        }))) => true,
+        _ => false,
+    }
+}
+
 impl ResolveFromStruct for FieldAccess {
     fn resolve_from_struct<E: crate::vm::external::Engine>(
         &mut self,
@@ -386,6 +652,19 @@ impl Desugar<Expression> for FieldAccess {
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<Option<Expression>, SemanticError> {
+        if self.is_method_call() {
+            self.rotate_method_chain();
+            // the method itself is only known once the receiver is resolved
+            if let Expression::Atomic(Atomic::Data(Data::Call(Call { args, .. }))) =
+                self.field.as_mut()
+            {
+                for arg in args.args.iter_mut() {
+                    if let Some(output) = arg.desugar::<E>(scope_manager, scope_id)? {
+                        *arg = output;
+                    }
+                }
+            }
+        }
         if let Some(output) = self.var.desugar::<E>(scope_manager, scope_id)? {
             self.var = output.into();
         }
@@ -420,7 +699,11 @@ impl Resolve for ExprCall {
         };
 
         if params.len() != self.args.args.len() {
-            return Err(SemanticError::IncorrectArguments);
+            return Err(SemanticError::IncorrectArgumentCount {
+                expected: params.len(),
+                found: self.args.args.len(),
+            }
+            .at(self.metadata.span));
         }
 
         let mut params_size = 0;
@@ -431,7 +714,13 @@ impl Resolve for ExprCall {
         let _ = self.args.size.insert(params_size);
 
         if context.is_some() && *context.as_ref().unwrap() != **return_type {
-            return Err(SemanticError::IncompatibleTypes);
+            return Err(SemanticError::mismatched(
+                context.as_ref().unwrap(),
+                return_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.metadata.span));
         }
         self.metadata.info = Info::Resolved {
             context: context.clone(),
@@ -461,7 +750,11 @@ impl ResolveFromStruct for ExprCall {
         };
 
         if params.len() != self.args.args.len() {
-            return Err(SemanticError::IncorrectArguments);
+            return Err(SemanticError::IncorrectArgumentCount {
+                expected: params.len(),
+                found: self.args.args.len(),
+            }
+            .at(self.metadata.span));
         }
 
         let mut params_size = 0;
@@ -594,8 +887,19 @@ fn resolve_binary_numerical_operation<E: crate::vm::external::Engine>(
 
     if is_number(&left_type) && is_number(&right_type) && left_type == right_type {
         return Ok(left_type);
+    } else if !is_number(&left_type) {
+        return Err(SemanticError::mismatched_kind(
+            "a number",
+            &left_type,
+            scope_manager,
+            scope_id,
+        )
+        .at(left.span()));
     } else {
-        return Err(SemanticError::IncompatibleTypes);
+        return Err(
+            SemanticError::mismatched(&left_type, &right_type, scope_manager, scope_id)
+                .at(right.span()),
+        );
     }
 }
 
@@ -1163,13 +1467,46 @@ impl Resolve for Cast {
     where
         Self: Sized,
     {
-        let _ = self
-            .left
-            .resolve::<E>(scope_manager, scope_id, context, &mut None)?;
         let _ = self
             .right
             .resolve::<E>(scope_manager, scope_id, &(), &mut ())?;
 
+        let right_type = self.right.type_of(&scope_manager, scope_id)?;
+        if let EType::User { id, .. } = right_type {
+            if let Ok(UserType::Interface(_)) = scope_manager.find_type_by_id(id, scope_id) {
+                // conversion of a value or of an address to an interface implemented by its type
+                let _ = self
+                    .left
+                    .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                let left_type = self.left.type_of(&scope_manager, scope_id)?;
+                let mismatch =
+                    SemanticError::mismatched(&right_type, &left_type, scope_manager, scope_id)
+                        .at(self.left.span());
+                let type_id = match &left_type {
+                    EType::User { id, .. } => *id,
+                    EType::Static(StaticType::Address(AddrType(sub_type))) => {
+                        match sub_type.as_ref() {
+                            EType::User { id, .. } => *id,
+                            _ => return Err(mismatch),
+                        }
+                    }
+                    _ => return Err(mismatch),
+                };
+                if type_id != id && !scope_manager.implements(id, type_id) {
+                    return Err(mismatch);
+                }
+                self.metadata.info = crate::semantic::Info::Resolved {
+                    context: context.clone(),
+                    signature: Some(self.type_of(scope_manager, scope_id)?),
+                };
+                return Ok(());
+            }
+        }
+
+        let _ = self
+            .left
+            .resolve::<E>(scope_manager, scope_id, context, &mut None)?;
+
         if let Some(l_metadata) = self.left.metadata_mut() {
             let signature = l_metadata.signature();
             let new_context = self.right.type_of(&scope_manager, scope_id)?;
@@ -1200,6 +1537,72 @@ impl Desugar<Expression> for Cast {
     }
 }
 
+impl Resolve for Propagate {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ = self
+            .value
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+        let value_type = self.value.type_of(&scope_manager, scope_id)?;
+        let Some((_, payload)) = Propagate::split_error(&value_type) else {
+            return Err(SemanticError::ExpectedError);
+        };
+
+        let Some(inner_scope) = scope_id.filter(|id| scope_manager.can_return(*id).is_some())
+        else {
+            return Err(SemanticError::ExpectedFunctionClosureLambda);
+        };
+        let Some(return_type) = scope_manager.find_return_type(inner_scope).cloned() else {
+            return Err(SemanticError::CantInferType("return type".to_string()));
+        };
+
+        // the error is returned along zeroed values, its payload must fit the returned error
+        let Some((_, return_payload)) = Propagate::split_error(&return_type) else {
+            return Err(SemanticError::mismatched_kind(
+                "a function returning an error",
+                &return_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.metadata.span));
+        };
+        if let (Some(return_payload), Some(payload)) = (&return_payload, &payload) {
+            let _ = return_payload.compatible_with(payload, &scope_manager, scope_id)?;
+        }
+        self.return_type = Some(return_type);
+
+        self.metadata.info = Info::Resolved {
+            context: context.clone(),
+            signature: Some(self.type_of(&scope_manager, scope_id)?),
+        };
+        Ok(())
+    }
+}
+
+impl Desugar<Expression> for Propagate {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Expression>, SemanticError> {
+        if let Some(output) = self.value.desugar::<E>(scope_manager, scope_id)? {
+            *self.value = output.into()
+        }
+        Ok(None)
+    }
+}
+
 impl ResolveNumber for Comparaison {
     fn is_unresolved_number(&self) -> bool {
         match self {
@@ -1318,7 +1721,13 @@ impl Resolve for Comparaison {
                 signature: Some(EType::Static(StaticType::Primitive(PrimitiveType::Bool))),
             }
         } else {
-            return Err(SemanticError::IncompatibleTypes);
+            return Err(SemanticError::mismatched(
+                &left_type,
+                &right_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(right.span()));
         }
         Ok(())
     }
@@ -1446,7 +1855,13 @@ impl Resolve for Equation {
                 signature: Some(EType::Static(StaticType::Primitive(PrimitiveType::Bool))),
             }
         } else {
-            return Err(SemanticError::IncompatibleTypes);
+            return Err(SemanticError::mismatched(
+                &left_type,
+                &right_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(right.span()));
         }
         Ok(())
     }
@@ -1514,15 +1929,25 @@ impl Resolve for LogicalAnd {
             scope_manager,
             scope_id,
         )?;
-        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) =
-            self.left.type_of(&scope_manager, scope_id)?
-        else {
-            return Err(SemanticError::IncompatibleTypes);
+        let left_type = self.left.type_of(&scope_manager, scope_id)?;
+        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) = left_type else {
+            return Err(SemanticError::mismatched(
+                &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                &left_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.left.span()));
         };
-        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) =
-            self.right.type_of(&scope_manager, scope_id)?
-        else {
-            return Err(SemanticError::IncompatibleTypes);
+        let right_type = self.right.type_of(&scope_manager, scope_id)?;
+        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) = right_type else {
+            return Err(SemanticError::mismatched(
+                &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                &right_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.right.span()));
         };
         self.metadata.info = Info::Resolved {
             context: context.clone(),
@@ -1582,15 +2007,25 @@ impl Resolve for LogicalOr {
             scope_id,
         )?;
 
-        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) =
-            self.left.type_of(&scope_manager, scope_id)?
-        else {
-            return Err(SemanticError::IncompatibleTypes);
+        let left_type = self.left.type_of(&scope_manager, scope_id)?;
+        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) = left_type else {
+            return Err(SemanticError::mismatched(
+                &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                &left_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.left.span()));
         };
-        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) =
-            self.right.type_of(&scope_manager, scope_id)?
-        else {
-            return Err(SemanticError::IncompatibleTypes);
+        let right_type = self.right.type_of(&scope_manager, scope_id)?;
+        let EType::Static(StaticType::Primitive(PrimitiveType::Bool)) = right_type else {
+            return Err(SemanticError::mismatched(
+                &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                &right_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.right.span()));
         };
         self.metadata.info = Info::Resolved {
             context: context.clone(),
@@ -1614,12 +2049,404 @@ impl Desugar<Expression> for LogicalOr {
         Ok(None)
     }
 }
+impl ResolveNumber for Range {
+    fn is_unresolved_number(&self) -> bool {
+        self.lower.is_unresolved_number() || self.upper.is_unresolved_number()
+    }
+
+    fn resolve_number(
+        &mut self,
+        to: crate::semantic::scope::static_types::NumberType,
+    ) -> Result<(), SemanticError> {
+        self.lower.resolve_number(to)?;
+        self.upper.resolve_number(to)?;
+        Ok(())
+    }
+}
+impl Resolve for Range {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        // the bounds are resolved with the number type of the expected range
+        let bounds_context = match context {
+            Some(EType::Static(StaticType::Range(RangeType { num, .. }))) => Some(EType::Static(
+                StaticType::Primitive(PrimitiveType::Number(*num)),
+            )),
+            _ => None,
+        };
+        let bounds_type = resolve_binary_numerical_operation::<E>(
+            &mut self.lower,
+            &mut self.upper,
+            scope_manager,
+            scope_id,
+            &bounds_context,
+        )?;
+
+        let EType::Static(StaticType::Primitive(PrimitiveType::Number(num))) = bounds_type else {
+            return Err(SemanticError::mismatched_kind(
+                "an integer",
+                &bounds_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.lower.span()));
+        };
+        if num == NumberType::F64 {
+            return Err(SemanticError::mismatched_kind(
+                "an integer",
+                &bounds_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.lower.span()));
+        }
+        self.metadata.info = Info::Resolved {
+            context: context.clone(),
+            signature: Some(EType::Static(StaticType::Range(RangeType {
+                num,
+                inclusive: self.inclusive,
+            }))),
+        };
+        Ok(())
+    }
+}
+impl Desugar<Expression> for Range {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Expression>, SemanticError> {
+        if let Some(output) = self.lower.desugar::<E>(scope_manager, scope_id)? {
+            *self.lower = output.into()
+        }
+        if let Some(output) = self.upper.desugar::<E>(scope_manager, scope_id)? {
+            *self.upper = output.into()
+        }
+        Ok(None)
+    }
+}
+impl ResolveNumber for Membership {
+    fn is_unresolved_number(&self) -> bool {
+        self.item.is_unresolved_number() || self.collection.is_unresolved_number()
+    }
+
+    fn resolve_number(
+        &mut self,
+        to: crate::semantic::scope::static_types::NumberType,
+    ) -> Result<(), SemanticError> {
+        self.item.resolve_number(to)?;
+        self.collection.resolve_number(to)?;
+        Ok(())
+    }
+}
+impl Resolve for Membership {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ = self
+            .collection
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+        let collection_type = self.collection.type_of(&scope_manager, scope_id)?;
+
+        // the item is resolved with the type held by the collection
+        // strings hold chars and substrings
+        let item_context = match &collection_type {
+            EType::Static(StaticType::Range(RangeType { num, .. })) => Some(EType::Static(
+                StaticType::Primitive(PrimitiveType::Number(*num)),
+            )),
+            EType::Static(StaticType::Vec(VecType(item_type)))
+            | EType::Static(StaticType::Slice(SliceType { item_type, .. })) => {
+                Some(item_type.as_ref().clone())
+            }
+            EType::Static(StaticType::Map(MapType { keys_type, .. })) => {
+                Some(keys_type.as_ref().clone())
+            }
+            EType::Static(StaticType::String(_)) | EType::Static(StaticType::StrSlice(_)) => None,
+            _ => return Err(SemanticError::ExpectedIterable),
+        };
+        let _ = self
+            .item
+            .resolve::<E>(scope_manager, scope_id, &item_context, &mut None)?;
+        let item_type = self.item.type_of(&scope_manager, scope_id)?;
+
+        match item_context {
+            Some(expected) if expected != item_type => {
+                return Err(SemanticError::mismatched(
+                    &expected,
+                    &item_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.item.span()))
+            }
+            Some(_) => {}
+            None => match item_type {
+                EType::Static(StaticType::Primitive(PrimitiveType::Char))
+                | EType::Static(StaticType::String(_))
+                | EType::Static(StaticType::StrSlice(_)) => {}
+                _ => {
+                    return Err(SemanticError::mismatched_kind(
+                        "a char or a string",
+                        &item_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.item.span()))
+                }
+            },
+        }
+        self.metadata.info = Info::Resolved {
+            context: context.clone(),
+            signature: Some(EType::Static(StaticType::Primitive(PrimitiveType::Bool))),
+        };
+        Ok(())
+    }
+}
+impl Desugar<Expression> for Membership {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Expression>, SemanticError> {
+        if let Some(output) = self.item.desugar::<E>(scope_manager, scope_id)? {
+            *self.item = output.into()
+        }
+        if let Some(output) = self.collection.desugar::<E>(scope_manager, scope_id)? {
+            *self.collection = output.into()
+        }
+        Ok(None)
+    }
+}
+
+fn const_eval_numbers(
+    left: &Expression,
+    right: &Expression,
+    operator: MathOperator,
+) -> Result<Option<Data>, SemanticError> {
+    let Some(Data::Primitive(Primitive::Number(left))) = left.const_eval()? else {
+        return Ok(None);
+    };
+    let Some(Data::Primitive(Primitive::Number(right))) = right.const_eval()? else {
+        return Ok(None);
+    };
+    left.fold(right, operator)
+        .map(|value| Some(Data::Primitive(Primitive::Number(value))))
+        .ok_or(SemanticError::ConstantOverflow)
+}
+
+fn const_eval_comparaison(
+    left: &Expression,
+    right: &Expression,
+    predicate: fn(Ordering) -> bool,
+) -> Result<Option<Data>, SemanticError> {
+    let Some(Data::Primitive(Primitive::Number(left))) = left.const_eval()? else {
+        return Ok(None);
+    };
+    let Some(Data::Primitive(Primitive::Number(right))) = right.const_eval()? else {
+        return Ok(None);
+    };
+    Ok(left
+        .compare(&right)
+        .map(|ordering| Data::Primitive(Primitive::Bool(predicate(ordering)))))
+}
+
+fn const_eval_equality(
+    left: &Expression,
+    right: &Expression,
+) -> Result<Option<bool>, SemanticError> {
+    let Some(Data::Primitive(left)) = left.const_eval()? else {
+        return Ok(None);
+    };
+    let Some(Data::Primitive(right)) = right.const_eval()? else {
+        return Ok(None);
+    };
+    Ok(match (left, right) {
+        // the runtime compares the bytes of the operands
+        (Primitive::Number(Number::F64(left)), Primitive::Number(Number::F64(right))) => {
+            Some(left.to_bits() == right.to_bits())
+        }
+        (Primitive::Number(left), Primitive::Number(right)) => left
+            .compare(&right)
+            .map(|ordering| ordering == Ordering::Equal),
+        (Primitive::Bool(left), Primitive::Bool(right)) => Some(left == right),
+        (Primitive::Char(left), Primitive::Char(right)) => Some(left == right),
+        _ => None,
+    })
+}
+
+fn const_eval_bools(
+    left: &Expression,
+    right: &Expression,
+) -> Result<Option<(bool, bool)>, SemanticError> {
+    let Some(Data::Primitive(Primitive::Bool(left))) = left.const_eval()? else {
+        return Ok(None);
+    };
+    let Some(Data::Primitive(Primitive::Bool(right))) = right.const_eval()? else {
+        return Ok(None);
+    };
+    Ok(Some((left, right)))
+}
+
+impl ConstEval for TupleAccess {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        let Some(Data::Tuple(tuple)) = self.var.const_eval()? else {
+            return Ok(None);
+        };
+        match tuple.value.get(self.index) {
+            Some(item) => item.const_eval(),
+            None => Ok(None),
+        }
+    }
+}
+
+impl ConstEval for UnaryOperation {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        match self {
+            UnaryOperation::Minus { value, .. } => {
+                let Some(Data::Primitive(Primitive::Number(value))) = value.const_eval()? else {
+                    return Ok(None);
+                };
+                value
+                    .negate()
+                    .map(|value| Some(Data::Primitive(Primitive::Number(value))))
+                    .ok_or(SemanticError::ConstantOverflow)
+            }
+            UnaryOperation::Not { value, .. } => {
+                let Some(Data::Primitive(Primitive::Bool(value))) = value.const_eval()? else {
+                    return Ok(None);
+                };
+                Ok(Some(Data::Primitive(Primitive::Bool(!value))))
+            }
+        }
+    }
+}
+
+impl ConstEval for Product {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        match self {
+            Product::Mult { left, right, .. } => {
+                const_eval_numbers(left, right, MathOperator::Mult)
+            }
+            Product::Div { left, right, .. } => const_eval_numbers(left, right, MathOperator::Div),
+            Product::Mod { left, right, .. } => const_eval_numbers(left, right, MathOperator::Mod),
+        }
+    }
+}
+
+impl ConstEval for Addition {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        const_eval_numbers(&self.left, &self.right, MathOperator::Add)
+    }
+}
+
+impl ConstEval for Substraction {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        const_eval_numbers(&self.left, &self.right, MathOperator::Sub)
+    }
+}
+
+impl ConstEval for Shift {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        match self {
+            Shift::Left { left, right, .. } => {
+                const_eval_numbers(left, right, MathOperator::ShiftLeft)
+            }
+            Shift::Right { left, right, .. } => {
+                const_eval_numbers(left, right, MathOperator::ShiftRight)
+            }
+        }
+    }
+}
+
+impl ConstEval for BitwiseAnd {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        const_eval_numbers(&self.left, &self.right, MathOperator::BitAnd)
+    }
+}
+
+impl ConstEval for BitwiseXOR {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        const_eval_numbers(&self.left, &self.right, MathOperator::BitXor)
+    }
+}
+
+impl ConstEval for BitwiseOR {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        const_eval_numbers(&self.left, &self.right, MathOperator::BitOr)
+    }
+}
+
+impl ConstEval for Comparaison {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        match self {
+            Comparaison::Less { left, right, .. } => {
+                const_eval_comparaison(left, right, Ordering::is_lt)
+            }
+            Comparaison::LessEqual { left, right, .. } => {
+                const_eval_comparaison(left, right, Ordering::is_le)
+            }
+            Comparaison::Greater { left, right, .. } => {
+                const_eval_comparaison(left, right, Ordering::is_gt)
+            }
+            Comparaison::GreaterEqual { left, right, .. } => {
+                const_eval_comparaison(left, right, Ordering::is_ge)
+            }
+        }
+    }
+}
+
+impl ConstEval for Equation {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        match self {
+            Equation::Equal { left, right, .. } => Ok(const_eval_equality(left, right)?
+                .map(|equal| Data::Primitive(Primitive::Bool(equal)))),
+            Equation::NotEqual { left, right, .. } => Ok(const_eval_equality(left, right)?
+                .map(|equal| Data::Primitive(Primitive::Bool(!equal)))),
+        }
+    }
+}
+
+impl ConstEval for LogicalAnd {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        Ok(const_eval_bools(&self.left, &self.right)?
+            .map(|(left, right)| Data::Primitive(Primitive::Bool(left && right))))
+    }
+}
+
+impl ConstEval for LogicalOr {
+    fn const_eval(&self) -> Result<Option<Data>, SemanticError> {
+        Ok(const_eval_bools(&self.left, &self.right)?
+            .map(|(left, right)| Data::Primitive(Primitive::Bool(left || right))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::{
         ast::{
             expressions::{operation::operation_parse::TryParseOperation, Expression},
+            statements::Statement,
             TryParse,
         },
         p_num,
@@ -2052,6 +2879,55 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn valid_membership() {
+        let mut expr = Expression::parse("2 in vec[1,2,3]".into()).unwrap().1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut None,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        let mut expr = Expression::parse("'a' in \"abc\"".into()).unwrap().1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut None,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    #[test]
+    fn robustness_membership() {
+        let mut expr = Expression::parse("'a' in vec[1,2,3]".into()).unwrap().1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut None,
+        );
+        assert!(res.is_err());
+
+        let mut expr = Expression::parse("1 in 10".into()).unwrap().1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut None,
+        );
+        assert!(matches!(
+            res.map_err(SemanticError::into_inner),
+            Err(SemanticError::ExpectedIterable)
+        ));
+    }
+
     #[test]
     fn valid_and_or() {
         let mut expr = Expression::parse("true and false".into()).unwrap().1;
@@ -2277,4 +3153,116 @@ mod tests {
     //         .resolve::<crate::vm::external::test::NoopEngine>(&mut scope_manager, None, &None, &mut None)
     //         .expect_err("Resolution should have failed");
     // }
+
+    #[test]
+    fn valid_propagate() {
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        for input in [
+            r##"
+            union Failure {
+                NotFound {},
+                Invalid { code : u64 },
+            }
+            "##,
+            r##"
+            fn find(x : i64) -> (i64, Error[Failure]) {
+                if x < 0 {
+                    return (0, Err(Failure::Invalid { code : 2 }));
+                }
+                return (x, Ok());
+            }
+            "##,
+            r##"
+            fn find_twice(x : i64) -> Error[Failure] {
+                let y = find(x)?;
+                let _ = find(y)?;
+                return Ok();
+            }
+            "##,
+            r##"
+            fn check_sign(x : i64) -> Error[str] {
+                if x < 0 {
+                    return Err("negative");
+                }
+                return Ok();
+            }
+            "##,
+            r##"
+            fn parse(x : i64) -> (u64, Error[str]) {
+                let _ = check_sign(x)?;
+                return (x as u64, Ok());
+            }
+            "##,
+        ] {
+            let mut statement = Statement::parse(input.into()).unwrap().1;
+            let _ = statement
+                .resolve::<crate::vm::external::test::NoopEngine>(
+                    &mut scope_manager,
+                    None,
+                    &None,
+                    &mut (),
+                )
+                .expect("Resolution should have succeeded");
+        }
+    }
+
+    #[test]
+    fn robustness_propagate() {
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let mut statement = Statement::parse(
+            r##"
+            fn check(x : i64) -> (i64, Error[u64]) {
+                return (x, Ok());
+            }
+            "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let _ = statement
+            .resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            )
+            .expect("Resolution should have succeeded");
+
+        for input in [
+            // outside of a function
+            r##"
+            let res = check(1)?;
+            "##,
+            // not an error
+            r##"
+            fn f() -> Error {
+                let x = 1?;
+                return Ok();
+            }
+            "##,
+            // the function does not return an error
+            r##"
+            fn f() -> i64 {
+                let x = check(1)?;
+                return x;
+            }
+            "##,
+            // incompatible payloads
+            r##"
+            fn f() -> Error[bool] {
+                let x = check(1)?;
+                return Ok();
+            }
+            "##,
+        ] {
+            let mut statement = Statement::parse(input.into()).unwrap().1;
+            let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            );
+            assert!(res.is_err(), "{input}");
+        }
+    }
 }
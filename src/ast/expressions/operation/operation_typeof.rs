@@ -5,8 +5,8 @@ use crate::semantic::{
 
 use super::{
     Addition, BitwiseAnd, BitwiseOR, BitwiseXOR, Cast, Comparaison, Equation, ExprCall,
-    FieldAccess, ListAccess, LogicalAnd, LogicalOr, Product, Shift, Substraction, TupleAccess,
-    UnaryOperation,
+    FieldAccess, ListAccess, LogicalAnd, LogicalOr, Membership, Product, Propagate, Range, Shift,
+    Substraction, TupleAccess, UnaryOperation,
 };
 
 impl TypeOf for UnaryOperation {
@@ -241,6 +241,55 @@ impl TypeOf for Cast {
     }
 }
 
+impl Propagate {
+    /*
+     * @desc Split an error type into the values returned along the error and its payload
+     * Error and Error[T] carry no values, (T1, ... , Error) carries the values T1, ...
+     */
+    pub(crate) fn split_error(error_type: &EType) -> Option<(Vec<EType>, Option<EType>)> {
+        match error_type {
+            EType::Static(StaticType::Error) => Some((Vec::default(), None)),
+            EType::Static(StaticType::TypedError(static_types::ErrorType(payload))) => {
+                Some((Vec::default(), Some(payload.as_ref().clone())))
+            }
+            EType::Static(StaticType::Tuple(static_types::TupleType(values))) => {
+                let (last, values) = values.split_last()?;
+                match last {
+                    EType::Static(StaticType::Error) => Some((values.to_vec(), None)),
+                    EType::Static(StaticType::TypedError(static_types::ErrorType(payload))) => {
+                        Some((values.to_vec(), Some(payload.as_ref().clone())))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TypeOf for Propagate {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        let value_type = self.value.type_of(&scope_manager, scope_id)?;
+        let Some((mut values, _)) = Self::split_error(&value_type) else {
+            return Err(SemanticError::ExpectedError);
+        };
+        match values.len() {
+            0 => Ok(EType::Static(StaticType::Unit)),
+            1 => Ok(values.remove(0)),
+            _ => Ok(EType::Static(StaticType::Tuple(static_types::TupleType(
+                values,
+            )))),
+        }
+    }
+}
+
 impl TypeOf for Comparaison {
     fn type_of(
         &self,
@@ -299,3 +348,46 @@ impl TypeOf for LogicalOr {
         )))
     }
 }
+
+impl TypeOf for Range {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        let lower_type = self.lower.type_of(&scope_manager, scope_id)?;
+        let EType::Static(StaticType::Primitive(static_types::PrimitiveType::Number(num))) =
+            lower_type
+        else {
+            return Err(SemanticError::mismatched_kind(
+                "a number",
+                &lower_type,
+                scope_manager,
+                scope_id,
+            )
+            .at(self.lower.span()));
+        };
+        Ok(EType::Static(StaticType::Range(static_types::RangeType {
+            num,
+            inclusive: self.inclusive,
+        })))
+    }
+}
+
+impl TypeOf for Membership {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        Ok(EType::Static(StaticType::Primitive(
+            static_types::PrimitiveType::Bool,
+        )))
+    }
+}
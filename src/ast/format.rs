@@ -0,0 +1,459 @@
+use std::collections::VecDeque;
+
+use crate::{
+    vm::external::{ExternProcessIdentifier, ExternThreadIdentifier},
+    CompilationError,
+};
+
+use super::{
+    modules::parse_module,
+    statements::parse_statements,
+    utils::{
+        io::{SourceSpan, Span},
+        strings::eater,
+    },
+};
+
+pub const MAX_WIDTH: usize = 100; // width of a line before a list is broken
+const INDENT: &str = "    ";
+
+/*
+ * @desc Print a parsed node back to its canonical source
+ */
+pub trait PrettyPrint {
+    fn pretty(&self, f: &mut Formatter);
+
+    fn to_source(&self) -> String
+    where
+        Self: Sized,
+    {
+        let mut f = Formatter::default();
+        self.pretty(&mut f);
+        f.finish()
+    }
+}
+
+/*
+ * @desc Canonical source being printed along the comments of the original source not printed yet
+ */
+#[derive(Debug, Default)]
+pub struct Formatter<'src> {
+    output: String,
+    source: &'src str,
+    comments: VecDeque<(SourceSpan, String)>,
+    indent: usize,
+    at_line_start: bool,
+    start_column: usize, // column of the first line of a part printed apart
+    last_end: usize,     // end of the last statement or comment printed
+}
+
+impl<'src> Formatter<'src> {
+    pub fn new(source: &'src str) -> Self {
+        Self {
+            source,
+            comments: eater::comments(Span::new(source)).into(),
+            at_line_start: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        self.flush_comments(usize::MAX);
+        self.output
+    }
+
+    pub fn write(&mut self, text: &str) {
+        if self.at_line_start {
+            for _ in 0..self.indent {
+                self.output.push_str(INDENT);
+            }
+            self.at_line_start = false;
+        }
+        self.output.push_str(text);
+    }
+
+    pub fn newline(&mut self) {
+        let length = self.output.trim_end_matches(' ').len();
+        self.output.truncate(length);
+        self.output.push('\n');
+        self.at_line_start = true;
+    }
+
+    pub fn indent(&mut self) {
+        self.indent += 1;
+    }
+
+    pub fn dedent(&mut self) {
+        self.indent = self.indent.saturating_sub(1);
+    }
+
+    pub fn column(&self) -> usize {
+        if self.at_line_start {
+            return self.indent * INDENT.len();
+        }
+        match self.output.rfind('\n') {
+            Some(index) => self.output[index + 1..].chars().count(),
+            None => self.start_column + self.output.chars().count(),
+        }
+    }
+
+    fn blank_line(&mut self) {
+        if self.output.is_empty() || self.output.ends_with("\n\n") || self.output.ends_with("{\n") {
+            return;
+        }
+        if self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+    }
+
+    fn has_blank_line(&self, from: usize, to: usize) -> bool {
+        if from >= to || to > self.source.len() {
+            return false;
+        }
+        let lines: Vec<&str> = self.source[from..to].split('\n').collect();
+        lines.len() > 2
+            && lines[1..lines.len() - 1]
+                .iter()
+                .any(|line| line.trim().is_empty())
+    }
+
+    pub fn has_comments_before(&self, offset: usize) -> bool {
+        self.comments
+            .front()
+            .map_or(false, |(span, _)| span.offset < offset)
+    }
+
+    /*
+     * @desc Print the comments located before an offset of the source
+     * A comment following code on its line stays at the end of the last printed line
+     */
+    pub fn flush_comments(&mut self, offset: usize) {
+        self.flush(offset, true);
+    }
+
+    fn flush(&mut self, offset: usize, keep_trailing: bool) {
+        while self.has_comments_before(offset) {
+            let Some((span, text)) = self.comments.pop_front() else {
+                break;
+            };
+            let line_start = self.source[..span.offset]
+                .rfind('\n')
+                .map_or(0, |index| index + 1);
+            let is_trailing =
+                keep_trailing && !self.source[line_start..span.offset].trim().is_empty();
+            if is_trailing && self.output.ends_with('\n') && !self.output.ends_with("\n\n") {
+                self.output.pop();
+                self.output.push(' ');
+                self.output.push_str(&text);
+                self.output.push('\n');
+            } else {
+                if !self.at_line_start {
+                    self.newline();
+                }
+                if self.has_blank_line(self.last_end, span.offset) {
+                    self.blank_line();
+                }
+                self.write_comment(&text, span.column.saturating_sub(1));
+                self.newline();
+            }
+            self.last_end = span.offset + span.length;
+        }
+    }
+
+    /*
+     * @desc Print a comment at the current indentation, its lines keep their relative indentation
+     */
+    fn write_comment(&mut self, text: &str, column: usize) {
+        for (index, line) in text.split('\n').enumerate() {
+            if index == 0 {
+                self.write(line);
+                continue;
+            }
+            self.newline();
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            self.write(&line[spaces.min(column)..]);
+        }
+    }
+
+    /*
+     * @desc Print a statement on its own line, after the comments located before it
+     * A blank line separating the statement from the previous one in the source is kept,
+     * the comments within the statement not printed by its nodes follow it
+     */
+    pub fn statement(&mut self, span: SourceSpan, print: impl FnOnce(&mut Self)) {
+        if span.is_known() {
+            self.flush_comments(span.offset);
+            if self.has_blank_line(self.last_end, span.offset) {
+                self.blank_line();
+            }
+        }
+        print(self);
+        self.newline();
+        if span.is_known() {
+            self.flush(span.offset + span.length, false);
+            self.last_end = span.offset + span.length;
+        }
+    }
+
+    /*
+     * @desc Print a part of a line apart to measure it, it consumes the comments it contains
+     */
+    fn render(&mut self, start_column: usize, print: impl FnOnce(&mut Formatter<'src>)) -> String {
+        let mut part = Formatter {
+            output: String::default(),
+            source: self.source,
+            comments: std::mem::take(&mut self.comments),
+            indent: self.indent,
+            at_line_start: false,
+            start_column,
+            last_end: self.last_end,
+        };
+        print(&mut part);
+        self.comments = part.comments;
+        self.last_end = part.last_end;
+        part.output
+    }
+
+    /*
+     * @desc Print a delimited list on the current line when it fits, one item per line otherwise
+     * A last item spanning several lines, such as a closure, follows the opening delimiter
+     */
+    pub fn list<T>(
+        &mut self,
+        open: &str,
+        items: &[T],
+        close: &str,
+        trailing_comma: bool,
+        mut print: impl FnMut(&mut Formatter<'src>, &T),
+    ) {
+        if items.is_empty() {
+            self.write(open.trim_end());
+            self.write(close.trim_start());
+            return;
+        }
+        let start = self.column() + open.len();
+        let parts: Vec<String> = items
+            .iter()
+            .map(|item| self.render(start, |f| print(f, item)))
+            .collect();
+
+        let inline = parts.join(", ");
+        let width = match inline.split_once('\n') {
+            Some((first_line, _)) => start + first_line.chars().count(),
+            None => start + inline.chars().count() + close.len(),
+        };
+        let is_inline = width <= MAX_WIDTH
+            && parts[..parts.len() - 1]
+                .iter()
+                .all(|part| !part.contains('\n'));
+        if is_inline {
+            self.write(open);
+            self.write(&inline);
+            self.write(close);
+            return;
+        }
+        self.write(open.trim_end());
+        self.newline();
+        self.indent();
+        let count = parts.len();
+        for (index, part) in parts.into_iter().enumerate() {
+            let lines: Vec<String> = part
+                .split('\n')
+                .enumerate()
+                .map(
+                    |(line_index, line)| match line_index == 0 || line.is_empty() {
+                        true => line.to_string(),
+                        false => format!("{}{}", INDENT, line),
+                    },
+                )
+                .collect();
+            self.write(&lines.join("\n"));
+            if trailing_comma || index + 1 < count {
+                self.write(",");
+            }
+            self.newline();
+        }
+        self.dedent();
+        self.write(close.trim_start());
+    }
+}
+
+/*
+ * @desc Literal of a string or a character, escaping the characters the parser unescapes
+ */
+pub fn escape(value: &str, quote: char, in_format: bool) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c {
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{08}' => output.push_str("\\b"),
+            '\u{0C}' => output.push_str("\\f"),
+            '\\' => output.push_str("\\\\"),
+            '"' if quote == '"' => output.push_str("\\\""),
+            '{' if in_format => output.push_str("{{"),
+            '}' if in_format => output.push_str("}}"),
+            c if c.is_control() => output.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/*
+ * @desc Format the statements of a source, its comments are kept
+ */
+pub fn format<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>>(
+    source: &str,
+) -> Result<String, CompilationError<PID, TID>> {
+    let statements = parse_statements::<PID, TID>(Span::new(source), 0)?;
+    let mut f = Formatter::new(source);
+    for statement in statements.iter() {
+        f.statement(statement.span, |f| statement.inner.pretty(f));
+    }
+    Ok(f.finish())
+}
+
+/*
+ * @desc Format the source of a module, its comments are kept
+ */
+pub fn format_module<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>>(
+    source: &str,
+) -> Result<String, CompilationError<PID, TID>> {
+    let module = parse_module::<PID, TID>(Span::new(source), 0)?;
+    let mut f = Formatter::new(source);
+    module.pretty(&mut f);
+    f.newline();
+    Ok(f.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vm::external::test::{DefaultProcessID, DefaultThreadID};
+
+    use super::*;
+
+    fn format_source(source: &str) -> String {
+        format::<DefaultProcessID, DefaultThreadID>(source)
+            .expect("Formatting should have succeeded")
+    }
+
+    fn format_module_source(source: &str) -> String {
+        format_module::<DefaultProcessID, DefaultThreadID>(source)
+            .expect("Formatting should have succeeded")
+    }
+
+    #[test]
+    fn valid_format_statements() {
+        let formatted = format_source(
+            r##"
+        let x:u64=  5;
+        fn add(a:u64,b:u64)->u64{ let c = a+b; return c; }
+        if x>2 {printf("big");} else {printf("small");}
+        "##,
+        );
+        assert_eq!(
+            formatted,
+            r##"let x : u64 = 5;
+fn add(a : u64, b : u64) -> u64 {
+    let c = a + b;
+    return c;
+}
+if x > 2 {
+    printf("big");
+} else {
+    printf("small");
+}
+"##
+        );
+        assert_eq!(format_source(&formatted), formatted);
+    }
+
+    #[test]
+    fn valid_format_comments() {
+        let formatted = format_source(
+            r##"
+        // leading
+        struct Point { x : u64, y : u64 } // trailing
+
+
+        /* before
+           the function */
+        fn f() {
+            let a = 1;   // one
+            // before b
+            let b = 2;
+
+            let c = 3;
+            // last
+        }
+        // end
+        "##,
+        );
+        assert_eq!(
+            formatted,
+            r##"// leading
+struct Point {
+    x : u64,
+    y : u64,
+} // trailing
+
+/* before
+   the function */
+fn f() {
+    let a = 1; // one
+    // before b
+    let b = 2;
+
+    let c = 3;
+    // last
+}
+// end
+"##
+        );
+        assert_eq!(format_source(&formatted), formatted);
+    }
+
+    #[test]
+    fn valid_format_module() {
+        let formatted = format_module_source(
+            r##"
+        module Geometry {
+            import "lib/math";
+
+            pub struct Point { x : i64, y : i64 }
+            interface Norm { fn norm(&self) -> i64; }
+            // norm of a point
+            impl Norm for Point {
+                fn norm(&self) -> i64 { self.x + self.y }
+            }
+            pub const ORIGIN : Point = Point { x : 0, y : 0 };
+        }
+        "##,
+        );
+        assert_eq!(
+            formatted,
+            r##"module Geometry {
+    import "lib/math";
+
+    pub struct Point {
+        x : i64,
+        y : i64,
+    }
+    interface Norm {
+        fn norm(&self) -> i64;
+    }
+    // norm of a point
+    impl Norm for Point {
+        fn norm(&self) -> i64 {
+            self.x + self.y
+        }
+    }
+    pub const ORIGIN : Point = Point { x : 0, y : 0 };
+}
+"##
+        );
+        assert_eq!(format_module_source(&formatted), formatted);
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::HashSet;
+
 use nom::{
     branch::alt,
-    combinator::map,
+    combinator::{map, opt},
     multi::fold_many0,
     sequence::{delimited, pair, preceded},
     Finish,
@@ -9,11 +11,12 @@ use nom::{
 use crate::{
     ast::utils::{
         lexem,
-        strings::{parse_id, wst},
+        strings::{parse_id, wst, wst_closed, ID},
     },
     semantic::{
+        lint::{LintCheck, LintId, Linter},
         scope::scope::{Type, Variable},
-        Desugar, EType, Resolve,
+        Desugar, EType, Resolve, SemanticError,
     },
     vm::{
         external::{ExternProcessIdentifier, ExternThreadIdentifier},
@@ -23,76 +26,257 @@ use crate::{
 };
 
 use super::{
-    statements::definition::{EnumDef, FnDef, StructDef, TypeDef, UnionDef},
-    utils::{error::generate_error_report, io::Span},
+    expressions::data::Data,
+    format::{Formatter, PrettyPrint},
+    statements::{
+        definition::{
+            ConstDef, EnumDef, FnDef, ImplDef, InterfaceDef, StructDef, TypeDef, UnionDef,
+        },
+        import_stat::Import,
+    },
+    utils::{
+        error::generate_error_report,
+        io::{located, SourceSpan, Span},
+    },
     TryParse,
 };
 
+/*
+ * @desc Item of a module, by its index in the items of its kind
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ItemRef {
+    Type(usize),
+    Function(usize),
+    Impl(usize),
+    Const(usize),
+    Module(usize),
+    Import(usize),
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Module {
     name: String,
     types: Vec<TypeDef>,
     functions: Vec<FnDef>,
+    impls: Vec<ImplDef>,
+    constants: Vec<ConstDef>,
+    modules: Vec<Module>,
+    imports: Vec<Import>,
+    exports: HashSet<ID>,              // names of the items declared with pub
+    items: Vec<(ItemRef, SourceSpan)>, // items in the order of the source, with their location
+    span: SourceSpan,
+    prefix: String,       // mangling prefix of the types, functions and constants
+    path: Option<String>, // path given to the module loader
 }
 
 impl Module {
-    pub fn find_var(&self, path: &[String], name: &str) -> Option<Variable> {
-        if path.len() != 1 {
-            return None;
-        }
-        if path[0] != self.name {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn set_path(&mut self, path: String) {
+        self.path = Some(path);
+    }
+
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    /*
+     * @desc Module reached by a path starting with the name of this module
+     * None when the path does not start with this module
+     */
+    pub fn find_module(&self, path: &[String]) -> Option<Result<&Module, SemanticError>> {
+        let (first, rest) = path.split_first()?;
+        if *first != self.name {
             return None;
         }
-        match self
-            .functions
-            .iter()
-            .find(|func| &func.name == format!("${}", name).as_str())
-        {
-            Some(func) => {
-                let Some((id, _, ctype)) = &func.id else {
-                    return None;
-                };
-                return Some(Variable {
-                    id: id.clone(),
-                    ctype: ctype.clone(),
-                    scope: None,
-                });
+        let mut module = self;
+        for segment in rest {
+            if let Err(err) = module.check_item(segment) {
+                return Some(Err(err));
             }
-            None => return None,
+            let Some(inner) = module.modules.iter().find(|inner| &inner.name == segment) else {
+                return Some(Err(SemanticError::UnknownItem(segment.clone())));
+            };
+            module = inner;
         }
+        Some(Ok(module))
     }
 
-    pub fn find_type(&self, path: &[String], name: &str) -> Option<Type> {
-        if path.len() != 1 {
-            return None;
+    /*
+     * @desc Check that an item of this module exists and is visible from outside of the module
+     */
+    pub fn check_item(&self, name: &str) -> Result<(), SemanticError> {
+        let exists = self.find_fn(name).is_some()
+            || self.find_type_def(name).is_some()
+            || self
+                .constants
+                .iter()
+                .any(|constant| constant.id == self.mangle(name))
+            || self.modules.iter().any(|module| module.name == name);
+        if !exists {
+            return Err(SemanticError::UnknownItem(name.to_string()));
+        }
+        if !self.exports.contains(name) {
+            return Err(SemanticError::PrivateItem(name.to_string()));
         }
-        if path[0] != self.name {
+        Ok(())
+    }
+
+    fn span_of(&self, item: ItemRef) -> SourceSpan {
+        self.items
+            .iter()
+            .find(|(other, _)| *other == item)
+            .map_or(SourceSpan::default(), |(_, span)| *span)
+    }
+
+    fn mangle(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    fn find_fn(&self, name: &str) -> Option<&FnDef> {
+        self.functions
+            .iter()
+            .find(|func| func.name == self.mangle(name))
+    }
+
+    fn find_type_def(&self, name: &str) -> Option<&TypeDef> {
+        self.types
+            .iter()
+            .find(|type_def| type_def.name() == self.mangle(name))
+    }
+
+    pub fn find_var(&self, name: &str) -> Option<Variable> {
+        let Some((id, _, ctype)) = &self.find_fn(name)?.id else {
+            return None;
+        };
+        Some(Variable {
+            id: id.clone(),
+            ctype: ctype.clone(),
+            scope: None,
+        })
+    }
+
+    pub fn find_constant(&self, name: &str) -> Option<Data> {
+        self.constants
+            .iter()
+            .find(|constant| constant.id == self.mangle(name))
+            .and_then(|constant| constant.value.clone())
+    }
+
+    pub fn find_type(&self, name: &str) -> Option<Type> {
+        let Some((EType::User { id, .. }, def)) = (match self.find_type_def(name)? {
+            TypeDef::Struct(value) => &value.signature,
+            TypeDef::Union(value) => &value.signature,
+            TypeDef::Enum(value) => &value.signature,
+            TypeDef::Interface(value) => &value.signature,
+        }) else {
             return None;
+        };
+        Some(Type {
+            id: id.clone(),
+            def: def.clone(),
+        })
+    }
+
+    fn resolve_with_prefix<E: crate::vm::external::Engine>(
+        &mut self,
+        prefix: String,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<(), SemanticError> {
+        self.prefix = prefix;
+
+        for index in 0..self.modules.len() {
+            let span = self.span_of(ItemRef::Module(index));
+            let module = &mut self.modules[index];
+            let prefix = format!("{}{}{}", self.prefix, module.name, lexem::SEP);
+            scope_manager
+                .at(span, |scope_manager| {
+                    module.resolve_with_prefix::<E>(prefix, scope_manager, scope_id)
+                })
+                .map_err(|err| err.at(span))?;
         }
-        match self.types.iter().find(|td| match *td {
-            TypeDef::Struct(StructDef { id, .. }) => id.as_str() == name,
-            TypeDef::Union(UnionDef { id, .. }) => id.as_str() == name,
-            TypeDef::Enum(EnumDef { id, .. }) => id.as_str() == name,
-        }) {
-            Some(td) => {
-                let Some((EType::User { id, size }, def)) = (match td {
-                    TypeDef::Struct(value) => &value.signature,
-                    TypeDef::Union(value) => &value.signature,
-                    TypeDef::Enum(value) => &value.signature,
-                }) else {
-                    return None;
-                };
-                return Some(Type {
-                    id: id.clone(),
-                    def: def.clone(),
-                });
+
+        let prefix = self.prefix.clone();
+        scope_manager.within_module(&prefix, |scope_manager| {
+            for index in 0..self.types.len() {
+                let span = self.span_of(ItemRef::Type(index));
+                let t = &mut self.types[index];
+                match t {
+                    TypeDef::Struct(StructDef { id, .. })
+                    | TypeDef::Union(UnionDef { id, .. })
+                    | TypeDef::Enum(EnumDef { id, .. })
+                    | TypeDef::Interface(InterfaceDef { id, .. }) => {
+                        *id = format!("{}{}", prefix, id)
+                    }
+                }
+                let _ = scope_manager
+                    .at(span, |scope_manager| {
+                        t.resolve::<E>(scope_manager, scope_id, &(), &mut ())
+                    })
+                    .map_err(|err| err.at(span))?;
             }
-            None => return None,
-        }
+
+            for index in 0..self.constants.len() {
+                let span = self.span_of(ItemRef::Const(index));
+                let constant = &mut self.constants[index];
+                constant.id = format!("{}{}", prefix, constant.id);
+                let _ = scope_manager
+                    .at(span, |scope_manager| {
+                        constant.resolve::<E>(scope_manager, scope_id, &(), &mut ())
+                    })
+                    .map_err(|err| err.at(span))?;
+            }
+
+            for index in 0..self.functions.len() {
+                let span = self.span_of(ItemRef::Function(index));
+                let func = &mut self.functions[index];
+                func.name = format!("{}{}", prefix, func.name);
+                let _ = func.desugar::<E>(scope_manager, scope_id)?;
+                let _ = scope_manager
+                    .at(span, |scope_manager| {
+                        func.resolve::<E>(scope_manager, scope_id, &(), &mut ())
+                    })
+                    .map_err(|err| err.at(span))?;
+            }
+
+            for index in 0..self.impls.len() {
+                let span = self.span_of(ItemRef::Impl(index));
+                let impl_def = &mut self.impls[index];
+                // the methods are named after their bare target type, e.g. Point::norm
+                for (_, method) in impl_def.methods.iter_mut() {
+                    method.name = format!("{}{}", prefix, method.name);
+                }
+                let _ = impl_def.desugar::<E>(scope_manager, scope_id)?;
+                let _ = scope_manager
+                    .at(span, |scope_manager| {
+                        impl_def.resolve::<E>(scope_manager, scope_id, &(), &mut ())
+                    })
+                    .map_err(|err| err.at(span))?;
+            }
+
+            Ok(())
+        })
     }
 }
 
 impl TryParse for Module {
+    /*
+     * @desc Parse a module
+     *
+     * @grammar
+     * Module := module ID { Module_items }
+     * Module_items := Module_item Module_items | ε
+     * Module_item := pub Item | Item
+     * Item := TypeDef | FnDef | ImplDef | ConstDef | Module | Import
+     */
     fn parse(input: super::utils::io::Span) -> super::utils::io::PResult<Self>
     where
         Self: Sized,
@@ -100,34 +284,73 @@ impl TryParse for Module {
         enum ModuleItem {
             Type(TypeDef),
             Function(FnDef),
+            Impl(ImplDef),
+            Const(ConstDef),
+            Module(Module),
+            Import(Import),
         }
         map(
-            pair(
-                preceded(wst(lexem::MODULE), parse_id),
+            located(pair(
+                preceded(wst_closed(lexem::MODULE), parse_id),
                 delimited(
                     wst(lexem::BRA_O),
                     fold_many0(
-                        alt((
-                            map(TypeDef::parse, ModuleItem::Type),
-                            map(FnDef::parse, ModuleItem::Function),
-                        )),
+                        pair(
+                            opt(wst_closed(lexem::PUB)),
+                            located(alt((
+                                map(TypeDef::parse, ModuleItem::Type),
+                                map(FnDef::parse, ModuleItem::Function),
+                                map(ImplDef::parse, ModuleItem::Impl),
+                                map(ConstDef::parse, ModuleItem::Const),
+                                map(Module::parse, ModuleItem::Module),
+                                map(Import::parse, ModuleItem::Import),
+                            ))),
+                        ),
                         Module::default,
-                        |mut acc, value| match value {
-                            ModuleItem::Type(type_def) => {
-                                acc.types.push(type_def);
-                                acc
-                            }
-                            ModuleItem::Function(fn_def) => {
-                                acc.functions.push(fn_def);
-                                acc
+                        |mut acc, (visibility, (span, value))| {
+                            let (item, name) = match value {
+                                ModuleItem::Type(type_def) => {
+                                    let name = type_def.name().to_string();
+                                    acc.types.push(type_def);
+                                    (ItemRef::Type(acc.types.len() - 1), Some(name))
+                                }
+                                ModuleItem::Function(fn_def) => {
+                                    let name = fn_def.name.clone();
+                                    acc.functions.push(fn_def);
+                                    (ItemRef::Function(acc.functions.len() - 1), Some(name))
+                                }
+                                ModuleItem::Impl(impl_def) => {
+                                    acc.impls.push(impl_def);
+                                    (ItemRef::Impl(acc.impls.len() - 1), None)
+                                }
+                                ModuleItem::Const(const_def) => {
+                                    let name = const_def.id.clone();
+                                    acc.constants.push(const_def);
+                                    (ItemRef::Const(acc.constants.len() - 1), Some(name))
+                                }
+                                ModuleItem::Module(module) => {
+                                    let name = module.name.clone();
+                                    acc.modules.push(module);
+                                    (ItemRef::Module(acc.modules.len() - 1), Some(name))
+                                }
+                                ModuleItem::Import(import) => {
+                                    acc.imports.push(import);
+                                    (ItemRef::Import(acc.imports.len() - 1), None)
+                                }
+                            };
+                            acc.items.push((item, span));
+                            if let (Some(name), Some(_)) = (name, visibility) {
+                                acc.exports.insert(name);
                             }
+                            acc
                         },
                     ),
                     wst(lexem::BRA_C),
                 ),
-            ),
-            |(name, mut module)| {
+            )),
+            |(span, (name, mut module))| {
                 module.name = name;
+                module.span = span;
                 module
             },
         )(input)
@@ -143,23 +366,15 @@ impl Resolve for Module {
         &mut self,
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
-        context: &Self::Context,
-        extra: &mut Self::Extra,
-    ) -> Result<Self::Output, crate::semantic::SemanticError>
+        _context: &Self::Context,
+        _extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
     where
         Self: Sized,
     {
-        for t in self.types.iter_mut() {
-            let _ = t.resolve::<E>(scope_manager, scope_id, context, extra)?;
-        }
-
-        for func in self.functions.iter_mut() {
-            func.name = format!("${}", func.name);
-            let _ = func.desugar::<E>(scope_manager, scope_id)?;
-            let _ = func.resolve::<E>(scope_manager, scope_id, context, extra)?;
-        }
-
-        Ok(())
+        // types, functions and constants are registered under their mangled name : $Module::inner::name
+        let prefix = format!("${}{}", self.name, lexem::SEP);
+        self.resolve_with_prefix::<E>(prefix, scope_manager, scope_id)
     }
 }
 
@@ -171,13 +386,107 @@ impl GenerateCode for Module {
         instructions: &mut crate::vm::program::Program<E>,
         context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
+        for module in self.modules.iter() {
+            module.gencode(scope_manager, scope_id, instructions, context)?;
+        }
         for func in self.functions.iter() {
             func.gencode(scope_manager, scope_id, instructions, context)?;
         }
+        for impl_def in self.impls.iter() {
+            impl_def.gencode(scope_manager, scope_id, instructions, context)?;
+        }
         Ok(())
     }
 }
 
+impl LintCheck for Module {
+    /*
+     * @desc Lint the items of a resolved module, the private items must be used within the module
+     */
+    fn lint(&self, linter: &mut Linter) {
+        for module in self.modules.iter() {
+            module.lint(linter);
+        }
+        for (index, type_def) in self.types.iter().enumerate() {
+            let name = type_def.name();
+            let name = name.strip_prefix(&self.prefix).unwrap_or(name);
+            let is_used = match type_def.signature() {
+                Some((EType::User { id, .. }, _)) => linter.scope_manager.is_type_used(*id),
+                _ => true,
+            };
+            if !is_used && !self.exports.contains(name) {
+                linter.with_annotations(type_def.annotations(), |linter| {
+                    linter.report(
+                        LintId::UnusedItem,
+                        self.span_of(ItemRef::Type(index)),
+                        format!("unused type {}", name),
+                    )
+                });
+            }
+        }
+        for (index, func) in self.functions.iter().enumerate() {
+            let name = func.name.strip_prefix(&self.prefix).unwrap_or(&func.name);
+            let is_used = func.id.as_ref().map_or(true, |(id, _, _)| {
+                linter.scope_manager.is_variable_used(*id)
+            });
+            if !is_used && !self.exports.contains(name) {
+                linter.with_annotations(&func.annotations, |linter| {
+                    linter.report(
+                        LintId::UnusedItem,
+                        self.span_of(ItemRef::Function(index)),
+                        format!("unused function {}", name),
+                    )
+                });
+            }
+            func.lint(linter);
+        }
+        for impl_def in self.impls.iter() {
+            impl_def.lint(linter);
+        }
+    }
+}
+
+impl PrettyPrint for Module {
+    /*
+     * @desc Print a module with its items in the order of the source
+     * The impl blocks and the imports have no visibility
+     */
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("module ");
+        f.write(&self.name);
+        f.write(" {");
+        f.newline();
+        f.indent();
+        for (item, span) in self.items.iter() {
+            let name = match *item {
+                ItemRef::Type(index) => Some(self.types[index].name()),
+                ItemRef::Function(index) => Some(self.functions[index].name.as_str()),
+                ItemRef::Const(index) => Some(self.constants[index].id.as_str()),
+                ItemRef::Module(index) => Some(self.modules[index].name.as_str()),
+                ItemRef::Impl(_) | ItemRef::Import(_) => None,
+            };
+            f.statement(*span, |f| {
+                if name.map_or(false, |name| self.exports.contains(name)) {
+                    f.write("pub ");
+                }
+                match *item {
+                    ItemRef::Type(index) => self.types[index].pretty(f),
+                    ItemRef::Function(index) => self.functions[index].pretty(f),
+                    ItemRef::Impl(index) => self.impls[index].pretty(f),
+                    ItemRef::Const(index) => self.constants[index].pretty(f),
+                    ItemRef::Module(index) => self.modules[index].pretty(f),
+                    ItemRef::Import(index) => self.imports[index].pretty(f),
+                }
+            });
+        }
+        if self.span.is_known() {
+            f.flush_comments(self.span.offset + self.span.length.saturating_sub(1));
+        }
+        f.dedent();
+        f.write("}");
+    }
+}
+
 pub fn parse_module<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>>(
     module: Span,
     line_offset: usize,
@@ -206,10 +515,11 @@ mod tests {
     use crate::{
         test_extract_variable, test_extract_variable_with,
         vm::{
+            allocator::MemoryAddress,
             asm::operation::{GetNumFrom, OpPrimitive},
             external::test::DefaultProcessID,
         },
-        Ciphel,
+        Ciphel, CompilationError, Diagnostic,
     };
 
     #[test]
@@ -230,14 +540,16 @@ mod tests {
         
         module Test {
         
-            struct Point {
+            pub struct Point {
                 x : i64,
                 y : i64,
             }
 
-            fn test() -> i64 {
+            pub fn test() -> i64 {
                 5
             }
+
+            pub const LIMIT : i64 = 7;
         
         }    
             "##,
@@ -250,6 +562,10 @@ mod tests {
             .spawn(DefaultProcessID::default(), &mut engine)
             .expect("Spawning should have succeeded");
 
+        // the types of a module are only reachable through its path
+        let res = ciphel.compile(tid.clone(), "let point = Point { x : 7 , y : 2 };", 0);
+        assert!(res.is_err(), "{:?}", res);
+
         ciphel
             .compile(
                 tid.clone(),
@@ -260,9 +576,9 @@ mod tests {
         }
         let res2 = Test::test();
         let res3 = test();
+        let res4 = Test::LIMIT;
             
         let point = Test::Point { x : 4 , y : 9 };
-        let point2 = Point { x : 7 , y : 2 };
 
         struct Point {
             x : i64,
@@ -302,6 +618,10 @@ mod tests {
             .expect("Deserialization should have succeeded");
         assert_eq!(res, 6);
 
+        let res = test_extract_variable::<i64>("res4", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 7);
+
         test_extract_variable_with(
             "point",
             |address, stack, heap| {
@@ -319,15 +639,18 @@ mod tests {
         );
 
         test_extract_variable_with(
-            "point2",
+            "point3",
             |address, stack, heap| {
                 let x = OpPrimitive::get_num_from::<u64>(address, stack, heap)
                     .expect("Deserialization should have succeeded");
                 let y = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)
                     .expect("Deserialization should have succeeded");
+                let z = OpPrimitive::get_num_from::<u64>(address.add(16), stack, heap)
+                    .expect("Deserialization should have succeeded");
 
                 assert_eq!(x, 7);
                 assert_eq!(y, 2);
+                assert_eq!(z, 6);
             },
             scope_manager,
             stack,
@@ -335,38 +658,544 @@ mod tests {
         );
 
         test_extract_variable_with(
-            "point3",
+            "point4",
             |address, stack, heap| {
                 let x = OpPrimitive::get_num_from::<u64>(address, stack, heap)
                     .expect("Deserialization should have succeeded");
                 let y = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)
                     .expect("Deserialization should have succeeded");
-                let z = OpPrimitive::get_num_from::<u64>(address.add(16), stack, heap)
-                    .expect("Deserialization should have succeeded");
 
-                assert_eq!(x, 7);
-                assert_eq!(y, 2);
-                assert_eq!(z, 6);
+                assert_eq!(x, 4);
+                assert_eq!(y, 9);
             },
             scope_manager,
             stack,
             &ciphel.heap,
         );
+    }
+
+    #[test]
+    fn valid_nested_module() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        ciphel
+            .import(
+                DefaultProcessID::default(),
+                r##"
+        module Lib {
+            pub module Math {
+                pub const SCALE : i64 = 3;
+
+                pub fn triple(x : i64) -> i64 {
+                    x * 3
+                }
+
+                fn hidden() -> i64 {
+                    0
+                }
+            }
+
+            pub fn answer() -> i64 {
+                42
+            }
+        }
+            "##,
+                0,
+            )
+            .expect("Module parsing should have succeeded");
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        let res1 = Lib::Math::triple(2);
+        let res2 = Lib::Math::SCALE;
+
+        use Lib::Math::{triple as times_three, SCALE};
+        let res3 = times_three(SCALE);
+
+        use Lib::Math;
+        let res4 = Math::triple(5);
+
+        use Lib::answer;
+        let res5 = answer();
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+
+        for (name, expected) in [
+            ("res1", 6),
+            ("res2", 3),
+            ("res3", 9),
+            ("res4", 15),
+            ("res5", 42),
+        ] {
+            let res = test_extract_variable::<i64>(name, scope_manager, stack, &ciphel.heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, expected, "{}", name);
+        }
+
+        for src_code in [
+            "let res = Lib::Math::hidden();",
+            "use Lib::Math::hidden;",
+            "use Lib::Math::unknown;",
+            "use Lib::answer; use Lib::answer;",
+        ] {
+            let res = ciphel.compile(tid.clone(), src_code, 0);
+            assert!(res.is_err(), "{}", src_code);
+        }
+    }
+
+    #[test]
+    fn valid_module_constant() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        ciphel
+            .import(
+                DefaultProcessID::default(),
+                r##"
+        module Bounds {
+            pub const LIMIT : i64 = 7;
+        }
+            "##,
+                0,
+            )
+            .expect("Module parsing should have succeeded");
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        let res = ciphel.compile(tid.clone(), "let res = LIMIT;", 0);
+        assert!(res.is_err(), "{:?}", res);
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        const LIMIT : i64 = 3;
+        let res1 = LIMIT;
+        let res2 = Bounds::LIMIT;
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+
+        let res = test_extract_variable::<i64>("res1", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 3);
+        let res = test_extract_variable::<i64>("res2", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 7);
+    }
+
+    #[test]
+    fn valid_module_type() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        for module in [
+            r##"
+        module First {
+            pub struct Hidden {
+                x : i64,
+            }
+
+            impl Hidden {
+                fn get(self) -> i64 {
+                    self.x
+                }
+            }
+
+            pub fn make() -> Hidden {
+                Hidden { x : 3 }
+            }
+        }
+            "##,
+            r##"
+        module Second {
+            pub struct Hidden {
+                x : i64,
+                y : i64,
+            }
+
+            impl Hidden {
+                fn get(self) -> i64 {
+                    self.x + self.y
+                }
+            }
+        }
+            "##,
+        ] {
+            ciphel
+                .import(DefaultProcessID::default(), module, 0)
+                .expect("Module parsing should have succeeded");
+        }
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        let res = ciphel.compile(tid.clone(), "let hidden = Hidden { x : 3 };", 0);
+        assert!(res.is_err(), "{:?}", res);
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        let first = First::make();
+        let res1 = first.get();
+        let second = Second::Hidden { x : 5 , y : 1 };
+        let res2 = second.get();
+
+        use First::Hidden;
+        let other = Hidden { x : 4 };
+        let res3 = other.get();
+        let text = format("{second}");
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+
+        for (name, expected) in [("res1", 3), ("res2", 6), ("res3", 4)] {
+            let res = test_extract_variable::<i64>(name, scope_manager, stack, &ciphel.heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, expected, "{}", name);
+        }
 
         test_extract_variable_with(
-            "point4",
+            "text",
             |address, stack, heap| {
-                let x = OpPrimitive::get_num_from::<u64>(address, stack, heap)
-                    .expect("Deserialization should have succeeded");
-                let y = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)
+                let address: MemoryAddress = OpPrimitive::get_num_from::<u64>(address, stack, heap)
+                    .expect("Deserialization should have succeeded")
+                    .try_into()
+                    .unwrap();
+                let text = OpPrimitive::get_string_from(address.add(8), stack, heap)
                     .expect("Deserialization should have succeeded");
-
-                assert_eq!(x, 4);
-                assert_eq!(y, 9);
+                assert_eq!(text, "Second::Hidden { x: 5, y: 1 }");
             },
             scope_manager,
             stack,
             &ciphel.heap,
         );
     }
+
+    #[test]
+    fn valid_module_loader() {
+        struct CountingLoader {
+            sources: std::collections::HashMap<String, String>,
+            loads: std::rc::Rc<std::cell::Cell<usize>>,
+        }
+        impl crate::vm::external::ModuleLoader<DefaultProcessID> for CountingLoader {
+            fn load(&mut self, _pid: DefaultProcessID, path: &str) -> Option<String> {
+                self.loads.set(self.loads.get() + 1);
+                self.sources.get(path).cloned()
+            }
+        }
+
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+
+        let loads = std::rc::Rc::new(std::cell::Cell::new(0));
+        ciphel.set_module_loader(CountingLoader {
+            sources: [
+                (
+                    "lib/math".to_string(),
+                    r##"
+        module Math {
+            pub fn mul(x : i64, y : i64) -> i64 {
+                x * y
+            }
+        }
+                    "##
+                    .to_string(),
+                ),
+                (
+                    "lib/geometry".to_string(),
+                    r##"
+        module Geometry {
+            import "lib/math";
+
+            pub fn area(w : i64, h : i64) -> i64 {
+                Math::mul(w, h)
+            }
+        }
+                    "##
+                    .to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            loads: loads.clone(),
+        });
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        import "lib/geometry";
+        let res1 = Geometry::area(2, 3);
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        import "lib/math";
+        let res2 = Math::mul(4, 5);
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        assert_eq!(loads.get(), 2);
+
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+
+        let res = test_extract_variable::<i64>("res1", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 6);
+        let res = test_extract_variable::<i64>("res2", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 20);
+    }
+
+    #[test]
+    fn robustness_module_loader() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        ciphel.set_module_loader(
+            [
+                ("a".to_string(), "module A { import \"b\"; }".to_string()),
+                ("b".to_string(), "module B { import \"a\"; }".to_string()),
+                (
+                    "c".to_string(),
+                    "module C {\n pub fn f() -> i64 {\n return true;\n }\n}".to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>(),
+        );
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        let res = ciphel.compile(tid.clone(), "\n import \"missing\";", 0);
+        assert!(
+            matches!(
+                res.as_ref().map_err(Vec::as_slice),
+                Err([Diagnostic {
+                    error: CompilationError::UnknownModule(2, path),
+                    ..
+                }]) if path == "missing"
+            ),
+            "{:?}",
+            res
+        );
+
+        let res = ciphel.compile(tid.clone(), "import \"a\";", 0);
+        assert!(
+            matches!(
+                res.as_ref().map_err(Vec::as_slice),
+                Err([Diagnostic {
+                    error: CompilationError::ModuleError(1, path, err),
+                    ..
+                }]) if path == "a" && matches!(
+                    &**err,
+                    CompilationError::ModuleError(1, path, err)
+                        if path == "b" && matches!(&**err, CompilationError::CyclicImport(1, path) if path == "a")
+                )
+            ),
+            "{:?}",
+            res
+        );
+
+        // the error is reported at its line in the module, under the import of the module
+        let res = ciphel.compile(tid.clone(), "\n\n import \"c\";", 0);
+        assert!(
+            matches!(
+                res.as_ref().map_err(Vec::as_slice),
+                Err([Diagnostic {
+                    error: CompilationError::ModuleError(3, path, err),
+                    ..
+                }]) if path == "c" && matches!(**err, CompilationError::SemanticError(3, _, _))
+            ),
+            "{:?}",
+            res
+        );
+
+        let res = ciphel.compile(tid.clone(), "let x = 1; { import \"missing\"; }", 0);
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    #[test]
+    fn robustness_rejected_import() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        ciphel.set_module_loader(
+            [(
+                "lib/math".to_string(),
+                r##"
+        module Math {
+            pub struct Pair {
+                x : i64,
+                y : i64,
+            }
+
+            pub fn mul(pair : Pair) -> i64 {
+                pair.x * pair.y
+            }
+        }
+                "##
+                .to_string(),
+            )]
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>(),
+        );
+
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        // the module imported by a rejected compilation is neither linked nor kept by the process
+        let res = ciphel.compile(tid.clone(), "import \"lib/math\"; let res = unknown;", 0);
+        assert!(res.is_err(), "{:?}", res);
+        assert!(ciphel.runtime.modules[&DefaultProcessID::default()].is_empty());
+
+        let res = ciphel.compile(
+            tid.clone(),
+            "let res = Math::mul(Math::Pair { x : 4 , y : 5 });",
+            0,
+        );
+        assert!(res.is_err(), "{:?}", res);
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        import "lib/math";
+        let res = Math::mul(Math::Pair { x : 4 , y : 5 });
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+        let res = test_extract_variable::<i64>("res", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 20);
+    }
 }
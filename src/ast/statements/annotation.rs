@@ -0,0 +1,412 @@
+use nom::{
+    branch::alt,
+    combinator::{cut, map, opt, verify},
+    multi::{many0, separated_list0},
+    sequence::{delimited, pair, preceded},
+};
+use nom_supreme::ParserExt;
+
+use crate::{
+    ast::{
+        format::{escape, Formatter, PrettyPrint},
+        utils::{
+            io::{PResult, Span},
+            lexem,
+            numbers::parse_number,
+            strings::{eater, parse_id, string_parser::parse_string, wst, ID},
+        },
+        TryParse,
+    },
+    semantic::{
+        lint::{LintCheck, Linter},
+        Desugar, EType, Resolve, SemanticError, TypeOf,
+    },
+    vm::GenerateCode,
+};
+
+use super::Statement;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationArg {
+    Number(u128),
+    Str(String),
+    Id(ID),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub name: ID,
+    pub args: Vec<AnnotationArg>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Annotations(pub Vec<Annotation>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated {
+    pub annotations: Annotations,
+    pub statement: Box<Statement>,
+}
+
+impl Annotations {
+    pub fn get(&self, name: &str) -> Option<&Annotation> {
+        self.0.iter().find(|annotation| annotation.name == name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl TryParse for Annotation {
+    /*
+     * @desc Parse an annotation
+     *
+     * @grammar
+     * Annotation := @ ID | @ ID \( Annotation_args \)
+     * Annotation_args := Annotation_arg , Annotation_args | Annotation_arg | ε
+     * Annotation_arg := UNumber | StaticString | ID
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            preceded(
+                wst(lexem::ANNOTATION),
+                cut(pair(
+                    parse_id.context("Invalid annotation name"),
+                    opt(delimited(
+                        wst(lexem::PAR_O),
+                        separated_list0(
+                            wst(lexem::COMA),
+                            eater::ws(alt((
+                                map(parse_number, AnnotationArg::Number),
+                                map(parse_string, AnnotationArg::Str),
+                                map(parse_id, AnnotationArg::Id),
+                            ))),
+                        ),
+                        wst(lexem::PAR_C),
+                    )),
+                )),
+            ),
+            |(name, args)| Annotation {
+                name,
+                args: args.unwrap_or_default(),
+            },
+        )(input)
+    }
+}
+
+impl TryParse for Annotations {
+    fn parse(input: Span) -> PResult<Self> {
+        map(many0(Annotation::parse), Annotations)(input)
+    }
+}
+
+impl TryParse for Annotated {
+    /*
+     * @desc Parse an annotated statement
+     *
+     * @grammar
+     * Annotated := Annotation Annotated | Annotation Statement
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            pair(
+                verify(Annotations::parse, |annotations| !annotations.is_empty()),
+                cut(Statement::parse),
+            ),
+            |(annotations, statement)| Annotated {
+                annotations,
+                statement: Box::new(statement),
+            },
+        )(input)
+    }
+}
+
+impl Resolve for Annotated {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        scope_manager.annotated(&self.annotations, |scope_manager| {
+            self.statement
+                .resolve::<E>(scope_manager, scope_id, context, extra)
+        })
+    }
+}
+
+impl LintCheck for Annotated {
+    fn lint(&self, linter: &mut Linter) {
+        linter.with_annotations(&self.annotations, |linter| self.statement.lint(linter));
+    }
+}
+
+impl Desugar<Statement> for Annotated {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        if let Some(output) = self.statement.desugar::<E>(scope_manager, scope_id)? {
+            *self.statement = output;
+        }
+        Ok(None)
+    }
+}
+
+impl TypeOf for Annotated {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        self.statement.type_of(&scope_manager, scope_id)
+    }
+}
+
+impl GenerateCode for Annotated {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        scope_manager.annotated(&self.annotations, |scope_manager| {
+            self.statement
+                .gencode::<E>(scope_manager, scope_id, instructions, context)
+        })
+    }
+}
+
+impl PrettyPrint for Annotation {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("@");
+        f.write(&self.name);
+        if self.args.is_empty() {
+            return;
+        }
+        f.list("(", &self.args, ")", false, |f, arg| match arg {
+            AnnotationArg::Number(value) => f.write(&value.to_string()),
+            AnnotationArg::Str(value) => f.write(&format!("\"{}\"", escape(value, '"', false))),
+            AnnotationArg::Id(value) => f.write(value),
+        });
+    }
+}
+
+impl PrettyPrint for Annotated {
+    fn pretty(&self, f: &mut Formatter) {
+        for annotation in self.annotations.0.iter() {
+            annotation.pretty(f);
+            f.newline();
+        }
+        self.statement.pretty(f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::statements::definition::{Definition, FnDef, TypeDef},
+        test_extract_variable, test_statements,
+    };
+
+    use super::*;
+
+    #[test]
+    fn valid_annotation() {
+        let res = Annotation::parse("@inline".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Annotation {
+                name: "inline".to_string(),
+                args: vec![],
+            },
+            value
+        );
+
+        let res = Annotation::parse(r#"@deprecated("use g", 10, strict)"#.into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Annotation {
+                name: "deprecated".to_string(),
+                args: vec![
+                    AnnotationArg::Str("use g".to_string()),
+                    AnnotationArg::Number(10),
+                    AnnotationArg::Id("strict".to_string()),
+                ],
+            },
+            value
+        );
+    }
+
+    #[test]
+    fn valid_annotated() {
+        let res = Statement::parse(
+            r#"
+        @test
+        @weight(10)
+        let x = 10;
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        let Statement::Annotated(Annotated { annotations, .. }) = value else {
+            panic!("Expected an annotated statement");
+        };
+        assert!(annotations.contains("test"));
+        assert_eq!(
+            Some(&vec![AnnotationArg::Number(10)]),
+            annotations.get("weight").map(|annotation| &annotation.args)
+        );
+    }
+
+    #[test]
+    fn robustness_annotation() {
+        let res = Annotation::parse("@ (10)".into());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn valid_annotated_definitions() {
+        let res = Statement::parse(
+            r#"
+        @inline
+        fn f() -> i64 {
+            return 10;
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        let Statement::Definition(Definition::Fn(FnDef { annotations, .. })) = value else {
+            panic!("Expected a function definition");
+        };
+        assert!(annotations.contains("inline"));
+
+        let res = Statement::parse(
+            r#"
+        @deprecated("use Point3")
+        struct Point {
+            x : i64,
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        let Statement::Definition(Definition::Type(type_def)) = value else {
+            panic!("Expected a type definition");
+        };
+        assert!(type_def.annotations().contains("deprecated"));
+        assert!(matches!(type_def, TypeDef::Struct(_)));
+    }
+
+    #[test]
+    fn valid_annotation_resolve() {
+        let mut engine = crate::vm::external::test::ExternFuncTestEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<u64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            true
+        }
+
+        // the extern function can only be resolved within a statement annotated with @test
+        test_statements(
+            r##"
+        @test
+        let res = test_annotated();
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+
+        let mut statement = Statement::parse("let res = test_annotated();".into())
+            .expect("Parsing should have succeeded")
+            .1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = statement.resolve::<crate::vm::external::test::ExternFuncTestEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        assert!(
+            matches!(
+                res.as_ref().map_err(|err| err.clone().into_inner()),
+                Err(SemanticError::UnknownItem(_))
+            ),
+            "{:?}",
+            res
+        );
+        assert!(scope_manager.annotation("test").is_none());
+    }
+
+    #[test]
+    fn valid_annotated_program() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 12);
+            true
+        }
+
+        test_statements(
+            r##"
+        @export
+        struct Point {
+            x : i64,
+        }
+
+        impl Point {
+            @inline
+            fn double(self) -> i64 {
+                return self.x * 2;
+            }
+        }
+
+        @inline
+        @weight(10)
+        fn add(x : i64, y : i64) -> i64 {
+            return x + y;
+        }
+
+        @test
+        let res = add(Point { x : 1 }.double(), 10);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+}
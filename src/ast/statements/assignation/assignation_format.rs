@@ -0,0 +1,38 @@
+use crate::ast::format::{Formatter, PrettyPrint};
+
+use super::{AssignValue, Assignation};
+
+impl Assignation {
+    /*
+     * @desc Print an assignation without its ending semicolon, as in the header of a for loop
+     */
+    pub(crate) fn pretty_without_semicolon(&self, f: &mut Formatter) {
+        self.left.pretty(f);
+        f.write(" = ");
+        self.right.pretty_without_semicolon(f);
+    }
+}
+
+impl AssignValue {
+    pub(crate) fn pretty_without_semicolon(&self, f: &mut Formatter) {
+        match self {
+            AssignValue::Block(value) => value.pretty(f),
+            AssignValue::Expr(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for AssignValue {
+    fn pretty(&self, f: &mut Formatter) {
+        self.pretty_without_semicolon(f);
+        f.write(";");
+    }
+}
+
+impl PrettyPrint for Assignation {
+    fn pretty(&self, f: &mut Formatter) {
+        self.left.pretty(f);
+        f.write(" = ");
+        self.right.pretty(f);
+    }
+}
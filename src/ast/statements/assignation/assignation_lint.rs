@@ -0,0 +1,17 @@
+use crate::semantic::lint::{LintCheck, Linter};
+
+use super::{AssignValue, Assignation};
+
+impl LintCheck for Assignation {
+    fn lint(&self, linter: &mut Linter) {
+        self.right.lint(linter);
+    }
+}
+
+impl LintCheck for AssignValue {
+    fn lint(&self, linter: &mut Linter) {
+        if let AssignValue::Block(block) = self {
+            block.lint(linter);
+        }
+    }
+}
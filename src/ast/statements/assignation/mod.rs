@@ -1,7 +1,9 @@
 use crate::ast::expressions::Expression;
 
 use super::block::ExprBlock;
+pub mod assignation_format;
 pub mod assignation_gencode;
+pub mod assignation_lint;
 pub mod assignation_parse;
 pub mod assignation_resolve;
 pub mod assignation_typeof;
@@ -0,0 +1,96 @@
+use crate::ast::{
+    expressions::Expression,
+    format::{Formatter, PrettyPrint},
+    statements::{return_stat::Return, Statement},
+    utils::io::{SourceSpan, SourceSpans},
+};
+
+use super::{Block, ClosureBlock, ExprBlock, FunctionBlock, LambdaBlock};
+
+/*
+ * @desc Print the statements of a block between braces, one statement per line
+ */
+fn pretty_block(
+    f: &mut Formatter,
+    statements: &[Statement],
+    spans: &SourceSpans,
+    span: SourceSpan,
+) {
+    let end = span
+        .is_known()
+        .then(|| span.offset + span.length.saturating_sub(1));
+    let has_comments = end.map_or(false, |end| f.has_comments_before(end));
+    if statements.is_empty() && !has_comments {
+        f.write("{}");
+        return;
+    }
+    f.write("{");
+    f.newline();
+    f.indent();
+    for (index, statement) in statements.iter().enumerate() {
+        f.statement(spans.get(index), |f| statement.pretty(f));
+    }
+    if let Some(end) = end {
+        f.flush_comments(end);
+    }
+    f.dedent();
+    f.write("}");
+}
+
+/*
+ * @desc Value of a block made of a single inline return without comments, it can be printed alone
+ */
+fn inline_value<'a>(
+    f: &Formatter,
+    statements: &'a [Statement],
+    span: SourceSpan,
+) -> Option<&'a Expression> {
+    let [Statement::Return(Return::Inline { expr, .. })] = statements else {
+        return None;
+    };
+    let has_comments = span.is_known() && f.has_comments_before(span.offset + span.length);
+    (!has_comments).then_some(expr.as_ref())
+}
+
+impl PrettyPrint for Block {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_block(f, &self.statements, &self.spans, self.metadata.span);
+    }
+}
+
+impl PrettyPrint for FunctionBlock {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_block(f, &self.statements, &self.spans, self.metadata.span);
+    }
+}
+
+impl PrettyPrint for ExprBlock {
+    fn pretty(&self, f: &mut Formatter) {
+        match inline_value(f, &self.statements, self.metadata.span) {
+            Some(value) => {
+                f.write("{ ");
+                value.pretty(f);
+                f.write(" }");
+            }
+            None => pretty_block(f, &self.statements, &self.spans, self.metadata.span),
+        }
+    }
+}
+
+impl PrettyPrint for ClosureBlock {
+    fn pretty(&self, f: &mut Formatter) {
+        match inline_value(f, &self.statements, self.metadata.span) {
+            Some(value) => value.pretty(f),
+            None => pretty_block(f, &self.statements, &self.spans, self.metadata.span),
+        }
+    }
+}
+
+impl PrettyPrint for LambdaBlock {
+    fn pretty(&self, f: &mut Formatter) {
+        match inline_value(f, &self.statements, self.metadata.span) {
+            Some(value) => value.pretty(f),
+            None => pretty_block(f, &self.statements, &self.spans, self.metadata.span),
+        }
+    }
+}
@@ -20,8 +20,10 @@ impl GenerateCode for Block {
         instructions: &mut crate::vm::program::Program<E>,
         context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
-        for statement in &self.statements {
-            let _ = statement.gencode::<E>(scope_manager, self.scope, instructions, context)?;
+        for (index, statement) in self.statements.iter().enumerate() {
+            let _ = instructions.at(self.spans.get(index), |instructions| {
+                statement.gencode::<E>(scope_manager, self.scope, instructions, context)
+            })?;
         }
         Ok(())
     }
@@ -69,17 +71,19 @@ impl GenerateCode for FunctionBlock {
         }
 
         // generate code for all statements
-        for statement in &self.statements {
-            let _ = statement.gencode::<E>(
-                scope_manager,
-                self.scope,
-                instructions,
-                &CodeGenerationContext {
-                    return_label: Some(epilog_label),
-                    break_label: None,
-                    continue_label: None,
-                },
-            )?;
+        for (index, statement) in self.statements.iter().enumerate() {
+            let _ = instructions.at(self.spans.get(index), |instructions| {
+                statement.gencode::<E>(
+                    scope_manager,
+                    self.scope,
+                    instructions,
+                    &CodeGenerationContext {
+                        return_label: Some(epilog_label),
+                        break_label: None,
+                        continue_label: None,
+                    },
+                )
+            })?;
         }
 
         // Function epilog
@@ -132,17 +136,19 @@ impl GenerateCode for ClosureBlock {
         }
 
         // generate code for all statements
-        for statement in &self.statements {
-            let _ = statement.gencode::<E>(
-                scope_manager,
-                self.scope,
-                instructions,
-                &CodeGenerationContext {
-                    return_label: Some(epilog_label),
-                    break_label: None,
-                    continue_label: None,
-                },
-            )?;
+        for (index, statement) in self.statements.iter().enumerate() {
+            let _ = instructions.at(self.spans.get(index), |instructions| {
+                statement.gencode::<E>(
+                    scope_manager,
+                    self.scope,
+                    instructions,
+                    &CodeGenerationContext {
+                        return_label: Some(epilog_label),
+                        break_label: None,
+                        continue_label: None,
+                    },
+                )
+            })?;
         }
 
         // Function epilog
@@ -195,17 +201,19 @@ impl GenerateCode for LambdaBlock {
         }
 
         // generate code for all statements
-        for statement in &self.statements {
-            let _ = statement.gencode::<E>(
-                scope_manager,
-                self.scope,
-                instructions,
-                &CodeGenerationContext {
-                    return_label: Some(epilog_label),
-                    break_label: None,
-                    continue_label: None,
-                },
-            )?;
+        for (index, statement) in self.statements.iter().enumerate() {
+            let _ = instructions.at(self.spans.get(index), |instructions| {
+                statement.gencode::<E>(
+                    scope_manager,
+                    self.scope,
+                    instructions,
+                    &CodeGenerationContext {
+                        return_label: Some(epilog_label),
+                        break_label: None,
+                        continue_label: None,
+                    },
+                )
+            })?;
         }
 
         // lambda epilog
@@ -288,17 +296,19 @@ impl GenerateCode for ExprBlock {
         }
 
         // generate code for all statements
-        for statement in &self.statements {
-            let _ = statement.gencode::<E>(
-                scope_manager,
-                self.scope,
-                instructions,
-                &CodeGenerationContext {
-                    return_label: Some(epilog_label),
-                    break_label: None,
-                    continue_label: None,
-                },
-            )?;
+        for (index, statement) in self.statements.iter().enumerate() {
+            let _ = instructions.at(self.spans.get(index), |instructions| {
+                statement.gencode::<E>(
+                    scope_manager,
+                    self.scope,
+                    instructions,
+                    &CodeGenerationContext {
+                        return_label: Some(epilog_label),
+                        break_label: None,
+                        continue_label: None,
+                    },
+                )
+            })?;
         }
 
         if ScopeState::IIFE == scope_state {
@@ -0,0 +1,24 @@
+use crate::{
+    ast::statements::lint_statements,
+    semantic::lint::{LintCheck, Linter},
+};
+
+use super::{Block, ExprBlock, FunctionBlock};
+
+impl LintCheck for Block {
+    fn lint(&self, linter: &mut Linter) {
+        linter.in_block(|linter| lint_statements(&self.statements, linter));
+    }
+}
+
+impl LintCheck for ExprBlock {
+    fn lint(&self, linter: &mut Linter) {
+        linter.in_block(|linter| lint_statements(&self.statements, linter));
+    }
+}
+
+impl LintCheck for FunctionBlock {
+    fn lint(&self, linter: &mut Linter) {
+        linter.in_block(|linter| lint_statements(&self.statements, linter));
+    }
+}
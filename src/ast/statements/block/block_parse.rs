@@ -10,7 +10,7 @@ use crate::{
         expressions::Expression,
         statements::{return_stat::Return, Statement},
         utils::{
-            io::{PResult, Span},
+            io::{located, PResult, SourceSpan, SourceSpans, Span},
             lexem,
             strings::wst,
         },
@@ -21,16 +21,34 @@ use crate::{
 
 use super::{Block, ClosureBlock, ExprBlock, FunctionBlock, LambdaBlock};
 
+/*
+ * @desc Parse the statements of a block along the location of the block and of each statement
+ *
+ * @grammar
+ * Scope := { Statements }
+ */
+fn parse_statements_block(input: Span) -> PResult<(SourceSpan, (Vec<Statement>, SourceSpans))> {
+    located(map(
+        delimited(
+            wst(lexem::BRA_O),
+            cut(many0(located(Statement::parse))),
+            cut(wst(lexem::BRA_C)),
+        ),
+        |statements| {
+            let (spans, statements) = statements.into_iter().unzip();
+            (statements, SourceSpans(spans))
+        },
+    ))(input)
+}
+
 impl TryParse for Block {
     fn parse(input: Span) -> PResult<Self> {
-        let (remainder, block) = map(
-            delimited(
-                wst(lexem::BRA_O),
-                cut(many0(Statement::parse)),
-                cut(wst(lexem::BRA_C)),
-            ),
-            |value| Block::new(value),
-        )(input)?;
+        let (remainder, block) = map(parse_statements_block, |(span, (statements, spans))| {
+            let mut block = Block::new(statements);
+            block.spans = spans;
+            block.metadata.span = span;
+            block
+        })(input)?;
 
         let mut found_inline_return_statement = false;
         for statement in block.statements.iter() {
@@ -53,14 +71,12 @@ impl TryParse for Block {
 
 impl TryParse for FunctionBlock {
     fn parse(input: Span) -> PResult<Self> {
-        let (remainder, block) = map(
-            delimited(
-                wst(lexem::BRA_O),
-                cut(many0(Statement::parse)),
-                cut(wst(lexem::BRA_C)),
-            ),
-            |value| FunctionBlock::new(value),
-        )(input)?;
+        let (remainder, block) = map(parse_statements_block, |(span, (statements, spans))| {
+            let mut block = FunctionBlock::new(statements);
+            block.spans = spans;
+            block.metadata.span = span;
+            block
+        })(input)?;
 
         let mut found_inline_return_statement = false;
         for statement in block.statements.iter() {
@@ -84,20 +100,21 @@ impl TryParse for FunctionBlock {
 impl TryParse for ClosureBlock {
     fn parse(input: Span) -> PResult<Self> {
         let (remainder, block) = alt((
-            map(
-                delimited(
-                    wst(lexem::BRA_O),
-                    cut(many0(Statement::parse)),
-                    cut(wst(lexem::BRA_C)),
-                ),
-                |value| ClosureBlock::new(value),
-            ),
-            map(Expression::parse, |expr| {
+            map(parse_statements_block, |(span, (statements, spans))| {
+                let mut block = ClosureBlock::new(statements);
+                block.spans = spans;
+                block.metadata.span = span;
+                block
+            }),
+            map(located(Expression::parse), |(span, expr)| {
                 let statement = Statement::Return(Return::Inline {
                     expr: Box::new(expr),
                     metadata: Metadata::default(),
                 });
-                ClosureBlock::new(vec![statement])
+                let mut block = ClosureBlock::new(vec![statement]);
+                block.spans = SourceSpans(vec![span]);
+                block.metadata.span = span;
+                block
             }),
         ))(input)?;
 
@@ -123,20 +140,21 @@ impl TryParse for ClosureBlock {
 impl TryParse for LambdaBlock {
     fn parse(input: Span) -> PResult<Self> {
         let (remainder, block) = alt((
-            map(
-                delimited(
-                    wst(lexem::BRA_O),
-                    cut(many0(Statement::parse)),
-                    cut(wst(lexem::BRA_C)),
-                ),
-                |value| LambdaBlock::new(value),
-            ),
-            map(Expression::parse, |expr| {
+            map(parse_statements_block, |(span, (statements, spans))| {
+                let mut block = LambdaBlock::new(statements);
+                block.spans = spans;
+                block.metadata.span = span;
+                block
+            }),
+            map(located(Expression::parse), |(span, expr)| {
                 let statement = Statement::Return(Return::Inline {
                     expr: Box::new(expr),
                     metadata: Metadata::default(),
                 });
-                LambdaBlock::new(vec![statement])
+                let mut block = LambdaBlock::new(vec![statement]);
+                block.spans = SourceSpans(vec![span]);
+                block.metadata.span = span;
+                block
             }),
         ))(input)?;
 
@@ -161,14 +179,12 @@ impl TryParse for LambdaBlock {
 
 impl TryParse for ExprBlock {
     fn parse(input: Span) -> PResult<Self> {
-        let (remainder, block) = map(
-            delimited(
-                wst(lexem::BRA_O),
-                cut(many0(Statement::parse)),
-                cut(wst(lexem::BRA_C)),
-            ),
-            |value| ExprBlock::new(value),
-        )(input)?;
+        let (remainder, block) = map(parse_statements_block, |(span, (statements, spans))| {
+            let mut block = ExprBlock::new(statements);
+            block.spans = spans;
+            block.metadata.span = span;
+            block
+        })(input)?;
 
         let mut found_inline_return_statement = false;
         for statement in block.statements.iter() {
@@ -26,8 +26,12 @@ impl Resolve for Block {
             self.scope = Some(inner_scope)
         }
 
-        for instruction in &mut self.statements {
-            let _ = instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())?;
+        for (index, instruction) in self.statements.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
         }
 
         // let return_type = self.type_of(&scope_manager, scope_id)?;
@@ -110,9 +114,16 @@ impl Resolve for FunctionBlock {
             let inner_scope = scope_manager.spawn_allocating(scope_id, true)?;
             self.scope = Some(inner_scope)
         }
+        if let Some(context) = context {
+            scope_manager.register_return_type(self.scope.unwrap(), context.clone());
+        }
 
-        for instruction in &mut self.statements {
-            let _ = instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())?;
+        for (index, instruction) in self.statements.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
         }
 
         // let return_type = self.type_of(&scope_manager, scope_id)?;
@@ -184,9 +195,16 @@ impl Resolve for ClosureBlock {
         scope_manager
             .scope_states
             .insert(self.scope.unwrap(), ScopeState::Closure);
+        if let Some(context) = context {
+            scope_manager.register_return_type(self.scope.unwrap(), context.clone());
+        }
 
-        for instruction in &mut self.statements {
-            let _ = instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())?;
+        for (index, instruction) in self.statements.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
         }
 
         // let return_type = self.type_of(&scope_manager, scope_id)?;
@@ -258,9 +276,16 @@ impl Resolve for LambdaBlock {
         scope_manager
             .scope_states
             .insert(self.scope.unwrap(), ScopeState::Lambda);
+        if let Some(context) = context {
+            scope_manager.register_return_type(self.scope.unwrap(), context.clone());
+        }
 
-        for instruction in &mut self.statements {
-            let _ = instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())?;
+        for (index, instruction) in self.statements.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
         }
 
         // let return_type = self.type_of(&scope_manager, scope_id)?;
@@ -338,8 +363,12 @@ impl Resolve for ExprBlock {
                 .insert(self.scope.unwrap(), ScopeState::Inline);
         }
 
-        for instruction in &mut self.statements {
-            let _ = instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())?;
+        for (index, instruction) in self.statements.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    instruction.resolve::<E>(scope_manager, self.scope, context, &mut ())
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
         }
 
         if let Some(mapping) = scope_manager.allocating_scope.get(&self.scope.unwrap()) {
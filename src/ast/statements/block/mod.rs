@@ -1,8 +1,13 @@
-use crate::semantic::{Metadata, SemanticError};
+use crate::{
+    ast::utils::io::SourceSpans,
+    semantic::{Metadata, SemanticError},
+};
 
 use super::Statement;
 
+pub mod block_format;
 pub mod block_gencode;
+pub mod block_lint;
 pub mod block_parse;
 pub mod block_resolve;
 pub mod block_typeof;
@@ -10,6 +15,7 @@ pub mod block_typeof;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     pub statements: Vec<Statement>,
+    pub spans: SourceSpans, // location of each statement
     pub scope: Option<u128>,
     pub metadata: Metadata,
 }
@@ -17,6 +23,7 @@ pub struct Block {
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionBlock {
     pub statements: Vec<Statement>,
+    pub spans: SourceSpans, // location of each statement
     pub scope: Option<u128>,
     pub metadata: Metadata,
 }
@@ -24,6 +31,7 @@ pub struct FunctionBlock {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ClosureBlock {
     pub statements: Vec<Statement>,
+    pub spans: SourceSpans, // location of each statement
     pub scope: Option<u128>,
     pub metadata: Metadata,
 }
@@ -31,6 +39,7 @@ pub struct ClosureBlock {
 #[derive(Debug, Clone, PartialEq)]
 pub struct LambdaBlock {
     pub statements: Vec<Statement>,
+    pub spans: SourceSpans, // location of each statement
     pub scope: Option<u128>,
     pub metadata: Metadata,
 }
@@ -38,6 +47,7 @@ pub struct LambdaBlock {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExprBlock {
     pub statements: Vec<Statement>,
+    pub spans: SourceSpans, // location of each statement
     pub scope: Option<u128>,
     pub metadata: Metadata,
 }
@@ -57,6 +67,7 @@ impl FunctionBlock {
         Self {
             metadata: Metadata::default(),
             statements,
+            spans: SourceSpans::default(),
             scope: None,
         }
     }
@@ -81,6 +92,7 @@ impl ClosureBlock {
         Self {
             metadata: Metadata::default(),
             statements,
+            spans: SourceSpans::default(),
             scope: None,
         }
     }
@@ -105,6 +117,7 @@ impl LambdaBlock {
         Self {
             metadata: Metadata::default(),
             statements,
+            spans: SourceSpans::default(),
             scope: None,
         }
     }
@@ -128,6 +141,7 @@ impl ExprBlock {
         Self {
             metadata: Metadata::default(),
             statements,
+            spans: SourceSpans::default(),
             scope: None,
         }
     }
@@ -152,6 +166,7 @@ impl Block {
         Self {
             metadata: Metadata::default(),
             statements,
+            spans: SourceSpans::default(),
             scope: None,
         }
     }
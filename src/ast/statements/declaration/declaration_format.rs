@@ -0,0 +1,85 @@
+use crate::ast::format::{Formatter, PrettyPrint};
+
+use super::{Declaration, DeclaredVar, PatternVar, TypedVar};
+
+impl Declaration {
+    /*
+     * @desc Print a declaration without its ending semicolon, as in the header of a for loop
+     */
+    pub(crate) fn pretty_without_semicolon(&self, f: &mut Formatter) {
+        f.write("let ");
+        match self {
+            Declaration::Declared(var) => var.pretty(f),
+            Declaration::Assigned { left, right } => {
+                left.pretty(f);
+                f.write(" = ");
+                right.pretty_without_semicolon(f);
+            }
+            Declaration::RecClosure {
+                name,
+                signature,
+                right,
+                ..
+            } => {
+                f.write("rec ");
+                f.write(name);
+                f.write(" : ");
+                signature.pretty(f);
+                f.write(" = ");
+                right.pretty(f);
+            }
+            Declaration::RecLambda {
+                name,
+                signature,
+                right,
+                ..
+            } => {
+                f.write("rec ");
+                f.write(name);
+                f.write(" : ");
+                signature.pretty(f);
+                f.write(" = ");
+                right.pretty(f);
+            }
+        }
+    }
+}
+
+impl PrettyPrint for Declaration {
+    fn pretty(&self, f: &mut Formatter) {
+        self.pretty_without_semicolon(f);
+        f.write(";");
+    }
+}
+
+impl PrettyPrint for TypedVar {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&self.name);
+        f.write(" : ");
+        self.signature.pretty(f);
+    }
+}
+
+impl PrettyPrint for DeclaredVar {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            DeclaredVar::Id { name, .. } => f.write(name),
+            DeclaredVar::Typed(value) => value.pretty(f),
+            DeclaredVar::Pattern(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for PatternVar {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            PatternVar::StructFields { typename, vars, .. } => {
+                f.write(typename);
+                f.list(" { ", vars, " }", false, |f, var| f.write(var));
+            }
+            PatternVar::Tuple { names, .. } => {
+                f.list("(", names, ")", false, |f, name| f.write(name));
+            }
+        }
+    }
+}
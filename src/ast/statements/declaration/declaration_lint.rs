@@ -0,0 +1,39 @@
+use crate::semantic::lint::{LintCheck, Linter};
+
+use super::{Declaration, DeclaredVar, PatternVar};
+
+impl LintCheck for Declaration {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            Declaration::Declared(var) => linter.declare(&var.name, var.id, var.span),
+            Declaration::Assigned { left, right } => {
+                // the value is computed before the variable exists
+                right.lint(linter);
+                left.lint(linter);
+            }
+            Declaration::RecClosure { name, id, .. } | Declaration::RecLambda { name, id, .. } => {
+                linter.declare(name, *id, Default::default())
+            }
+        }
+    }
+}
+
+impl LintCheck for DeclaredVar {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            DeclaredVar::Id { name, id, span } => linter.declare(name, *id, *span),
+            DeclaredVar::Typed(var) => linter.declare(&var.name, var.id, var.span),
+            DeclaredVar::Pattern(
+                PatternVar::StructFields {
+                    vars: names, ids, ..
+                }
+                | PatternVar::Tuple { names, ids },
+            ) => {
+                for (index, name) in names.iter().enumerate() {
+                    let id = ids.as_ref().and_then(|ids| ids.get(index).copied());
+                    linter.declare(name, id, Default::default());
+                }
+            }
+        }
+    }
+}
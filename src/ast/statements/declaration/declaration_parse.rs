@@ -12,7 +12,7 @@ use crate::ast::{
     types::{ClosureType, LambdaType, Type},
     utils::{
         error::squash,
-        io::{PResult, Span},
+        io::{spanned, PResult, SourceSpan, Span},
         lexem,
         strings::{parse_id, wst, wst_closed},
     },
@@ -119,7 +119,7 @@ impl TryParse for TypedVar {
      * TypedVar := ID : Type
      */
     fn parse(input: Span) -> PResult<Self> {
-        map(
+        spanned(map(
             separated_pair(
                 parse_id,
                 wst(lexem::COLON),
@@ -129,8 +129,9 @@ impl TryParse for TypedVar {
                 id: None,
                 signature,
                 name,
+                span: SourceSpan::default(),
             },
-        )(input)
+        ))(input)
     }
 }
 
@@ -142,14 +143,18 @@ impl TryParse for DeclaredVar {
      * DeclaredVar := ID | TypedVar | PatternVar
      */
     fn parse(input: Span) -> PResult<Self> {
-        squash(
+        spanned(squash(
             alt((
                 map(TypedVar::parse, |value| DeclaredVar::Typed(value)),
                 map(PatternVar::parse, |value| DeclaredVar::Pattern(value)),
-                map(parse_id, |name| DeclaredVar::Id { name, id: None }),
+                map(parse_id, |name| DeclaredVar::Id {
+                    name,
+                    id: None,
+                    span: SourceSpan::default(),
+                }),
             )),
             "Expected a valid variable or pattern",
-        )(input)
+        ))(input)
     }
 }
 
@@ -219,7 +224,8 @@ mod tests {
             Declaration::Declared(TypedVar {
                 id: None,
                 signature: Type::Primitive(PrimitiveType::Number(NumberType::U64)),
-                name: "x".to_string()
+                name: "x".to_string(),
+                span: SourceSpan::default(),
             }),
             value
         );
@@ -236,6 +242,7 @@ mod tests {
                     name: "x".to_string(),
                     id: None,
                     signature: Type::Primitive(PrimitiveType::Number(NumberType::U64)),
+                    span: SourceSpan::default(),
                 }),
                 right: AssignValue::Expr(Box::new(Expression::Atomic(Atomic::Data(
                     Data::Primitive(v_num!(Unresolved, 10))
@@ -251,7 +258,8 @@ mod tests {
             Declaration::Assigned {
                 left: DeclaredVar::Id {
                     name: "x".to_string().into(),
-                    id: None
+                    id: None,
+                    span: SourceSpan::default(),
                 },
                 right: AssignValue::Expr(Box::new(Expression::Atomic(Atomic::Data(
                     Data::Primitive(v_num!(Unresolved, 10))
@@ -179,7 +179,7 @@ impl Resolve for DeclaredVar {
         Self: Sized,
     {
         match self {
-            DeclaredVar::Id { name, id } => {
+            DeclaredVar::Id { name, id, .. } => {
                 let Some(var_type) = context else {
                     return Err(SemanticError::CantInferType(format!(
                         "of this variable {}",
@@ -226,7 +226,15 @@ impl Resolve for PatternVar {
                 ids,
             } => {
                 let Some(EType::User { id: type_id, .. }) = context else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(match context {
+                        Some(found) => SemanticError::mismatched_kind(
+                            "a struct",
+                            found,
+                            scope_manager,
+                            scope_id,
+                        ),
+                        None => SemanticError::CantInferType(typename.to_string()),
+                    });
                 };
 
                 let UserType::Struct(Struct { fields, .. }) =
@@ -249,7 +257,15 @@ impl Resolve for PatternVar {
             }
             PatternVar::Tuple { names, ids } => {
                 let Some(EType::Static(StaticType::Tuple(TupleType(types)))) = context else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(match context {
+                        Some(found) => SemanticError::mismatched_kind(
+                            "a tuple",
+                            found,
+                            scope_manager,
+                            scope_id,
+                        ),
+                        None => SemanticError::CantInferType("of the tuple".to_string()),
+                    });
                 };
 
                 if names.len() != types.len() {
@@ -1,11 +1,14 @@
 use crate::ast::{
     expressions::data::{Closure, Lambda},
     types::{ClosureType, LambdaType, Type},
+    utils::io::{HasSpan, SourceSpan},
 };
 
 use super::assignation::AssignValue;
 
+pub mod declaration_format;
 pub mod declaration_gencode;
+pub mod declaration_lint;
 pub mod declaration_parse;
 pub mod declaration_resolve;
 pub mod declaration_typeof;
@@ -30,18 +33,66 @@ pub enum Declaration {
         right: Lambda,
     },
 }
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct TypedVar {
     pub name: String,
     pub id: Option<u64>,
     pub signature: Type,
+    pub span: SourceSpan,
 }
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum DeclaredVar {
-    Id { name: String, id: Option<u64> },
+    Id {
+        name: String,
+        id: Option<u64>,
+        span: SourceSpan,
+    },
     Typed(TypedVar),
     Pattern(PatternVar),
 }
+
+// the location of a variable does not take part in its equality
+impl PartialEq for TypedVar {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.id == other.id && self.signature == other.signature
+    }
+}
+
+impl PartialEq for DeclaredVar {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                DeclaredVar::Id { name, id, .. },
+                DeclaredVar::Id {
+                    name: other_name,
+                    id: other_id,
+                    ..
+                },
+            ) => name == other_name && id == other_id,
+            (DeclaredVar::Typed(value), DeclaredVar::Typed(other)) => value == other,
+            (DeclaredVar::Pattern(value), DeclaredVar::Pattern(other)) => value == other,
+            _ => false,
+        }
+    }
+}
+
+impl HasSpan for TypedVar {
+    fn set_span(&mut self, span: SourceSpan) {
+        if !self.span.is_known() {
+            self.span = span;
+        }
+    }
+}
+
+impl HasSpan for DeclaredVar {
+    fn set_span(&mut self, span: SourceSpan) {
+        match self {
+            DeclaredVar::Id { span: value, .. } if !value.is_known() => *value = span,
+            DeclaredVar::Typed(value) => value.set_span(span),
+            _ => {}
+        }
+    }
+}
 #[derive(Debug, Clone, PartialEq)]
 pub enum PatternVar {
     StructFields {
@@ -0,0 +1,255 @@
+use crate::{
+    ast::{
+        format::{Formatter, PrettyPrint},
+        statements::annotation::Annotations,
+        types::Type,
+        utils::strings::ID,
+    },
+    vm::scheduler::{EventExclusivity, EventKind},
+};
+
+use super::{
+    ConstDef, Definition, EnumDef, EventDef, FnDef, ImplDef, InterfaceDef, StructDef, TypeDef,
+    UnionDef,
+};
+
+/*
+ * @desc Print the annotations of a definition, one annotation per line
+ */
+fn pretty_annotations(f: &mut Formatter, annotations: &Annotations) {
+    for annotation in annotations.0.iter() {
+        annotation.pretty(f);
+        f.newline();
+    }
+}
+
+fn pretty_generics(f: &mut Formatter, generics: &[ID]) {
+    if !generics.is_empty() {
+        f.list("<", generics, ">", false, |f, generic| f.write(generic));
+    }
+}
+
+fn pretty_return(f: &mut Formatter, ret: &Type) {
+    if *ret != Type::Unit {
+        f.write(" -> ");
+        ret.pretty(f);
+    }
+}
+
+/*
+ * @desc Print the fields of a struct or of a union variant, one field per line
+ */
+fn pretty_fields(f: &mut Formatter, fields: &[(ID, Type)]) {
+    if fields.is_empty() {
+        f.write("{}");
+        return;
+    }
+    f.write("{");
+    f.newline();
+    f.indent();
+    for (name, signature) in fields {
+        f.write(name);
+        f.write(" : ");
+        signature.pretty(f);
+        f.write(",");
+        f.newline();
+    }
+    f.dedent();
+    f.write("}");
+}
+
+impl PrettyPrint for Definition {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Definition::Type(value) => value.pretty(f),
+            Definition::Fn(value) => value.pretty(f),
+            Definition::Event(value) => value.pretty(f),
+            Definition::Impl(value) => value.pretty(f),
+            Definition::Const(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for TypeDef {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_annotations(f, self.annotations());
+        match self {
+            TypeDef::Struct(value) => value.pretty(f),
+            TypeDef::Union(value) => value.pretty(f),
+            TypeDef::Enum(value) => value.pretty(f),
+            TypeDef::Interface(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for StructDef {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("struct ");
+        f.write(&self.id);
+        pretty_generics(f, &self.generics);
+        f.write(" ");
+        pretty_fields(f, &self.fields);
+    }
+}
+
+impl PrettyPrint for UnionDef {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("union ");
+        f.write(&self.id);
+        pretty_generics(f, &self.generics);
+        f.write(" {");
+        f.newline();
+        f.indent();
+        for (name, fields) in self.variants.iter() {
+            f.write(name);
+            f.list(" { ", fields, " }", false, |f, (field, signature)| {
+                f.write(field);
+                f.write(" : ");
+                signature.pretty(f);
+            });
+            f.write(",");
+            f.newline();
+        }
+        f.dedent();
+        f.write("}");
+    }
+}
+
+impl PrettyPrint for EnumDef {
+    /*
+     * @desc Print an enum, the value of a variant is written unless it follows the previous one
+     */
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("enum ");
+        f.write(&self.id);
+        f.write(" {");
+        f.newline();
+        f.indent();
+        let mut next = 0;
+        for (name, value) in self.values.iter() {
+            f.write(name);
+            if *value != next {
+                f.write(&format!(" = {}", value));
+            }
+            f.write(",");
+            f.newline();
+            next = value + 1;
+        }
+        f.dedent();
+        f.write("}");
+    }
+}
+
+impl PrettyPrint for InterfaceDef {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("interface ");
+        f.write(&self.id);
+        if self.methods.is_empty() {
+            f.write(" {}");
+            return;
+        }
+        f.write(" {");
+        f.newline();
+        f.indent();
+        for (index, (name, signature)) in self.methods.iter().enumerate() {
+            let names = self.param_names.get(index);
+            let params: Vec<String> = std::iter::once("&self".to_string())
+                .chain(signature.params.iter().enumerate().map(|(index, param)| {
+                    let name = names.and_then(|names| names.get(index));
+                    format!("{} : {}", name.map_or("_", ID::as_str), param.to_source())
+                }))
+                .collect();
+            f.write("fn ");
+            f.write(name);
+            f.list("(", &params, ")", false, |f, param| f.write(param));
+            pretty_return(f, &signature.ret);
+            f.write(";");
+            f.newline();
+        }
+        f.dedent();
+        f.write("}");
+    }
+}
+
+impl FnDef {
+    /*
+     * @desc Print a function under the given name, a method prints its receiver as self or &self
+     */
+    fn pretty_as(&self, f: &mut Formatter, name: &str) {
+        pretty_annotations(f, &self.annotations);
+        f.write("fn ");
+        f.write(name);
+        pretty_generics(f, &self.generics);
+        f.list("(", &self.params, ")", false, |f, param| {
+            match (param.name.as_str(), &param.signature) {
+                ("self", Type::Address(_)) => f.write("&self"),
+                ("self", _) => f.write("self"),
+                _ => param.pretty(f),
+            }
+        });
+        pretty_return(f, &self.ret);
+        f.write(" ");
+        self.scope.pretty(f);
+    }
+}
+
+impl PrettyPrint for FnDef {
+    fn pretty(&self, f: &mut Formatter) {
+        self.pretty_as(f, &self.name);
+    }
+}
+
+impl PrettyPrint for ImplDef {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("impl ");
+        if let Some(interface) = &self.interface {
+            f.write(interface);
+            f.write(" for ");
+        }
+        f.write(&self.target);
+        if self.methods.is_empty() {
+            f.write(" {}");
+            return;
+        }
+        f.write(" {");
+        f.newline();
+        f.indent();
+        for (index, (name, method)) in self.methods.iter().enumerate() {
+            f.statement(self.spans.get(index), |f| method.pretty_as(f, name));
+        }
+        f.dedent();
+        f.write("}");
+    }
+}
+
+impl PrettyPrint for ConstDef {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("const ");
+        f.write(&self.id);
+        f.write(" : ");
+        self.signature.pretty(f);
+        f.write(" = ");
+        self.expr.pretty(f);
+        f.write(";");
+    }
+}
+
+impl PrettyPrint for EventDef {
+    /*
+     * @desc Print an event, its kind and its exclusivity are written when they are not the default
+     */
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("event ");
+        if self.conf.kind == EventKind::Repetable {
+            f.write("repeat ");
+        }
+        if self.conf.exclu == EventExclusivity::PerTID {
+            f.write("per_tid ");
+        }
+        f.write(&self.name);
+        f.write("(");
+        self.trigger.pretty(f);
+        f.write(") ");
+        self.callback.block.pretty(f);
+    }
+}
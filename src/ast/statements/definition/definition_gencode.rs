@@ -1,6 +1,8 @@
-use super::{Definition, FnDef, TypeDef};
+use super::{Definition, EventDef, FnDef, ImplDef, TypeDef};
 use crate::semantic::scope::scope::VariableInfo;
-use crate::vm::{CodeGenerationError, GenerateCode};
+use crate::vm::core::{thread::ThreadAsm, CoreAsm};
+use crate::vm::{CodeGenerationContext, CodeGenerationError, GenerateCode};
+use ulid::Ulid;
 
 use crate::vm::asm::{
     branch::{Goto, Label},
@@ -23,6 +25,14 @@ impl GenerateCode for Definition {
             Definition::Fn(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
+            Definition::Event(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+            Definition::Impl(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+            // constants are inlined at each use
+            Definition::Const(_) => Ok(()),
         }
     }
 }
@@ -47,15 +57,39 @@ impl GenerateCode for FnDef {
         instructions: &mut crate::vm::program::Program<E>,
         context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
-        let function_label = Label::gen();
+        if !self.generics.is_empty() && self.bindings.is_none() {
+            // generate the instances resolved since the definition of the generic function
+            for instance in scope_manager.take_pending_fn_instances(&self.name) {
+                instance.gencode::<E>(scope_manager, None, instructions, context)?;
+            }
+            return Ok(());
+        }
+        self.gencode_labeled::<E>(scope_manager, scope_id, instructions, context, Label::gen())
+    }
+}
+
+impl FnDef {
+    /*
+     * @desc Generate the function at the given label
+     */
+    fn gencode_labeled<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+        function_label: Ulid,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
         let store_label = Label::gen();
 
         instructions.push(Asm::Goto(Goto {
             label: Some(store_label),
         }));
         instructions.push_label_by_id(function_label, format!("fn_{0}", self.name));
-        self.scope
-            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        instructions.in_function(function_label, |instructions| {
+            self.scope
+                .gencode::<E>(scope_manager, scope_id, instructions, context)
+        })?;
 
         instructions.push_label_by_id(store_label, format!("store_fn_{0}", self.name));
 
@@ -96,6 +130,71 @@ impl GenerateCode for FnDef {
     }
 }
 
+impl GenerateCode for ImplDef {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let mut labels = Vec::with_capacity(self.methods.len());
+        for (_, method) in self.methods.iter() {
+            let label = Label::gen();
+            if method.generics.is_empty() {
+                method.gencode_labeled::<E>(
+                    scope_manager,
+                    scope_id,
+                    instructions,
+                    context,
+                    label,
+                )?;
+            } else {
+                method.gencode::<E>(scope_manager, scope_id, instructions, context)?;
+            }
+            labels.push(label);
+        }
+        if let Some((interface_id, type_id, dispatch)) = &self.dispatch {
+            let functions = dispatch.iter().map(|idx| labels[*idx]).collect();
+            let _ = instructions
+                .vtable
+                .alloc(*interface_id, *type_id, functions);
+        }
+        Ok(())
+    }
+}
+
+impl GenerateCode for EventDef {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let _ = self
+            .trigger
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+
+        // the callback is pushed on the stack as a heap allocated closure
+        let _ = self.callback.gencode::<E>(
+            scope_manager,
+            None,
+            instructions,
+            &CodeGenerationContext::default(),
+        )?;
+        // the callback is labeled by the name of the event
+        if let Some(label) = self.callback.label() {
+            instructions.rename_label(&label, format!("event_{0}", self.name));
+        }
+
+        instructions.push(Asm::Core(CoreAsm::Thread(ThreadAsm::EventRegistration(
+            self.conf,
+        ))));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -158,4 +257,556 @@ mod tests {
             assert_fn,
         );
     }
+
+    #[test]
+    fn valid_generic_fn() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<u64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, -1);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 7);
+            let res = test_extract_variable::<u32>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 4);
+            true
+        }
+
+        test_statements(
+            r##"
+        fn first<T>(v:Vec[T]) -> T {
+            return v[0];
+        }
+        fn id<T>(x:T) -> T {
+            return x;
+        }
+        fn pick<A,B>(x:A,y:B) -> B {
+            return y;
+        }
+
+        let res1 = first(vec[2u64,3u64]);
+        let res2 = first(vec[-1,3]);
+        let res3 = pick(2u64,id(7));
+        let res4 : u32 = id(4);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_generic_struct() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<u64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 5);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            let res = test_extract_variable::<u64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 9);
+            true
+        }
+
+        test_statements(
+            r##"
+        struct Pair<A,B> {
+            first : A,
+            second : B,
+        }
+        fn pair<A,B>(first:A,second:B) -> Pair<A,B> {
+            return Pair { first : first, second : second };
+        }
+
+        let p1 = Pair { first : 5u64, second : 3 };
+        let p2 : Pair<i64,u64> = Pair { first : 1, second : 9 };
+        let res1 = p1.first;
+        let res2 = pair(1u64,3).second;
+        let res3 = p2.second;
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_generic_struct_of_closure() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 7);
+            true
+        }
+
+        test_statements(
+            r##"
+        struct Holder<T> {
+            value : T,
+        }
+        let z = 3;
+        let holder = Holder { value : move (x:i64) -> x + z };
+        let add = holder.value;
+        let res = add(4);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_methods() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 7);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 13);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 15);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            let res = test_extract_variable::<u64>("res5", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res6", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 20);
+            true
+        }
+
+        test_statements(
+            r##"
+        struct Point {
+            x : i64,
+            y : i64,
+        }
+        struct Line {
+            start : Point,
+            end : Point,
+        }
+        enum Color {
+            Red,
+            Green,
+        }
+
+        impl Point {
+            fn new(x:i64,y:i64) -> Point {
+                return Point { x : x, y : y };
+            }
+            fn sum(self) -> i64 {
+                return self.x + self.y;
+            }
+            fn scale(&self, factor:i64) {
+                self.x = self.x * factor;
+                self.y = self.y * factor;
+            }
+            fn shifted(self, dx:i64) -> Point {
+                return Point { x : self.x + dx, y : self.y };
+            }
+        }
+        impl Color {
+            fn code(self) -> u64 {
+                let red = Color::Red;
+                if self == red {
+                    return 0u64;
+                }
+                return 1u64;
+            }
+        }
+
+        let p = Point::new(3,4);
+        let res1 = p.sum();
+        let res2 = p.shifted(6).sum();
+        p.scale(2);
+        let l = Line { start : Point::new(1,3), end : p };
+        let res3 = (l.end.x + l.end.sum()) - (l.start.x + l.start.sum());
+        let res4 = (l.start.shifted(2).y + l.start.sum()) - (l.start.shifted(1).x + 2);
+        let c = Color::Green;
+        let res5 = c.code();
+        let res6 = Point::sum(l.end) + (l.end.shifted(4).x - 4);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_interfaces() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            for (name, expected) in [
+                ("res1", 4),
+                ("res2", 6),
+                ("res3", 24),
+                ("res4", 18),
+                ("res5", 9),
+                ("res6", 7),
+            ] {
+                let res = test_extract_variable::<i64>(name, scope_manager, stack, heap)
+                    .expect("Deserialization should have succeeded");
+                assert_eq!(res, expected, "{name}");
+            }
+            true
+        }
+
+        test_statements(
+            r##"
+        interface Shape {
+            fn area(&self) -> i64;
+            fn scale(&self, factor:i64);
+        }
+        struct Square {
+            side : i64,
+        }
+        struct Rect {
+            w : i64,
+            h : i64,
+        }
+        impl Shape for Square {
+            fn area(&self) -> i64 {
+                return self.side * self.side;
+            }
+            fn scale(&self, factor:i64) {
+                self.side = self.side * factor;
+            }
+        }
+        impl Shape for Rect {
+            fn scale(&self, factor:i64) {
+                self.w = self.w * factor;
+                self.h = self.h * factor;
+            }
+            fn area(&self) -> i64 {
+                return self.w * self.h;
+            }
+            fn perimeter(self) -> i64 {
+                return 2 * (self.w + self.h);
+            }
+        }
+        fn total(shapes:Vec[Shape]) -> i64 {
+            return shapes[0].area() + shapes[1].area();
+        }
+
+        let shapes = vec[Square { side : 2 } as Shape, Rect { w : 2, h : 3 } as Shape];
+        let res1 = shapes[0].area();
+        let res2 = shapes[1].area();
+        shapes[1].scale(2);
+        let res3 = shapes[1].area();
+
+        let r = Rect { w : 1, h : 5 };
+        let shape = &r as Shape;
+        shape.scale(3);
+        let res4 = r.perimeter() / 2;
+
+        let by_name = map { "square" : Square { side : 3 } as Shape };
+        let (square, err) = get(by_name, "square");
+        let res5 = square.area();
+        let res6 = total(vec[Square { side : 1 } as Shape, Rect { w : 2, h : 3 } as Shape]);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_interface_free() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 30);
+            assert_eq!(heap.allocated_size(), 0);
+            true
+        }
+
+        test_statements(
+            r##"
+        interface Shape {
+            fn area(&self) -> i64;
+        }
+        struct Square {
+            side : i64,
+        }
+        impl Shape for Square {
+            fn area(&self) -> i64 {
+                return self.side * self.side;
+            }
+        }
+        let res = 0;
+        let side = 0;
+        while side < 5 {
+            let shape = Square { side : side } as Shape;
+            res = res + shape.area();
+            free(shape);
+            side = side + 1;
+        }
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn robustness_interface_free_borrowed() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let freed = test_extract_variable::<u8>("freed", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(freed, crate::vm::core::ERROR_VALUE);
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 19);
+            assert_eq!(heap.allocated_size(), 0);
+            true
+        }
+
+        test_statements(
+            r##"
+        interface Shape {
+            fn area(&self) -> i64;
+        }
+        struct Square {
+            side : i64,
+        }
+        impl Shape for Square {
+            fn area(&self) -> i64 {
+                return self.side * self.side;
+            }
+        }
+        let (square,err) = alloc(size_of(Square)) as (&Square,Error);
+        (*square).side = 4;
+        let shape = square as Shape;
+        let freed = free(shape);
+        let res = shape.area() + 3;
+        free(square);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_interface_free_in_containers() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 13);
+            assert_eq!(heap.allocated_size(), 0);
+            true
+        }
+
+        test_statements(
+            r##"
+        interface Shape {
+            fn area(&self) -> i64;
+        }
+        struct Square {
+            side : i64,
+        }
+        impl Shape for Square {
+            fn area(&self) -> i64 {
+                return self.side * self.side;
+            }
+        }
+        let square = Square { side : 2 };
+        let shapes = vec[Square { side : 3 } as Shape, &square as Shape];
+        let res = shapes[0].area() + shapes[1].area();
+        free(shapes);
+
+        let by_key : Map[u64]Shape = map {
+            1 : Square { side : 4 } as Shape,
+            2 : &square as Shape,
+        };
+        free(by_key);
+
+        let by_name : Map[u64]Shape = map {
+            1 : Square { side : 5 } as Shape,
+        };
+        free_map(by_name);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_same_method_in_interfaces() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1234);
+            true
+        }
+
+        test_statements(
+            r##"
+        interface Shape {
+            fn area(&self) -> i64;
+        }
+        interface Surface {
+            fn area(&self) -> i64;
+        }
+        struct Square {
+            side : i64,
+        }
+        impl Square {
+            fn area(&self) -> i64 {
+                return 4;
+            }
+        }
+        impl Shape for Square {
+            fn area(&self) -> i64 {
+                return 30;
+            }
+        }
+        impl Surface for Square {
+            fn area(&self) -> i64 {
+                return 200;
+            }
+        }
+        let square = Square { side : 2 };
+        let shape = square as Shape;
+        let surface = square as Surface;
+        let res = square.area() + shape.area() + surface.area() + 1000;
+        free(shape);
+        free(surface);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_const() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 21);
+            let res = test_extract_variable::<u8>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 255);
+            let res = test_extract_variable::<u8>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 21);
+            let res = test_extract_variable::<u8>("res5", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<u8>("res6", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res7", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+
+            // constants are inlined and never allocated
+            assert!(scope_manager
+                .find_var_by_name("ENERGY", None, None)
+                .is_err());
+            true
+        }
+
+        test_statements(
+            r##"
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        const ENERGY : i64 = 10;
+        const DOUBLE : i64 = ENERGY * 2 + 1;
+        const SMALL : u8 = 250 + 5;
+        const FLAG : bool = DOUBLE > 20 and !false;
+        const PAIR : (i64,bool) = (ENERGY, DOUBLE > 20);
+        const FAVORITE : Color = Color::Green;
+        const NAME : str = "ciphel";
+
+        fn energy() -> i64 {
+            return DOUBLE - ENERGY;
+        }
+
+        fn shadow() -> i64 {
+            let ENERGY = 3;
+            return ENERGY;
+        }
+
+        let res1 = DOUBLE;
+        let res2 = SMALL;
+        let res3 = FLAG;
+        let res4 = PAIR.0 + energy();
+        let res5 = FAVORITE == Color::Green;
+        let res6 = 'p' in NAME;
+        let res7 = shadow();
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
 }
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use super::{FnDef, TypeDef};
+use crate::ast::expressions::Expression;
+use crate::ast::types::types_generics::generic_name;
+use crate::semantic::scope::scope::ScopeManager;
+use crate::semantic::scope::user_types::UserType;
+use crate::semantic::scope::BuildUserType;
+use crate::semantic::{CompatibleWith, Desugar, EType, Resolve, SemanticError, SizeOf, TypeOf};
+
+impl TypeDef {
+    pub fn generics(&self) -> &[String] {
+        match self {
+            TypeDef::Struct(value) => &value.generics,
+            TypeDef::Union(value) => &value.generics,
+            TypeDef::Enum(_) | TypeDef::Interface(_) => &[],
+        }
+    }
+
+    /*
+     * @desc Instantiate the generic type template registered under the given name
+     * The instance is registered as a global type named after its type arguments, e.g. Pair<u64,bool>
+     */
+    pub fn instantiate<E: crate::vm::external::Engine>(
+        scope_manager: &mut ScopeManager,
+        name: &str,
+        args: Vec<EType>,
+    ) -> Result<EType, SemanticError> {
+        let instance_name = generic_name(name, &args, scope_manager)?;
+        if let Ok(instance) = scope_manager.find_type_by_name(None, &instance_name, None) {
+            return Ok(EType::User {
+                id: instance.id,
+                size: instance.def.size_of(),
+            });
+        }
+
+        let Some(mut instance) = scope_manager.find_generic_type(name).cloned() else {
+            return Err(SemanticError::UnknownType(name.to_string()));
+        };
+        if instance.generics().len() != args.len() {
+            return Err(SemanticError::IncorrectGenerics(name.to_string()));
+        }
+
+        // The type parameters are bound in a scope dropped once the instance is built
+        let params = instance
+            .generics()
+            .iter()
+            .cloned()
+            .zip(args.iter().cloned())
+            .collect();
+        let type_def = scope_manager.with_type_params(params, |scope_manager, inner_scope| {
+            match &mut instance {
+                TypeDef::Struct(value) => {
+                    value.id = instance_name.clone();
+                    value.generics.clear();
+                    let _ = value.resolve::<E>(scope_manager, Some(inner_scope), &(), &mut ())?;
+                }
+                TypeDef::Union(value) => {
+                    value.id = instance_name.clone();
+                    value.generics.clear();
+                    let _ = value.resolve::<E>(scope_manager, Some(inner_scope), &(), &mut ())?;
+                }
+                TypeDef::Enum(_) | TypeDef::Interface(_) => {
+                    return Err(SemanticError::IncorrectGenerics(name.to_string()))
+                }
+            }
+            UserType::build_usertype(&instance, scope_manager, Some(inner_scope))
+        })??;
+        let size = type_def.size_of();
+        let id = scope_manager.register_type(&instance_name, type_def, None)?;
+        scope_manager.register_generic_type_instance(id, name, args);
+
+        Ok(EType::User { id, size })
+    }
+
+    /*
+     * @desc Infer the type arguments of a generic type template from the fields of a literal
+     * The fields typed by a type parameter are resolved to infer it, and are not resolved again
+     * Returns the name of the instance and the names of the resolved fields
+     */
+    pub fn instantiate_from_fields<E: crate::vm::external::Engine>(
+        scope_manager: &mut ScopeManager,
+        scope_id: Option<u128>,
+        name: &str,
+        variant: Option<&str>,
+        fields: &mut [(String, Expression)],
+        context: &Option<EType>,
+    ) -> Result<(String, Vec<String>), SemanticError> {
+        let Some(template) = scope_manager.find_generic_type(name).cloned() else {
+            return Err(SemanticError::UnknownType(name.to_string()));
+        };
+
+        // The expected type is an instance of this template
+        if let Some(EType::User { id, .. }) = context {
+            if let Some(instance) = scope_manager.find_generic_type_instance(*id) {
+                if instance.template == name {
+                    let instance_name = generic_name(name, &instance.args.clone(), scope_manager)?;
+                    return Ok((instance_name, Vec::default()));
+                }
+            }
+        }
+
+        let template_fields = match (&template, variant) {
+            (TypeDef::Struct(value), None) => value.fields.clone(),
+            (TypeDef::Union(value), Some(variant)) => {
+                let Some((_, fields)) = value.variants.iter().find(|(id, _)| id == variant) else {
+                    return Err(SemanticError::UnknownField(variant.to_string()));
+                };
+                fields.clone()
+            }
+            (TypeDef::Union(_), None) => return Err(SemanticError::ExpectedStruct),
+            _ => return Err(SemanticError::ExpectedStruct),
+        };
+
+        let generics = template.generics();
+        let mut bindings = HashMap::new();
+        let mut resolved_fields = Vec::new();
+        for (field_name, expr) in fields.iter_mut() {
+            let Some((_, field_type)) = template_fields.iter().find(|(n, _)| n == field_name)
+            else {
+                return Err(SemanticError::UnknownField(field_name.clone()));
+            };
+            if !field_type.is_generic_over(generics) {
+                continue;
+            }
+            let _ = expr.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            let expr_type = expr.type_of(&scope_manager, scope_id)?;
+            let _ =
+                field_type.infer_generics(&expr_type, generics, &mut bindings, scope_manager)?;
+            resolved_fields.push(field_name.clone());
+        }
+
+        let mut args = Vec::with_capacity(generics.len());
+        for generic in generics {
+            let Some(arg) = bindings.remove(generic) else {
+                return Err(SemanticError::CantInferType(generic.clone()));
+            };
+            args.push(arg);
+        }
+        let _ = TypeDef::instantiate::<E>(scope_manager, name, args.clone())?;
+        Ok((generic_name(name, &args, scope_manager)?, resolved_fields))
+    }
+}
+
+impl FnDef {
+    /*
+     * @desc Resolve the arguments of a call to a generic function, infer its type arguments
+     * and instantiate it as a global function named after its type arguments, e.g. first<u64>
+     * Returns the name of the instance
+     */
+    pub fn instantiate_from_call<E: crate::vm::external::Engine>(
+        scope_manager: &mut ScopeManager,
+        scope_id: Option<u128>,
+        name: &str,
+        args: &mut [Expression],
+        context: &Option<EType>,
+    ) -> Result<String, SemanticError> {
+        let Some(template) = scope_manager.find_generic_fn(name).cloned() else {
+            return Err(SemanticError::UnknownVar(name.to_string()));
+        };
+        if template.params.len() != args.len() {
+            return Err(SemanticError::IncorrectArgumentCount {
+                expected: template.params.len(),
+                found: args.len(),
+            });
+        }
+
+        let mut bindings = HashMap::new();
+        if let Some(context) = context {
+            // a mismatching return type is reported by the caller
+            let _ = template.ret.infer_generics(
+                context,
+                &template.generics,
+                &mut bindings,
+                scope_manager,
+            );
+        }
+
+        for (arg, param) in args.iter_mut().zip(template.params.iter()) {
+            if param.signature.is_generic_over(&template.generics) {
+                // the type parameters inferred so far give the expected type of the argument
+                let inferred = bindings
+                    .iter()
+                    .map(|(name, ctype)| (name.clone(), ctype.clone()))
+                    .collect();
+                let expected = scope_manager
+                    .with_type_params(inferred, |scope_manager, inferred_scope| {
+                        param
+                            .signature
+                            .type_of(&scope_manager, Some(inferred_scope))
+                            .ok()
+                    })?;
+                let _ = arg.resolve::<E>(scope_manager, scope_id, &expected, &mut None)?;
+                let arg_type = arg.type_of(&scope_manager, scope_id)?;
+                let _ = param.signature.infer_generics(
+                    &arg_type,
+                    &template.generics,
+                    &mut bindings,
+                    scope_manager,
+                )?;
+            } else {
+                let param_type = param.signature.type_of(&scope_manager, None)?;
+                let _ = arg.resolve::<E>(
+                    scope_manager,
+                    scope_id,
+                    &Some(param_type.clone()),
+                    &mut None,
+                )?;
+                let _ = param_type.compatible_with(
+                    &arg.type_of(&scope_manager, scope_id)?,
+                    &scope_manager,
+                    scope_id,
+                )?;
+            }
+        }
+        let mut types = Vec::with_capacity(template.generics.len());
+        for generic in template.generics.iter() {
+            let Some(arg) = bindings.remove(generic) else {
+                return Err(SemanticError::CantInferType(generic.clone()));
+            };
+            types.push(arg);
+        }
+
+        let instance_name = generic_name(name, &types, scope_manager)?;
+        if scope_manager
+            .find_var_by_name(&instance_name, None, None)
+            .is_ok()
+        {
+            return Ok(instance_name);
+        }
+
+        let mut instance = template;
+        instance.name = instance_name.clone();
+        let _ = instance.bindings.insert(types);
+        let _ = instance.desugar::<E>(scope_manager, None)?;
+        let _ = instance.resolve::<E>(scope_manager, None, &(), &mut ())?;
+        scope_manager.register_generic_fn_instance(name, instance);
+
+        Ok(instance_name)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::semantic::lint::{LintCheck, Linter};
+
+use super::{Definition, FnDef, ImplDef};
+
+impl LintCheck for Definition {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            Definition::Fn(value) => value.lint(linter),
+            Definition::Impl(value) => value.lint(linter),
+            Definition::Type(_) | Definition::Event(_) | Definition::Const(_) => {}
+        }
+    }
+}
+
+impl LintCheck for FnDef {
+    fn lint(&self, linter: &mut Linter) {
+        linter.with_annotations(&self.annotations, |linter| {
+            linter.in_block(|linter| {
+                for param in self.params.iter() {
+                    linter.bind(&param.name);
+                }
+                self.scope.lint(linter);
+            })
+        });
+    }
+}
+
+impl LintCheck for ImplDef {
+    fn lint(&self, linter: &mut Linter) {
+        for (_, method) in self.methods.iter() {
+            method.lint(linter);
+        }
+    }
+}
@@ -1,24 +1,34 @@
 use nom::{
     branch::alt,
-    combinator::{cut, map, opt},
-    multi::{separated_list0, separated_list1},
+    combinator::{cut, map, opt, value},
+    multi::{many0, separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, separated_pair, tuple},
 };
 
 use crate::ast::{
-    statements::{block::FunctionBlock, declaration::TypedVar},
-    types::Type,
+    expressions::{data::Closure, Expression},
+    statements::{
+        annotation::Annotations,
+        block::{ClosureBlock, FunctionBlock},
+        declaration::TypedVar,
+    },
+    types::{AddrType, FunctionType, Type},
     utils::{
         error::squash,
-        io::{PResult, Span},
+        io::{located, PResult, SourceSpan, SourceSpans, Span},
         lexem,
         numbers::{parse_number, parse_number_u64},
-        strings::{parse_id, wst, wst_closed},
+        strings::{parse_id, wst, wst_closed, ID},
     },
     TryParse,
 };
 
-use super::{Definition, EnumDef, FnDef, StructDef, TypeDef, UnionDef};
+use crate::vm::scheduler::{EventConf, EventExclusivity, EventKind};
+
+use super::{
+    ConstDef, Definition, EnumDef, EventDef, FnDef, ImplDef, InterfaceDef, StructDef, TypeDef,
+    UnionDef,
+};
 
 impl TryParse for Definition {
     fn parse(input: Span) -> PResult<Self> {
@@ -26,19 +36,68 @@ impl TryParse for Definition {
             alt((
                 map(TypeDef::parse, |value| Definition::Type(value)),
                 map(FnDef::parse, |value| Definition::Fn(value)),
+                map(EventDef::parse, |value| Definition::Event(value)),
+                map(ImplDef::parse, |value| Definition::Impl(value)),
+                map(ConstDef::parse, |value| Definition::Const(value)),
             )),
-            "Expected a type definition (a struct, enum or union) or a static function definition",
+            "Expected a type definition (a struct, enum, union or interface), a static function definition, an event definition, an impl block or a constant",
         )(input)
     }
 }
 
+/*
+ * @desc Parse the type parameters of a generic definition
+ *
+ * @grammar
+ * Generics := < ID , Generics_params > | < ID >
+ */
+fn parse_generics(input: Span) -> PResult<Vec<ID>> {
+    map(
+        opt(delimited(
+            wst(lexem::LESSER),
+            separated_list1(wst(lexem::COMA), parse_id),
+            wst(lexem::GREATER),
+        )),
+        |generics| generics.unwrap_or_default(),
+    )(input)
+}
+
 impl TryParse for TypeDef {
+    /*
+     * @desc Parse type definition
+     *
+     * @grammar
+     * TypeDef := Annotations (Struct | Union | Enum | Interface)
+     */
     fn parse(input: Span) -> PResult<Self> {
-        alt((
-            map(StructDef::parse, |value| TypeDef::Struct(value)),
-            map(UnionDef::parse, |value| TypeDef::Union(value)),
-            map(EnumDef::parse, |value| TypeDef::Enum(value)),
-        ))(input)
+        map(
+            pair(
+                Annotations::parse,
+                alt((
+                    map(StructDef::parse, |value| TypeDef::Struct(value)),
+                    map(UnionDef::parse, |value| TypeDef::Union(value)),
+                    map(EnumDef::parse, |value| TypeDef::Enum(value)),
+                    map(InterfaceDef::parse, |value| TypeDef::Interface(value)),
+                )),
+            ),
+            |(annotations, mut value)| {
+                match &mut value {
+                    TypeDef::Struct(StructDef {
+                        annotations: field, ..
+                    })
+                    | TypeDef::Union(UnionDef {
+                        annotations: field, ..
+                    })
+                    | TypeDef::Enum(EnumDef {
+                        annotations: field, ..
+                    })
+                    | TypeDef::Interface(InterfaceDef {
+                        annotations: field, ..
+                    }) => *field = annotations,
+                }
+                value
+            },
+        )(input)
     }
 }
 
@@ -47,14 +106,15 @@ impl TryParse for StructDef {
      * @desc Parse struct definition
      *
      * @grammar
-     * Struct := struct ID { Struct_fields } | struct ID \( Types \)
+     * Struct := struct ID Generics? { Struct_fields } | struct ID \( Types \)
      * Struct_fields := Struct_field , Struct_fields | Struct_field
      * Struct_field := ID : Type
      */
     fn parse(input: Span) -> PResult<Self> {
         map(
-            pair(
+            tuple((
                 preceded(wst_closed(lexem::STRUCT), parse_id),
+                parse_generics,
                 cut(delimited(
                     wst(lexem::BRA_O),
                     separated_list0(
@@ -63,11 +123,13 @@ impl TryParse for StructDef {
                     ),
                     preceded(opt(wst(lexem::COMA)), wst(lexem::BRA_C)),
                 )),
-            ),
-            |(id, fields)| StructDef {
+            )),
+            |(id, generics, fields)| StructDef {
                 id,
+                generics,
                 fields,
                 signature: None,
+                annotations: Annotations::default(),
             },
         )(input)
     }
@@ -78,14 +140,15 @@ impl TryParse for UnionDef {
      * @desc Parse union definition
      *
      * @grammar
-     * Union := union ID { Union_fields }
+     * Union := union ID Generics? { Union_fields }
      * Union_fields := Union_field , Union_fields | Union_field
      * Union_field := ID | ID { Struct_fields } | ID \(  Types \)
      */
     fn parse(input: Span) -> PResult<Self> {
         map(
-            pair(
+            tuple((
                 preceded(wst_closed(lexem::UNION), parse_id),
+                parse_generics,
                 cut(delimited(
                     wst(lexem::BRA_O),
                     separated_list1(
@@ -104,11 +167,13 @@ impl TryParse for UnionDef {
                     ),
                     preceded(opt(wst(lexem::COMA)), wst(lexem::BRA_C)),
                 )),
-            ),
-            |(id, variants)| UnionDef {
+            )),
+            |(id, generics, variants)| UnionDef {
                 id,
+                generics,
                 variants,
                 signature: None,
+                annotations: Annotations::default(),
             },
         )(input)
     }
@@ -153,6 +218,66 @@ impl TryParse for EnumDef {
                     id,
                     values,
                     signature: None,
+                    annotations: Annotations::default(),
+                }
+            },
+        )(input)
+    }
+}
+
+impl TryParse for InterfaceDef {
+    /*
+     * @desc Parse interface definition
+     * The methods of an interface take their receiver by address
+     *
+     * @grammar
+     * Interface := interface ID { Interface_methods }
+     * Interface_methods := Interface_method Interface_methods | ε
+     * Interface_method := fn ID \( &self , Fn_Params \) -> Type ;
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            pair(
+                preceded(wst_closed(lexem::INTERFACE), parse_id),
+                cut(delimited(
+                    wst(lexem::BRA_O),
+                    many0(tuple((
+                        preceded(wst_closed(lexem::FN), parse_id),
+                        cut(delimited(
+                            pair(
+                                wst(lexem::PAR_O),
+                                preceded(wst(lexem::ADDR), wst_closed(lexem::SELF)),
+                            ),
+                            many0(preceded(wst(lexem::COMA), TypedVar::parse)),
+                            wst(lexem::PAR_C),
+                        )),
+                        map(
+                            opt(preceded(wst(lexem::ARROW), Type::parse)),
+                            |opt_return_type| opt_return_type.unwrap_or(Type::Unit),
+                        ),
+                        cut(wst(lexem::SEMI_COLON)),
+                    ))),
+                    wst(lexem::BRA_C),
+                )),
+            ),
+            |(id, methods)| {
+                let (methods, param_names) = methods
+                    .into_iter()
+                    .map(|(name, params, ret, _)| {
+                        let names = params.iter().map(|param| param.name.clone()).collect();
+                        let signature = FunctionType {
+                            params: params.into_iter().map(|param| param.signature).collect(),
+                            ret: Box::new(ret),
+                        };
+                        ((name, signature), names)
+                    })
+                    .unzip();
+                InterfaceDef {
+                    id,
+                    methods,
+                    param_names,
+                    signature: None,
+                    annotations: Annotations::default(),
                 }
             },
         )(input)
@@ -164,12 +289,14 @@ impl TryParse for FnDef {
      * @desc Parse function definition
      *
      * @grammar
-     * FnDef := fn ID \( Fn_Params \) -> Type Scope
+     * FnDef := Annotations fn ID Generics? \( Fn_Params \) -> Type Scope
      */
     fn parse(input: Span) -> PResult<Self> {
         map(
             tuple((
+                Annotations::parse,
                 preceded(wst_closed(lexem::FN), parse_id),
+                parse_generics,
                 cut(delimited(
                     wst(lexem::PAR_O),
                     separated_list0(wst(lexem::COMA), TypedVar::parse),
@@ -181,12 +308,207 @@ impl TryParse for FnDef {
                 ),
                 FunctionBlock::parse,
             )),
-            |(name, params, ret, scope)| FnDef {
+            |(annotations, name, generics, params, ret, scope)| FnDef {
                 name,
+                generics,
+                bindings: None,
                 id: None,
                 params,
                 ret: Box::new(ret),
                 scope,
+                annotations,
+            },
+        )(input)
+    }
+}
+
+impl TryParse for ImplDef {
+    /*
+     * @desc Parse the methods of a user type, optionally implementing an interface
+     *
+     * @grammar
+     * ImplDef := impl ID { Methods } | impl ID for ID { Methods }
+     * Methods := Method Methods | ε
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        let (input, (first, second)) = preceded(
+            wst_closed(lexem::IMPL),
+            cut(pair(
+                parse_id,
+                opt(preceded(wst_closed(lexem::FOR), parse_id)),
+            )),
+        )(input)?;
+        let (target, interface) = match second {
+            Some(target) => (target, Some(first)),
+            None => (first, None),
+        };
+        let (input, methods) = cut(delimited(
+            wst(lexem::BRA_O),
+            many0(located(|input| parse_method(input, &target, interface.as_ref()))),
+            wst(lexem::BRA_C),
+        ))(input)?;
+        let (spans, methods) = methods.into_iter().unzip();
+        Ok((
+            input,
+            ImplDef {
+                target,
+                interface,
+                methods,
+                spans: SourceSpans(spans),
+                dispatch: None,
+            },
+        ))
+    }
+}
+
+impl TryParse for ConstDef {
+    /*
+     * @desc Parse a compile time constant
+     *
+     * @grammar
+     * ConstDef := const ID : Type = Expr ;
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            preceded(
+                wst_closed(lexem::CONST),
+                cut(pair(
+                    separated_pair(parse_id, wst(lexem::COLON), Type::parse),
+                    delimited(wst(lexem::EQUAL), Expression::parse, wst(lexem::SEMI_COLON)),
+                )),
+            ),
+            |((id, signature), expr)| ConstDef {
+                id,
+                signature,
+                expr: Box::new(expr),
+                value: None,
+            },
+        )(input)
+    }
+}
+
+/*
+ * @desc Parse a method of the given user type, implementing the given interface if any
+ * The receiver is the first parameter of the method, named self
+ *
+ * @grammar
+ * Method := fn ID Generics? \( Receiver , Fn_Params \) -> Type Scope | FnDef
+ * Receiver := self | &self
+ */
+fn parse_method<'input>(
+    input: Span<'input>,
+    target: &ID,
+    interface: Option<&ID>,
+) -> PResult<'input, (ID, FnDef)> {
+    let receiver_type = Type::UserType {
+        path: Default::default(),
+        name: target.clone(),
+        generics: Vec::default(),
+    };
+    let receiver = alt((
+        value(
+            Type::Address(AddrType(Box::new(receiver_type.clone()))),
+            preceded(wst(lexem::ADDR), wst_closed(lexem::SELF)),
+        ),
+        value(receiver_type, wst_closed(lexem::SELF)),
+    ));
+    map(
+        tuple((
+            Annotations::parse,
+            preceded(wst_closed(lexem::FN), parse_id),
+            parse_generics,
+            cut(delimited(
+                wst(lexem::PAR_O),
+                alt((
+                    pair(
+                        map(receiver, Some),
+                        many0(preceded(wst(lexem::COMA), TypedVar::parse)),
+                    ),
+                    map(
+                        separated_list0(wst(lexem::COMA), TypedVar::parse),
+                        |params| (None, params),
+                    ),
+                )),
+                wst(lexem::PAR_C),
+            )),
+            map(
+                opt(preceded(wst(lexem::ARROW), Type::parse)),
+                |opt_return_type| opt_return_type.unwrap_or(Type::Unit),
+            ),
+            FunctionBlock::parse,
+        )),
+        |(annotations, name, generics, (receiver, mut params), ret, scope)| {
+            if let Some(signature) = receiver {
+                params.insert(
+                    0,
+                    TypedVar {
+                        name: lexem::SELF.to_string(),
+                        id: None,
+                        signature,
+                        span: SourceSpan::default(),
+                    },
+                );
+            }
+            // the function implementing the method is named after the type and the interface
+            let function = FnDef {
+                name: match interface {
+                    Some(interface) => format!("{}::{}::{}", target, interface, name),
+                    None => format!("{}::{}", target, name),
+                },
+                generics,
+                bindings: None,
+                id: None,
+                params,
+                ret: Box::new(ret),
+                scope,
+                annotations,
+            };
+            (name, function)
+        },
+    )(input)
+}
+
+impl TryParse for EventDef {
+    /*
+     * @desc Parse event definition
+     *
+     * @grammar
+     * EventDef := event Event_kind Event_exclu ID \( Expr \) Scope
+     * Event_kind := once | repeat | ε
+     * Event_exclu := per_pid | per_tid | ε
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            tuple((
+                preceded(
+                    wst_closed(lexem::EVENT),
+                    pair(
+                        opt(alt((
+                            map(wst_closed(lexem::ONCE), |_| EventKind::Once),
+                            map(wst_closed(lexem::REPEAT), |_| EventKind::Repetable),
+                        ))),
+                        opt(alt((
+                            map(wst_closed(lexem::PER_PID), |_| EventExclusivity::PerPID),
+                            map(wst_closed(lexem::PER_TID), |_| EventExclusivity::PerTID),
+                        ))),
+                    ),
+                ),
+                cut(parse_id),
+                cut(delimited(
+                    wst(lexem::PAR_O),
+                    Expression::parse,
+                    wst(lexem::PAR_C),
+                )),
+                cut(ClosureBlock::parse),
+            )),
+            |((kind, exclu), name, trigger, block)| EventDef {
+                name,
+                conf: EventConf {
+                    kind: kind.unwrap_or(EventKind::Once),
+                    exclu: exclu.unwrap_or(EventExclusivity::PerPID),
+                },
+                trigger: Box::new(trigger),
+                callback: Closure::new(Vec::default(), block),
             },
         )(input)
     }
@@ -223,6 +545,7 @@ mod tests {
         assert_eq!(
             StructDef {
                 id: "Point".to_string().into(),
+                generics: Vec::default(),
                 fields: vec![
                     (
                         "x".to_string().into(),
@@ -234,6 +557,7 @@ mod tests {
                     )
                 ],
                 signature: None,
+                annotations: Annotations::default(),
             },
             value
         );
@@ -257,6 +581,7 @@ mod tests {
         assert_eq!(
             UnionDef {
                 id: "Geo".to_string().into(),
+                generics: Vec::default(),
                 variants: vec![(
                     "Point".to_string().into(),
                     vec![
@@ -271,6 +596,7 @@ mod tests {
                     ]
                 ),],
                 signature: None,
+                annotations: Annotations::default(),
             },
             value
         );
@@ -294,6 +620,7 @@ mod tests {
                 id: "Sport".to_string(),
                 values: vec![("Football".to_string(), 0), ("Basketball".to_string(), 1)],
                 signature: None,
+                annotations: Annotations::default(),
             },
             value
         );
@@ -314,6 +641,7 @@ mod tests {
                 id: "Sport".to_string(),
                 values: vec![("Football".to_string(), 2), ("Basketball".to_string(), 3)],
                 signature: None,
+                annotations: Annotations::default(),
             },
             value
         );
@@ -334,11 +662,14 @@ mod tests {
         assert_eq!(
             FnDef {
                 name: "f".to_string(),
+                generics: Vec::default(),
+                bindings: None,
                 id: None,
                 params: vec![TypedVar {
                     name: "x".to_string(),
                     id: None,
-                    signature: Type::Primitive(PrimitiveType::Number(NumberType::U64))
+                    signature: Type::Primitive(PrimitiveType::Number(NumberType::U64)),
+                    span: SourceSpan::default(),
                 }],
                 ret: Box::new(Type::Primitive(PrimitiveType::Number(NumberType::U64))),
                 scope: FunctionBlock::new(vec![Statement::Return(Return::Expr {
@@ -346,9 +677,126 @@ mod tests {
                         Unresolved, 10
                     ))))),
                     metadata: Metadata::default()
-                })])
+                })]),
+                annotations: Annotations::default(),
+            },
+            value
+        );
+    }
+
+    #[test]
+    fn valid_generic_defs() {
+        let res = StructDef::parse(
+            r#"
+        struct Pair<A,B> {
+            first : A,
+            second : B
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.generics, vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(
+            value.fields[0].1,
+            Type::UserType {
+                path: crate::ast::expressions::Path::Empty,
+                name: "A".to_string(),
+                generics: Vec::default(),
+            }
+        );
+
+        let res = UnionDef::parse(
+            r#"
+        union Maybe<T> {
+            Some { value : T },
+            None {}
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.generics, vec!["T".to_string()]);
+
+        let res = FnDef::parse(
+            r#"
+        fn first<T>(v : Vec[T]) -> T {
+            return v[0];
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.generics, vec!["T".to_string()]);
+        assert_eq!(value.bindings, None);
+    }
+
+    #[test]
+    fn valid_event_def() {
+        let res = EventDef::parse(
+            r#"
+        event on_signal(1) {
+            let x = 10;
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.name, "on_signal");
+        assert_eq!(
+            value.conf,
+            EventConf {
+                kind: EventKind::Once,
+                exclu: EventExclusivity::PerPID,
+            }
+        );
+
+        let res = EventDef::parse(
+            r#"
+        event repeat per_tid on_signal(1) {
+            let x = 10;
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            value.conf,
+            EventConf {
+                kind: EventKind::Repetable,
+                exclu: EventExclusivity::PerTID,
+            }
+        );
+    }
+
+    #[test]
+    fn valid_const_def() {
+        let res = ConstDef::parse(
+            r#"
+        const MAX_ENERGY : u64 = 10;
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            ConstDef {
+                id: "MAX_ENERGY".to_string(),
+                signature: Type::Primitive(PrimitiveType::Number(NumberType::U64)),
+                expr: Box::new(Expression::Atomic(Atomic::Data(Data::Primitive(v_num!(
+                    Unresolved, 10
+                ))))),
+                value: None,
             },
             value
         );
+
+        let res = ConstDef::parse("const SIZE : u64 = 10".into());
+        assert!(res.is_err());
     }
 }
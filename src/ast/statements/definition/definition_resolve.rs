@@ -1,17 +1,23 @@
-use super::{Definition, EnumDef, FnDef, StructDef, TypeDef, UnionDef};
+use super::{
+    ConstDef, Definition, EnumDef, EventDef, FnDef, ImplDef, InterfaceDef, StructDef, TypeDef,
+    UnionDef,
+};
 
 use crate::ast::statements::block::BlockCommonApi;
 use crate::ast::statements::Statement;
-use crate::semantic::scope::scope::ScopeState;
-use crate::semantic::scope::static_types::FunctionType;
+use crate::ast::types::Type;
+use crate::ast::utils::lexem;
+use crate::p_num;
+use crate::semantic::scope::scope::{MethodInfo, MethodReceiver, ScopeState};
+use crate::semantic::scope::static_types::{ClosureType, FunctionType};
 use crate::semantic::scope::BuildUserType;
-use crate::semantic::Desugar;
 use crate::semantic::EType;
 use crate::semantic::SizeOf;
 use crate::semantic::{
     scope::{static_types::StaticType, user_types::UserType},
     Resolve, SemanticError, TypeOf,
 };
+use crate::semantic::{CompatibleWith, ConstEval, Desugar};
 
 impl Resolve for Definition {
     type Output = ();
@@ -30,6 +36,9 @@ impl Resolve for Definition {
         match self {
             Definition::Type(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
             Definition::Fn(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
+            Definition::Event(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
+            Definition::Impl(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
+            Definition::Const(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
         }
     }
 }
@@ -43,6 +52,9 @@ impl Desugar<Statement> for Definition {
         match self {
             Definition::Type(type_def) => Ok(None),
             Definition::Fn(fn_def) => fn_def.desugar::<E>(scope_manager, scope_id),
+            Definition::Event(event_def) => event_def.desugar::<E>(scope_manager, scope_id),
+            Definition::Impl(impl_def) => impl_def.desugar::<E>(scope_manager, scope_id),
+            Definition::Const(const_def) => const_def.desugar::<E>(scope_manager, scope_id),
         }
     }
 }
@@ -61,15 +73,29 @@ impl Resolve for TypeDef {
     where
         Self: Sized,
     {
+        if !self.generics().is_empty() {
+            // generic types are resolved when instantiated
+            let id = match &self {
+                TypeDef::Struct(value) => value.id.clone(),
+                TypeDef::Union(value) => value.id.clone(),
+                TypeDef::Enum(value) => value.id.clone(),
+                TypeDef::Interface(value) => value.id.clone(),
+            };
+            return scope_manager.register_generic_type(&id, self.clone());
+        }
         let _ = match self {
             TypeDef::Struct(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             TypeDef::Union(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             TypeDef::Enum(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            TypeDef::Interface(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, extra)
+            }
         }?;
         let id = match &self {
             TypeDef::Struct(value) => &value.id,
             TypeDef::Union(value) => &value.id,
             TypeDef::Enum(value) => &value.id,
+            TypeDef::Interface(value) => &value.id,
         };
 
         let type_def = UserType::build_usertype(self, &scope_manager, scope_id)?;
@@ -86,6 +112,9 @@ impl Resolve for TypeDef {
             TypeDef::Enum(value) => {
                 let _ = value.signature.insert((EType::User { id, size }, type_def));
             }
+            TypeDef::Interface(value) => {
+                let _ = value.signature.insert((EType::User { id, size }, type_def));
+            }
         }
         Ok(())
     }
@@ -154,6 +183,32 @@ impl Resolve for EnumDef {
     }
 }
 
+impl Resolve for InterfaceDef {
+    type Output = ();
+    type Context = ();
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        for (_, method) in &mut self.methods {
+            for param in &mut method.params {
+                let _ = param.resolve::<E>(scope_manager, scope_id, context, extra)?;
+            }
+            let _ = method
+                .ret
+                .resolve::<E>(scope_manager, scope_id, context, extra)?;
+        }
+        Ok(())
+    }
+}
+
 impl Resolve for FnDef {
     type Output = ();
     type Context = ();
@@ -172,23 +227,38 @@ impl Resolve for FnDef {
             return Err(SemanticError::Default);
         }
 
-        for value in &mut self.params {
-            let _ = value.resolve::<E>(scope_manager, scope_id, context, extra)?;
+        if !self.generics.is_empty() && self.bindings.is_none() {
+            // generic functions are resolved when instantiated
+            return scope_manager.register_generic_fn(self.clone());
         }
 
-        let _ = self
-            .ret
-            .resolve::<E>(scope_manager, scope_id, context, extra)?;
-        let return_type = self.ret.type_of(&scope_manager, scope_id)?;
-
         let inner_scope = self.scope.init_from_parent(scope_manager, scope_id)?;
         scope_manager
             .scope_states
             .insert(inner_scope, ScopeState::Function);
+        if let Some(bindings) = &self.bindings {
+            scope_manager.register_type_params(
+                inner_scope,
+                self.generics
+                    .iter()
+                    .cloned()
+                    .zip(bindings.iter().cloned())
+                    .collect(),
+            );
+        }
+
+        for value in &mut self.params {
+            let _ = value.resolve::<E>(scope_manager, Some(inner_scope), context, extra)?;
+        }
+
+        let _ = self
+            .ret
+            .resolve::<E>(scope_manager, Some(inner_scope), context, extra)?;
+        let return_type = self.ret.type_of(&scope_manager, Some(inner_scope))?;
 
         let mut param_types = Vec::with_capacity(self.params.len());
         for arg in &self.params {
-            let argtype = arg.type_of(scope_manager, scope_id)?;
+            let argtype = arg.type_of(scope_manager, Some(inner_scope))?;
             param_types.push(argtype.clone());
         }
 
@@ -205,7 +275,7 @@ impl Resolve for FnDef {
 
         let _ = self.id.insert((id, id_internal, fn_type_sig));
         for arg in &self.params {
-            let argtype = arg.type_of(scope_manager, scope_id)?;
+            let argtype = arg.type_of(scope_manager, Some(inner_scope))?;
             let _ =
                 scope_manager.register_parameter(arg.name.as_str(), argtype, Some(inner_scope))?;
         }
@@ -225,12 +295,224 @@ impl Desugar<Statement> for FnDef {
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<Option<Statement>, SemanticError> {
+        if !self.generics.is_empty() && self.bindings.is_none() {
+            return Ok(None);
+        }
         if let Some(output) = self.scope.desugar::<E>(scope_manager, scope_id)? {
             self.scope = output;
         }
         Ok(None)
     }
 }
+
+impl Resolve for ImplDef {
+    type Output = ();
+    type Context = ();
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        if scope_id.is_some() {
+            return Err(SemanticError::NestedImpl);
+        }
+        let type_id = scope_manager
+            .find_type_by_name(None, &self.target, scope_id)?
+            .id;
+
+        // The methods are registered before their resolution so that a method can be called recursively through its receiver
+        for (name, method) in self.methods.iter() {
+            let receiver = match method.params.first() {
+                Some(param) if param.name == lexem::SELF => match param.signature {
+                    Type::Address(_) => MethodReceiver::Address,
+                    _ => MethodReceiver::Value,
+                },
+                _ => MethodReceiver::None,
+            };
+            // the methods of an interface are registered under the name of the interface
+            let name = match &self.interface {
+                Some(interface) => format!("{interface}::{name}"),
+                None => name.clone(),
+            };
+            scope_manager.register_method(
+                type_id,
+                &name,
+                MethodInfo {
+                    function: method.name.clone(),
+                    receiver,
+                },
+            )?;
+        }
+
+        for (index, (_, method)) in self.methods.iter_mut().enumerate() {
+            let _ = scope_manager
+                .at(self.spans.get(index), |scope_manager| {
+                    method.resolve::<E>(scope_manager, scope_id, context, extra)
+                })
+                .map_err(|err| err.at(self.spans.get(index)))?;
+        }
+
+        if let Some(interface) = &self.interface {
+            let interface_type = scope_manager.find_type_by_name(None, interface, scope_id)?;
+            let UserType::Interface(interface_def) = &interface_type.def else {
+                return Err(SemanticError::ExpectedInterface);
+            };
+            // index of the method implementing each method of the interface, in the order of the interface
+            let mut dispatch = Vec::with_capacity(interface_def.methods.len());
+            for (name, FunctionType { params, ret }) in interface_def.methods.iter() {
+                let Some(idx) = self.methods.iter().position(|(id, _)| id == name) else {
+                    return Err(SemanticError::IncorrectImplementation(interface.clone()));
+                };
+                let method = &self.methods[idx].1;
+                let Some((_, _, EType::Static(StaticType::Function(signature)))) = &method.id
+                else {
+                    return Err(SemanticError::NotResolvedYet);
+                };
+                let is_address_receiver = match method.params.first() {
+                    Some(param) => {
+                        param.name == lexem::SELF && matches!(param.signature, Type::Address(_))
+                    }
+                    None => false,
+                };
+                if !is_address_receiver
+                    || signature.params[1..] != params[..]
+                    || signature.ret != *ret
+                {
+                    return Err(SemanticError::IncorrectImplementation(interface.clone()));
+                }
+                dispatch.push(idx);
+            }
+            scope_manager.register_interface_impl(interface_type.id, type_id, interface)?;
+            self.dispatch = Some((interface_type.id, type_id, dispatch));
+        }
+        Ok(())
+    }
+}
+
+impl Desugar<Statement> for ImplDef {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        for (_, method) in self.methods.iter_mut() {
+            let _ = method.desugar::<E>(scope_manager, scope_id)?;
+        }
+        Ok(None)
+    }
+}
+
+impl Resolve for EventDef {
+    type Output = ();
+    type Context = ();
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ =
+            self.trigger
+                .resolve::<E>(scope_manager, scope_id, &Some(p_num!(U64)), &mut None)?;
+        let trigger_type = self.trigger.type_of(&scope_manager, scope_id)?;
+        if trigger_type != p_num!(U64) {
+            return Err(
+                SemanticError::mismatched(&p_num!(U64), &trigger_type, scope_manager, scope_id)
+                    .at(self.trigger.span()),
+            );
+        }
+
+        // The scope of an event always has the general scope as parent
+        let callback_type = EType::Static(StaticType::Closure(ClosureType {
+            params: Vec::default(),
+            ret: Box::new(EType::Static(StaticType::Unit)),
+        }));
+        let _ = self
+            .callback
+            .resolve::<E>(scope_manager, None, &Some(callback_type), &mut ())?;
+        Ok(())
+    }
+}
+
+impl Desugar<Statement> for EventDef {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        if let Some(output) = self.trigger.desugar::<E>(scope_manager, scope_id)? {
+            *self.trigger = output;
+        }
+        let _ = self.callback.desugar::<E>(scope_manager, None)?;
+        Ok(None)
+    }
+}
+
+impl Resolve for ConstDef {
+    type Output = ();
+    type Context = ();
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ = self
+            .signature
+            .resolve::<E>(scope_manager, scope_id, &(), &mut ())?;
+        let const_type = self.signature.type_of(&scope_manager, scope_id)?;
+
+        let _ = self.expr.resolve::<E>(
+            scope_manager,
+            scope_id,
+            &Some(const_type.clone()),
+            &mut None,
+        )?;
+        let _ = const_type.compatible_with(
+            &self.expr.type_of(&scope_manager, scope_id)?,
+            &scope_manager,
+            scope_id,
+        )?;
+
+        // the value is folded once and inlined at each use of the constant
+        let Some(value) = self.expr.const_eval()? else {
+            return Err(SemanticError::ExpectedConstant);
+        };
+        let _ = scope_manager.register_constant(&self.id, value.clone(), scope_id)?;
+        self.value = Some(value);
+        Ok(())
+    }
+}
+
+impl Desugar<Statement> for ConstDef {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        if let Some(output) = self.expr.desugar::<E>(scope_manager, scope_id)? {
+            *self.expr = output;
+        }
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -239,7 +521,7 @@ mod tests {
         e_static, p_num,
         semantic::scope::{
             scope,
-            static_types::{FunctionType, StaticType, StringType},
+            static_types::{FunctionType, PrimitiveType, StaticType, StringType},
             user_types::{Enum, Struct, Union, UserType},
         },
     };
@@ -690,4 +972,227 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn robustness_interface_impl() {
+        let mut scope_manager = scope::ScopeManager::default();
+        for definition in [
+            r##"
+            interface Shape {
+                fn area(&self) -> i64;
+            }
+            "##,
+            r##"
+            struct Square {
+                side : i64,
+            }
+            "##,
+        ] {
+            let mut definition = Definition::parse(definition.into()).unwrap().1;
+            let res = definition.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            );
+            assert!(res.is_ok(), "{:?}", res);
+        }
+
+        for implementation in [
+            // missing method
+            r##"
+            impl Shape for Square {
+                fn perimeter(&self) -> i64 {
+                    return 4 * self.side;
+                }
+            }
+            "##,
+            // receiver taken by value
+            r##"
+            impl Shape for Square {
+                fn area(self) -> i64 {
+                    return self.side * self.side;
+                }
+            }
+            "##,
+            // incorrect return type
+            r##"
+            impl Shape for Square {
+                fn area(&self) -> u64 {
+                    return 1u64;
+                }
+            }
+            "##,
+        ] {
+            let mut implementation = ImplDef::parse(implementation.into()).unwrap().1;
+            scope_manager.open_transaction();
+            let res = implementation.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &(),
+                &mut (),
+            );
+            scope_manager.reject_transaction();
+            assert!(
+                matches!(res, Err(SemanticError::IncorrectImplementation(_))),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn robustness_interface_call_return() {
+        let mut scope_manager = scope::ScopeManager::default();
+        for statement in [
+            r##"
+            struct Square {
+                side : i64,
+            }
+            "##,
+            r##"
+            interface Shape {
+                fn area(&self) -> i64;
+            }
+            "##,
+            r##"
+            impl Shape for Square {
+                fn area(&self) -> i64 {
+                    return self.side * self.side;
+                }
+            }
+            "##,
+            "let square = Square { side : 2 };",
+            "let shape = &square as Shape;",
+            "let area : i64 = shape.area();",
+        ] {
+            let mut statement = Statement::parse(statement.into()).unwrap().1;
+            let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            );
+            assert!(res.is_ok(), "{:?}", res);
+        }
+
+        let mut statement = Statement::parse("let area : u64 = shape.area();".into())
+            .unwrap()
+            .1;
+        let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        match res.map_err(SemanticError::into_inner) {
+            Err(SemanticError::MismatchedTypes { expected, found }) => {
+                assert_eq!(expected.name, "u64");
+                assert_eq!(found.name, "i64");
+            }
+            res => panic!("Expected a type mismatch, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn robustness_nested_impl() {
+        let mut scope_manager = scope::ScopeManager::default();
+        let mut definition = Definition::parse(
+            r##"
+            struct Point {
+                x : i64,
+            }
+            "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let res = definition.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        let mut implementation = ImplDef::parse(
+            r##"
+            impl Point {
+                fn x(&self) -> i64 {
+                    return self.x;
+                }
+            }
+            "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let inner_scope = scope_manager
+            .spawn(None)
+            .expect("Spawning should have succeeded");
+        let res = implementation.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            Some(inner_scope),
+            &(),
+            &mut (),
+        );
+        assert!(matches!(res, Err(SemanticError::NestedImpl)), "{:?}", res);
+    }
+
+    #[test]
+    fn robustness_const() {
+        let mut scope_manager = scope::ScopeManager::default();
+        let mut statement = Statement::parse("let x = 5;".into()).unwrap().1;
+        let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        let mut definition = ConstDef::parse("const LIMIT : i64 = 10;".into()).unwrap().1;
+        let res = definition.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &(),
+            &mut (),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        for (definition, expected) in [
+            ("const Y : i64 = x + 1;", SemanticError::ExpectedConstant),
+            (
+                "const Y : u8 = true;",
+                SemanticError::MismatchedTypes {
+                    expected: crate::semantic::NamedType {
+                        etype: p_num!(U8),
+                        name: "u8".to_string(),
+                    },
+                    found: crate::semantic::NamedType {
+                        etype: e_static!(StaticType::Primitive(PrimitiveType::Bool)),
+                        name: "bool".to_string(),
+                    },
+                },
+            ),
+            (
+                "const LIMIT : i64 = 11;",
+                SemanticError::AlreadyDefined("LIMIT".to_string()),
+            ),
+            ("const Y : u8 = 255 + 1;", SemanticError::ConstantOverflow),
+            ("const Y : i64 = LIMIT / 0;", SemanticError::ConstantOverflow),
+        ] {
+            let mut definition = ConstDef::parse(definition.into()).unwrap().1;
+            let res = definition.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &(),
+                &mut (),
+            );
+            assert_eq!(
+                std::mem::discriminant(&res.unwrap_err()),
+                std::mem::discriminant(&expected)
+            );
+        }
+    }
 }
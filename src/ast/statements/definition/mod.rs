@@ -1,11 +1,22 @@
 use crate::{
-    ast::{types::Type, utils::strings::ID},
+    ast::{
+        expressions::{
+            data::{Closure, Data},
+            Expression,
+        },
+        types::{FunctionType, Type},
+        utils::{io::SourceSpans, strings::ID},
+    },
     semantic::{scope::user_types::UserType, EType},
+    vm::scheduler::EventConf,
 };
 
-use super::{block::FunctionBlock, declaration::TypedVar};
+use super::{annotation::Annotations, block::FunctionBlock, declaration::TypedVar};
 
+pub mod definition_format;
 pub mod definition_gencode;
+pub mod definition_generics;
+pub mod definition_lint;
 pub mod definition_parse;
 pub mod definition_resolve;
 pub mod definition_typeof;
@@ -14,6 +25,9 @@ pub mod definition_typeof;
 pub enum Definition {
     Type(TypeDef),
     Fn(FnDef),
+    Event(EventDef),
+    Impl(ImplDef),
+    Const(ConstDef),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,20 +35,54 @@ pub enum TypeDef {
     Struct(StructDef),
     Union(UnionDef),
     Enum(EnumDef),
+    Interface(InterfaceDef),
+}
+
+impl TypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDef::Struct(StructDef { id, .. })
+            | TypeDef::Union(UnionDef { id, .. })
+            | TypeDef::Enum(EnumDef { id, .. })
+            | TypeDef::Interface(InterfaceDef { id, .. }) => id,
+        }
+    }
+
+    pub fn signature(&self) -> Option<&(EType, UserType)> {
+        match self {
+            TypeDef::Struct(StructDef { signature, .. })
+            | TypeDef::Union(UnionDef { signature, .. })
+            | TypeDef::Enum(EnumDef { signature, .. })
+            | TypeDef::Interface(InterfaceDef { signature, .. }) => signature.as_ref(),
+        }
+    }
+
+    pub fn annotations(&self) -> &Annotations {
+        match self {
+            TypeDef::Struct(StructDef { annotations, .. })
+            | TypeDef::Union(UnionDef { annotations, .. })
+            | TypeDef::Enum(EnumDef { annotations, .. })
+            | TypeDef::Interface(InterfaceDef { annotations, .. }) => annotations,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructDef {
     pub id: ID,
+    pub generics: Vec<ID>,
     pub fields: Vec<(ID, Type)>,
     pub signature: Option<(EType, UserType)>,
+    pub annotations: Annotations,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UnionDef {
     pub id: ID,
+    pub generics: Vec<ID>,
     pub variants: Vec<(ID, Vec<(ID, Type)>)>,
     pub signature: Option<(EType, UserType)>,
+    pub annotations: Annotations,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -42,13 +90,51 @@ pub struct EnumDef {
     pub id: String,
     pub values: Vec<(String, u64)>,
     pub signature: Option<(EType, UserType)>,
+    pub annotations: Annotations,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceDef {
+    pub id: ID,
+    pub methods: Vec<(ID, FunctionType)>,
+    pub param_names: Vec<Vec<ID>>, // names of the parameters of each method
+    pub signature: Option<(EType, UserType)>,
+    pub annotations: Annotations,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FnDef {
     pub name: String,
+    pub generics: Vec<ID>,
+    pub bindings: Option<Vec<EType>>,
     pub id: Option<(u64, u64, EType)>,
     params: Vec<TypedVar>,
     ret: Box<Type>,
     scope: FunctionBlock,
+    pub annotations: Annotations,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplDef {
+    pub target: ID,
+    pub interface: Option<ID>,
+    pub methods: Vec<(ID, FnDef)>,
+    pub spans: SourceSpans, // location of each method
+    pub dispatch: Option<(u64, u64, Vec<usize>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstDef {
+    pub id: ID,
+    pub signature: Type,
+    pub expr: Box<Expression>,
+    pub value: Option<Data>, // compile time value of the expression
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventDef {
+    pub name: String,
+    pub conf: EventConf,
+    trigger: Box<Expression>,
+    callback: Closure,
 }
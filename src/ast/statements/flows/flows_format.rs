@@ -0,0 +1,81 @@
+use crate::ast::{
+    expressions::flows::flows_format::pretty_match,
+    format::{Formatter, PrettyPrint},
+};
+
+use super::{CallStat, Flow, IfStat, MatchStat, RaiseStat, TryStat};
+
+impl PrettyPrint for Flow {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Flow::If(value) => value.pretty(f),
+            Flow::Match(value) => value.pretty(f),
+            Flow::Try(value) => value.pretty(f),
+            Flow::Raise(value) => value.pretty(f),
+            Flow::Printf(value) => {
+                value.pretty(f);
+                f.write(";");
+            }
+            Flow::Call(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for IfStat {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("if ");
+        self.condition.pretty(f);
+        f.write(" ");
+        self.then_branch.pretty(f);
+        for (condition, block) in self.else_if_branches.iter() {
+            f.write(" else if ");
+            condition.pretty(f);
+            f.write(" ");
+            block.pretty(f);
+        }
+        if let Some(else_branch) = &self.else_branch {
+            f.write(" else ");
+            else_branch.pretty(f);
+        }
+    }
+}
+
+impl PrettyPrint for MatchStat {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_match(f, &self.expr, &self.cases, self.else_branch.as_deref());
+    }
+}
+
+impl PrettyPrint for TryStat {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("try ");
+        self.try_branch.pretty(f);
+        if let Some(else_branch) = &self.else_branch {
+            f.write(" else ");
+            if let Some(name) = &self.error_name {
+                f.write(name);
+                if let Some(message) = &self.message_name {
+                    f.write(", ");
+                    f.write(message);
+                }
+                f.write(" ");
+            }
+            else_branch.pretty(f);
+        }
+    }
+}
+
+impl PrettyPrint for RaiseStat {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("raise ");
+        self.message.pretty(f);
+        f.write(";");
+    }
+}
+
+impl PrettyPrint for CallStat {
+    fn pretty(&self, f: &mut Formatter) {
+        self.call.pretty(f);
+        f.write(";");
+    }
+}
@@ -1,14 +1,18 @@
 use crate::vm::{asm::branch::BranchTry, CodeGenerationContext, CodeGenerationError, GenerateCode};
 
 use crate::{
-    semantic::SizeOf,
+    semantic::{
+        scope::{scope::VariableInfo, static_types::POINTER_SIZE},
+        SizeOf,
+    },
     vm::asm::{
         branch::{BranchIf, Goto, Label},
+        mem::Mem,
         Asm,
     },
 };
 
-use super::{CallStat, Flow, IfStat, MatchStat, TryStat};
+use super::{CallStat, Flow, IfStat, MatchStat, RaiseStat, TryStat};
 
 impl GenerateCode for Flow {
     fn gencode<E: crate::vm::external::Engine>(
@@ -24,6 +28,9 @@ impl GenerateCode for Flow {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
             Flow::Try(value) => value.gencode::<E>(scope_manager, scope_id, instructions, context),
+            Flow::Raise(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
             Flow::Printf(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
@@ -43,7 +50,7 @@ impl GenerateCode for CallStat {
         let _ = self
             .call
             .gencode::<E>(scope_manager, scope_id, instructions, context)?;
-        let Some(return_type) = self.call.metadata.signature() else {
+        let Some(return_type) = self.call.signature() else {
             return Err(CodeGenerationError::UnresolvedError);
         };
         let size = return_type.size_of();
@@ -55,6 +62,24 @@ impl GenerateCode for CallStat {
     }
 }
 
+impl GenerateCode for RaiseStat {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let _ = self
+            .message
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        instructions.push(Asm::Try(BranchTry::Raise {
+            for_string: self.for_string,
+        }));
+        Ok(())
+    }
+}
+
 impl GenerateCode for IfStat {
     fn gencode<E: crate::vm::external::Engine>(
         &self,
@@ -209,15 +234,42 @@ impl GenerateCode for TryStat {
             .try_branch
             .gencode::<E>(scope_manager, scope_id, instructions, context)?;
 
+        instructions.push(Asm::Try(BranchTry::EndTry));
         instructions.push(Asm::Goto(Goto {
             label: Some(end_try_label),
         }));
 
         instructions.push_label_by_id(recover_else_label, "recover_else".to_string().into());
+        if self.message_id.is_some() {
+            instructions.push(Asm::Try(BranchTry::Message));
+        }
 
         instructions.push_label_by_id(else_label, "else".to_string().into());
         instructions.push(Asm::Try(BranchTry::EndTry));
 
+        // the code of the caught error is on top of the stack, below its message if bound
+        match self.error_id {
+            Some(id) => {
+                for id in self.message_id.into_iter().chain([id]) {
+                    if scope_manager.is_var_global(id) {
+                        let _ = scope_manager.alloc_global_var_by_id(id)?;
+                    }
+                    let Some(VariableInfo { address, ctype, .. }) =
+                        scope_manager.find_var_by_id(id).ok()
+                    else {
+                        return Err(CodeGenerationError::UnresolvedError);
+                    };
+                    instructions.push(Asm::Mem(Mem::Store {
+                        size: ctype.size_of(),
+                        address: (*address)
+                            .try_into()
+                            .map_err(|_| CodeGenerationError::UnresolvedError)?,
+                    }));
+                }
+            }
+            None => instructions.push(Asm::Pop(POINTER_SIZE)),
+        }
+
         if let Some(block) = &self.else_branch {
             block.gencode::<E>(scope_manager, scope_id, instructions, context)?;
         }
@@ -504,4 +556,216 @@ mod tests {
             assert_fn,
         );
     }
+
+    #[test]
+    fn valid_try_bound_error() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 4);
+            true
+        }
+
+        test_statements(
+            r##"
+        let arr = [1,2,3];
+        let zero = 0;
+
+        let res1 = 0;
+        try {
+            let x = arr[4];
+            res1 = 5;
+        } else err {
+            match err {
+                case RuntimeError::IndexOutOfBound => {
+                    res1 = 1;
+                },
+                else => {
+                    res1 = 5;
+                }
+            }
+        }
+
+        let res2 = 0;
+        try {
+            res2 = 10 / zero;
+        } else err {
+            match err {
+                case RuntimeError::MathError => {
+                    res2 = 2;
+                },
+                else => {
+                    res2 = 5;
+                }
+            }
+        }
+
+        fn catch(index:u64) -> i64 {
+            let res = 0;
+            try {
+                res = arr[index];
+            } else err {
+                match err {
+                    case RuntimeError::IndexOutOfBound => {
+                        res = 3;
+                    },
+                    else => {
+                        res = 5;
+                    }
+                }
+            }
+            return res;
+        }
+        let res3 = catch(8);
+
+        let res4 = 0;
+        try {
+            let _ = catch(2);
+            res4 = 4;
+        } else {
+            res4 = 5;
+        }
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_raise() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 1);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            true
+        }
+
+        test_statements(
+            r##"
+        let res1 = 0;
+        try {
+            raise "invalid state";
+            res1 = 5;
+        } else err {
+            match err {
+                case RuntimeError::Raised => {
+                    res1 = 1;
+                },
+                else => {
+                    res1 = 5;
+                }
+            }
+        }
+
+        fn check(x:i64) -> i64 {
+            if x < 0 {
+                raise string("negative value");
+            }
+            return x;
+        }
+        fn twice(x:i64) -> i64 {
+            let y = check(x);
+            return y * 2;
+        }
+
+        let res2 = 0;
+        try {
+            res2 = twice(-1);
+        } else {
+            res2 = 2;
+        }
+
+        let res3 = 0;
+        try {
+            res3 = twice(1) + 1;
+        } else {
+            res3 = 5;
+        }
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn valid_raise_message() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<u64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 14);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            true
+        }
+
+        test_statements(
+            r##"
+        let negative = "negative value";
+        fn check(x:i64) -> i64 {
+            if x < 0 {
+                raise negative;
+            }
+            return x;
+        }
+
+        let res1 = 0u64;
+        try {
+            let _ = check(-1);
+        } else err, message {
+            res1 = len(message);
+        }
+
+        let arr = [1,2,3];
+        let res2 = 0;
+        try {
+            res2 = arr[4];
+        } else err, message {
+            match err {
+                case RuntimeError::IndexOutOfBound => {
+                    res2 = 2;
+                },
+                else => {
+                    res2 = 5;
+                }
+            }
+        }
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
 }
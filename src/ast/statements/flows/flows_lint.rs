@@ -0,0 +1,86 @@
+use crate::{
+    ast::expressions::{flows::Cases, operation::Propagate},
+    semantic::lint::{LintCheck, LintId, Linter},
+};
+
+use super::{CallStat, Flow, IfStat, MatchStat, TryStat};
+
+impl LintCheck for Flow {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            Flow::If(value) => value.lint(linter),
+            Flow::Match(value) => value.lint(linter),
+            Flow::Try(value) => value.lint(linter),
+            Flow::Call(value) => value.lint(linter),
+            Flow::Raise(_) | Flow::Printf(_) => {}
+        }
+    }
+}
+
+impl LintCheck for IfStat {
+    fn lint(&self, linter: &mut Linter) {
+        self.then_branch.lint(linter);
+        for (_, block) in self.else_if_branches.iter() {
+            block.lint(linter);
+        }
+        if let Some(block) = &self.else_branch {
+            block.lint(linter);
+        }
+    }
+}
+
+impl LintCheck for MatchStat {
+    fn lint(&self, linter: &mut Linter) {
+        match &self.cases {
+            Cases::Primitive { cases } => cases.iter().for_each(|case| case.block.lint(linter)),
+            Cases::String { cases } => cases.iter().for_each(|case| case.block.lint(linter)),
+            Cases::Enum { cases } => cases.iter().for_each(|case| case.block.lint(linter)),
+            Cases::Union { cases } => {
+                for case in cases.iter() {
+                    linter.in_block(|linter| {
+                        for name in case.pattern.vars_names.iter() {
+                            linter.bind(name);
+                        }
+                        case.block.lint(linter);
+                    });
+                }
+            }
+        }
+        if let Some(block) = &self.else_branch {
+            block.lint(linter);
+        }
+    }
+}
+
+impl LintCheck for TryStat {
+    fn lint(&self, linter: &mut Linter) {
+        self.try_branch.lint(linter);
+        if let Some(block) = &self.else_branch {
+            linter.in_block(|linter| {
+                if let Some(name) = &self.error_name {
+                    linter.bind(name);
+                }
+                if let Some(name) = &self.message_name {
+                    linter.bind(name);
+                }
+                block.lint(linter);
+            });
+        }
+    }
+}
+
+impl LintCheck for CallStat {
+    fn lint(&self, linter: &mut Linter) {
+        let is_error = self
+            .call
+            .signature()
+            .is_some_and(|signature| Propagate::split_error(&signature).is_some());
+        if is_error {
+            linter.report(
+                LintId::UnusedError,
+                self.call.span(),
+                "the error returned by this call is ignored".to_string(),
+            );
+        }
+    }
+}
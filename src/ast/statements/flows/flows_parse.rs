@@ -1,8 +1,9 @@
 use crate::ast::{
     expressions::{
-        data::{Call, Printf},
+        data::{Call, Data, Printf},
         flows::Cases,
-        operation::operation_parse::TryParseOperation,
+        operation::{operation_parse::TryParseOperation, FieldAccess},
+        Atomic,
     },
     statements::block::Block,
     utils::{error::squash, strings::wst_closed},
@@ -10,7 +11,7 @@ use crate::ast::{
 };
 use nom::{
     branch::alt,
-    combinator::{cut, map, opt},
+    combinator::{cut, map, opt, verify},
     multi::many0,
     sequence::{delimited, pair, preceded, terminated},
 };
@@ -19,13 +20,13 @@ use nom_supreme::ParserExt;
 use crate::ast::{
     expressions::Expression,
     utils::{
-        io::{PResult, Span},
+        io::{spanned, PResult, Span},
         lexem,
-        strings::wst,
+        strings::{parse_id, wst},
     },
 };
 
-use super::{CallStat, Flow, IfStat, MatchStat, TryStat};
+use super::{CallStat, Flow, IfStat, MatchStat, RaiseStat, TryStat};
 
 impl TryParse for Flow {
     fn parse(input: Span) -> PResult<Self> {
@@ -34,12 +35,13 @@ impl TryParse for Flow {
                 map(IfStat::parse, |value| Flow::If(value)),
                 map(MatchStat::parse, |value| Flow::Match(value)),
                 map(TryStat::parse, |value| Flow::Try(value)),
+                map(RaiseStat::parse, |value| Flow::Raise(value)),
                 map(terminated(Printf::parse, wst(lexem::SEMI_COLON)), |value| {
                     Flow::Printf(value)
                 }),
                 map(CallStat::parse, |value| Flow::Call(value)),
             )),
-            "Expected an if, match, try statement or a function call",
+            "Expected an if, match, try, raise statement or a function call",
         )(input)
     }
 }
@@ -126,7 +128,7 @@ impl TryParse for TryStat {
      * @desc Parse try statements
      *
      * @grammar
-     * Try_Stat := try Scope ( else Scope | Λ  )
+     * Try_Stat := try Scope ( else ( ID ( , ID | Λ ) | Λ ) Scope | Λ  )
      */
     fn parse(input: Span) -> PResult<Self> {
         map(
@@ -137,12 +139,51 @@ impl TryParse for TryStat {
                 ),
                 opt(preceded(
                     wst_closed(lexem::ELSE),
-                    cut(Block::parse).context("Invalid block in else statement"),
+                    pair(
+                        opt(pair(parse_id, opt(preceded(wst(lexem::COMA), parse_id)))),
+                        cut(Block::parse).context("Invalid block in else statement"),
+                    ),
                 )),
             ),
-            |(try_branch, else_branch)| TryStat {
-                try_branch: Box::new(try_branch),
-                else_branch: else_branch.map(|value| Box::new(value)),
+            |(try_branch, else_branch)| {
+                let (names, else_branch) = match else_branch {
+                    Some((names, else_branch)) => (names, Some(Box::new(else_branch))),
+                    None => (None, None),
+                };
+                let (error_name, message_name) = match names {
+                    Some((error_name, message_name)) => (Some(error_name), message_name),
+                    None => (None, None),
+                };
+                TryStat {
+                    try_branch: Box::new(try_branch),
+                    else_branch,
+                    error_name,
+                    error_id: None,
+                    message_name,
+                    message_id: None,
+                }
+            },
+        )(input)
+    }
+}
+
+impl TryParse for RaiseStat {
+    /*
+     * @desc Parse raise statements
+     *
+     * @grammar
+     * Raise_Stat := raise Expr ;
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            delimited(
+                wst_closed(lexem::RAISE),
+                cut(Expression::parse).context("Invalid message in raise statement"),
+                cut(wst(lexem::SEMI_COLON)),
+            ),
+            |message| RaiseStat {
+                message: Box::new(message),
+                for_string: false,
             },
         )(input)
     }
@@ -153,12 +194,24 @@ impl TryParse for CallStat {
      * @desc Parse call statements
      *
      * @grammar
-     * FnCallStat := ID \(  Fn_Args \) ;
+     * FnCallStat := ID \(  Fn_Args \) ; | Expr . ID \(  Fn_Args \) ;
      */
     fn parse(input: Span) -> PResult<Self> {
-        map(terminated(Call::parse, wst(lexem::SEMI_COLON)), |call| {
-            CallStat { call }
-        })(input)
+        map(
+            terminated(
+                spanned(alt((
+                    map(Call::parse, |call| {
+                        Expression::Atomic(Atomic::Data(Data::Call(call)))
+                    }),
+                    verify(
+                        FieldAccess::parse,
+                        |expr| matches!(expr, Expression::FieldAccess(value) if value.is_method_call()),
+                    ),
+                ))),
+                wst(lexem::SEMI_COLON),
+            ),
+            |call| CallStat { call },
+        )(input)
     }
 }
 
@@ -219,4 +272,39 @@ mod tests {
         assert!(res.is_ok(), "{:?}", res);
         let value = res.unwrap().1;
     }
+
+    #[test]
+    fn valid_try_bound_error() {
+        let res = TryStat::parse(
+            r#"
+        try {
+            f(10);
+        } else err {
+            f(10);
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.error_name, Some("err".to_string()));
+    }
+
+    #[test]
+    fn valid_try_bound_message() {
+        let res = TryStat::parse(
+            r#"
+        try {
+            f(10);
+        } else err, message {
+            f(10);
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(value.error_name, Some("err".to_string()));
+        assert_eq!(value.message_name, Some("message".to_string()));
+    }
 }
@@ -1,7 +1,7 @@
-use super::{CallStat, Flow, IfStat, MatchStat, TryStat};
-use crate::ast::statements::block::Block;
+use super::{CallStat, Flow, IfStat, MatchStat, RaiseStat, TryStat};
+use crate::ast::statements::block::{Block, BlockCommonApi};
 use crate::ast::statements::Statement;
-use crate::semantic::scope::static_types::PrimitiveType;
+use crate::semantic::scope::static_types::{PrimitiveType, StringType};
 use crate::semantic::Desugar;
 use crate::semantic::{
     scope::{
@@ -30,6 +30,7 @@ impl Resolve for Flow {
             Flow::If(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Flow::Match(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Flow::Try(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            Flow::Raise(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
             Flow::Printf(value) => value.resolve::<E>(scope_manager, scope_id, &None, &mut ()),
             Flow::Call(value) => value.resolve::<E>(scope_manager, scope_id, &(), &mut ()),
         }
@@ -46,6 +47,7 @@ impl Desugar<Statement> for Flow {
             Flow::If(value) => value.desugar::<E>(scope_manager, scope_id),
             Flow::Match(value) => value.desugar::<E>(scope_manager, scope_id),
             Flow::Try(value) => value.desugar::<E>(scope_manager, scope_id),
+            Flow::Raise(value) => value.desugar::<E>(scope_manager, scope_id),
             Flow::Printf(value) => {
                 let _ = value.desugar::<E>(scope_manager, scope_id);
                 Ok(None)
@@ -148,7 +150,13 @@ impl Resolve for MatchStat {
         match &mut self.cases {
             super::Cases::Primitive { cases } => {
                 let EType::Static(StaticType::Primitive(_)) = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a primitive value",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 for case in cases {
                     let _ = case.resolve::<E>(
@@ -163,7 +171,15 @@ impl Resolve for MatchStat {
                 match expr_type {
                     EType::Static(StaticType::StrSlice(_))
                     | EType::Static(StaticType::String(_)) => {}
-                    _ => return Err(SemanticError::IncompatibleTypes),
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a string",
+                            &expr_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(self.expr.span()));
+                    }
                 }
 
                 for case in cases {
@@ -177,12 +193,24 @@ impl Resolve for MatchStat {
             }
             super::Cases::Enum { cases } => {
                 let EType::User { id, size } = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "an enum",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 let UserType::Enum(Enum { id, values }) =
                     scope_manager.find_type_by_id(id, scope_id)?.clone()
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "an enum",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
 
                 for case in cases.iter_mut() {
@@ -213,12 +241,24 @@ impl Resolve for MatchStat {
             }
             super::Cases::Union { cases } => {
                 let EType::User { id, size } = expr_type else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a union",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
                 let UserType::Union(Union { id, variants }) =
                     scope_manager.find_type_by_id(id, scope_id)?.clone()
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a union",
+                        &expr_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(self.expr.span()));
                 };
 
                 for case in cases.iter_mut() {
@@ -286,6 +326,19 @@ impl Resolve for TryStat {
             .try_branch
             .resolve::<E>(scope_manager, scope_id, &context, &mut ())?;
         if let Some(else_branch) = &mut self.else_branch {
+            if let Some(error_name) = &self.error_name {
+                // bind the caught error in the else block
+                let inner_scope = else_branch.init_from_parent(scope_manager, scope_id)?;
+                let error_type = scope_manager.runtime_error_type()?;
+                let id = scope_manager.register_var(error_name, error_type, Some(inner_scope))?;
+                let _ = self.error_id.insert(id);
+                if let Some(message_name) = &self.message_name {
+                    let message_type = EType::Static(StaticType::String(StringType()));
+                    let id =
+                        scope_manager.register_var(message_name, message_type, Some(inner_scope))?;
+                    let _ = self.message_id.insert(id);
+                }
+            }
             let _ = else_branch.resolve::<E>(scope_manager, scope_id, &context, &mut ())?;
         }
         Ok(())
@@ -312,6 +365,58 @@ impl Desugar<Statement> for TryStat {
     }
 }
 
+impl Resolve for RaiseStat {
+    type Output = ();
+    type Context = ();
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        _context: &Self::Context,
+        _extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ = self
+            .message
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+        let message_type = self.message.type_of(&scope_manager, scope_id)?;
+        match message_type {
+            EType::Static(StaticType::StrSlice(_)) => {
+                self.for_string = false;
+            }
+            EType::Static(StaticType::String(_)) => {
+                self.for_string = true;
+            }
+            _ => {
+                return Err(SemanticError::mismatched_kind(
+                    "a string",
+                    &message_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(self.message.span()))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Desugar<Statement> for RaiseStat {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        if let Some(output) = self.message.desugar::<E>(scope_manager, scope_id)? {
+            self.message = output.into();
+        }
+        Ok(None)
+    }
+}
+
 impl Resolve for CallStat {
     type Output = ();
     type Context = ();
@@ -327,7 +432,7 @@ impl Resolve for CallStat {
         Self: Sized,
     {
         self.call
-            .resolve::<E>(scope_manager, scope_id, &None, &mut ())
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)
     }
 }
 
@@ -337,7 +442,9 @@ impl Desugar<Statement> for CallStat {
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<Option<Statement>, SemanticError> {
-        let _ = self.call.desugar::<E>(scope_manager, scope_id)?;
+        if let Some(output) = self.call.desugar::<E>(scope_manager, scope_id)? {
+            self.call = output;
+        }
         Ok(None)
     }
 }
@@ -487,4 +594,52 @@ mod tests {
         );
         assert!(res.is_ok(), "{:?}", res);
     }
+
+    #[test]
+    fn valid_raise() {
+        let mut expr = RaiseStat::parse(
+            r##"
+            raise "invalid state";
+        "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &(),
+            &mut (),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert!(!expr.for_string);
+    }
+
+    #[test]
+    fn robustness_raise() {
+        let mut expr = RaiseStat::parse(
+            r##"
+            raise 10;
+        "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let res = expr.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &(),
+            &mut (),
+        );
+        assert!(
+            matches!(
+                res.as_ref().map_err(|err| err.clone().into_inner()),
+                Err(SemanticError::MismatchedTypes { .. })
+            ),
+            "{:?}",
+            res
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use super::{CallStat, Flow, IfStat, MatchStat, TryStat};
+use super::{CallStat, Flow, IfStat, MatchStat, RaiseStat, TryStat};
 use crate::semantic::EType;
 use crate::semantic::{scope::static_types::StaticType, Resolve, SemanticError, TypeOf};
 
@@ -15,6 +15,7 @@ impl TypeOf for Flow {
             Flow::If(value) => value.type_of(&scope_manager, scope_id),
             Flow::Match(value) => value.type_of(&scope_manager, scope_id),
             Flow::Try(value) => value.type_of(&scope_manager, scope_id),
+            Flow::Raise(value) => value.type_of(&scope_manager, scope_id),
             Flow::Printf(value) => value.type_of(&scope_manager, scope_id),
             Flow::Call(value) => value.type_of(&scope_manager, scope_id),
         }
@@ -58,6 +59,19 @@ impl TypeOf for TryStat {
     }
 }
 
+impl TypeOf for RaiseStat {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        Ok(EType::Static(StaticType::Unit))
+    }
+}
+
 impl TypeOf for CallStat {
     fn type_of(
         &self,
@@ -1,12 +1,13 @@
-use crate::ast::expressions::{
-    data::{Call, Printf},
-    flows::Cases,
-    Expression,
+use crate::ast::{
+    expressions::{data::Printf, flows::Cases, Expression},
+    utils::strings::ID,
 };
 
 use super::block::{Block, ExprBlock};
 
+pub mod flows_format;
 pub mod flows_gencode;
+pub mod flows_lint;
 pub mod flows_parse;
 pub mod flows_resolve;
 pub mod flows_typeof;
@@ -16,6 +17,7 @@ pub enum Flow {
     If(IfStat),
     Match(MatchStat),
     Try(TryStat),
+    Raise(RaiseStat),
     Printf(Printf),
     Call(CallStat),
 }
@@ -39,9 +41,19 @@ pub struct MatchStat {
 pub struct TryStat {
     try_branch: Box<Block>,
     else_branch: Option<Box<Block>>,
+    error_name: Option<ID>,
+    error_id: Option<u64>,
+    message_name: Option<ID>,
+    message_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaiseStat {
+    message: Box<Expression>,
+    for_string: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CallStat {
-    pub call: Call,
+    pub call: Expression,
 }
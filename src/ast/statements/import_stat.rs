@@ -0,0 +1,130 @@
+use nom::{combinator::cut, sequence::terminated};
+
+use crate::{
+    ast::{
+        format::{escape, Formatter, PrettyPrint},
+        utils::{
+            io::{PResult, Span},
+            lexem,
+            strings::{eater, string_parser::parse_string, wst, wst_closed},
+        },
+        TryParse,
+    },
+    semantic::{scope::static_types::StaticType, Desugar, EType, Resolve, SemanticError, TypeOf},
+    vm::GenerateCode,
+};
+
+use super::Statement;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub path: String,
+    pub line: usize, // line of the import in its source
+}
+
+impl TryParse for Import {
+    /*
+     * @desc Parse an import of a module served by the module loader of the host
+     *
+     * @grammar
+     * Import := import StaticString ;
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        let (input, _) = wst_closed(lexem::IMPORT)(input)?;
+        let line = input.location_line() as usize;
+        let (input, path) =
+            cut(terminated(eater::ws(parse_string), wst(lexem::SEMI_COLON)))(input)?;
+        Ok((input, Import { path, line }))
+    }
+}
+
+impl Resolve for Import {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+        _context: &Self::Context,
+        _extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        // the modules are loaded before the resolution of the statements
+        if scope_manager
+            .modules
+            .iter()
+            .any(|module| module.path() == Some(self.path.as_str()))
+        {
+            Ok(())
+        } else {
+            Err(SemanticError::UnknownModule(self.path.clone()))
+        }
+    }
+}
+
+impl Desugar<Statement> for Import {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        _scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        Ok(None)
+    }
+}
+
+impl TypeOf for Import {
+    fn type_of(
+        &self,
+        _scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        Ok(EType::Static(StaticType::Unit))
+    }
+}
+
+impl GenerateCode for Import {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        _scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+        _instructions: &mut crate::vm::program::Program<E>,
+        _context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        // the code of the module is generated when it is linked to the thread
+        Ok(())
+    }
+}
+
+impl PrettyPrint for Import {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&format!("import \"{}\";", escape(&self.path, '"', false)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_import() {
+        let res = Import::parse("\n import \"std/math\";".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Import {
+                path: "std/math".to_string(),
+                line: 2,
+            },
+            value
+        );
+
+        let res = Import::parse("import std;".into());
+        assert!(res.is_err(), "{:?}", res);
+    }
+}
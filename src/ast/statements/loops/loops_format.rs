@@ -0,0 +1,70 @@
+use crate::ast::format::{Formatter, PrettyPrint};
+
+use super::{ForInLoop, ForInit, ForLoop, Loop, WhileLoop};
+
+impl PrettyPrint for Loop {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Loop::For(value) => value.pretty(f),
+            Loop::ForIn(value) => value.pretty(f),
+            Loop::While(value) => value.pretty(f),
+            Loop::Loop(block) => {
+                f.write("loop ");
+                block.pretty(f);
+            }
+        }
+    }
+}
+
+impl PrettyPrint for ForInit {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            ForInit::Assignation(value) => value.pretty_without_semicolon(f),
+            ForInit::Declaration(value) => value.pretty_without_semicolon(f),
+        }
+    }
+}
+
+impl PrettyPrint for ForLoop {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("for (");
+        for (index, init) in self.indices.iter().enumerate() {
+            if index > 0 {
+                f.write(", ");
+            }
+            init.pretty(f);
+        }
+        f.write(";");
+        if let Some(condition) = &self.condition {
+            f.write(" ");
+            condition.pretty(f);
+        }
+        f.write(";");
+        for (index, increment) in self.increments.iter().enumerate() {
+            f.write(if index > 0 { ", " } else { " " });
+            increment.pretty_without_semicolon(f);
+        }
+        f.write(") ");
+        self.block.pretty(f);
+    }
+}
+
+impl PrettyPrint for ForInLoop {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("for ");
+        self.item.pretty(f);
+        f.write(" in ");
+        self.iterator.pretty(f);
+        f.write(" ");
+        self.block.pretty(f);
+    }
+}
+
+impl PrettyPrint for WhileLoop {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("while ");
+        self.condition.pretty(f);
+        f.write(" ");
+        self.block.pretty(f);
+    }
+}
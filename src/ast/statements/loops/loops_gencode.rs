@@ -1,12 +1,24 @@
+use crate::ast::statements::declaration::{DeclaredVar, PatternVar};
+use crate::semantic::scope::scope::VariableInfo;
+use crate::semantic::SizeOf;
+use crate::vm::allocator::MemoryAddress;
 use crate::vm::asm::branch::BranchIf;
 
 use crate::vm::asm::{
+    alloc::Access,
     branch::{Goto, Label},
+    data::Data,
+    mem::Mem,
     Asm,
 };
-use crate::vm::{CodeGenerationContext, GenerateCode};
+use crate::vm::core::{
+    alloc::AllocAsm,
+    iter::{IterAsm, Iterable},
+    CoreAsm, OK_SLICE,
+};
+use crate::vm::{CodeGenerationContext, CodeGenerationError, GenerateCode};
 
-use super::{ForInit, ForLoop, Loop, WhileLoop};
+use super::{ForInLoop, ForInit, ForLoop, Loop, WhileLoop};
 
 impl GenerateCode for Loop {
     fn gencode<E: crate::vm::external::Engine>(
@@ -18,6 +30,9 @@ impl GenerateCode for Loop {
     ) -> Result<(), crate::vm::CodeGenerationError> {
         match self {
             Loop::For(value) => value.gencode::<E>(scope_manager, scope_id, instructions, context),
+            Loop::ForIn(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
             Loop::While(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
@@ -130,6 +145,158 @@ impl GenerateCode for ForLoop {
     }
 }
 
+impl GenerateCode for ForInLoop {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        instructions: &mut crate::vm::program::Program<E>,
+        context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        let (Some(iterable), Some(iterator_id), Some(index_id)) =
+            (&self.iterable, self.iterator_id, self.index_id)
+        else {
+            return Err(CodeGenerationError::UnresolvedError);
+        };
+        let item_ids = match &self.item {
+            DeclaredVar::Id { id: Some(id), .. } => vec![*id],
+            DeclaredVar::Pattern(PatternVar::Tuple { ids: Some(ids), .. })
+            | DeclaredVar::Pattern(PatternVar::StructFields { ids: Some(ids), .. }) => ids.clone(),
+            _ => return Err(CodeGenerationError::UnresolvedError),
+        };
+
+        // Alloc the variables
+        for id in [iterator_id, index_id].iter().chain(item_ids.iter()) {
+            if scope_manager.is_var_global(*id) {
+                let _ = scope_manager.alloc_global_var_by_id(*id)?;
+            }
+        }
+        let var_location = |scope_manager: &crate::semantic::scope::scope::ScopeManager,
+                            id: u64|
+         -> Result<(MemoryAddress, usize), CodeGenerationError> {
+            let Some(VariableInfo { address, ctype, .. }) = scope_manager.find_var_by_id(id).ok()
+            else {
+                return Err(CodeGenerationError::UnresolvedError);
+            };
+            let address = (*address)
+                .try_into()
+                .map_err(|_| CodeGenerationError::UnresolvedError)?;
+            Ok((address, ctype.size_of()))
+        };
+        let (iterator_address, iterator_size) = var_location(scope_manager, iterator_id)?;
+        let (index_address, index_size) = var_location(scope_manager, index_id)?;
+
+        let break_label = Label::gen();
+        let continue_label = Label::gen();
+        let end_label = Label::gen();
+        let start_label = Label::gen();
+        // a return out of the loop goes through the release of the items gathered from the map
+        let return_label = match (iterable, context.return_label) {
+            (Iterable::Items { .. }, Some(_)) => Some(Label::gen()),
+            _ => context.return_label,
+        };
+
+        let _ = self
+            .iterator
+            .gencode::<E>(scope_manager, scope_id, instructions, context)?;
+        if let Iterable::Items {
+            key_size,
+            value_size,
+        } = iterable
+        {
+            instructions.push(Asm::Core(CoreAsm::Iter(IterAsm::MapItems {
+                key_size: *key_size,
+                value_size: *value_size,
+            })));
+        }
+        instructions.push(Asm::Mem(Mem::Store {
+            size: iterator_size,
+            address: iterator_address,
+        }));
+        instructions.push(Asm::Data(Data::Serialized {
+            data: 0u64.to_le_bytes().into(),
+        }));
+        instructions.push(Asm::Mem(Mem::Store {
+            size: index_size,
+            address: index_address,
+        }));
+
+        instructions.push_label_by_id(start_label, "start_loop".to_string());
+        instructions.push(Asm::Access(Access::Static {
+            address: iterator_address,
+            size: iterator_size,
+        }));
+        instructions.push(Asm::Access(Access::Static {
+            address: index_address,
+            size: index_size,
+        }));
+        instructions.push(Asm::Core(CoreAsm::Iter(IterAsm::Next(iterable.clone()))));
+        instructions.push(Asm::If(BranchIf {
+            else_label: end_label,
+        }));
+        instructions.push(Asm::Mem(Mem::Store {
+            size: index_size,
+            address: index_address,
+        }));
+        for id in item_ids.iter().rev() {
+            let (address, size) = var_location(scope_manager, *id)?;
+            instructions.push(Asm::Mem(Mem::Store { size, address }));
+        }
+
+        self.block.gencode::<E>(
+            scope_manager,
+            scope_id,
+            instructions,
+            &CodeGenerationContext {
+                return_label,
+                break_label: Some(break_label),
+                continue_label: Some(continue_label),
+            },
+        )?;
+
+        instructions.push_label_by_id(continue_label, "continue_loop".to_string());
+        instructions.push(Asm::Goto(Goto {
+            label: Some(start_label),
+        }));
+        instructions.push_label_by_id(break_label, "break_loop".to_string());
+        instructions.push(Asm::Goto(Goto {
+            label: Some(end_label),
+        }));
+        instructions.push_label_by_id(end_label, "end_loop".to_string());
+
+        // Release the items gathered from the map
+        if let Iterable::Items { .. } = iterable {
+            let free_items = |instructions: &mut crate::vm::program::Program<E>| {
+                instructions.push(Asm::Access(Access::Static {
+                    address: iterator_address,
+                    size: iterator_size,
+                }));
+                instructions.push(Asm::Core(CoreAsm::Alloc(AllocAsm::Free)));
+                instructions.push(Asm::Pop(OK_SLICE.len()));
+            };
+            free_items(instructions);
+
+            if let (Some(return_label), Some(outer_return_label)) =
+                (return_label, context.return_label)
+            {
+                let after_label = Label::gen();
+                instructions.push(Asm::Goto(Goto {
+                    label: Some(after_label),
+                }));
+                // the returned value stays below the items while they are released
+                instructions.push_label_by_id(return_label, "return_loop".to_string());
+                free_items(instructions);
+                instructions.push(Asm::Goto(Goto {
+                    label: Some(outer_return_label),
+                }));
+                instructions.push_label_by_id(after_label, "after_loop".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl GenerateCode for WhileLoop {
     fn gencode<E: crate::vm::external::Engine>(
         &self,
@@ -253,6 +420,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn valid_for_in() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<i64>("res1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 45);
+            let res = test_extract_variable::<i64>("res2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 55);
+            let res = test_extract_variable::<i64>("res3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 16);
+            let res = test_extract_variable::<i64>("res4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 9);
+            let res = test_extract_variable::<i64>("res5", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 6);
+            let res = test_extract_variable::<i64>("res6", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 4);
+            let res = test_extract_variable::<u64>("res7", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 101 * 5 + 102 * 6 + 103 * 7);
+            let res = test_extract_variable::<i64>("res8", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, -3);
+            let res = test_extract_variable::<i64>("res9", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 10);
+            true
+        }
+
+        test_statements(
+            r##"
+        let res1 = 0;
+        for i in 0..10 {
+            res1 = res1 + i;
+        }
+
+        let res2 = 0;
+        let range = 1..=10;
+        for i in range {
+            res2 = res2 + i;
+        }
+
+        let res3 = 0;
+        for i in 0..10 {
+            if i % 2 != 0 {
+                continue;
+            }
+            if i > 6 {
+                break;
+            }
+            res3 = res3 + i + 1;
+        }
+
+        let res4 = 0;
+        for x in vec[2,3,4] {
+            res4 = res4 + x;
+        }
+
+        let res5 = 0;
+        for x in [1,2,3] {
+            res5 = res5 + x;
+        }
+
+        let res6 = 0;
+        for c in string("hé世!") {
+            if c != 'x' {
+                res6 = res6 + 1;
+            }
+        }
+
+        let hmap : Map[u64]u64 = map {
+            101 : 5,
+            102 : 6,
+            103 : 7,
+        };
+        let res7 = 0u64;
+        for (key,value) in hmap {
+            res7 = res7 + key * value;
+        }
+
+        let res8 = 0;
+        for i in -3..3 {
+            res8 = res8 + i;
+        }
+
+        fn sum(n:i64) -> i64 {
+            let res = 0;
+            for i in 0..n {
+                res = res + i;
+            }
+            return res;
+        }
+        let res9 = sum(5);
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
     #[test]
     fn valid_while() {
         let mut engine = crate::vm::external::test::NoopEngine {};
@@ -345,4 +621,63 @@ mod tests {
             assert_fn,
         );
     }
+
+    #[test]
+    fn valid_return_from_for_in_map() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+        let mut ciphel = crate::Ciphel::<
+            crate::vm::external::test::NoopEngine,
+            crate::vm::scheduler::ToCompletion,
+        >::default();
+        let tid = ciphel
+            .runtime
+            .spawn(crate::vm::external::test::DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        fn find(m : Map[u64]u64, target : u64) -> u64 {
+            for (key,value) in m {
+                if value == target {
+                    return key;
+                }
+            }
+            return 0u64;
+        }
+        let hmap : Map[u64]u64 = map {
+            101 : 5,
+            102 : 6,
+            103 : 7,
+        };
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+        let allocated = ciphel.heap.allocated_size();
+
+        ciphel
+            .compile(tid.clone(), "let res = find(hmap, 6u64);", 0)
+            .expect("Compilation should have succeeded");
+        ciphel
+            .run(&mut engine)
+            .expect("Execution should have succeeded");
+        // the items gathered from the map are released by the return
+        assert_eq!(ciphel.heap.allocated_size(), allocated);
+
+        let (
+            crate::vm::runtime::Thread { stack, .. },
+            crate::vm::runtime::ThreadContext { scope_manager, .. },
+        ) = ciphel
+            .runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+        let res = test_extract_variable::<u64>("res", scope_manager, stack, &ciphel.heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 102);
+    }
 }
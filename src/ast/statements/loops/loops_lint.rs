@@ -0,0 +1,43 @@
+use crate::semantic::lint::{LintCheck, Linter};
+
+use super::{ForInLoop, ForInit, ForLoop, Loop, WhileLoop};
+
+impl LintCheck for Loop {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            Loop::For(value) => value.lint(linter),
+            Loop::ForIn(value) => value.lint(linter),
+            Loop::While(value) => value.lint(linter),
+            Loop::Loop(block) => block.lint(linter),
+        }
+    }
+}
+
+impl LintCheck for ForLoop {
+    fn lint(&self, linter: &mut Linter) {
+        linter.in_block(|linter| {
+            for index in self.indices.iter() {
+                match index {
+                    ForInit::Assignation(value) => value.lint(linter),
+                    ForInit::Declaration(value) => value.lint(linter),
+                }
+            }
+            self.block.lint(linter);
+        });
+    }
+}
+
+impl LintCheck for ForInLoop {
+    fn lint(&self, linter: &mut Linter) {
+        linter.in_block(|linter| {
+            self.item.lint(linter);
+            self.block.lint(linter);
+        });
+    }
+}
+
+impl LintCheck for WhileLoop {
+    fn lint(&self, linter: &mut Linter) {
+        self.block.lint(linter);
+    }
+}
@@ -8,17 +8,21 @@ use nom_supreme::ParserExt;
 
 use crate::ast::{
     expressions::Expression,
-    statements::{assignation::Assignation, block::Block, declaration::Declaration},
+    statements::{
+        assignation::Assignation,
+        block::Block,
+        declaration::{Declaration, DeclaredVar, PatternVar},
+    },
     utils::{
         error::squash,
-        io::{PResult, Span},
+        io::{spanned, PResult, SourceSpan, Span},
         lexem,
-        strings::{wst, wst_closed},
+        strings::{parse_id, wst, wst_closed},
     },
     TryParse,
 };
 
-use super::{ForInit, ForLoop, Loop, WhileLoop};
+use super::{ForInLoop, ForInit, ForLoop, Loop, WhileLoop};
 
 impl TryParse for Loop {
     /*
@@ -37,7 +41,7 @@ impl TryParse for Loop {
                     Loop::Loop(Box::new(scope))
                 }),
                 map(ForLoop::parse, |value| Loop::For(value)),
-                //map(ForInLoop::parse, |value| Loop::ForIn(value)),
+                map(ForInLoop::parse, |value| Loop::ForIn(value)),
                 map(WhileLoop::parse, |value| Loop::While(value)),
             )),
             "Expected a loop, while or for loop",
@@ -81,6 +85,42 @@ impl TryParse for ForLoop {
     }
 }
 
+impl TryParse for ForInLoop {
+    /*
+     * @desc Parse for-in loop
+     *
+     * @grammar
+     *  for (PatternVar | ID) in Expr Scope
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            tuple((
+                preceded(
+                    wst_closed(lexem::FOR),
+                    spanned(alt((
+                        map(PatternVar::parse, |value| DeclaredVar::Pattern(value)),
+                        map(parse_id, |name| DeclaredVar::Id {
+                            name,
+                            id: None,
+                            span: SourceSpan::default(),
+                        }),
+                    ))),
+                ),
+                preceded(wst_closed(lexem::IN), cut(Expression::parse)),
+                cut(Block::parse).context("Invalid block in for-loop statement"),
+            )),
+            |(item, iterator, block)| ForInLoop {
+                item,
+                iterator: Box::new(iterator),
+                block: Box::new(block),
+                iterable: None,
+                iterator_id: None,
+                index_id: None,
+            },
+        )(input)
+    }
+}
+
 impl TryParse for WhileLoop {
     /*
      * @desc Parse while loop
@@ -120,6 +160,33 @@ mod tests {
         let value = res.unwrap().1;
     }
 
+    #[test]
+    fn valid_for_in() {
+        let res = Loop::parse(
+            r#"
+        for i in 0..10 {
+            f(i);
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert!(matches!(value, Loop::ForIn(_)));
+
+        let res = Loop::parse(
+            r#"
+        for (key,value) in hmap {
+            f(key,value);
+        }
+        "#
+            .into(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert!(matches!(value, Loop::ForIn(_)));
+    }
+
     #[test]
     fn valid_while() {
         let res = WhileLoop::parse(
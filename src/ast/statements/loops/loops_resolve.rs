@@ -1,11 +1,15 @@
-use super::{ForLoop, Loop, WhileLoop};
+use super::{ForInLoop, ForLoop, Loop, WhileLoop};
 use crate::ast::statements::block::{Block, BlockCommonApi};
 use crate::ast::statements::loops::ForInit;
 use crate::ast::statements::Statement;
 use crate::semantic::scope::scope::ScopeState;
-use crate::semantic::scope::static_types::{PrimitiveType, StaticType};
-use crate::semantic::{Desugar, EType};
+use crate::semantic::scope::static_types::{
+    AddrType, MapType, PrimitiveType, RangeType, SliceType, StaticType, TupleType, VecType,
+};
+use crate::semantic::{Desugar, EType, SizeOf};
 use crate::semantic::{Resolve, SemanticError, TypeOf};
+use crate::vm::core::iter::Iterable;
+use crate::{e_static, p_num};
 
 impl Resolve for Loop {
     type Output = ();
@@ -23,6 +27,7 @@ impl Resolve for Loop {
     {
         match self {
             Loop::For(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            Loop::ForIn(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Loop::While(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Loop::Loop(value) => {
                 let inner_scope = value.init_from_parent(scope_manager, scope_id)?;
@@ -45,6 +50,7 @@ impl Desugar<Statement> for Loop {
     ) -> Result<Option<Statement>, SemanticError> {
         match self {
             Loop::For(for_loop) => for_loop.desugar::<E>(scope_manager, scope_id),
+            Loop::ForIn(for_loop) => for_loop.desugar::<E>(scope_manager, scope_id),
             Loop::While(while_loop) => while_loop.desugar::<E>(scope_manager, scope_id),
             Loop::Loop(block) => {
                 let _: Option<Block> = block.desugar::<E>(scope_manager, scope_id)?;
@@ -100,6 +106,21 @@ impl Desugar<Statement> for ForLoop {
         Ok(None)
     }
 }
+impl Desugar<Statement> for ForInLoop {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        if let Some(output) = self.iterator.desugar::<E>(scope_manager, scope_id)? {
+            *self.iterator = output;
+        }
+        if let Some(output) = self.block.desugar::<E>(scope_manager, scope_id)? {
+            self.block = Box::new(output);
+        }
+        Ok(None)
+    }
+}
 impl Desugar<Statement> for WhileLoop {
     fn desugar<E: crate::vm::external::Engine>(
         &mut self,
@@ -143,10 +164,15 @@ impl Resolve for ForLoop {
                 &Some(EType::Static(StaticType::Primitive(PrimitiveType::Bool))),
                 &mut None,
             )?;
-            if EType::Static(StaticType::Primitive(PrimitiveType::Bool))
-                != condition.type_of(scope_manager, scope_id)?
-            {
-                return Err(SemanticError::IncompatibleTypes);
+            let condition_type = condition.type_of(scope_manager, scope_id)?;
+            if EType::Static(StaticType::Primitive(PrimitiveType::Bool)) != condition_type {
+                return Err(SemanticError::mismatched(
+                    &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                    &condition_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(condition.span()));
             }
         }
 
@@ -165,6 +191,109 @@ impl Resolve for ForLoop {
     }
 }
 
+impl Resolve for ForInLoop {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        let _ = self
+            .iterator
+            .resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+        let iterator_type = self.iterator.type_of(&scope_manager, scope_id)?;
+
+        // The iterated value is kept in a hidden variable, maps being walked through their items
+        let (iterable, item_type, iterator_type) = match iterator_type {
+            EType::Static(StaticType::Range(RangeType { num, inclusive })) => (
+                Iterable::Range { num, inclusive },
+                e_static!(StaticType::Primitive(PrimitiveType::Number(num))),
+                iterator_type,
+            ),
+            EType::Static(StaticType::Vec(VecType(ref item_type))) => (
+                Iterable::Vec {
+                    item_size: item_type.size_of(),
+                },
+                item_type.as_ref().clone(),
+                iterator_type,
+            ),
+            EType::Static(StaticType::Slice(SliceType {
+                size,
+                ref item_type,
+            })) => (
+                Iterable::Slice {
+                    len: size,
+                    item_size: item_type.size_of(),
+                },
+                item_type.as_ref().clone(),
+                iterator_type,
+            ),
+            EType::Static(StaticType::String(_)) => (
+                Iterable::String,
+                e_static!(StaticType::Primitive(PrimitiveType::Char)),
+                iterator_type,
+            ),
+            EType::Static(StaticType::StrSlice(_)) => (
+                Iterable::StrSlice,
+                e_static!(StaticType::Primitive(PrimitiveType::Char)),
+                iterator_type,
+            ),
+            EType::Static(StaticType::Map(MapType {
+                keys_type,
+                values_type,
+            })) => (
+                Iterable::Items {
+                    key_size: keys_type.size_of(),
+                    value_size: values_type.size_of(),
+                },
+                e_static!(StaticType::Tuple(TupleType(vec![
+                    keys_type.as_ref().clone(),
+                    values_type.as_ref().clone()
+                ]))),
+                e_static!(StaticType::Vec(VecType(Box::new(e_static!(
+                    StaticType::Tuple(TupleType(vec![
+                        e_static!(StaticType::Address(AddrType(keys_type))),
+                        e_static!(StaticType::Address(AddrType(values_type)))
+                    ]))
+                ))))),
+            ),
+            _ => return Err(SemanticError::ExpectedIterable),
+        };
+
+        let inner_scope = self.block.init_from_parent(scope_manager, scope_id)?;
+        scope_manager
+            .scope_states
+            .insert(inner_scope, ScopeState::Loop);
+
+        let _ = self.iterator_id.insert(scope_manager.register_var(
+            "$iterator",
+            iterator_type,
+            Some(inner_scope),
+        )?);
+        let _ = self.index_id.insert(scope_manager.register_var(
+            "$index",
+            p_num!(U64),
+            Some(inner_scope),
+        )?);
+        let _ =
+            self.item
+                .resolve::<E>(scope_manager, Some(inner_scope), &Some(item_type), &mut ())?;
+
+        let _ = self
+            .block
+            .resolve::<E>(scope_manager, scope_id, context, extra)?;
+        let _ = self.iterable.insert(iterable);
+        Ok(())
+    }
+}
+
 impl Resolve for WhileLoop {
     type Output = ();
     type Context = Option<EType>;
@@ -251,6 +380,52 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn valid_for_in_loop() {
+        let mut expr_loop = ForInLoop::parse(
+            r##"
+        for i in 0..x {
+            x = x + i;
+        }
+        "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let mut scope_manager = scope::ScopeManager::default();
+        let _ = scope_manager.register_var("x", p_num!(I64), None).unwrap();
+        let res = expr_loop.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    #[test]
+    fn robustness_for_in() {
+        let mut expr_loop = ForInLoop::parse(
+            r##"
+        for i in x {
+            x = x + 1;
+        }
+        "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let mut scope_manager = scope::ScopeManager::default();
+        let _ = scope_manager.register_var("x", p_num!(I64), None).unwrap();
+        let res = expr_loop.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        assert!(matches!(res, Err(SemanticError::ExpectedIterable)));
+    }
+
     #[test]
     fn valid_loop() {
         let mut expr_loop = Loop::parse(
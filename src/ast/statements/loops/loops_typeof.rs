@@ -1,6 +1,6 @@
 use crate::semantic::{EType, Resolve, SemanticError, TypeOf};
 
-use super::{ForLoop, Loop, WhileLoop};
+use super::{ForInLoop, ForLoop, Loop, WhileLoop};
 
 impl TypeOf for Loop {
     fn type_of(
@@ -13,6 +13,7 @@ impl TypeOf for Loop {
     {
         match self {
             Loop::For(value) => value.type_of(&scope_manager, scope_id),
+            Loop::ForIn(value) => value.type_of(&scope_manager, scope_id),
             Loop::While(value) => value.type_of(&scope_manager, scope_id),
             Loop::Loop(value) => value.type_of(&scope_manager, scope_id),
         }
@@ -32,6 +33,19 @@ impl TypeOf for ForLoop {
     }
 }
 
+impl TypeOf for ForInLoop {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        self.block.type_of(&scope_manager, scope_id)
+    }
+}
+
 impl TypeOf for WhileLoop {
     fn type_of(
         &self,
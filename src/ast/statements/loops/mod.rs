@@ -1,8 +1,12 @@
 use crate::ast::expressions::Expression;
 
-use super::block::Block;
+use crate::vm::core::iter::Iterable;
 
+use super::{block::Block, declaration::DeclaredVar};
+
+pub mod loops_format;
 pub mod loops_gencode;
+pub mod loops_lint;
 pub mod loops_parse;
 pub mod loops_resolve;
 pub mod loops_typeof;
@@ -10,6 +14,7 @@ pub mod loops_typeof;
 #[derive(Debug, Clone, PartialEq)]
 pub enum Loop {
     For(ForLoop),
+    ForIn(ForInLoop),
     While(WhileLoop),
     Loop(Box<Block>),
 }
@@ -37,3 +42,13 @@ pub struct WhileLoop {
     condition: Box<Expression>,
     block: Box<Block>,
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForInLoop {
+    item: DeclaredVar,
+    iterator: Box<Expression>,
+    block: Box<Block>,
+    iterable: Option<Iterable>,
+    iterator_id: Option<u64>,
+    index_id: Option<u64>,
+}
@@ -1,8 +1,16 @@
-use nom::{branch::alt, combinator::map, multi::many0, Finish, Parser};
+use nom::{
+    branch::alt,
+    bytes::complete::take,
+    combinator::{map, success},
+    Finish, Parser,
+};
 
 use self::return_stat::Return;
 use crate::{
-    semantic::{Desugar, Metadata},
+    semantic::{
+        lint::{LintCheck, LintId, Linter},
+        Desugar, Metadata,
+    },
     vm::{
         external::{ExternProcessIdentifier, ExternThreadIdentifier},
         GenerateCode,
@@ -12,6 +20,7 @@ use crate::{
 
 use super::{
     expressions::Expression,
+    format::{Formatter, PrettyPrint},
     utils::{
         error::{generate_error_report, squash},
         strings::eater::{self, ws},
@@ -19,22 +28,32 @@ use super::{
     TryParse,
 };
 use crate::{
-    ast::utils::io::{PResult, Span},
+    ast::utils::io::{spanned, HasSpan, PResult, SourceSpan, Span},
     semantic::{scope::static_types::StaticType, EType, Resolve, SemanticError, TypeOf},
 };
 
+pub mod annotation;
 pub mod assignation;
 pub mod block;
 pub mod declaration;
 pub mod definition;
 pub mod flows;
+pub mod import_stat;
 pub mod loops;
 pub mod return_stat;
+pub mod use_stat;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct WithLine<T> {
     pub inner: T,
     pub line: usize,
+    pub span: SourceSpan,
+}
+
+impl<T> HasSpan for WithLine<T> {
+    fn set_span(&mut self, span: SourceSpan) {
+        self.span = span;
+    }
 }
 
 impl<T: Resolve> Resolve for WithLine<T> {
@@ -52,8 +71,12 @@ impl<T: Resolve> Resolve for WithLine<T> {
     where
         Self: Sized,
     {
-        self.inner
-            .resolve::<E>(scope_manager, scope_id, context, extra)
+        let inner = &mut self.inner;
+        scope_manager
+            .at(self.span, |scope_manager| {
+                inner.resolve::<E>(scope_manager, scope_id, context, extra)
+            })
+            .map_err(|err| err.at(self.span))
     }
 }
 
@@ -67,6 +90,12 @@ impl<T: Desugar<()>> Desugar<()> for WithLine<T> {
     }
 }
 
+impl<T: LintCheck> LintCheck for WithLine<T> {
+    fn lint(&self, linter: &mut Linter) {
+        linter.at(self.span, |linter| self.inner.lint(linter));
+    }
+}
+
 impl<T: GenerateCode> GenerateCode for WithLine<T> {
     fn gencode<E: crate::vm::external::Engine>(
         &self,
@@ -75,8 +104,10 @@ impl<T: GenerateCode> GenerateCode for WithLine<T> {
         instructions: &mut crate::vm::program::Program<E>,
         context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
-        self.inner
-            .gencode::<E>(scope_manager, scope_id, instructions, context)
+        let inner = &self.inner;
+        instructions.at(self.span, |instructions| {
+            inner.gencode::<E>(scope_manager, scope_id, instructions, context)
+        })
     }
 }
 
@@ -89,13 +120,34 @@ pub enum Statement {
     Definition(definition::Definition),
     Loops(loops::Loop),
     Return(Return),
+    Annotated(annotation::Annotated),
+    Use(use_stat::Use),
+    Import(import_stat::Import),
 }
 
 pub fn parse_statements<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>>(
     input: Span,
     line_offset: usize,
 ) -> Result<Vec<WithLine<Statement>>, CompilationError<PID, TID>> {
-    let mut parser = ws(many0(|input| {
+    let (statements, errors) = parse_statements_with_recovery(input, line_offset);
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(statements),
+    }
+}
+
+/*
+ * @desc Parse the statements of an input and keep going after an invalid statement
+ * The parsing resumes after the ; or the } ending the invalid statement
+ */
+pub fn parse_statements_with_recovery<
+    PID: ExternProcessIdentifier,
+    TID: ExternThreadIdentifier<PID>,
+>(
+    input: Span,
+    line_offset: usize,
+) -> (Vec<WithLine<Statement>>, Vec<CompilationError<PID, TID>>) {
+    let mut parser = spanned(|input| {
         let (input, statement) = Statement::parse(input)?;
         let line = input.location_line();
         Ok((
@@ -103,27 +155,85 @@ pub fn parse_statements<PID: ExternProcessIdentifier, TID: ExternThreadIdentifie
             WithLine {
                 inner: statement,
                 line: line as usize + line_offset,
+                span: SourceSpan::default(),
             },
         ))
-    }));
-
-    match parser(input).finish() {
-        Ok((remaining, statements)) => {
-            if !remaining.fragment().is_empty() {
-                let error_report = format!(
-                    "Parsing completed, but input remains: {:?}",
-                    remaining.fragment()
-                );
-                Err(CompilationError::ParsingError(error_report))
-            } else {
-                Ok(statements)
+    });
+
+    let mut statements = Vec::default();
+    let mut errors = Vec::default();
+    let mut remaining = input;
+    loop {
+        remaining = match ws(success(()))(remaining) {
+            Ok((rest, _)) => rest,
+            Err(_) => remaining,
+        };
+        if remaining.fragment().is_empty() {
+            break;
+        }
+        match parser(remaining).finish() {
+            Ok((rest, statement)) => {
+                statements.push(statement);
+                remaining = rest;
+            }
+            Err(e) => {
+                let error_report = generate_error_report(&input, &e, line_offset);
+                errors.push(CompilationError::ParsingError(error_report));
+                let skipped = resync_length(remaining.fragment());
+                remaining = match take::<_, _, ()>(skipped)(remaining) {
+                    Ok((rest, _)) => rest,
+                    Err(_) => break,
+                };
             }
         }
-        Err(e) => {
-            let error_report = generate_error_report(&input, &e, line_offset);
-            Err(CompilationError::ParsingError(error_report))
+    }
+    (statements, errors)
+}
+
+/*
+ * @desc Number of characters up to the end of an invalid statement
+ * The statement ends at the first ; outside of a block or at the } closing its block,
+ * the strings and the comments are skipped
+ */
+fn resync_length(input: &str) -> usize {
+    let mut depth = 0;
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                while let Some((_, inner)) = chars.next() {
+                    if inner == '\\' {
+                        chars.next();
+                    } else if inner == c {
+                        break;
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                for (_, inner) in chars.by_ref() {
+                    if inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, inner) in chars.by_ref() {
+                    if previous == '*' && inner == '/' {
+                        break;
+                    }
+                    previous = inner;
+                }
+            }
+            '{' => depth += 1,
+            '}' if depth <= 1 => return index + 1,
+            '}' => depth -= 1,
+            ';' if depth == 0 => return index + 1,
+            _ => {}
         }
     }
+    input.chars().count()
 }
 
 impl TryParse for Statement {
@@ -131,10 +241,13 @@ impl TryParse for Statement {
         eater::ws(squash(
             alt((
                 map(Return::parse, Statement::Return),
+                map(use_stat::Use::parse, Statement::Use),
+                map(import_stat::Import::parse, Statement::Import),
                 map(block::Block::parse, Statement::Scope),
                 map(declaration::Declaration::parse, Statement::Declaration),
                 map(flows::Flow::parse, Statement::Flow),
                 map(definition::Definition::parse, Statement::Definition),
+                map(annotation::Annotated::parse, Statement::Annotated),
                 map(loops::Loop::parse, Statement::Loops),
                 map(assignation::Assignation::parse, Statement::Assignation),
                 map(Expression::parse, |expr| {
@@ -182,6 +295,13 @@ impl Resolve for Statement {
             }
             Statement::Loops(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Statement::Return(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            Statement::Annotated(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, extra)
+            }
+            Statement::Use(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
+            Statement::Import(value) => {
+                value.resolve::<E>(scope_manager, scope_id, context, extra)
+            }
         }
     }
 }
@@ -206,6 +326,9 @@ impl Desugar<Statement> for Statement {
             }
             Statement::Loops(loops) => loops.desugar::<E>(scope_manager, scope_id)?,
             Statement::Return(returns) => returns.desugar::<E>(scope_manager, scope_id)?,
+            Statement::Annotated(annotated) => annotated.desugar::<E>(scope_manager, scope_id)?,
+            Statement::Use(value) => value.desugar::<E>(scope_manager, scope_id)?,
+            Statement::Import(value) => value.desugar::<E>(scope_manager, scope_id)?,
         };
 
         if let Some(output) = output {
@@ -233,6 +356,9 @@ impl TypeOf for Statement {
             Statement::Definition(_value) => Ok(EType::Static(StaticType::Unit)),
             Statement::Loops(value) => value.type_of(&scope_manager, scope_id),
             Statement::Return(value) => value.type_of(&scope_manager, scope_id),
+            Statement::Annotated(value) => value.type_of(&scope_manager, scope_id),
+            Statement::Use(value) => value.type_of(&scope_manager, scope_id),
+            Statement::Import(value) => value.type_of(&scope_manager, scope_id),
         }
     }
 }
@@ -268,6 +394,168 @@ impl GenerateCode for Statement {
             Statement::Return(value) => {
                 value.gencode::<E>(scope_manager, scope_id, instructions, context)
             }
+            Statement::Annotated(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+            Statement::Use(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+            Statement::Import(value) => {
+                value.gencode::<E>(scope_manager, scope_id, instructions, context)
+            }
+        }
+    }
+}
+
+impl Statement {
+    /*
+     * @desc Best known location of a statement, a nested statement has no location of its own
+     */
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Statement::Flow(flows::Flow::Call(flows::CallStat { call })) => call.span(),
+            Statement::Declaration(declaration::Declaration::Declared(var)) => var.span,
+            Statement::Declaration(declaration::Declaration::Assigned { left, .. }) => match left {
+                declaration::DeclaredVar::Id { span, .. } => *span,
+                declaration::DeclaredVar::Typed(var) => var.span,
+                declaration::DeclaredVar::Pattern(_) => SourceSpan::default(),
+            },
+            Statement::Assignation(value) => value.left.span(),
+            Statement::Return(Return::Expr { expr, .. } | Return::Inline { expr, .. }) => {
+                expr.span()
+            }
+            Statement::Annotated(value) => value.statement.span(),
+            _ => SourceSpan::default(),
+        }
+    }
+}
+
+/*
+ * @desc Lint a sequence of statements, a statement following an exit of the block is unreachable
+ */
+pub fn lint_statements(statements: &[Statement], linter: &mut Linter) {
+    let mut exit = None;
+    for statement in statements {
+        if let Some(exit) = exit {
+            linter.report(
+                LintId::UnreachableCode,
+                statement.span(),
+                format!("unreachable statement after {}", exit),
+            );
+            return;
+        }
+        statement.lint(linter);
+        exit = match statement {
+            Statement::Return(Return::Unit | Return::Expr { .. }) => Some("return"),
+            Statement::Return(Return::Break) => Some("break"),
+            Statement::Return(Return::Continue) => Some("continue"),
+            Statement::Flow(flows::Flow::Raise(_)) => Some("raise"),
+            _ => None,
+        };
+    }
+}
+
+impl LintCheck for Statement {
+    fn lint(&self, linter: &mut Linter) {
+        match self {
+            Statement::Scope(value) => value.lint(linter),
+            Statement::Flow(value) => value.lint(linter),
+            Statement::Assignation(value) => value.lint(linter),
+            Statement::Declaration(value) => value.lint(linter),
+            Statement::Definition(value) => value.lint(linter),
+            Statement::Loops(value) => value.lint(linter),
+            Statement::Annotated(value) => value.lint(linter),
+            Statement::Return(_) | Statement::Use(_) | Statement::Import(_) => {}
+        }
+    }
+}
+
+impl PrettyPrint for Statement {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Statement::Scope(value) => value.pretty(f),
+            Statement::Flow(value) => value.pretty(f),
+            Statement::Assignation(value) => value.pretty(f),
+            Statement::Declaration(value) => value.pretty(f),
+            Statement::Definition(value) => value.pretty(f),
+            Statement::Loops(value) => value.pretty(f),
+            Statement::Return(value) => value.pretty(f),
+            Statement::Annotated(value) => value.pretty(f),
+            Statement::Use(value) => value.pretty(f),
+            Statement::Import(value) => value.pretty(f),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        vm::external::test::{DefaultProcessID, DefaultThreadID, NoopEngine},
+        Ciphel, Diagnostic,
+    };
+
+    use super::*;
+
+    #[test]
+    fn valid_parse_statements_with_recovery() {
+        let (statements, errors) =
+            parse_statements_with_recovery::<DefaultProcessID, DefaultThreadID>(
+                r##"
+        let x = 1;
+        let = ;
+        let s = "a;b" + ;
+        let y = 2;
+        fn f( { let z = 4; }
+        let z = 3;
+            "##
+                .into(),
+                0,
+            );
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert_eq!(
+            statements
+                .iter()
+                .map(|statement| statement.span.line)
+                .collect::<Vec<_>>(),
+            vec![2, 5, 7]
+        );
+    }
+
+    #[test]
+    fn robustness_compile_diagnostics() {
+        let mut engine = NoopEngine {};
+        let mut ciphel = Ciphel::<NoopEngine, crate::vm::scheduler::ToCompletion>::default();
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        let res = ciphel.compile(
+            tid.clone(),
+            r##"
+        let a = 1;
+        let b = unknown;
+        let c : u64 = true;
+        let = ;
+            "##,
+            10,
+        );
+        let diagnostics = res.expect_err("Compilation should have failed");
+        assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        let lines = diagnostics
+            .iter()
+            .filter_map(|diagnostic| match &diagnostic.error {
+                CompilationError::SemanticError(line, ..) => Some(*line),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![13, 14]);
+
+        // the statements of the failed compilation have been rolled back
+        let res = ciphel.compile(tid.clone(), "let d = a;", 0);
+        assert!(res.is_err(), "{:?}", res);
+        let res = ciphel.compile(tid.clone(), "let a = 1;", 0);
+        assert!(matches!(res.as_deref(), Ok([])), "{:?}", res);
+    }
+}
@@ -8,6 +8,7 @@ use crate::vm::{CodeGenerationError, GenerateCode};
 use crate::{
     ast::{
         expressions::Expression,
+        format::{Formatter, PrettyPrint},
         utils::{
             io::{PResult, Span},
             lexem,
@@ -146,7 +147,12 @@ impl Resolve for Return {
                 }
                 if let Some(context) = context {
                     if *context != EType::Static(StaticType::Unit) {
-                        return Err(SemanticError::IncompatibleTypes);
+                        return Err(SemanticError::mismatched(
+                            context,
+                            &EType::Static(StaticType::Unit),
+                            scope_manager,
+                            Some(scope_id),
+                        ));
                     }
                 }
                 Ok(())
@@ -174,7 +180,13 @@ impl Resolve for Return {
                 }
                 if let Some(context) = context {
                     if *context != return_type {
-                        return Err(SemanticError::IncompatibleTypes);
+                        return Err(SemanticError::mismatched(
+                            context,
+                            &return_type,
+                            scope_manager,
+                            Some(scope_id),
+                        )
+                        .at(metadata.span));
                     }
                 }
                 metadata.info = Info::Resolved {
@@ -211,7 +223,13 @@ impl Resolve for Return {
 
                 if let Some(context) = context {
                     if *context != return_type {
-                        return Err(SemanticError::IncompatibleTypes);
+                        return Err(SemanticError::mismatched(
+                            context,
+                            &return_type,
+                            scope_manager,
+                            Some(scope_id),
+                        )
+                        .at(metadata.span));
                     }
                 }
                 metadata.info = Info::Resolved {
@@ -316,3 +334,19 @@ impl GenerateCode for Return {
         }
     }
 }
+
+impl PrettyPrint for Return {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Return::Unit => f.write("return;"),
+            Return::Expr { expr, .. } => {
+                f.write("return ");
+                expr.pretty(f);
+                f.write(";");
+            }
+            Return::Inline { expr, .. } => expr.pretty(f),
+            Return::Break => f.write("break;"),
+            Return::Continue => f.write("continue;"),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use nom::{
+    branch::alt,
+    combinator::{cut, map, opt},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, terminated},
+};
+
+use crate::{
+    ast::{
+        format::{Formatter, PrettyPrint},
+        utils::{
+            io::{PResult, Span},
+            lexem,
+            strings::{parse_id, wst, wst_closed, ID},
+        },
+        TryParse,
+    },
+    semantic::{scope::static_types::StaticType, Desugar, EType, Resolve, SemanticError, TypeOf},
+    vm::GenerateCode,
+};
+
+use super::Statement;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseItem {
+    pub name: ID,
+    pub alias: Option<ID>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Use {
+    pub path: Vec<ID>,
+    pub items: Vec<UseItem>,
+}
+
+impl TryParse for UseItem {
+    /*
+     * @desc Parse an imported item
+     *
+     * @grammar
+     * UseItem := ID | ID as ID
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            pair(parse_id, opt(preceded(wst_closed(lexem::AS), parse_id))),
+            |(name, alias)| UseItem { name, alias },
+        )(input)
+    }
+}
+
+impl TryParse for Use {
+    /*
+     * @desc Parse an import of module items
+     *
+     * @grammar
+     * Use := use Path UseItem ; | use Path { UseItems } ;
+     * UseItems := UseItem , UseItems | UseItem
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        preceded(
+            wst_closed(lexem::USE),
+            cut(terminated(
+                alt((
+                    map(
+                        pair(
+                            many1(terminated(parse_id, wst(lexem::SEP))),
+                            delimited(
+                                wst(lexem::BRA_O),
+                                separated_list1(wst(lexem::COMA), UseItem::parse),
+                                preceded(opt(wst(lexem::COMA)), wst(lexem::BRA_C)),
+                            ),
+                        ),
+                        |(path, items)| Use { path, items },
+                    ),
+                    map(
+                        pair(many0(terminated(parse_id, wst(lexem::SEP))), UseItem::parse),
+                        |(path, item)| Use {
+                            path,
+                            items: vec![item],
+                        },
+                    ),
+                )),
+                wst(lexem::SEMI_COLON),
+            )),
+        )(input)
+    }
+}
+
+impl Resolve for Use {
+    type Output = ();
+    type Context = Option<EType>;
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        _context: &Self::Context,
+        _extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        for UseItem { name, alias } in self.items.iter() {
+            let mut target = self.path.clone();
+            target.push(name.clone());
+            scope_manager.register_import(alias.as_ref().unwrap_or(name), target, scope_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl Desugar<Statement> for Use {
+    fn desugar<E: crate::vm::external::Engine>(
+        &mut self,
+        _scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+    ) -> Result<Option<Statement>, SemanticError> {
+        Ok(None)
+    }
+}
+
+impl TypeOf for Use {
+    fn type_of(
+        &self,
+        _scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized + Resolve,
+    {
+        Ok(EType::Static(StaticType::Unit))
+    }
+}
+
+impl GenerateCode for Use {
+    fn gencode<E: crate::vm::external::Engine>(
+        &self,
+        _scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+        _instructions: &mut crate::vm::program::Program<E>,
+        _context: &crate::vm::CodeGenerationContext,
+    ) -> Result<(), crate::vm::CodeGenerationError> {
+        // imports only exist during the resolution of the names
+        Ok(())
+    }
+}
+
+impl PrettyPrint for UseItem {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&self.name);
+        if let Some(alias) = &self.alias {
+            f.write(" as ");
+            f.write(alias);
+        }
+    }
+}
+
+impl PrettyPrint for Use {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("use ");
+        for segment in self.path.iter() {
+            f.write(segment);
+            f.write("::");
+        }
+        match self.items.as_slice() {
+            [item] => item.pretty(f),
+            items => f.list("{", items, "}", false, |f, item| item.pretty(f)),
+        }
+        f.write(";");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_use() {
+        let res = Use::parse("use math::geometry::{area, Point as P};".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Use {
+                path: vec!["math".to_string(), "geometry".to_string()],
+                items: vec![
+                    UseItem {
+                        name: "area".to_string(),
+                        alias: None,
+                    },
+                    UseItem {
+                        name: "Point".to_string(),
+                        alias: Some("P".to_string()),
+                    },
+                ],
+            },
+            value
+        );
+
+        let res = Use::parse("use math::geometry as geo;".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Use {
+                path: vec!["math".to_string()],
+                items: vec![UseItem {
+                    name: "geometry".to_string(),
+                    alias: Some("geo".to_string()),
+                }],
+            },
+            value
+        );
+    }
+
+    #[test]
+    fn robustness_use() {
+        let res = Use::parse("use math::{};".into());
+        assert!(res.is_err(), "{:?}", res);
+        let res = Use::parse("use math::area".into());
+        assert!(res.is_err(), "{:?}", res);
+    }
+}
@@ -1,5 +1,7 @@
 use super::{expressions::Path, utils::strings::ID};
 
+pub mod types_format;
+pub mod types_generics;
 pub mod types_parse;
 pub mod types_resolve;
 pub mod types_typeof;
@@ -12,7 +14,11 @@ pub enum Type {
     Slice(SliceType),
     String(StringType),
     StrSlice(StrSliceType),
-    UserType { path: Path, name: String },
+    UserType {
+        path: Path,
+        name: String,
+        generics: Types,
+    },
     Vec(VecType),
     Function(FunctionType),
     Closure(ClosureType),
@@ -21,6 +27,7 @@ pub enum Type {
     Unit,
     Any,
     Error,
+    TypedError(ErrorType),
     Address(AddrType),
     Map(MapType),
 }
@@ -61,6 +68,9 @@ pub struct StringType();
 #[derive(Debug, Clone, PartialEq)]
 pub struct VecType(pub SubType);
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorType(pub SubType);
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionType {
     pub params: Types,
@@ -0,0 +1,141 @@
+use crate::ast::format::{Formatter, PrettyPrint};
+
+use super::{
+    AddrType, ClosureType, ErrorType, FunctionType, LambdaType, MapType, NumberType, PrimitiveType,
+    SliceType, TupleType, Type, Types, VecType,
+};
+
+/*
+ * @desc Print the parameters and the return type of a function, a closure or a lambda type
+ */
+fn pretty_signature(f: &mut Formatter, params: &Types, ret: &Type) {
+    f.list("(", params, ")", false, |f, param| param.pretty(f));
+    f.write(" -> ");
+    ret.pretty(f);
+}
+
+impl PrettyPrint for Type {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            Type::Primitive(value) => value.pretty(f),
+            Type::Slice(value) => value.pretty(f),
+            Type::String(_) => f.write("String"),
+            Type::StrSlice(_) => f.write("str"),
+            Type::UserType {
+                path,
+                name,
+                generics,
+            } => {
+                path.pretty(f);
+                f.write(name);
+                if !generics.is_empty() {
+                    f.list("<", generics, ">", false, |f, generic| generic.pretty(f));
+                }
+            }
+            Type::Vec(value) => value.pretty(f),
+            Type::Function(value) => value.pretty(f),
+            Type::Closure(value) => value.pretty(f),
+            Type::Lambda(value) => value.pretty(f),
+            Type::Tuple(value) => value.pretty(f),
+            Type::Unit => f.write("Unit"),
+            Type::Any => f.write("Any"),
+            Type::Error => f.write("Error"),
+            Type::TypedError(value) => value.pretty(f),
+            Type::Address(value) => value.pretty(f),
+            Type::Map(value) => value.pretty(f),
+        }
+    }
+}
+
+impl PrettyPrint for PrimitiveType {
+    fn pretty(&self, f: &mut Formatter) {
+        match self {
+            PrimitiveType::Number(value) => value.pretty(f),
+            PrimitiveType::Char => f.write("char"),
+            PrimitiveType::Bool => f.write("bool"),
+        }
+    }
+}
+
+impl PrettyPrint for NumberType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(match self {
+            NumberType::U8 => "u8",
+            NumberType::U16 => "u16",
+            NumberType::U32 => "u32",
+            NumberType::U64 => "u64",
+            NumberType::U128 => "u128",
+            NumberType::I8 => "i8",
+            NumberType::I16 => "i16",
+            NumberType::I32 => "i32",
+            NumberType::I64 => "i64",
+            NumberType::I128 => "i128",
+            NumberType::F64 => "f64",
+        });
+    }
+}
+
+impl PrettyPrint for SliceType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write(&format!("[{}]", self.size));
+        self.item_type.pretty(f);
+    }
+}
+
+impl PrettyPrint for VecType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("Vec[");
+        self.0.pretty(f);
+        f.write("]");
+    }
+}
+
+impl PrettyPrint for ErrorType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("Error[");
+        self.0.pretty(f);
+        f.write("]");
+    }
+}
+
+impl PrettyPrint for FunctionType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("fn");
+        pretty_signature(f, &self.params, &self.ret);
+    }
+}
+
+impl PrettyPrint for ClosureType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("closed");
+        pretty_signature(f, &self.params, &self.ret);
+    }
+}
+
+impl PrettyPrint for LambdaType {
+    fn pretty(&self, f: &mut Formatter) {
+        pretty_signature(f, &self.params, &self.ret);
+    }
+}
+
+impl PrettyPrint for TupleType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.list("(", &self.0, ")", false, |f, item| item.pretty(f));
+    }
+}
+
+impl PrettyPrint for AddrType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("&");
+        self.0.pretty(f);
+    }
+}
+
+impl PrettyPrint for MapType {
+    fn pretty(&self, f: &mut Formatter) {
+        f.write("Map[");
+        self.keys_type.pretty(f);
+        f.write("]");
+        self.values_type.pretty(f);
+    }
+}
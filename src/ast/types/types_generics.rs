@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use super::{
+    AddrType, ClosureType, ErrorType, FunctionType, LambdaType, MapType, SliceType, TupleType,
+    Type, VecType,
+};
+use crate::ast::{expressions::Path, utils::strings::ID};
+use crate::semantic::scope::scope::ScopeManager;
+use crate::semantic::scope::static_types::{self, StaticType};
+use crate::semantic::{EType, SemanticError};
+
+/*
+ * @desc Build the name of the instance of a generic function or user type
+ * for the given type arguments, e.g. Pair<u64,bool>
+ */
+pub fn generic_name(
+    name: &str,
+    args: &[EType],
+    scope_manager: &ScopeManager,
+) -> Result<String, SemanticError> {
+    let mut args_names = Vec::with_capacity(args.len());
+    for arg in args {
+        args_names.push(
+            arg.name(scope_manager, None)
+                .map_err(|_| SemanticError::CantInferType(name.to_string()))?,
+        );
+    }
+    Ok(format!("{0}<{1}>", name, args_names.join(",")))
+}
+
+impl Type {
+    /*
+     * @desc Returns true if the type mentions one of the given type parameters
+     */
+    pub fn is_generic_over(&self, generics: &[ID]) -> bool {
+        match self {
+            Type::UserType {
+                path: Path::Empty,
+                name,
+                generics: args,
+            } if args.is_empty() => generics.contains(name),
+            Type::UserType { generics: args, .. } => {
+                args.iter().any(|arg| arg.is_generic_over(generics))
+            }
+            Type::Slice(SliceType { item_type, .. }) => item_type.is_generic_over(generics),
+            Type::Vec(VecType(item_type)) => item_type.is_generic_over(generics),
+            Type::Address(AddrType(item_type)) => item_type.is_generic_over(generics),
+            Type::TypedError(ErrorType(payload)) => payload.is_generic_over(generics),
+            Type::Map(MapType {
+                keys_type,
+                values_type,
+            }) => keys_type.is_generic_over(generics) || values_type.is_generic_over(generics),
+            Type::Tuple(TupleType(items)) => {
+                items.iter().any(|item| item.is_generic_over(generics))
+            }
+            Type::Function(FunctionType { params, ret })
+            | Type::Closure(ClosureType { params, ret })
+            | Type::Lambda(LambdaType { params, ret }) => {
+                params.iter().any(|param| param.is_generic_over(generics))
+                    || ret.is_generic_over(generics)
+            }
+            _ => false,
+        }
+    }
+
+    /*
+     * @desc Kind of a generic type, such as a vector, to report the concrete types that do not match it
+     */
+    fn kind(&self) -> String {
+        match self {
+            Type::UserType { name, .. } => format!("an instance of {name}"),
+            Type::Slice(_) => "an array".to_string(),
+            Type::Vec(_) => "a vector".to_string(),
+            Type::Address(_) => "an address".to_string(),
+            Type::TypedError(_) => "an error".to_string(),
+            Type::Map(_) => "a map".to_string(),
+            Type::Tuple(TupleType(items)) => format!("a tuple of {} values", items.len()),
+            Type::Function(FunctionType { params, .. }) => {
+                format!("a function of {} parameters", params.len())
+            }
+            Type::Closure(ClosureType { params, .. }) => {
+                format!("a closure of {} parameters", params.len())
+            }
+            Type::Lambda(LambdaType { params, .. }) => {
+                format!("a lambda of {} parameters", params.len())
+            }
+            _ => "a concrete type".to_string(),
+        }
+    }
+
+    /*
+     * @desc Infer the type parameters by matching the type against a concrete type
+     * The already inferred type parameters must be consistent with the new occurences
+     */
+    pub fn infer_generics(
+        &self,
+        actual: &EType,
+        generics: &[ID],
+        bindings: &mut HashMap<ID, EType>,
+        scope_manager: &ScopeManager,
+    ) -> Result<(), SemanticError> {
+        match (self, actual) {
+            (
+                Type::UserType {
+                    path: Path::Empty,
+                    name,
+                    generics: args,
+                },
+                _,
+            ) if args.is_empty() && generics.contains(name) => match bindings.get(name) {
+                Some(bound) if bound != actual => Err(SemanticError::mismatched(
+                    bound,
+                    actual,
+                    scope_manager,
+                    None,
+                )),
+                Some(_) => Ok(()),
+                None => {
+                    bindings.insert(name.clone(), actual.clone());
+                    Ok(())
+                }
+            },
+            (
+                Type::UserType {
+                    name,
+                    generics: args,
+                    ..
+                },
+                EType::User { id, .. },
+            ) if !args.is_empty() => {
+                let Some(instance) = scope_manager.find_generic_type_instance(*id) else {
+                    return Err(SemanticError::mismatched_kind(
+                        &self.kind(),
+                        actual,
+                        scope_manager,
+                        None,
+                    ));
+                };
+                if instance.template != *name || instance.args.len() != args.len() {
+                    return Err(SemanticError::mismatched_kind(
+                        &self.kind(),
+                        actual,
+                        scope_manager,
+                        None,
+                    ));
+                }
+                for (arg, actual) in args.iter().zip(instance.args.iter()) {
+                    arg.infer_generics(actual, generics, bindings, scope_manager)?;
+                }
+                Ok(())
+            }
+            (
+                Type::Slice(SliceType { item_type, .. }),
+                EType::Static(StaticType::Slice(static_types::SliceType {
+                    item_type: actual, ..
+                })),
+            )
+            | (
+                Type::Vec(VecType(item_type)),
+                EType::Static(StaticType::Vec(static_types::VecType(actual))),
+            )
+            | (
+                Type::Address(AddrType(item_type)),
+                EType::Static(StaticType::Address(static_types::AddrType(actual))),
+            )
+            | (
+                Type::TypedError(ErrorType(item_type)),
+                EType::Static(StaticType::TypedError(static_types::ErrorType(actual))),
+            ) => item_type.infer_generics(actual, generics, bindings, scope_manager),
+            (
+                Type::Map(MapType {
+                    keys_type,
+                    values_type,
+                }),
+                EType::Static(StaticType::Map(static_types::MapType {
+                    keys_type: actual_keys,
+                    values_type: actual_values,
+                })),
+            ) => {
+                keys_type.infer_generics(actual_keys, generics, bindings, scope_manager)?;
+                values_type.infer_generics(actual_values, generics, bindings, scope_manager)
+            }
+            (
+                Type::Tuple(TupleType(items)),
+                EType::Static(StaticType::Tuple(static_types::TupleType(actual_items))),
+            ) => {
+                if items.len() != actual_items.len() {
+                    return Err(SemanticError::mismatched_kind(
+                        &self.kind(),
+                        actual,
+                        scope_manager,
+                        None,
+                    ));
+                }
+                for (item, actual) in items.iter().zip(actual_items.iter()) {
+                    item.infer_generics(actual, generics, bindings, scope_manager)?;
+                }
+                Ok(())
+            }
+            (
+                Type::Function(FunctionType { params, ret }),
+                EType::Static(StaticType::Function(static_types::FunctionType {
+                    params: actual_params,
+                    ret: actual_ret,
+                })),
+            )
+            | (
+                Type::Closure(ClosureType { params, ret }),
+                EType::Static(StaticType::Closure(static_types::ClosureType {
+                    params: actual_params,
+                    ret: actual_ret,
+                    ..
+                })),
+            )
+            | (
+                Type::Lambda(LambdaType { params, ret }),
+                EType::Static(StaticType::Lambda(static_types::LambdaType {
+                    params: actual_params,
+                    ret: actual_ret,
+                })),
+            ) => {
+                if params.len() != actual_params.len() {
+                    return Err(SemanticError::mismatched_kind(
+                        &self.kind(),
+                        actual,
+                        scope_manager,
+                        None,
+                    ));
+                }
+                for (param, actual) in params.iter().zip(actual_params.iter()) {
+                    param.infer_generics(actual, generics, bindings, scope_manager)?;
+                }
+                ret.infer_generics(actual_ret, generics, bindings, scope_manager)
+            }
+            _ if self.is_generic_over(generics) => Err(SemanticError::mismatched_kind(
+                &self.kind(),
+                actual,
+                scope_manager,
+                None,
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ast::TryParse, e_static, p_num};
+
+    #[test]
+    fn valid_infer_generics() {
+        let scope_manager = ScopeManager::default();
+        let generics = vec!["K".to_string(), "V".to_string()];
+        let signature = Type::parse("Map[K]Vec[V]".into()).unwrap().1;
+        assert!(signature.is_generic_over(&generics));
+
+        let mut bindings = HashMap::default();
+        let res = signature.infer_generics(
+            &e_static!(StaticType::Map(static_types::MapType {
+                keys_type: Box::new(p_num!(U64)),
+                values_type: Box::new(e_static!(StaticType::Vec(static_types::VecType(Box::new(
+                    e_static!(StaticType::Primitive(static_types::PrimitiveType::Bool))
+                ))))),
+            })),
+            &generics,
+            &mut bindings,
+            &scope_manager,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(bindings.get("K"), Some(&p_num!(U64)));
+        assert_eq!(
+            bindings.get("V"),
+            Some(&e_static!(StaticType::Primitive(
+                static_types::PrimitiveType::Bool
+            )))
+        );
+    }
+
+    #[test]
+    fn robustness_infer_generics() {
+        let scope_manager = ScopeManager::default();
+        let generics = vec!["T".to_string()];
+        let signature = Type::parse("(T,T)".into()).unwrap().1;
+
+        let mut bindings = HashMap::default();
+        let res = signature.infer_generics(
+            &e_static!(StaticType::Tuple(static_types::TupleType(vec![
+                p_num!(U64),
+                p_num!(I64)
+            ]))),
+            &generics,
+            &mut bindings,
+            &scope_manager,
+        );
+        assert!(res.is_err());
+    }
+}
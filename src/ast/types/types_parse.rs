@@ -1,6 +1,6 @@
 use nom::{
     branch::alt,
-    combinator::{map, value},
+    combinator::{map, opt, value},
     multi::separated_list1,
     sequence::{delimited, pair, preceded, tuple},
 };
@@ -19,8 +19,8 @@ use crate::ast::{
 use crate::ast::TryParse;
 
 use super::{
-    AddrType, ClosureType, FunctionType, LambdaType, MapType, NumberType, PrimitiveType, SliceType,
-    StrSliceType, StringType, TupleType, Type, Types, VecType,
+    AddrType, ClosureType, ErrorType, FunctionType, LambdaType, MapType, NumberType, PrimitiveType,
+    SliceType, StrSliceType, StringType, TupleType, Type, Types, VecType,
 };
 
 impl TryParse for Type {
@@ -28,7 +28,7 @@ impl TryParse for Type {
      * @desc Parse Type
      *
      * @grammar
-     * Type :=  Primitive | ID | Vec |  Fn  | Slice | Tuple | Unit | Address | Map | Gen
+     * Type :=  Primitive | ID | ID<Types> | Vec |  Fn  | Slice | Tuple | Unit | Address | Map | Gen
      */
     fn parse(input: Span) -> PResult<Self> {
         squash(
@@ -39,6 +39,7 @@ impl TryParse for Type {
                 map(StringType::parse, |value| Type::String(value)),
                 value(Type::Unit, wst_closed(lexem::UUNIT)),
                 value(Type::Any, wst_closed(lexem::ANY)),
+                map(ErrorType::parse, |value| Type::TypedError(value)),
                 value(Type::Error, wst_closed(lexem::ERR)),
                 map(VecType::parse, |value| Type::Vec(value)),
                 map(FunctionType::parse, |value| Type::Function(value)),
@@ -47,9 +48,21 @@ impl TryParse for Type {
                 map(TupleType::parse, |value| Type::Tuple(value)),
                 map(AddrType::parse, |value| Type::Address(value)),
                 map(MapType::parse, |value| Type::Map(value)),
-                map(CompletePath::parse, |CompletePath { path, name }| {
-                    Type::UserType { path, name }
-                }),
+                map(
+                    pair(
+                        CompletePath::parse,
+                        opt(delimited(
+                            wst(lexem::LESSER),
+                            Types::parse,
+                            wst(lexem::GREATER),
+                        )),
+                    ),
+                    |(CompletePath { path, name }, generics)| Type::UserType {
+                        path,
+                        name,
+                        generics: generics.unwrap_or_default(),
+                    },
+                ),
             )),
             "Expected a valid type",
         )(input)
@@ -160,6 +173,24 @@ impl TryParse for VecType {
     }
 }
 
+impl TryParse for ErrorType {
+    /*
+     * @desc Parse Error Type carrying a payload
+     *
+     * @grammar
+     * Error := Error[Type]
+     */
+    fn parse(input: Span) -> PResult<Self> {
+        map(
+            preceded(
+                wst_closed(lexem::ERR),
+                delimited(wst(lexem::SQ_BRA_O), Type::parse, wst(lexem::SQ_BRA_C)),
+            ),
+            |value| ErrorType(Box::new(value)),
+        )(input)
+    }
+}
+
 impl TryParse for FunctionType {
     /*
      * @desc Parse Closure Type Definition
@@ -322,6 +353,43 @@ mod tests {
         assert_eq!(PrimitiveType::Number(NumberType::F64), value);
     }
 
+    #[test]
+    fn valid_user_type() {
+        let res = Type::parse("Point".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Type::UserType {
+                path: crate::ast::expressions::Path::Empty,
+                name: "Point".to_string(),
+                generics: Vec::default(),
+            },
+            value
+        );
+
+        let res = Type::parse("Pair<u64,Pair<bool,char>>".into());
+        assert!(res.is_ok(), "{:?}", res);
+        let value = res.unwrap().1;
+        assert_eq!(
+            Type::UserType {
+                path: crate::ast::expressions::Path::Empty,
+                name: "Pair".to_string(),
+                generics: vec![
+                    Type::Primitive(PrimitiveType::Number(NumberType::U64)),
+                    Type::UserType {
+                        path: crate::ast::expressions::Path::Empty,
+                        name: "Pair".to_string(),
+                        generics: vec![
+                            Type::Primitive(PrimitiveType::Bool),
+                            Type::Primitive(PrimitiveType::Char),
+                        ],
+                    }
+                ],
+            },
+            value
+        );
+    }
+
     #[test]
     fn valid_string() {
         let res = StringType::parse("String".into());
@@ -1,8 +1,9 @@
 use super::{
-    AddrType, ClosureType, FunctionType, LambdaType, MapType, PrimitiveType, SliceType,
+    AddrType, ClosureType, ErrorType, FunctionType, LambdaType, MapType, PrimitiveType, SliceType,
     StrSliceType, StringType, TupleType, Type, Types, VecType,
 };
-use crate::semantic::{Resolve, SemanticError};
+use crate::ast::statements::definition::TypeDef;
+use crate::semantic::{Resolve, SemanticError, TypeOf};
 
 impl Resolve for Type {
     type Output = ();
@@ -23,8 +24,29 @@ impl Resolve for Type {
             Type::Primitive(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Type::Slice(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Type::StrSlice(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
-            Type::UserType { ref path, ref name } => {
-                let _ = scope_manager.find_type_by_name(
+            Type::UserType {
+                path,
+                name,
+                generics,
+            } if !generics.is_empty() => {
+                if let crate::ast::expressions::Path::Segment(_) = path {
+                    return Err(SemanticError::UnknownType(name.to_string()));
+                }
+                let mut args = Vec::with_capacity(generics.len());
+                for arg in generics.iter_mut() {
+                    let _ = arg.resolve::<E>(scope_manager, scope_id, context, extra)?;
+                    args.push(arg.type_of(&scope_manager, scope_id)?);
+                }
+                let _ = TypeDef::instantiate::<E>(scope_manager, name, args)?;
+                Ok(())
+            }
+            Type::UserType {
+                path: crate::ast::expressions::Path::Empty,
+                name,
+                ..
+            } if scope_manager.find_type_param(name, scope_id).is_some() => Ok(()),
+            Type::UserType { path, name, .. } => {
+                let user_type = scope_manager.find_type_by_name(
                     match path {
                         crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
                         crate::ast::expressions::Path::Empty => None,
@@ -32,6 +54,7 @@ impl Resolve for Type {
                     name,
                     scope_id,
                 )?;
+                scope_manager.signal_type_use(user_type.id);
                 Ok(())
             }
             Type::Vec(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
@@ -45,6 +68,7 @@ impl Resolve for Type {
             Type::Map(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Type::String(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
             Type::Error => Ok(()),
+            Type::TypedError(value) => value.resolve::<E>(scope_manager, scope_id, context, extra),
         }
     }
 }
@@ -143,6 +167,25 @@ impl Resolve for VecType {
     }
 }
 
+impl Resolve for ErrorType {
+    type Output = ();
+    type Context = ();
+
+    type Extra = ();
+    fn resolve<E: crate::vm::external::Engine>(
+        &mut self,
+        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        context: &Self::Context,
+        extra: &mut Self::Extra,
+    ) -> Result<Self::Output, SemanticError>
+    where
+        Self: Sized,
+    {
+        self.0.resolve::<E>(scope_manager, scope_id, context, extra)
+    }
+}
+
 impl Resolve for FunctionType {
     type Output = ();
     type Context = ();
@@ -1,7 +1,8 @@
 use super::{
-    AddrType, ClosureType, FunctionType, LambdaType, MapType, PrimitiveType, SliceType,
-    StrSliceType, StringType, TupleType, Type, VecType,
+    AddrType, ClosureType, ErrorType, FunctionType, LambdaType, MapType, PrimitiveType,
+    SliceType, StrSliceType, StringType, TupleType, Type, VecType,
 };
+use super::types_generics::generic_name;
 use crate::e_static;
 use crate::semantic::scope::static_types::{self, StaticType};
 
@@ -23,13 +24,32 @@ impl TypeOf for Type {
             Type::Primitive(value) => value.type_of(&scope_manager, scope_id),
             Type::Slice(value) => value.type_of(&scope_manager, scope_id),
             Type::StrSlice(value) => value.type_of(&scope_manager, scope_id),
-            Type::UserType { path, name } => {
+            Type::UserType {
+                path,
+                name,
+                generics,
+            } => {
+                if *path == crate::ast::expressions::Path::Empty && generics.is_empty() {
+                    // type parameter of a generic instance
+                    if let Some(ctype) = scope_manager.find_type_param(name, scope_id) {
+                        return Ok(ctype);
+                    }
+                }
+                let name = if generics.is_empty() {
+                    name.clone()
+                } else {
+                    let mut args = Vec::with_capacity(generics.len());
+                    for arg in generics {
+                        args.push(arg.type_of(&scope_manager, scope_id)?);
+                    }
+                    generic_name(name, &args, scope_manager)?
+                };
                 let user_type = scope_manager.find_type_by_name(
                     match path {
                         crate::ast::expressions::Path::Segment(vec) => Some(vec.as_slice()),
                         crate::ast::expressions::Path::Empty => None,
                     },
-                    name,
+                    &name,
                     scope_id,
                 )?;
 
@@ -54,6 +74,7 @@ impl TypeOf for Type {
             Type::Map(value) => value.type_of(&scope_manager, scope_id),
             Type::String(value) => value.type_of(&scope_manager, scope_id),
             Type::Error => Ok(e_static!(StaticType::Error)),
+            Type::TypedError(value) => value.type_of(&scope_manager, scope_id),
             Type::Function(value) => value.type_of(&scope_manager, scope_id),
             Type::Lambda(value) => value.type_of(&scope_manager, scope_id),
         }
@@ -175,6 +196,21 @@ impl TypeOf for VecType {
 }
 impl CompatibleWith for static_types::VecType {}
 
+impl TypeOf for ErrorType {
+    fn type_of(
+        &self,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<EType, SemanticError>
+    where
+        Self: Sized,
+    {
+        Ok(EType::Static(static_types::StaticType::TypedError(
+            static_types::ErrorType(Box::new(self.0.type_of(scope_manager, scope_id)?)),
+        )))
+    }
+}
+
 impl TypeOf for FunctionType {
     fn type_of(
         &self,
@@ -1,6 +1,8 @@
 use nom_supreme::error::{BaseErrorKind, ErrorTree, Expectation, GenericErrorTree, StackContext};
 
-use super::io::{PResult, Span};
+use crate::semantic::SemanticError;
+
+use super::io::{PResult, SourceSpan, Span};
 
 pub fn squash<'input, O, F>(
     mut parser: F,
@@ -129,3 +131,132 @@ pub fn get_error_snippet(input: &Span, location: &Span) -> String {
     let snippet = &input[start..end];
     format!("{:?}", snippet)
 }
+
+/*
+ * @desc Report of a semantic error with the snippets of the source it refers to
+ * The report follows the layout of rustc : the message, the location, the source line
+ * with carets under the faulty code and the related locations as notes
+ */
+pub fn generate_semantic_error_report(
+    input: &str,
+    error: &SemanticError,
+    line_offset: usize,
+) -> String {
+    let mut report = format!("error: {}\n", error);
+    if let SemanticError::Spanned { span, notes, .. } = error {
+        add_span_snippet(input, span, &mut report, line_offset);
+        for (span, note) in notes {
+            report.push_str(&format!("note: {}\n", note));
+            add_span_snippet(input, span, &mut report, line_offset);
+        }
+    }
+    report
+}
+
+pub fn add_span_snippet(input: &str, span: &SourceSpan, report: &mut String, line_offset: usize) {
+    let Some(source_line) = input.lines().nth(span.line.saturating_sub(1)) else {
+        return;
+    };
+    let line = (span.line + line_offset).to_string();
+    let gutter = " ".repeat(line.len());
+    // a span over several lines is underlined until the end of its first line
+    let start = source_line
+        .char_indices()
+        .nth(span.column.saturating_sub(1))
+        .map(|(index, _)| index)
+        .unwrap_or(source_line.len());
+    let end = (start + span.length).min(source_line.len());
+    let width = source_line
+        .get(start..end)
+        .map(|text| text.chars().count())
+        .unwrap_or_default()
+        .max(1);
+
+    report.push_str(&format!(
+        "{}--> line {}, column {}\n",
+        gutter, line, span.column
+    ));
+    report.push_str(&format!("{} |\n", gutter));
+    report.push_str(&format!("{} | {}\n", line, source_line));
+    report.push_str(&format!(
+        "{} | {}{}\n",
+        gutter,
+        " ".repeat(span.column.saturating_sub(1)),
+        "^".repeat(width)
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::statements::parse_statements,
+        semantic::{scope::scope::ScopeManager, Resolve},
+        vm::external::test::{DefaultProcessID, DefaultThreadID, NoopEngine},
+    };
+
+    use super::*;
+
+    fn resolve_error(input: &str) -> SemanticError {
+        let mut statements = parse_statements::<DefaultProcessID, DefaultThreadID>(input.into(), 0)
+            .expect("Parsing should have succeeded");
+        let mut scope_manager = ScopeManager::default();
+        for statement in statements.iter_mut() {
+            if let Err(err) =
+                statement.resolve::<NoopEngine>(&mut scope_manager, None, &None, &mut ())
+            {
+                return err;
+            }
+        }
+        panic!("Resolution should have failed");
+    }
+
+    #[test]
+    fn valid_semantic_error_report() {
+        let input = "fn f(x:u64) -> u64 {\n    return x;\n}\nlet b = true;\nlet y = f(b);";
+        let error = resolve_error(input);
+        assert_eq!(
+            error.span(),
+            Some(SourceSpan {
+                offset: 61,
+                length: 1,
+                line: 5,
+                column: 11,
+            })
+        );
+        assert!(matches!(
+            error.clone().into_inner(),
+            SemanticError::InvalidArgument { position: 1, .. }
+        ));
+
+        let report = generate_semantic_error_report(input, &error, 10);
+        assert_eq!(
+            report,
+            "error: invalid argument 1 : mismatched types : expected u64, found bool\n  \
+             --> line 15, column 11\n   \
+             |\n\
+             15 | let y = f(b);\n   \
+             |           ^\n\
+             note: in this call\n  \
+             --> line 15, column 9\n   \
+             |\n\
+             15 | let y = f(b);\n   \
+             |         ^^^^\n"
+        );
+    }
+
+    #[test]
+    fn valid_semantic_error_report_field() {
+        let input = "struct Point {\n    x : u64,\n}\nlet c = 'a';\nlet p = Point { x : c };";
+        let error = resolve_error(input);
+        assert!(matches!(
+            error.clone().into_inner(),
+            SemanticError::InvalidField { field, .. } if field == "x"
+        ));
+        let report = generate_semantic_error_report(input, &error, 0);
+        assert!(
+            report.ends_with("5 | let p = Point { x : c };\n  |                     ^\n"),
+            "{}",
+            report
+        );
+    }
+}
@@ -1,5 +1,109 @@
+use nom::combinator::success;
 use nom_supreme::error::ErrorTree;
 
+use super::strings::eater;
+
 pub type Span<'input> = nom_locate::LocatedSpan<&'input str>;
 pub type PResult<'input, Output> = nom::IResult<Span<'input>, Output, ErrorTree<Span<'input>>>;
 pub type PError<'input> = ErrorTree<Span<'input>>;
+
+/*
+ * @desc Location of a node in its source, the line and the column start at 1
+ * A default span (length 0 and line 0) is an unknown location
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub offset: usize,
+    pub length: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    /*
+     * @desc Span of the input consumed by a parser, trailing whitespaces and comments excluded
+     */
+    pub fn between(start: &Span, remainder: &Span) -> Self {
+        let consumed = remainder.location_offset() - start.location_offset();
+        let text = start.fragment()[..consumed].trim_end();
+        let mut length = text.len();
+        let may_end_with_comment = text.ends_with("*/")
+            || text
+                .rsplit('\n')
+                .next()
+                .map_or(false, |line| line.contains("//"));
+        if may_end_with_comment {
+            for (span, _) in eater::comments(Span::new(text)).into_iter().rev() {
+                if span.offset + span.length != length {
+                    break;
+                }
+                length = text[..span.offset].trim_end().len();
+            }
+        }
+        Self {
+            offset: start.location_offset(),
+            length,
+            line: start.location_line() as usize,
+            column: start.get_utf8_column(),
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.line != 0
+    }
+}
+
+/*
+ * @desc Locations of a sequence of nodes, such as the statements of a block
+ * They do not take part in the equality of the node holding them
+ */
+#[derive(Debug, Clone, Default)]
+pub struct SourceSpans(pub Vec<SourceSpan>);
+
+impl SourceSpans {
+    pub fn get(&self, index: usize) -> SourceSpan {
+        self.0.get(index).copied().unwrap_or_default()
+    }
+}
+
+impl PartialEq for SourceSpans {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+pub trait HasSpan {
+    fn set_span(&mut self, span: SourceSpan);
+}
+
+/*
+ * @desc Run a parser and return the location of its output along it, leading whitespaces excluded
+ */
+pub fn located<'input, O, F>(
+    mut parser: F,
+) -> impl FnMut(Span<'input>) -> PResult<'input, (SourceSpan, O)>
+where
+    F: FnMut(Span<'input>) -> PResult<'input, O>,
+{
+    move |input: Span<'input>| {
+        let (start, _) = eater::ws(success(()))(input)?;
+        let (remainder, output) = parser(start)?;
+        Ok((remainder, (SourceSpan::between(&start, &remainder), output)))
+    }
+}
+
+/*
+ * @desc Run a parser and record the location of its output, leading whitespaces excluded
+ */
+pub fn spanned<'input, O, F>(parser: F) -> impl FnMut(Span<'input>) -> PResult<'input, O>
+where
+    O: HasSpan,
+    F: FnMut(Span<'input>) -> PResult<'input, O>,
+{
+    let mut parser = located(parser);
+    move |input: Span<'input>| {
+        let (remainder, (span, mut output)) = parser(input)?;
+        output.set_span(span);
+        Ok((remainder, output))
+    }
+}
@@ -1,4 +1,7 @@
 pub const MODULE: &str = "module";
+pub const USE: &str = "use";
+pub const IMPORT: &str = "import";
+pub const PUB: &str = "pub";
 pub const ENUM: &str = "enum";
 pub const STRUCT: &str = "struct";
 pub const UNION: &str = "union";
@@ -7,12 +10,14 @@ pub const ARROW: &str = "->";
 pub const BIGARROW: &str = "=>";
 pub const ELSE: &str = "else";
 pub const TRY: &str = "try";
+pub const RAISE: &str = "raise";
 pub const IF: &str = "if";
 pub const THEN: &str = "then";
 pub const MATCH: &str = "match";
 pub const CASE: &str = "case";
 pub const RETURN: &str = "return";
 pub const LET: &str = "let";
+pub const CONST: &str = "const";
 pub const WHILE: &str = "while";
 pub const FOR: &str = "for";
 pub const LOOP: &str = "loop";
@@ -21,6 +26,14 @@ pub const CONTINUE: &str = "continue";
 pub const MOVE: &str = "move";
 pub const REC: &str = "rec";
 pub const CLOSED: &str = "closed";
+pub const EVENT: &str = "event";
+pub const ONCE: &str = "once";
+pub const REPEAT: &str = "repeat";
+pub const PER_TID: &str = "per_tid";
+pub const PER_PID: &str = "per_pid";
+pub const IMPL: &str = "impl";
+pub const SELF: &str = "self";
+pub const INTERFACE: &str = "interface";
 
 // TYPE
 pub const U8: &str = "u8";
@@ -43,6 +56,7 @@ pub const UNIT: &str = "unit";
 pub const ANY: &str = "Any";
 pub const UUNIT: &str = "Unit";
 pub const ERR: &str = "Error";
+pub const RUNTIME_ERROR: &str = "RuntimeError";
 pub const UVEC: &str = "Vec";
 pub const UMAP: &str = "Map";
 pub const FN: &str = "fn";
@@ -70,6 +84,7 @@ pub const COLON: &str = ":";
 pub const ADDR: &str = "&";
 pub const SEP: &str = "::";
 pub const RANGE_SEP: &str = "..";
+pub const RANGE_INCL_SEP: &str = "..=";
 pub const ACCESS: &str = "*";
 pub const BAR: &str = "|";
 
@@ -96,3 +111,4 @@ pub const GE: &str = ">";
 pub const EGE: &str = ">=";
 pub const EQ: &str = "==";
 pub const NEQ: &str = "!=";
+pub const PROPAGATE: &str = "?";
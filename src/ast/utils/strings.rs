@@ -24,16 +24,17 @@ pub mod eater {
     use nom::{
         branch::alt,
         bytes::complete::{is_not, take_until},
-        character::complete::{multispace0, one_of},
-        combinator::value,
+        character::complete::{anychar, multispace0, one_of},
+        combinator::{opt, recognize, value},
         multi::many0,
         sequence::{delimited, pair, terminated, tuple},
         Parser,
     };
     use nom_supreme::tag::complete::tag;
 
+    use super::string_parser::{parse_char, parse_string};
     use crate::ast::utils::{
-        io::{PError, PResult, Span},
+        io::{PError, PResult, SourceSpan, Span},
         lexem,
     };
     #[inline]
@@ -75,6 +76,46 @@ pub mod eater {
         )(input)
     }
 
+    /*
+     * @desc Comments of a source along their location, kept aside as trivia for the formatter
+     * The strings and the characters are skipped as they may contain comment delimiters
+     */
+    pub fn comments(input: Span) -> Vec<(SourceSpan, String)> {
+        let mut comment = recognize::<_, _, PError, _>(alt((
+            recognize(tuple((
+                tag(lexem::ML_OP_COMMENT),
+                take_until(lexem::ML_CL_COMMENT),
+                tag(lexem::ML_CL_COMMENT),
+            ))),
+            recognize(pair(tag(lexem::SL_COMMENT), opt(is_not("\n\r\0")))),
+        )));
+        let mut comments = Vec::default();
+        let mut remaining = input;
+        while !remaining.fragment().is_empty() {
+            if let Ok((rest, text)) = comment(remaining) {
+                let text = text.fragment().trim_end();
+                let span = SourceSpan {
+                    offset: remaining.location_offset(),
+                    length: text.len(),
+                    line: remaining.location_line() as usize,
+                    column: remaining.get_utf8_column(),
+                };
+                comments.push((span, text.to_string()));
+                remaining = rest;
+            } else if let Ok((rest, _)) = parse_string(remaining) {
+                remaining = rest;
+            } else if let Ok((rest, _)) = parse_char(remaining) {
+                remaining = rest;
+            } else {
+                match anychar::<_, PError>(remaining) {
+                    Ok((rest, _)) => remaining = rest,
+                    Err(_) => break,
+                }
+            }
+        }
+        comments
+    }
+
     #[inline]
     pub fn ws<'input, F, O>(inner: F) -> impl FnMut(Span<'input>) -> PResult<O>
     where
@@ -105,12 +146,16 @@ pub fn parse_id(input: Span) -> PResult<ID> {
         |_id| {
             let id = _id.to_string();
             let is_in_lexem = match id.as_str() {
+                lexem::USE => true,
+                lexem::IMPORT => true,
+                lexem::PUB => true,
                 lexem::ENUM => true,
                 lexem::STRUCT => true,
                 lexem::UNION => true,
                 lexem::AS => true,
                 lexem::ELSE => true,
                 lexem::TRY => true,
+                lexem::RAISE => true,
                 // Core::utils::lexem::ERROR => true,
                 // Core::utils::lexem::OK => true,
                 lexem::IF => true,
@@ -119,14 +164,19 @@ pub fn parse_id(input: Span) -> PResult<ID> {
                 lexem::CASE => true,
                 lexem::RETURN => true,
                 lexem::LET => true,
+                lexem::CONST => true,
                 lexem::WHILE => true,
                 lexem::FOR => true,
+                lexem::IN => true,
                 lexem::LOOP => true,
                 lexem::BREAK => true,
                 lexem::CONTINUE => true,
                 lexem::MOVE => true,
                 lexem::REC => true,
                 lexem::CLOSED => true,
+                lexem::EVENT => true,
+                lexem::IMPL => true,
+                lexem::INTERFACE => true,
                 lexem::U8 => true,
                 lexem::U16 => true,
                 lexem::U32 => true,
@@ -0,0 +1,358 @@
+use ciphel::{
+    ast::utils::{io::SourceSpan, lexem},
+    semantic::{
+        scope::scope::ScopeManager,
+        symbols::{resolve_module, resolve_statements, Symbol},
+        NamedType,
+    },
+    vm::{
+        core::lexem as core,
+        external::test::{DefaultProcessID, DefaultThreadID, NoopEngine},
+        scheduler::ToCompletion,
+    },
+    Ciphel, CompilationError, Diagnostic, Severity,
+};
+use serde_json::{json, Value};
+
+use crate::position::{line_range, range_of};
+
+type Engine = NoopEngine;
+
+const ERROR: u8 = 1;
+const WARNING: u8 = 2;
+const FUNCTION: u8 = 3;
+const MODULE: u8 = 9;
+
+/*
+ * @desc A document holding a module is compiled as a module, as statements otherwise
+ */
+pub fn is_module(source: &str) -> bool {
+    let mut rest = source.trim_start();
+    loop {
+        if rest.starts_with(lexem::SL_COMMENT) {
+            rest = rest.find('\n').map_or("", |index| &rest[index..]);
+        } else if rest.starts_with(lexem::ML_OP_COMMENT) {
+            rest = rest
+                .find(lexem::ML_CL_COMMENT)
+                .map_or("", |index| &rest[index + lexem::ML_CL_COMMENT.len()..]);
+        } else {
+            break;
+        }
+        rest = rest.trim_start();
+    }
+    rest.strip_prefix(lexem::MODULE)
+        .map_or(false, |rest| rest.starts_with(char::is_whitespace))
+}
+
+/*
+ * @desc Diagnostics of the compilation of a document, in the format of the editor
+ */
+pub fn diagnostics(source: &str) -> Vec<Value> {
+    if is_module(source) {
+        return match Ciphel::<Engine, ToCompletion>::compile_module(source) {
+            Ok(()) => Vec::default(),
+            Err(err) => vec![editor_diagnostic(source, &Diagnostic::error(err))],
+        };
+    }
+    let mut engine = NoopEngine {};
+    let mut ciphel = Ciphel::<Engine, ToCompletion>::default();
+    let pid = DefaultProcessID::default();
+    ciphel.runtime.modules.insert(pid, Vec::default());
+    let tid = match ciphel.runtime.spawn(pid, &mut engine) {
+        Ok(tid) => tid,
+        Err(err) => return vec![message_diagnostic(ERROR, &err.to_string())],
+    };
+    let (Ok(diagnostics) | Err(diagnostics)) = ciphel.compile(tid, source, 0);
+    diagnostics
+        .iter()
+        .map(|diagnostic| editor_diagnostic(source, diagnostic))
+        .collect()
+}
+
+fn editor_diagnostic(
+    source: &str,
+    diagnostic: &Diagnostic<DefaultProcessID, DefaultThreadID>,
+) -> Value {
+    let severity = match diagnostic.severity {
+        Severity::Error => ERROR,
+        Severity::Warning => WARNING,
+    };
+    let (range, message) = match &diagnostic.error {
+        CompilationError::ParsingError(report) => {
+            let (line, column) = parsing_error_location(report).unwrap_or((1, 1));
+            (line_range(source, line, column), report.trim().to_string())
+        }
+        CompilationError::SemanticError(line, err, report) => {
+            let range = match err.span() {
+                Some(span) => range_of(source, span),
+                None => line_range(source, *line, 1),
+            };
+            (range, report.trim_end().to_string())
+        }
+        CompilationError::Lint(line, lint) if lint.span.is_known() => {
+            (range_of(source, lint.span), lint.to_string())
+        }
+        CompilationError::Lint(line, lint) => (line_range(source, *line, 1), lint.to_string()),
+        CompilationError::CodeGen(line, err) => (line_range(source, *line, 1), err.to_string()),
+        CompilationError::UnknownModule(line, path) => (
+            line_range(source, *line, 1),
+            format!("unknown module \"{}\"", path),
+        ),
+        CompilationError::CyclicImport(line, path) => (
+            line_range(source, *line, 1),
+            format!("cyclic import of the module \"{}\"", path),
+        ),
+        CompilationError::ModuleError(line, path, err) => {
+            // the error is located in the module, the import is the only place of the source to show
+            let inner = editor_diagnostic(
+                "",
+                &Diagnostic {
+                    severity: diagnostic.severity,
+                    error: (**err).clone(),
+                },
+            );
+            (
+                line_range(source, *line, 1),
+                format!(
+                    "in the module \"{}\" : {}",
+                    path,
+                    inner["message"].as_str().unwrap_or_default()
+                ),
+            )
+        }
+        err => (line_range(source, 1, 1), err.to_string()),
+    };
+    json!({ "range": range, "severity": severity, "source": "ciphel", "message": message })
+}
+
+fn message_diagnostic(severity: u8, message: &str) -> Value {
+    json!({
+        "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+        "severity": severity,
+        "source": "ciphel",
+        "message": message,
+    })
+}
+
+/*
+ * @desc Location of the first error of a parsing report : Error at line L, column C:
+ */
+fn parsing_error_location(report: &str) -> Option<(usize, usize)> {
+    let (_, rest) = report.split_once("Error at line ")?;
+    let (line, rest) = rest.split_once(", column ")?;
+    let (column, _) = rest.split_once(':')?;
+    Some((line.trim().parse().ok()?, column.trim().parse().ok()?))
+}
+
+/*
+ * @desc Resolved symbols of a document, the resolution goes on after an error
+ */
+pub struct Analysis {
+    scope_manager: ScopeManager,
+}
+
+impl Analysis {
+    pub fn new(source: &str) -> Self {
+        let scope_manager = match is_module(source) {
+            true => resolve_module::<Engine>(source),
+            false => resolve_statements::<Engine>(source),
+        };
+        Self { scope_manager }
+    }
+
+    /*
+     * @desc Description of the variable or the expression at an offset, with its resolved type
+     */
+    pub fn hover(&self, source: &str, offset: usize) -> Option<String> {
+        let symbols = self.scope_manager.symbols()?;
+        if let Some(Symbol::Variable(id)) = symbols.reference_at(offset) {
+            if let Ok(var) = self.scope_manager.find_var_by_id(id) {
+                let ctype = NamedType::new(&var.ctype, &self.scope_manager, var.scope);
+                return Some(format!("{} : {}", var.name, ctype));
+            }
+        }
+        if let Some(signature) = symbols.signature_at(offset) {
+            return Some(NamedType::new(signature, &self.scope_manager, None).to_string());
+        }
+        let word = word_at(source, offset)?;
+        let _ = self
+            .scope_manager
+            .find_type_by_name(None, word, None)
+            .ok()?;
+        Some(format!("type {}", word))
+    }
+
+    /*
+     * @desc Location of the statement declaring the variable or the type at an offset
+     */
+    pub fn definition(&self, source: &str, offset: usize) -> Option<SourceSpan> {
+        let reference = self
+            .scope_manager
+            .symbols()
+            .and_then(|symbols| symbols.reference_at(offset));
+        let span = match reference {
+            Some(Symbol::Variable(id)) => self.scope_manager.find_var_by_id(id).ok()?.span,
+            Some(Symbol::Type(id)) => self.scope_manager.find_type_info_by_id(id)?.span,
+            None => {
+                let word = word_at(source, offset)?;
+                let ctype = self
+                    .scope_manager
+                    .find_type_by_name(None, word, None)
+                    .ok()?;
+                self.scope_manager.find_type_info_by_id(ctype.id)?.span
+            }
+        };
+        span.is_known().then_some(span)
+    }
+}
+
+/*
+ * @desc Identifier around an offset of the source
+ */
+fn word_at(source: &str, offset: usize) -> Option<&str> {
+    let is_id = |c: char| c.is_alphanumeric() || c == '_';
+    let offset = offset.min(source.len());
+    let start = source[..offset]
+        .rfind(|c: char| !is_id(c))
+        .map_or(0, |index| index + 1);
+    let end = source[offset..]
+        .find(|c: char| !is_id(c))
+        .map_or(source.len(), |index| offset + index);
+    (start < end).then(|| &source[start..end])
+}
+
+/*
+ * @desc Names of the core library completing the path being typed at an offset
+ * After library:: only the functions of the library are proposed
+ */
+pub fn completions(source: &str, offset: usize) -> Vec<Value> {
+    let offset = offset.min(source.len());
+    let line = &source[source[..offset].rfind('\n').map_or(0, |index| index + 1)..offset];
+    let path: Vec<&str> = line
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .next()
+        .unwrap_or("")
+        .split(lexem::SEP)
+        .collect();
+    let path = match path.first() {
+        Some(&core::CORE) => &path[1..],
+        _ => &path[..],
+    };
+
+    let function = |library: &str, name: &str| {
+        let detail = format!("{}::{}", core::CORE, library);
+        json!({ "label": name, "kind": FUNCTION, "detail": detail })
+    };
+    match path {
+        [library, _] => core::LIBRARIES
+            .iter()
+            .filter(|(name, _)| name == library)
+            .flat_map(|(library, functions)| functions.iter().map(|name| function(library, name)))
+            .collect(),
+        _ => core::LIBRARIES
+            .iter()
+            .map(|(library, _)| json!({ "label": library, "kind": MODULE }))
+            .chain(core::LIBRARIES.iter().flat_map(|(library, functions)| {
+                functions.iter().map(|name| function(library, name))
+            }))
+            .chain(
+                core::BUILTINS
+                    .iter()
+                    .map(|name| json!({ "label": name, "kind": FUNCTION, "detail": core::CORE })),
+            )
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_is_module() {
+        assert!(is_module("// lib\n/* math */ module Math {}"));
+        assert!(!is_module("let module_count = 1;"));
+        assert!(!is_module("modules();"));
+    }
+
+    #[test]
+    fn valid_hover_and_definition() {
+        let source =
+            "struct Point { x : u64, y : u64 }\nlet p = Point { x : 1, y : 2 };\nlet a = p.x + 3;";
+        let analysis = Analysis::new(source);
+
+        let offset = source.rfind("p.x").unwrap();
+        assert_eq!(
+            analysis.hover(source, offset),
+            Some("p : Point".to_string())
+        );
+        assert_eq!(
+            analysis.hover(source, source.rfind('3').unwrap()),
+            Some("u64".to_string())
+        );
+
+        let span = analysis.definition(source, offset).unwrap();
+        assert_eq!(span.line, 2);
+        let span = analysis
+            .definition(source, source.find("Point {").unwrap() + 1)
+            .unwrap();
+        assert_eq!(span.line, 1);
+        assert_eq!(
+            analysis.definition(source, source.find("let").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn valid_diagnostics() {
+        let source = "let a : u64 = 1;\nlet b = a + c;\nlet d = ;";
+        let diagnostics = diagnostics(source);
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic["severity"] == ERROR));
+        let lines: Vec<&Value> = diagnostics
+            .iter()
+            .map(|diagnostic| &diagnostic["range"]["start"]["line"])
+            .collect();
+        assert!(
+            lines.contains(&&json!(1)) && lines.contains(&&json!(2)),
+            "{:?}",
+            lines
+        );
+        // the message of a semantic error is its report on the source
+        assert!(
+            diagnostics.iter().any(|diagnostic| diagnostic["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("2 | let b = a + c;"))),
+            "{:?}",
+            diagnostics
+        );
+
+        // the other errors are shown by their message
+        let diagnostic = editor_diagnostic(
+            source,
+            &Diagnostic::error(CompilationError::InvalidTID(DefaultThreadID(3))),
+        );
+        assert_eq!(diagnostic["message"], "Invalid thread id : thread 3");
+    }
+
+    #[test]
+    fn valid_completions() {
+        let labels = |items: Vec<Value>| -> Vec<String> {
+            items
+                .iter()
+                .map(|item| item["label"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let source = "let x = math::";
+        let items = labels(completions(source, source.len()));
+        assert!(items.contains(&"sqrt".to_string()));
+        assert!(!items.contains(&"push".to_string()));
+
+        let items = labels(completions("let x = ", 8));
+        assert!(items.contains(&"math".to_string()));
+        assert!(items.contains(&"push".to_string()));
+        assert!(items.contains(&"assert".to_string()));
+    }
+}
@@ -0,0 +1,25 @@
+/*
+ * @desc Language server of Ciphel, it speaks the language server protocol over stdio
+ * It reports the diagnostics of the open documents and answers hover, definition and completion
+ * It is built with the lsp feature : cargo build --features lsp
+ */
+mod analysis;
+mod position;
+mod rpc;
+mod server;
+
+use std::{io, process::ExitCode};
+
+fn main() -> ExitCode {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    match server::run(&mut stdin.lock(), &mut stdout.lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        // the editor exited without asking for a shutdown first
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("ciphel-lsp : {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
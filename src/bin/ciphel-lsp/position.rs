@@ -0,0 +1,93 @@
+use ciphel::ast::utils::io::SourceSpan;
+use serde_json::{json, Value};
+
+/*
+ * @desc Offset of a position of the editor
+ * Its line starts at 0 and its character counts utf-16 units
+ * A position past the end of its line is the end of the line
+ */
+pub fn offset_of(source: &str, line: usize, character: usize) -> usize {
+    let line_start = line_start(source, line);
+    let mut units = 0;
+    for (index, c) in source[line_start..].char_indices() {
+        if units >= character || c == '\n' {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    source.len()
+}
+
+/*
+ * @desc Position of the editor of an offset of the source
+ */
+pub fn position_of(source: &str, offset: usize) -> Value {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    json!({
+        "line": before.matches('\n').count(),
+        "character": before[line_start..].encode_utf16().count(),
+    })
+}
+
+pub fn range_of(source: &str, span: SourceSpan) -> Value {
+    json!({
+        "start": position_of(source, span.offset),
+        "end": position_of(source, span.offset + span.length),
+    })
+}
+
+/*
+ * @desc Range from a location of the source to the end of its line
+ * The line and the column start at 1
+ */
+pub fn line_range(source: &str, line: usize, column: usize) -> Value {
+    let line_start = line_start(source, line.saturating_sub(1));
+    let line_end = source[line_start..]
+        .find('\n')
+        .map_or(source.len(), |index| line_start + index);
+    let start = source[line_start..line_end]
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line_end, |(index, _)| line_start + index);
+    json!({
+        "start": position_of(source, start),
+        "end": position_of(source, line_end),
+    })
+}
+
+fn line_start(source: &str, line: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+    source
+        .match_indices('\n')
+        .nth(line - 1)
+        .map_or(source.len(), |(index, _)| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_positions() {
+        let source = "let a = 1;\nlet é = \"😀\" + b;\n";
+        let offset = offset_of(source, 1, 13);
+        assert_eq!(&source[offset..offset + 1], "+");
+        assert_eq!(
+            position_of(source, offset),
+            json!({ "line": 1, "character": 13 })
+        );
+        assert_eq!(offset_of(source, 0, 42), source.find('\n').unwrap());
+        assert_eq!(offset_of(source, 5, 0), source.len());
+        assert_eq!(
+            line_range(source, 2, 5),
+            json!({
+                "start": { "line": 1, "character": 4 },
+                "end": { "line": 1, "character": 17 },
+            })
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("io error : {0}")]
+    Io(#[from] io::Error),
+
+    #[error("message without a Content-Length header")]
+    MissingLength,
+
+    #[error("invalid message : {0}")]
+    InvalidJson(#[from] serde_json::Error),
+}
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/*
+ * @desc Read a message framed by its headers, none at the end of the input
+ *
+ * @grammar
+ * Message := Headers \r\n Content
+ * Headers := Header \r\n Headers | Header \r\n
+ * Header := Content-Length: Number | Name: Value
+ */
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, RpcError> {
+    let mut length = None;
+    let mut has_headers = false;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            // blank lines between two messages
+            if !has_headers {
+                continue;
+            }
+            break;
+        }
+        has_headers = true;
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = length else {
+        return Err(RpcError::MissingLength);
+    };
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), RpcError> {
+    let content = message.to_string();
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_read_write() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" });
+        let mut input = Vec::default();
+        write_message(&mut input, &message).unwrap();
+        write_message(&mut input, &message).unwrap();
+
+        let mut reader = io::BufReader::new(input.as_slice());
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message.clone()));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn robustness_read() {
+        let mut reader = io::BufReader::new("Content-Type: json\r\n\r\n{}".as_bytes());
+        assert!(matches!(
+            read_message(&mut reader),
+            Err(RpcError::MissingLength)
+        ));
+
+        let mut reader = io::BufReader::new("Content-Length: 3\r\n\r\n{{{".as_bytes());
+        assert!(matches!(
+            read_message(&mut reader),
+            Err(RpcError::InvalidJson(_))
+        ));
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    analysis::{completions, diagnostics, Analysis},
+    position::{offset_of, range_of},
+    rpc::{
+        error_response, notification, read_message, response, write_message, RpcError,
+        INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+    },
+};
+
+const FULL_SYNC: u8 = 1;
+
+/*
+ * @desc State of a session with an editor : the open documents by their uri
+ */
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+    is_shut_down: bool,
+    has_exited: bool,
+}
+
+impl Server {
+    /*
+     * @desc Handle a message of the editor, it returns the responses and notifications to send
+     */
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = message.get("id");
+        let params = &message["params"];
+        let Some(method) = message["method"].as_str() else {
+            return match id {
+                Some(id) => vec![error_response(id, INVALID_REQUEST, "missing method")],
+                // a response of the editor, no request of the server awaits it
+                None => Vec::default(),
+            };
+        };
+        if self.is_shut_down && method != "exit" {
+            return match id {
+                Some(id) => vec![error_response(id, INVALID_REQUEST, "server is shut down")],
+                None => Vec::default(),
+            };
+        }
+        match (method, id) {
+            ("initialize", Some(id)) => vec![response(id, capabilities())],
+            ("shutdown", Some(id)) => {
+                self.is_shut_down = true;
+                vec![response(id, Value::Null)]
+            }
+            ("exit", _) => {
+                self.has_exited = true;
+                Vec::default()
+            }
+            ("textDocument/didOpen", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            ("textDocument/didChange", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                // the documents are synchronized in full, the last change holds the whole text
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return Vec::default();
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            ("textDocument/didClose", None) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                self.documents.remove(uri);
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            ("textDocument/hover", Some(id)) => {
+                let result = self.at_position(params, |source, offset| {
+                    let contents = Analysis::new(source).hover(source, offset)?;
+                    let value = format!("```ciphel\n{}\n```", contents);
+                    Some(json!({ "contents": { "kind": "markdown", "value": value } }))
+                });
+                vec![response(id, result.unwrap_or(Value::Null))]
+            }
+            ("textDocument/definition", Some(id)) => {
+                let uri = &params["textDocument"]["uri"];
+                let result = self.at_position(params, |source, offset| {
+                    let span = Analysis::new(source).definition(source, offset)?;
+                    Some(json!({ "uri": uri, "range": range_of(source, span) }))
+                });
+                vec![response(id, result.unwrap_or(Value::Null))]
+            }
+            ("textDocument/completion", Some(id)) => {
+                let result = self.at_position(params, |source, offset| {
+                    Some(Value::Array(completions(source, offset)))
+                });
+                vec![response(id, result.unwrap_or(Value::Null))]
+            }
+            (_, Some(id)) => vec![error_response(id, METHOD_NOT_FOUND, method)],
+            (_, None) => Vec::default(),
+        }
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.has_exited
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let source = self.documents.get(uri).map_or("", String::as_str);
+        notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics(source) }),
+        )
+    }
+
+    /*
+     * @desc Answer a request about a position of an open document
+     */
+    fn at_position(
+        &self,
+        params: &Value,
+        answer: impl FnOnce(&str, usize) -> Option<Value>,
+    ) -> Option<Value> {
+        let source = self
+            .documents
+            .get(params["textDocument"]["uri"].as_str()?)?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let character = params["position"]["character"].as_u64()? as usize;
+        answer(source, offset_of(source, line, character))
+    }
+}
+
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": FULL_SYNC,
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "completionProvider": { "triggerCharacters": [":"] },
+        },
+        "serverInfo": { "name": "ciphel-lsp", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/*
+ * @desc Serve an editor until it exits, the process fails if it exits without a shutdown
+ */
+pub fn run(reader: &mut impl BufRead, writer: &mut impl Write) -> Result<bool, RpcError> {
+    let mut server = Server::default();
+    while !server.has_exited() {
+        let message = match read_message(reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(RpcError::InvalidJson(err)) => {
+                let error = error_response(&Value::Null, PARSE_ERROR, &err.to_string());
+                write_message(writer, &error)?;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        for answer in server.handle(&message) {
+            write_message(writer, &answer)?;
+        }
+    }
+    Ok(server.is_shut_down)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    /*
+     * @desc Run the server over the messages of a session, it returns the messages it sent
+     */
+    fn session(messages: &[Value]) -> Vec<Value> {
+        let mut input = Vec::default();
+        for message in messages {
+            write_message(&mut input, message).unwrap();
+        }
+        let mut output = Vec::default();
+        let is_shut_down = run(&mut BufReader::new(input.as_slice()), &mut output).unwrap();
+        assert!(is_shut_down);
+
+        let mut reader = BufReader::new(output.as_slice());
+        let mut answers = Vec::default();
+        while let Some(answer) = read_message(&mut reader).unwrap() {
+            answers.push(answer);
+        }
+        answers
+    }
+
+    fn request(id: u64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn at(uri: &str, line: u64, character: u64) -> Value {
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    fn open(uri: &str, text: &str) -> Value {
+        let document = json!({ "uri": uri, "languageId": "ciphel", "version": 1, "text": text });
+        notification("textDocument/didOpen", json!({ "textDocument": document }))
+    }
+
+    fn answer(answers: &[Value], id: u64) -> &Value {
+        answers
+            .iter()
+            .find(|answer| answer["id"] == id)
+            .map(|answer| &answer["result"])
+            .unwrap()
+    }
+
+    const URI: &str = "file:///game/main.ci";
+    const SOURCE: &str = "\
+struct Point { x : i64, y : i64 }
+let origin = Point { x : 0, y : 0 };
+fn norm(p : Point) -> i64 {
+    return p.x + p.y;
+}
+let n = norm(origin);
+";
+
+    #[test]
+    fn valid_session() {
+        let answers = session(&[
+            request(1, "initialize", json!({ "capabilities": {} })),
+            notification("initialized", json!({})),
+            open(URI, SOURCE),
+            request(2, "textDocument/hover", at(URI, 5, 13)),
+            request(3, "textDocument/definition", at(URI, 5, 9)),
+            request(4, "textDocument/definition", at(URI, 2, 12)),
+            request(5, "textDocument/completion", at(URI, 0, 0)),
+            request(6, "textDocument/formatting", json!({})),
+            request(7, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ]);
+
+        assert_eq!(answer(&answers, 1)["capabilities"]["hoverProvider"], true);
+        let diagnostics = answers
+            .iter()
+            .find(|answer| answer["method"] == "textDocument/publishDiagnostics")
+            .unwrap();
+        assert_eq!(diagnostics["params"]["uri"], URI);
+        assert_eq!(diagnostics["params"]["diagnostics"], json!([]));
+
+        assert_eq!(
+            answer(&answers, 2)["contents"]["value"],
+            "```ciphel\norigin : Point\n```"
+        );
+        assert_eq!(
+            answer(&answers, 3),
+            &json!({
+                "uri": URI,
+                "range": {
+                    "start": { "line": 2, "character": 0 },
+                    "end": { "line": 4, "character": 1 },
+                },
+            })
+        );
+        assert_eq!(
+            answer(&answers, 4)["range"]["start"],
+            json!({ "line": 0, "character": 0 })
+        );
+        assert!(answer(&answers, 5).as_array().unwrap().len() > 50);
+
+        let unknown = answers.iter().find(|answer| answer["id"] == 6).unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(answer(&answers, 7), &Value::Null);
+    }
+
+    #[test]
+    fn valid_diagnostics_on_change() {
+        let answers = session(&[
+            request(1, "initialize", json!({ "capabilities": {} })),
+            open(URI, SOURCE),
+            notification(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": URI, "version": 2 },
+                    "contentChanges": [{ "text": "let a = 1;\nlet b = a + unknown;\n" }],
+                }),
+            ),
+            notification(
+                "textDocument/didClose",
+                json!({ "textDocument": { "uri": URI } }),
+            ),
+            request(2, "shutdown", Value::Null),
+            notification("exit", Value::Null),
+        ]);
+        let published: Vec<&Value> = answers
+            .iter()
+            .filter(|answer| answer["method"] == "textDocument/publishDiagnostics")
+            .map(|answer| &answer["params"]["diagnostics"])
+            .collect();
+        assert_eq!(published.len(), 3);
+        assert_eq!(published[0], &json!([]));
+        let errors = published[1].as_array().unwrap();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert_eq!(
+            errors[0]["range"]["start"],
+            json!({ "line": 1, "character": 12 })
+        );
+        assert_eq!(errors[0]["severity"], 1);
+        assert_eq!(published[2], &json!([]));
+    }
+
+    #[test]
+    fn robustness_session() {
+        let mut input = Vec::default();
+        input.extend_from_slice(b"Content-Length: 5\r\n\r\n{oops");
+        write_message(&mut input, &request(1, "shutdown", Value::Null)).unwrap();
+        write_message(&mut input, &request(2, "hover", Value::Null)).unwrap();
+        write_message(&mut input, &notification("exit", Value::Null)).unwrap();
+
+        let mut output = Vec::default();
+        let is_shut_down = run(&mut BufReader::new(input.as_slice()), &mut output).unwrap();
+        assert!(is_shut_down);
+
+        let mut reader = BufReader::new(output.as_slice());
+        let error = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(error["error"]["code"], PARSE_ERROR);
+        let shutdown = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(shutdown["result"], Value::Null);
+        let refused = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(refused["error"]["code"], INVALID_REQUEST);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use ciphel::{
+    semantic::{EType, SemanticError},
+    vm::{
+        allocator::{heap::Heap, stack::Stack},
+        external::{
+            test::DefaultExecutionContext, Engine, ExternEnergyDispenser, ExternEventManager,
+            ExternFunction, ExternIO, ExternPathFinder, ExternProcessIdentifier, ExternResolve,
+            ExternThreadHandler, ExternThreadIdentifier,
+        },
+        program::Program,
+        runtime::RuntimeError,
+        scheduler::{Executable, ExecutionContext, Scheduler, SchedulingPolicy},
+        signal::SignalHandler,
+        stdio::StdIO,
+        AsmName, AsmWeight,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ProcessID(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ThreadID(pub u64);
+
+impl ExternProcessIdentifier for ProcessID {}
+
+impl ExternThreadIdentifier<ProcessID> for ThreadID {
+    fn to_u64(&self) -> u64 {
+        self.0
+    }
+
+    fn from_u64(tid: u64) -> Option<Self> {
+        Some(Self(tid))
+    }
+
+    fn pid(&self) -> ProcessID {
+        // the terminal runs a single process
+        ProcessID::default()
+    }
+}
+
+impl fmt::Display for ProcessID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "process {}", self.0)
+    }
+}
+
+impl fmt::Display for ThreadID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "thread {}", self.0)
+    }
+}
+
+/*
+ * @desc Extern function of the stdio engine, the terminal provides none
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StdioFunction {}
+
+impl<E: Engine> AsmName<E> for StdioFunction {
+    fn name(&self, stdio: &mut StdIO, program: &Program<E>, engine: &mut E, pid: E::PID) {
+        match *self {}
+    }
+}
+
+impl AsmWeight for StdioFunction {}
+
+impl ExternEventManager<DefaultExecutionContext, ProcessID, ThreadID> for StdioFunction {
+    type E = StdioEngine;
+}
+
+impl<E: Engine> Executable<E> for StdioFunction {
+    fn execute<P: SchedulingPolicy>(
+        &self,
+        program: &Program<E>,
+        scheduler: &mut Scheduler<P>,
+        signal_handler: &mut SignalHandler<E>,
+        stack: &mut Stack,
+        heap: &mut Heap,
+        stdio: &mut StdIO,
+        engine: &mut E,
+        context: &ExecutionContext<E::FunctionContext, E::PID, E::TID>,
+    ) -> Result<(), RuntimeError> {
+        match *self {}
+    }
+}
+
+impl ExternResolve for StdioFunction {
+    fn resolve<E: Engine>(
+        &mut self,
+        scope: &mut ciphel::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+        params: &mut Vec<ciphel::ast::expressions::Expression>,
+    ) -> Result<EType, SemanticError> {
+        match *self {}
+    }
+}
+
+impl ExternFunction<StdioEngine> for StdioFunction {}
+
+/*
+ * @desc Reference engine of the terminal : the program writes to the real stdout and stderr
+ * and reads the scans from stdin. The energy is unlimited unless a budget is given
+ */
+#[derive(Debug, Default)]
+pub struct StdioEngine {
+    energy: Option<usize>,
+    last_tid: u64,
+    asm: Option<Vec<String>>,
+    stdin_closed: bool,
+}
+
+impl StdioEngine {
+    pub fn new(energy: Option<usize>) -> Self {
+        Self {
+            energy,
+            ..Self::default()
+        }
+    }
+
+    /*
+     * @desc Energy left to the process, None when it is unlimited
+     */
+    pub fn energy(&self) -> Option<usize> {
+        self.energy
+    }
+
+    /*
+     * @desc Collect the assembly printed by the instructions until the capture is taken
+     */
+    pub fn capture_asm(&mut self) {
+        self.asm = Some(Vec::default());
+    }
+
+    pub fn take_asm(&mut self) -> Vec<String> {
+        self.asm.take().unwrap_or_default()
+    }
+
+    /*
+     * @desc A scan after the end of stdin can never be answered
+     */
+    pub fn is_stdin_closed(&self) -> bool {
+        self.stdin_closed
+    }
+}
+
+impl ExternIO<ProcessID, ThreadID> for StdioEngine {
+    fn stdout_print(&mut self, pid: ProcessID, content: String) {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{}", content);
+        let _ = stdout.flush();
+    }
+    fn stdout_println(&mut self, pid: ProcessID, content: String) {
+        println!("{}", content);
+    }
+
+    fn stderr_print(&mut self, pid: ProcessID, content: String) {
+        eprintln!("{}", content);
+    }
+
+    fn stdin_scan(&mut self, tid: ThreadID) -> Option<String> {
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                self.stdin_closed = true;
+                None
+            }
+            Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_string()),
+        }
+    }
+    fn stdin_request(&mut self, tid: ThreadID) {
+        // the printed prompt has to be visible before the terminal blocks on stdin
+        let _ = io::stdout().flush();
+    }
+
+    fn stdasm_print(&mut self, pid: ProcessID, content: String) {
+        if let Some(asm) = self.asm.as_mut() {
+            asm.push(content);
+        }
+    }
+}
+
+impl ExternEnergyDispenser<ProcessID, ThreadID> for StdioEngine {
+    fn get_energy(&self, pid: ProcessID) -> usize {
+        self.energy.unwrap_or(usize::MAX)
+    }
+
+    fn consume_energy(&mut self, energy: usize, pid: ProcessID) -> Result<(), RuntimeError> {
+        let Some(left) = self.energy else {
+            return Ok(());
+        };
+        let left = left
+            .checked_sub(energy)
+            .ok_or(RuntimeError::NotEnoughEnergy)?;
+        self.energy = Some(left);
+        Ok(())
+    }
+}
+
+impl ExternThreadHandler for StdioEngine {
+    type PID = ProcessID;
+    type TID = ThreadID;
+
+    fn spawn(&mut self, pid: &Self::PID) -> Result<Self::TID, RuntimeError> {
+        self.last_tid += 1;
+        Ok(ThreadID(self.last_tid))
+    }
+
+    fn close(&mut self, pid: &Self::PID, tid: &Self::TID) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+}
+
+impl ExternPathFinder for StdioEngine {
+    fn find(path: &[String], name: &str) -> Option<<Self as Engine>::Function>
+    where
+        Self: Engine,
+    {
+        None
+    }
+}
+
+impl Engine for StdioEngine {
+    type Function = StdioFunction;
+    type FunctionContext = DefaultExecutionContext;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_energy() {
+        let pid = ProcessID::default();
+        let mut unlimited = StdioEngine::new(None);
+        assert!(unlimited.consume_energy(1_000, pid).is_ok());
+        assert_eq!(unlimited.get_energy(pid), usize::MAX);
+
+        let mut limited = StdioEngine::new(Some(10));
+        assert!(limited.consume_energy(4, pid).is_ok());
+        assert_eq!(limited.energy(), Some(6));
+        assert!(limited.consume_energy(7, pid).is_err());
+        assert_eq!(limited.get_energy(pid), 6);
+    }
+}
@@ -0,0 +1,122 @@
+#![allow(unused_variables)]
+
+/*
+ * @desc Command line of Ciphel : an interactive repl and a runner of source files
+ * The programs run on a reference engine bound to the terminal
+ */
+mod engine;
+mod repl;
+mod session;
+mod value;
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use session::Session;
+
+const USAGE: &str = "\
+usage :
+    ciphel repl [--energy <amount>]
+    ciphel run <file.ci> [--energy <amount>]";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    Repl,
+    Run(String),
+}
+
+/*
+ * @desc Mode and energy budget given on the command line, the energy is unlimited by default
+ */
+fn parse_args(args: &[String]) -> Result<(Mode, Option<usize>), String> {
+    let mut positionals = Vec::default();
+    let mut energy = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--energy" => {
+                let amount = args.next().ok_or("missing amount of energy")?;
+                let amount = amount
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid amount of energy \"{}\"", amount))?;
+                energy = Some(amount);
+            }
+            _ => positionals.push(arg.as_str()),
+        }
+    }
+    let mode = match positionals.as_slice() {
+        ["repl"] => Mode::Repl,
+        ["run", file] => Mode::Run(file.to_string()),
+        _ => return Err(USAGE.to_string()),
+    };
+    Ok((mode, energy))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (mode, energy) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let res = match mode {
+        Mode::Repl => Session::new(energy, Path::new("."))
+            .map_err(|err| err.to_string())
+            .and_then(|session| repl::run(session).map_err(|err| err.to_string())),
+        Mode::Run(file) => run_file(&file, energy),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/*
+ * @desc Compile a file as a whole and run it until the runtime is idle
+ * Its imports are loaded from the directory of the file
+ */
+fn run_file(file: &str, energy: Option<usize>) -> Result<(), String> {
+    let source =
+        fs::read_to_string(file).map_err(|err| format!("cannot read \"{}\" : {}", file, err))?;
+    let root = Path::new(file).parent().unwrap_or(Path::new("."));
+    let mut session = Session::new(energy, root).map_err(|err| err.to_string())?;
+    let warnings = session.compile(&source).map_err(|err| err.to_string())?;
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+    session.run_until_idle().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn valid_parse_args() {
+        assert_eq!(parse_args(&args(&["repl"])), Ok((Mode::Repl, None)));
+        assert_eq!(
+            parse_args(&args(&["run", "main.ci", "--energy", "100"])),
+            Ok((Mode::Run("main.ci".to_string()), Some(100)))
+        );
+        assert_eq!(
+            parse_args(&args(&["--energy", "5", "repl"])),
+            Ok((Mode::Repl, Some(5)))
+        );
+    }
+
+    #[test]
+    fn robustness_parse_args() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["run"])).is_err());
+        assert!(parse_args(&args(&["repl", "--energy"])).is_err());
+        assert!(parse_args(&args(&["repl", "--energy", "many"])).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use std::io::{self, BufRead, Write};
+
+use crate::session::Session;
+
+const PROMPT: &str = "ciphel> ";
+const CONTINUATION_PROMPT: &str = "   ...> ";
+
+const HELP: &str = "\
+:show <variable>  print the value of a variable
+:asm              print the assembly of the last accepted input
+:energy           print the energy left to the process
+:reset            drop every variable, function and type
+:help             print this message
+:quit             leave the repl";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command<'a> {
+    Show(&'a str),
+    Asm,
+    Energy,
+    Reset,
+    Help,
+    Quit,
+}
+
+/*
+ * @desc Command of the repl, the inputs that do not start with ':' are Ciphel sources
+ */
+pub fn parse_command(input: &str) -> Option<Result<Command<'_>, String>> {
+    let input = input.trim().strip_prefix(':')?;
+    let mut words = input.split_whitespace();
+    let command = match (words.next(), words.next(), words.next()) {
+        (Some("show"), Some(name), None) => Ok(Command::Show(name)),
+        (Some("show"), _, _) => Err("usage : :show <variable>".to_string()),
+        (Some("asm"), None, _) => Ok(Command::Asm),
+        (Some("energy"), None, _) => Ok(Command::Energy),
+        (Some("reset"), None, _) => Ok(Command::Reset),
+        (Some("help"), None, _) => Ok(Command::Help),
+        (Some("quit" | "q"), None, _) => Ok(Command::Quit),
+        _ => Err(format!("unknown command \":{}\", try :help", input)),
+    };
+    Some(command)
+}
+
+/*
+ * @desc An input is complete once its braces, brackets and parentheses are closed
+ * The delimiters inside strings, chars and comments are ignored
+ */
+pub fn is_complete(input: &str) -> bool {
+    let mut depth: isize = 0;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            '"' | '\'' => {
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '\\' => {
+                            chars.next();
+                        }
+                        _ if inner == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().take_while(|c| *c != '\n').for_each(drop);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for inner in chars.by_ref() {
+                    if previous == '*' && inner == '/' {
+                        break;
+                    }
+                    previous = inner;
+                }
+            }
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+/*
+ * @desc Read, compile and run the inputs of the terminal until the end of stdin or :quit
+ */
+pub fn run(mut session: Session) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut input = String::new();
+    loop {
+        let prompt = if input.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+        write!(stdout, "{}", prompt)?;
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            writeln!(stdout)?;
+            return Ok(());
+        }
+
+        if input.is_empty() {
+            match parse_command(&line) {
+                Some(Ok(Command::Quit)) => return Ok(()),
+                Some(Ok(command)) => {
+                    execute(&mut session, command);
+                    continue;
+                }
+                Some(Err(err)) => {
+                    eprintln!("{}", err);
+                    continue;
+                }
+                None => {}
+            }
+        }
+
+        input.push_str(&line);
+        if input.trim().is_empty() {
+            input.clear();
+            continue;
+        }
+        if !is_complete(&input) {
+            continue;
+        }
+        let source = std::mem::take(&mut input);
+        match session.compile(&source) {
+            Ok(warnings) => {
+                for warning in warnings {
+                    eprintln!("{}", warning);
+                }
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                continue;
+            }
+        }
+        if let Err(err) = session.run_until_idle() {
+            eprintln!("{}", err);
+        }
+    }
+}
+
+fn execute(session: &mut Session, command: Command) {
+    let res = match command {
+        Command::Show(name) => session
+            .show(name)
+            .map(|value| println!("{} = {}", name, value)),
+        Command::Asm => session.disassemble().map(|asm| {
+            for line in asm {
+                println!("{}", line);
+            }
+        }),
+        Command::Energy => {
+            match session.energy() {
+                Some(energy) => println!("{}", energy),
+                None => println!("unlimited"),
+            }
+            Ok(())
+        }
+        Command::Reset => session.reset(),
+        Command::Help => {
+            println!("{}", HELP);
+            Ok(())
+        }
+        Command::Quit => Ok(()),
+    };
+    if let Err(err) = res {
+        eprintln!("{}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_parse_command() {
+        assert_eq!(parse_command("let x = 1;"), None);
+        assert_eq!(parse_command(":show x\n"), Some(Ok(Command::Show("x"))));
+        assert_eq!(parse_command(" :asm"), Some(Ok(Command::Asm)));
+        assert_eq!(parse_command(":reset"), Some(Ok(Command::Reset)));
+        assert_eq!(parse_command(":q"), Some(Ok(Command::Quit)));
+        assert!(matches!(parse_command(":show"), Some(Err(_))));
+        assert!(matches!(parse_command(":show x y"), Some(Err(_))));
+        assert!(matches!(parse_command(":unknown"), Some(Err(_))));
+    }
+
+    #[test]
+    fn valid_is_complete() {
+        assert!(is_complete("let x = 1;"));
+        assert!(!is_complete("fn f() -> u64 {"));
+        assert!(is_complete("fn f() -> u64 {\n return 1;\n}"));
+        assert!(is_complete("let s = \"{\";"));
+        assert!(is_complete("let c = '(';"));
+        assert!(is_complete("let x = 1; // {"));
+        assert!(!is_complete("/* } */ let v = vec[1,"));
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    fmt, fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use ciphel::{
+    vm::{
+        external::{ExternThreadIdentifier, ModuleLoader},
+        runtime::RuntimeError,
+        scheduler::EnergyPolicy,
+        AsmName,
+    },
+    Ciphel, Diagnostic, Diagnostics,
+};
+
+use crate::{
+    engine::{ProcessID, StdioEngine, ThreadID},
+    value,
+};
+
+pub const EXTENSION: &str = "ci";
+
+#[derive(Debug)]
+pub enum SessionError {
+    Compilation(Diagnostics<ProcessID, ThreadID>),
+    Runtime(RuntimeError),
+    UnknownVariable(String),
+    OutOfEnergy,
+    ClosedStdin,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Compilation(diagnostics) => {
+                let diagnostics: Vec<String> = diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.is_error())
+                    .map(Diagnostic::to_string)
+                    .collect();
+                write!(f, "{}", diagnostics.join("\n"))
+            }
+            SessionError::Runtime(err) => write!(f, "runtime error: {}", err),
+            SessionError::UnknownVariable(name) => write!(f, "unknown variable \"{}\"", name),
+            SessionError::OutOfEnergy => write!(f, "the process has run out of energy"),
+            SessionError::ClosedStdin => write!(f, "stdin was closed while the program scanned it"),
+        }
+    }
+}
+
+/*
+ * @desc Loader of the imported modules : import "path"; reads the file path.ci from a directory
+ */
+#[derive(Debug, Clone)]
+pub struct FileLoader {
+    root: PathBuf,
+}
+
+impl FileLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl ModuleLoader<ProcessID> for FileLoader {
+    fn load(&mut self, _pid: ProcessID, path: &str) -> Option<String> {
+        let mut file = self.root.join(path);
+        if file.extension().is_none() {
+            file.set_extension(EXTENSION);
+        }
+        fs::read_to_string(file).ok()
+    }
+}
+
+/*
+ * @desc Single thread of a process run by the stdio engine
+ * Each compiled source is appended to the program of the thread and run until the runtime is idle
+ */
+pub struct Session {
+    ciphel: Ciphel<StdioEngine, EnergyPolicy>,
+    engine: StdioEngine,
+    tid: ThreadID,
+    energy: Option<usize>,
+    root: PathBuf,
+    // instructions generated by the last accepted compilation
+    last: Range<usize>,
+    line_offset: usize,
+}
+
+impl Session {
+    pub fn new(energy: Option<usize>, root: &Path) -> Result<Self, SessionError> {
+        let mut engine = StdioEngine::new(energy);
+        let mut ciphel = Ciphel::<StdioEngine, EnergyPolicy>::default();
+        let pid = ProcessID::default();
+        ciphel.runtime.modules.insert(pid, Vec::default());
+        ciphel.set_module_loader(FileLoader::new(root));
+        let tid = ciphel
+            .runtime
+            .spawn(pid, &mut engine)
+            .map_err(SessionError::Runtime)?;
+        Ok(Self {
+            ciphel,
+            engine,
+            tid,
+            energy,
+            root: root.to_path_buf(),
+            last: 0..0,
+            line_offset: 0,
+        })
+    }
+
+    /*
+     * @desc Drop the variables, functions and instructions of the session
+     * The energy budget is given back in full
+     */
+    pub fn reset(&mut self) -> Result<(), SessionError> {
+        *self = Self::new(self.energy, &self.root)?;
+        Ok(())
+    }
+
+    pub fn energy(&self) -> Option<usize> {
+        self.engine.energy()
+    }
+
+    /*
+     * @desc Compile a source after the sources already compiled in the session
+     * A source with an error leaves the session as it was, the warnings are returned otherwise
+     */
+    pub fn compile(
+        &mut self,
+        src_code: &str,
+    ) -> Result<Diagnostics<ProcessID, ThreadID>, SessionError> {
+        let start = self.program_len()?;
+        match self.ciphel.compile(self.tid, src_code, self.line_offset) {
+            Ok(warnings) => {
+                self.last = start..self.program_len()?;
+                self.line_offset += src_code.lines().count().max(1);
+                Ok(warnings)
+            }
+            Err(diagnostics) => Err(SessionError::Compilation(diagnostics)),
+        }
+    }
+
+    /*
+     * @desc Run MAFs until no thread can progress anymore
+     * The instructions left to the thread are dropped on an uncaught error
+     */
+    pub fn run_until_idle(&mut self) -> Result<(), SessionError> {
+        while !self.ciphel.runtime.is_idle() {
+            let energy = self.engine.energy();
+            let before = self.ciphel.runtime.snapshot();
+            if let Err((_, err)) = self.ciphel.run(&mut self.engine) {
+                return Err(self.abort(SessionError::Runtime(err)));
+            }
+            if self.engine.is_stdin_closed() {
+                return Err(self.abort(SessionError::ClosedStdin));
+            }
+            // a MAF that neither consumed energy nor changed a thread could not pay its first instruction
+            let stalled = before.states == self.ciphel.runtime.snapshot().states;
+            if energy.is_some() && energy == self.engine.energy() && stalled {
+                return Err(self.abort(SessionError::OutOfEnergy));
+            }
+        }
+        Ok(())
+    }
+
+    fn abort(&mut self, err: SessionError) -> SessionError {
+        match self.ciphel.runtime.abort(&self.tid) {
+            Ok(()) => err,
+            Err(abort_err) => SessionError::Runtime(abort_err),
+        }
+    }
+
+    /*
+     * @desc Text of the current value of a global variable
+     */
+    pub fn show(&self, name: &str) -> Result<String, SessionError> {
+        let (thread, context) = self
+            .ciphel
+            .runtime
+            .thread_with_context_of(&self.tid)
+            .map_err(SessionError::Runtime)?;
+        let unknown = || SessionError::UnknownVariable(name.to_string());
+        let variable = context
+            .scope_manager
+            .find_var_by_name(name, None, None)
+            .map_err(|_| unknown())?;
+        let info = context
+            .scope_manager
+            .find_var_by_id(variable.id)
+            .map_err(|_| unknown())?;
+        let address = info.address.try_into().map_err(|_| unknown())?;
+        value::render(
+            &info.ctype,
+            address,
+            &context.scope_manager,
+            &thread.stack,
+            &self.ciphel.heap,
+        )
+        .map_err(SessionError::Runtime)
+    }
+
+    /*
+     * @desc Assembly of the instructions generated by the last accepted compilation
+     */
+    pub fn disassemble(&mut self) -> Result<Vec<String>, SessionError> {
+        let pid = self.tid.pid();
+        let program = &self
+            .ciphel
+            .runtime
+            .context_of(&self.tid)
+            .map_err(SessionError::Runtime)?
+            .program;
+        self.engine.capture_asm();
+        for instruction in &program.instructions[self.last.clone()] {
+            instruction.name(&mut self.ciphel.stdio, program, &mut self.engine, pid);
+        }
+        Ok(self.engine.take_asm())
+    }
+
+    fn program_len(&mut self) -> Result<usize, SessionError> {
+        self.ciphel
+            .runtime
+            .context_of(&self.tid)
+            .map(|context| context.program.len())
+            .map_err(SessionError::Runtime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session::new(None, Path::new(".")).expect("the session should have started")
+    }
+
+    #[test]
+    fn valid_show_after_run() {
+        let mut session = session();
+        session
+            .compile("let x = 40 + 2;")
+            .expect("the compilation should have succeeded");
+        session
+            .run_until_idle()
+            .expect("the run should have succeeded");
+        assert_eq!(session.show("x").unwrap(), "42");
+
+        session
+            .compile("x = x * 2; let s = \"ciphel\";")
+            .expect("the compilation should have succeeded");
+        session
+            .run_until_idle()
+            .expect("the run should have succeeded");
+        assert_eq!(session.show("x").unwrap(), "84");
+        assert_eq!(session.show("s").unwrap(), "\"ciphel\"");
+    }
+
+    #[test]
+    fn valid_rollback_of_bad_source() {
+        let mut session = session();
+        session
+            .compile("let x = 1;")
+            .expect("the compilation should have succeeded");
+        session
+            .run_until_idle()
+            .expect("the run should have succeeded");
+
+        let res = session.compile("let y = 2; let z = unknown;");
+        assert!(matches!(res, Err(SessionError::Compilation(_))));
+        assert!(matches!(
+            session.show("y"),
+            Err(SessionError::UnknownVariable(_))
+        ));
+
+        session
+            .compile("let y = x + 1;")
+            .expect("the compilation should have succeeded");
+        session
+            .run_until_idle()
+            .expect("the run should have succeeded");
+        assert_eq!(session.show("y").unwrap(), "2");
+    }
+
+    #[test]
+    fn valid_error_report() {
+        let mut session = session();
+        session
+            .compile("let x = 1;")
+            .expect("the compilation should have succeeded");
+
+        let err = session
+            .compile("let b = true;\nlet y : u64 = b;")
+            .expect_err("the compilation should have failed");
+        let report = err.to_string();
+        // the error is shown on the line of the session it was typed at
+        assert!(report.contains("--> line 3, column"), "{}", report);
+        assert!(report.contains("3 | let y : u64 = b;"), "{}", report);
+    }
+
+    #[test]
+    fn valid_reset() {
+        let mut session = session();
+        session
+            .compile("let x = 1;")
+            .expect("the compilation should have succeeded");
+        session
+            .run_until_idle()
+            .expect("the run should have succeeded");
+        session.reset().expect("the reset should have succeeded");
+        assert!(matches!(
+            session.show("x"),
+            Err(SessionError::UnknownVariable(_))
+        ));
+        assert!(session.disassemble().unwrap().is_empty());
+    }
+
+    #[test]
+    fn valid_disassemble_last_source() {
+        let mut session = session();
+        session
+            .compile("let x = 1;")
+            .expect("the compilation should have succeeded");
+        let first = session.disassemble().unwrap();
+        assert!(!first.is_empty());
+        session
+            .compile("let y = 2; let z = 3;")
+            .expect("the compilation should have succeeded");
+        assert!(session.disassemble().unwrap().len() > first.len());
+    }
+
+    #[test]
+    fn robustness_out_of_energy() {
+        let mut session =
+            Session::new(Some(5), Path::new(".")).expect("the session should have started");
+        session
+            .compile("let i = 0; while i < 100 { i = i + 1; }")
+            .expect("the compilation should have succeeded");
+        assert!(matches!(
+            session.run_until_idle(),
+            Err(SessionError::OutOfEnergy)
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use ciphel::{
+    semantic::{
+        scope::{
+            scope::ScopeManager,
+            static_types::{NumberType, PrimitiveType, StaticType},
+            user_types::UserType,
+        },
+        EType, NamedType, SizeOf,
+    },
+    vm::{
+        allocator::{heap::Heap, stack::Stack, MemoryAddress},
+        asm::operation::{GetNumFrom, OpPrimitive},
+        core::vector::VEC_HEADER,
+        runtime::RuntimeError,
+    },
+};
+
+/*
+ * @desc Text of the value of a given type stored at an address
+ * The values that cannot be read back, such as closures, are shown by their type
+ */
+pub fn render(
+    etype: &EType,
+    address: MemoryAddress,
+    scope_manager: &ScopeManager,
+    stack: &Stack,
+    heap: &Heap,
+) -> Result<String, RuntimeError> {
+    let render_at = |etype: &EType, address| render(etype, address, scope_manager, stack, heap);
+    let pointer = || -> Result<MemoryAddress, RuntimeError> {
+        OpPrimitive::get_num_from::<u64>(address, stack, heap)?.try_into()
+    };
+    let text = match etype {
+        EType::Static(StaticType::Primitive(PrimitiveType::Number(number))) => {
+            render_number(*number, address, stack, heap)?
+        }
+        EType::Static(StaticType::Primitive(PrimitiveType::Bool)) => {
+            OpPrimitive::get_bool_from(address, stack, heap)?.to_string()
+        }
+        EType::Static(StaticType::Primitive(PrimitiveType::Char)) => {
+            format!("{:?}", OpPrimitive::get_char_from(address, stack, heap)?)
+        }
+        EType::Static(StaticType::StrSlice(_)) => {
+            format!(
+                "{:?}",
+                OpPrimitive::get_string_from(pointer()?, stack, heap)?
+            )
+        }
+        EType::Static(StaticType::String(_)) => {
+            // the capacity of the string comes before its length
+            format!(
+                "{:?}",
+                OpPrimitive::get_string_from(pointer()?.add(8), stack, heap)?
+            )
+        }
+        EType::Static(StaticType::Unit) => "unit".to_string(),
+        EType::Static(StaticType::Tuple(tuple)) => {
+            let items = render_sequence(tuple.0.iter(), address, render_at)?;
+            format!("({})", items.join(", "))
+        }
+        EType::Static(StaticType::Vec(vector)) => {
+            let pointer = pointer()?;
+            let len = OpPrimitive::get_num_from::<u64>(pointer.add(8), stack, heap)? as usize;
+            let items = render_sequence(
+                std::iter::repeat(vector.0.as_ref()).take(len),
+                pointer.add(VEC_HEADER),
+                render_at,
+            )?;
+            format!("[{}]", items.join(", "))
+        }
+        EType::User { id, .. } => match scope_manager.find_type_info_by_id(*id) {
+            Some(info) => match &info.def {
+                UserType::Struct(def) => {
+                    let values =
+                        render_sequence(def.fields.iter().map(|(_, t)| t), address, render_at)?;
+                    let fields: Vec<String> = def
+                        .fields
+                        .iter()
+                        .zip(values)
+                        .map(|((name, _), value)| format!("{} : {}", name, value))
+                        .collect();
+                    format!("{} {{ {} }}", info.name, fields.join(", "))
+                }
+                UserType::Enum(def) => {
+                    let value = OpPrimitive::get_num_from::<u64>(address, stack, heap)?;
+                    match def.values.iter().find(|(_, index)| *index == value) {
+                        Some((name, _)) => format!("{}::{}", info.name, name),
+                        None => return Err(RuntimeError::Deserialization),
+                    }
+                }
+                UserType::Union(_) | UserType::Interface(_) => opaque(etype, scope_manager),
+            },
+            None => opaque(etype, scope_manager),
+        },
+        _ => opaque(etype, scope_manager),
+    };
+    Ok(text)
+}
+
+fn render_number(
+    number: NumberType,
+    address: MemoryAddress,
+    stack: &Stack,
+    heap: &Heap,
+) -> Result<String, RuntimeError> {
+    Ok(match number {
+        NumberType::U8 => OpPrimitive::get_num_from::<u8>(address, stack, heap)?.to_string(),
+        NumberType::U16 => OpPrimitive::get_num_from::<u16>(address, stack, heap)?.to_string(),
+        NumberType::U32 => OpPrimitive::get_num_from::<u32>(address, stack, heap)?.to_string(),
+        NumberType::U64 => OpPrimitive::get_num_from::<u64>(address, stack, heap)?.to_string(),
+        NumberType::U128 => OpPrimitive::get_num_from::<u128>(address, stack, heap)?.to_string(),
+        NumberType::I8 => OpPrimitive::get_num_from::<i8>(address, stack, heap)?.to_string(),
+        NumberType::I16 => OpPrimitive::get_num_from::<i16>(address, stack, heap)?.to_string(),
+        NumberType::I32 => OpPrimitive::get_num_from::<i32>(address, stack, heap)?.to_string(),
+        NumberType::I64 => OpPrimitive::get_num_from::<i64>(address, stack, heap)?.to_string(),
+        NumberType::I128 => OpPrimitive::get_num_from::<i128>(address, stack, heap)?.to_string(),
+        NumberType::F64 => format!("{:?}", OpPrimitive::get_float_from(address, stack, heap)?),
+    })
+}
+
+/*
+ * @desc Values laid out one after the other from an address
+ */
+fn render_sequence<'a>(
+    types: impl Iterator<Item = &'a EType>,
+    mut address: MemoryAddress,
+    render_at: impl Fn(&EType, MemoryAddress) -> Result<String, RuntimeError>,
+) -> Result<Vec<String>, RuntimeError> {
+    let mut items = Vec::default();
+    for etype in types {
+        items.push(render_at(etype, address)?);
+        address = address.add(etype.size_of());
+    }
+    Ok(items)
+}
+
+fn opaque(etype: &EType, scope_manager: &ScopeManager) -> String {
+    format!("<{}>", NamedType::new(etype, scope_manager, None))
+}
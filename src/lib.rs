@@ -1,7 +1,17 @@
 #![allow(unused_variables)]
 
-use ast::{modules::parse_module, statements::parse_statements};
-use semantic::{Resolve, SemanticError};
+use std::collections::HashMap;
+
+use ast::{
+    modules::{parse_module, Module},
+    utils::error::generate_semantic_error_report,
+    statements::{parse_statements, parse_statements_with_recovery, Statement, WithLine},
+};
+use semantic::{
+    lint::{Lint, LintCheck, LintConfig, LintId, LintLevel, Linter},
+    scope::scope::ScopeManager,
+    Resolve, SemanticError,
+};
 
 pub mod ast;
 pub mod semantic;
@@ -12,7 +22,7 @@ pub type CiphelResult<T> = Option<T>;
 use thiserror::Error;
 use vm::{
     allocator::heap::Heap,
-    external::{ExternProcessIdentifier, ExternThreadIdentifier},
+    external::{ExternProcessIdentifier, ExternThreadIdentifier, ModuleLoader},
     runtime::{Runtime, RuntimeError},
     scheduler::SchedulingPolicy,
     stdio::StdIO,
@@ -24,8 +34,9 @@ pub enum CompilationError<PID: ExternProcessIdentifier, TID: ExternThreadIdentif
     #[error("Parsing Error :\n{0}")]
     ParsingError(String),
 
-    #[error("Compilation Error at line {0}:\n{1}")]
-    SemanticError(usize, SemanticError),
+    // the report shows the error on the snippets of the source it was found in
+    #[error("Compilation Error at line {0}:\n{2}")]
+    SemanticError(usize, SemanticError, String),
 
     #[error("Compilation Error at line {0}:\n{1}")]
     CodeGen(usize, CodeGenerationError),
@@ -35,14 +46,109 @@ pub enum CompilationError<PID: ExternProcessIdentifier, TID: ExternThreadIdentif
 
     #[error("Invalid process id : {0}")]
     InvalidPID(PID),
+
+    #[error("Unknown module \"{1}\" imported at line {0}")]
+    UnknownModule(usize, String),
+
+    #[error("Cyclic import of the module \"{1}\" at line {0}")]
+    CyclicImport(usize, String),
+
+    // the error is located in the source of the module imported at the line
+    #[error("Error in the module \"{1}\" imported at line {0}:\n{}", .2.to_string())]
+    ModuleError(usize, String, Box<CompilationError<PID, TID>>),
+
+    #[error("Lint at line {0}:\n{1}")]
+    Lint(usize, Lint),
+
     #[error("Transaction Error : {0}")]
     TransactionError(&'static str),
 }
 
+impl<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> CompilationError<PID, TID> {
+    /*
+     * @desc Semantic error found in a source, reported on the snippets of the source
+     */
+    pub fn semantic(source: &str, line_offset: usize, line: usize, error: SemanticError) -> Self {
+        let report = generate_semantic_error_report(source, &error, line_offset);
+        CompilationError::SemanticError(line, error, report)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/*
+ * @desc Problem found during a compilation, a compilation reports all of its diagnostics at once
+ */
+#[derive(Debug, Clone)]
+pub struct Diagnostic<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> {
+    pub severity: Severity,
+    pub error: CompilationError<PID, TID>,
+}
+
+impl<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> Diagnostic<PID, TID> {
+    pub fn error(error: CompilationError<PID, TID>) -> Self {
+        Self {
+            severity: Severity::Error,
+            error,
+        }
+    }
+
+    pub fn warning(error: CompilationError<PID, TID>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            error,
+        }
+    }
+
+    /*
+     * @desc Diagnostic of a lint, a denied lint is an error
+     */
+    pub fn lint(line: usize, lint: Lint) -> Self {
+        let severity = match lint.level {
+            LintLevel::Deny => Severity::Error,
+            LintLevel::Allow | LintLevel::Warn => Severity::Warning,
+        };
+        Self {
+            severity,
+            error: CompilationError::Lint(line, lint),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> std::fmt::Display
+    for Diagnostic<PID, TID>
+where
+    CompilationError<PID, TID>: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.severity, &self.error) {
+            // the report of a semantic error is already headed by its severity
+            (Severity::Error, CompilationError::SemanticError(_, _, report)) => {
+                write!(f, "{}", report.trim_end())
+            }
+            (Severity::Error, error) => write!(f, "error: {}", error),
+            (Severity::Warning, error) => write!(f, "warning: {}", error),
+        }
+    }
+}
+
+pub type Diagnostics<PID, TID> = Vec<Diagnostic<PID, TID>>;
+
 pub struct Ciphel<E: crate::vm::external::Engine, P: SchedulingPolicy> {
     pub runtime: Runtime<E, P>,
     pub heap: Heap,
     pub stdio: StdIO,
+    pub loader: Option<Box<dyn ModuleLoader<E::PID>>>,
+    pub lints: LintConfig,
+    checked_modules: HashMap<E::PID, ScopeManager>, // the modules of each process once resolved
 }
 
 impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Default for Ciphel<E, P> {
@@ -51,63 +157,328 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Default for Ciphel<E,
             runtime: Runtime::default(),
             heap: Heap::new(),
             stdio: StdIO::default(),
+            loader: None,
+            lints: LintConfig::default(),
+            checked_modules: HashMap::default(),
         }
     }
 }
 
 impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Ciphel<E, P> {
 
-    pub fn compile_module(module: &str) -> Result<(), CompilationError<E::PID, E::TID>> {
-        let mut module = parse_module(module.into(), 0)?;
+    pub fn compile_module(source: &str) -> Result<(), CompilationError<E::PID, E::TID>> {
+        let mut module = parse_module(source.into(), 0)?;
         let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
         match module
             .resolve::<E>(&mut scope_manager, None, &(), &mut ()) {
             Ok(_) => {},
-            Err(err) => return Err(CompilationError::SemanticError(0, err)),
+            Err(err) => {
+                let line = err.span().map_or(0, |span| span.line);
+                return Err(CompilationError::semantic(source, 0, line, err));
+            }
         }
         Ok(())
     }
 
+    pub fn set_module_loader(&mut self, loader: impl ModuleLoader<E::PID> + 'static) {
+        self.loader = Some(Box::new(loader));
+    }
+
+    pub fn set_lint_level(&mut self, id: LintId, level: LintLevel) {
+        self.lints.set(id, level);
+    }
+
     pub fn import(
         &mut self,
         pid: E::PID,
-        module: &str,
+        source: &str,
         line_offset: usize,
+    ) -> Result<Diagnostics<E::PID, E::TID>, Diagnostics<E::PID, E::TID>> {
+        let mut diagnostics = Diagnostics::default();
+        let res = parse_module(source.into(), line_offset).and_then(|module| {
+            let mut loading = Vec::default();
+            for import in module.imports() {
+                let line = import.line + line_offset;
+                self.load_module(pid, &import.path, line, &mut loading, &mut diagnostics)?;
+            }
+            self.push_module(pid, module, source, line_offset, &mut diagnostics)
+        });
+        match res {
+            Ok(()) => Ok(diagnostics),
+            Err(err) => {
+                diagnostics.push(Diagnostic::error(err));
+                Err(diagnostics)
+            }
+        }
+    }
+
+    /*
+     * @desc Load a module and its imports through the module loader, once per process
+     * The imports of a module are pushed before the module itself
+     */
+    fn load_module(
+        &mut self,
+        pid: E::PID,
+        path: &str,
+        line: usize,
+        loading: &mut Vec<String>,
+        diagnostics: &mut Diagnostics<E::PID, E::TID>,
+    ) -> Result<(), CompilationError<E::PID, E::TID>> {
+        let Some(modules) = self.runtime.modules.get(&pid) else {
+            return Err(CompilationError::InvalidPID(pid));
+        };
+        if modules.iter().any(|module| module.path() == Some(path)) {
+            return Ok(());
+        }
+        if loading.iter().any(|loading_path| loading_path == path) {
+            return Err(CompilationError::CyclicImport(line, path.to_string()));
+        }
+        let Some(source) = self
+            .loader
+            .as_mut()
+            .and_then(|loader| loader.load(pid, path))
+        else {
+            return Err(CompilationError::UnknownModule(line, path.to_string()));
+        };
+
+        // the diagnostics of the module are reported at the import, along the path of the module
+        let in_module = |err| CompilationError::ModuleError(line, path.to_string(), Box::new(err));
+        let reported = diagnostics.len();
+        let res = parse_module(source.as_str().into(), 0).and_then(|mut module| {
+            module.set_path(path.to_string());
+
+            loading.push(path.to_string());
+            for import in module.imports() {
+                self.load_module(pid, &import.path, import.line, loading, diagnostics)?;
+            }
+            loading.pop();
+
+            self.push_module(pid, module, &source, 0, diagnostics)
+        });
+        for diagnostic in diagnostics[reported..].iter_mut() {
+            diagnostic.error = in_module(diagnostic.error.clone());
+        }
+        res.map_err(in_module)
+    }
+
+    /*
+     * @desc Check a module against the modules of the process before sharing it with the threads
+     * The lints of the module are reported, a denied lint prevents the module from being shared
+     */
+    fn push_module(
+        &mut self,
+        pid: E::PID,
+        module: Module,
+        source: &str,
+        line_offset: usize,
+        diagnostics: &mut Diagnostics<E::PID, E::TID>,
     ) -> Result<(), CompilationError<E::PID, E::TID>> {
-        let module = parse_module(module.into(), 0)?;
         let Some(modules) = self.runtime.modules.get_mut(&pid) else {
             return Err(CompilationError::InvalidPID(pid));
         };
+        // the known modules are resolved once, again only when the modules of the process were rolled back
+        let scope_manager = self.checked_modules.entry(pid).or_default();
+        if scope_manager.modules.len() != modules.len() {
+            *scope_manager = ScopeManager::default();
+            for known in modules.iter() {
+                let mut known = known.clone();
+                known
+                    .resolve::<E>(scope_manager, None, &(), &mut ())
+                    .map_err(|err| CompilationError::semantic("", 0, 0, err))?;
+                scope_manager.modules.push(known);
+            }
+        }
+
+        scope_manager.open_transaction();
+        let mut resolved = module.clone();
+        if let Err(err) = resolved.resolve::<E>(scope_manager, None, &(), &mut ()) {
+            scope_manager.reject_transaction();
+            let line = err
+                .span()
+                .map_or(line_offset, |span| span.line + line_offset);
+            return Err(CompilationError::semantic(source, line_offset, line, err));
+        }
+        scope_manager.modules.push(resolved);
+
+        let mut linter = Linter::new(scope_manager, self.lints.clone());
+        if let Some(resolved) = scope_manager.modules.last() {
+            resolved.lint(&mut linter);
+        }
+        // the first denied lint is the error of the module, the others are reported along it
+        let mut denied = None;
+        for lint in linter.lints() {
+            let line = lint.span.line + line_offset;
+            match lint.level {
+                LintLevel::Deny if denied.is_none() => {
+                    denied = Some(CompilationError::Lint(line, lint))
+                }
+                _ => diagnostics.push(Diagnostic::lint(line, lint)),
+            }
+        }
+        if let Some(err) = denied {
+            scope_manager.reject_transaction();
+            return Err(err);
+        }
+        scope_manager.accept_transaction();
         modules.push(module);
         Ok(())
     }
 
+    /*
+     * @desc Compile a source in the context of a thread
+     * The compilation goes on after an error to report every diagnostic of the source,
+     * the changes of the compilation, including the modules it imports, are rolled back if any of them is an error
+     */
     pub fn compile(
         &mut self,
         tid: E::TID,
         src_code: &str,
         line_offset: usize,
-    ) -> Result<(), CompilationError<E::PID, E::TID>> {
-        let mut statements = parse_statements(src_code.into(), line_offset)?;
-        let vm::runtime::ThreadContext {
-            scope_manager,
-            program,
-            ..
-        } = self
+    ) -> Result<Diagnostics<E::PID, E::TID>, Diagnostics<E::PID, E::TID>> {
+        let (mut statements, errors) =
+            parse_statements_with_recovery(src_code.into(), line_offset);
+        let mut diagnostics: Diagnostics<E::PID, E::TID> =
+            errors.into_iter().map(Diagnostic::error).collect();
+
+        // the modules loaded by the imports are only kept along with the compilation
+        let staged = self.runtime.modules.get(&tid.pid()).map_or(0, Vec::len);
+        // the code generation can fail after some instructions were pushed
+        let checkpoint = self
             .runtime
             .context_of(&tid)
-            .map_err(|_| CompilationError::InvalidTID(tid))?;
+            .ok()
+            .map(|context| context.program.checkpoint());
+        let mut loading = Vec::default();
+        let mut failed_imports = Vec::default();
+        for (index, statement) in statements.iter().enumerate() {
+            if let Statement::Import(import) = &statement.inner {
+                let line = import.line + line_offset;
+                if let Err(err) = self.load_module(
+                    tid.pid(),
+                    &import.path,
+                    line,
+                    &mut loading,
+                    &mut diagnostics,
+                ) {
+                    diagnostics.push(Diagnostic::error(err));
+                    failed_imports.push(index);
+                }
+            }
+        }
+
+        let res = self.compile_statements(
+            &tid,
+            src_code,
+            &mut statements,
+            &failed_imports,
+            diagnostics,
+            line_offset,
+        );
+        if res.is_err() {
+            if let Some(modules) = self.runtime.modules.get_mut(&tid.pid()) {
+                modules.truncate(staged);
+            }
+            self.checked_modules.remove(&tid.pid());
+            if let (Some(checkpoint), Ok(context)) = (checkpoint, self.runtime.context_of(&tid)) {
+                context.program.rollback(checkpoint);
+            }
+        }
+        res
+    }
 
+    /*
+     * @desc Resolve and generate the statements of a source once its imports are loaded
+     * The modules the thread does not know yet are linked within the transaction of the statements
+     */
+    fn compile_statements(
+        &mut self,
+        tid: &E::TID,
+        source: &str,
+        statements: &mut [WithLine<Statement>],
+        failed_imports: &[usize],
+        mut diagnostics: Diagnostics<E::PID, E::TID>,
+        line_offset: usize,
+    ) -> Result<Diagnostics<E::PID, E::TID>, Diagnostics<E::PID, E::TID>> {
+        let Ok(vm::runtime::ThreadContext { scope_manager, .. }) = self.runtime.context_of(tid)
+        else {
+            diagnostics.push(Diagnostic::error(CompilationError::InvalidTID(tid.clone())));
+            return Err(diagnostics);
+        };
         scope_manager.open_transaction();
 
-        for statement in statements.iter_mut() {
+        let linked = self.runtime.resolve_modules(tid);
+        let Ok(vm::runtime::ThreadContext { scope_manager, .. }) = self.runtime.context_of(tid)
+        else {
+            diagnostics.push(Diagnostic::error(CompilationError::InvalidTID(tid.clone())));
+            return Err(diagnostics);
+        };
+        let linked = match linked {
+            Ok(linked) => linked,
+            Err(err) => {
+                scope_manager.reject_transaction();
+                diagnostics.push(Diagnostic::error(err));
+                return Err(diagnostics);
+            }
+        };
+
+        for (index, statement) in statements.iter_mut().enumerate() {
+            // the import that could not be loaded has already been reported
+            if failed_imports.contains(&index) {
+                continue;
+            }
             if let Err(err) = statement.resolve::<E>(scope_manager, None, &None, &mut ()) {
+                let line = err
+                    .span()
+                    .map(|span| span.line + line_offset)
+                    .unwrap_or(statement.line);
+                diagnostics.push(Diagnostic::error(CompilationError::semantic(
+                    source,
+                    line_offset,
+                    line,
+                    err,
+                )));
+            }
+        }
+        if diagnostics.iter().any(Diagnostic::is_error) {
+            scope_manager.reject_transaction();
+            return Err(diagnostics);
+        }
+
+        let mut linter = Linter::new(scope_manager, self.lints.clone());
+        for statement in statements.iter() {
+            statement.lint(&mut linter);
+        }
+        for lint in linter.lints() {
+            let line = lint.span.line + line_offset;
+            diagnostics.push(Diagnostic::lint(line, lint));
+        }
+        if diagnostics.iter().any(Diagnostic::is_error) {
+            scope_manager.reject_transaction();
+            return Err(diagnostics);
+        }
+
+        // the code of the linked modules is generated once the source is known to be valid
+        if let Err(err) = self.runtime.gencode_modules(tid, linked) {
+            if let Ok(vm::runtime::ThreadContext { scope_manager, .. }) =
+                self.runtime.context_of(tid)
+            {
                 scope_manager.reject_transaction();
-                return Err(CompilationError::SemanticError(statement.line, err));
             }
+            diagnostics.push(Diagnostic::error(err));
+            return Err(diagnostics);
         }
+        let Ok(vm::runtime::ThreadContext {
+            scope_manager,
+            program,
+            ..
+        }) = self.runtime.context_of(tid)
+        else {
+            diagnostics.push(Diagnostic::error(CompilationError::InvalidTID(tid.clone())));
+            return Err(diagnostics);
+        };
 
-        for statement in statements {
+        program.source_map.set_line_offset(line_offset);
+        for statement in statements.iter() {
             if let Err(err) = statement.gencode::<E>(
                 scope_manager,
                 None,
@@ -115,11 +486,15 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Ciphel<E, P> {
                 &crate::vm::CodeGenerationContext::default(),
             ) {
                 scope_manager.reject_transaction();
-                return Err(CompilationError::CodeGen(statement.line, err));
+                diagnostics.push(Diagnostic::error(CompilationError::CodeGen(
+                    statement.line,
+                    err,
+                )));
+                return Err(diagnostics);
             }
         }
         scope_manager.accept_transaction();
-        Ok(())
+        Ok(diagnostics)
     }
 
     pub fn run(&mut self, engine: &mut E) -> Result<(), (E::PID,RuntimeError)> {
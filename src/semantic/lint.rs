@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::ast::{
+    statements::annotation::{AnnotationArg, Annotations},
+    utils::io::SourceSpan,
+};
+
+use super::scope::scope::ScopeManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintId {
+    UnusedVariable,
+    UnusedItem,
+    ShadowedVariable,
+    UnreachableCode,
+    UnusedError,
+}
+
+impl LintId {
+    pub const ALL: [LintId; 5] = [
+        LintId::UnusedVariable,
+        LintId::UnusedItem,
+        LintId::ShadowedVariable,
+        LintId::UnreachableCode,
+        LintId::UnusedError,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintId::UnusedVariable => "unused_variable",
+            LintId::UnusedItem => "unused_item",
+            LintId::ShadowedVariable => "shadowed_variable",
+            LintId::UnreachableCode => "unreachable_code",
+            LintId::UnusedError => "unused_error",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.name() == name)
+    }
+}
+
+impl std::fmt::Display for LintId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LintLevel {
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    fn from_annotation(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * @desc Level of each lint, a lint is a warning unless the host or an annotation says otherwise
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintConfig {
+    levels: HashMap<LintId, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn set(&mut self, id: LintId, level: LintLevel) {
+        self.levels.insert(id, level);
+    }
+
+    pub fn level(&self, id: LintId) -> LintLevel {
+        self.levels.get(&id).copied().unwrap_or_default()
+    }
+
+    /*
+     * @desc Configuration overridden by the lint annotations of a statement
+     *
+     * @grammar
+     * LintAnnotation := @allow( LintIds ) | @warn( LintIds ) | @deny( LintIds )
+     */
+    pub fn with_annotations(&self, annotations: &Annotations) -> Self {
+        let mut config = self.clone();
+        for annotation in annotations.0.iter() {
+            let Some(level) = LintLevel::from_annotation(&annotation.name) else {
+                continue;
+            };
+            for arg in annotation.args.iter() {
+                if let AnnotationArg::Id(name) = arg {
+                    if let Some(id) = LintId::from_name(name) {
+                        config.set(id, level);
+                    }
+                }
+            }
+        }
+        config
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{message} [{id}]")]
+pub struct Lint {
+    pub id: LintId,
+    pub level: LintLevel,
+    pub span: SourceSpan,
+    pub message: String,
+}
+
+/*
+ * @desc Walk over resolved statements collecting the lints
+ */
+pub struct Linter<'a> {
+    pub scope_manager: &'a ScopeManager,
+    config: LintConfig,
+    span: SourceSpan,         // location of the enclosing top level statement
+    frames: Vec<Vec<String>>, // variables declared by the enclosing blocks
+    lints: Vec<Lint>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(scope_manager: &'a ScopeManager, config: LintConfig) -> Self {
+        Self {
+            scope_manager,
+            config,
+            span: SourceSpan::default(),
+            frames: vec![Vec::default()],
+            lints: Vec::default(),
+        }
+    }
+
+    pub fn lints(self) -> Vec<Lint> {
+        self.lints
+    }
+
+    /*
+     * @desc Report a lint at the given location, or at the enclosing statement if it is unknown
+     */
+    pub fn report(&mut self, id: LintId, span: SourceSpan, message: String) {
+        let level = self.config.level(id);
+        if level == LintLevel::Allow {
+            return;
+        }
+        self.lints.push(Lint {
+            id,
+            level,
+            span: if span.is_known() { span } else { self.span },
+            message,
+        });
+    }
+
+    pub fn at(&mut self, span: SourceSpan, check: impl FnOnce(&mut Self)) {
+        let enclosing = std::mem::replace(&mut self.span, span);
+        check(self);
+        self.span = enclosing;
+    }
+
+    pub fn with_annotations(&mut self, annotations: &Annotations, check: impl FnOnce(&mut Self)) {
+        let config = self.config.with_annotations(annotations);
+        let enclosing = std::mem::replace(&mut self.config, config);
+        check(self);
+        self.config = enclosing;
+    }
+
+    pub fn in_block(&mut self, check: impl FnOnce(&mut Self)) {
+        self.frames.push(Vec::default());
+        check(self);
+        self.frames.pop();
+    }
+
+    /*
+     * @desc Bring a name in the current block without checking it, such as a parameter
+     */
+    pub fn bind(&mut self, name: &str) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.push(name.to_string());
+        }
+    }
+
+    /*
+     * @desc Record a variable declared by a let and check its shadowing and its use
+     * The top level variables are not checked for use as a later compilation can read them
+     */
+    pub fn declare(&mut self, name: &str, id: Option<u64>, span: SourceSpan) {
+        if self
+            .frames
+            .iter()
+            .flatten()
+            .any(|declared| declared == name)
+        {
+            self.report(
+                LintId::ShadowedVariable,
+                span,
+                format!("the variable {} shadows a previous variable", name),
+            );
+        }
+        let is_local = self.frames.len() > 1;
+        if let Some(id) = id.filter(|_| is_local && !name.starts_with('_')) {
+            if !self.scope_manager.is_variable_used(id) {
+                self.report(
+                    LintId::UnusedVariable,
+                    span,
+                    format!("unused variable {}", name),
+                );
+            }
+        }
+        self.bind(name);
+    }
+}
+
+pub trait LintCheck {
+    fn lint(&self, linter: &mut Linter);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        vm::external::test::{DefaultProcessID, DefaultThreadID, NoopEngine},
+        vm::scheduler::ToCompletion,
+        Ciphel, CompilationError, Diagnostics,
+    };
+
+    use super::*;
+
+    fn new_ciphel() -> (Ciphel<NoopEngine, ToCompletion>, DefaultThreadID) {
+        let mut engine = NoopEngine {};
+        let mut ciphel = Ciphel::<NoopEngine, ToCompletion>::default();
+        ciphel
+            .runtime
+            .modules
+            .insert(DefaultProcessID::default(), Vec::default());
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+        (ciphel, tid)
+    }
+
+    fn lints_of(
+        diagnostics: &Diagnostics<DefaultProcessID, DefaultThreadID>,
+    ) -> Vec<(LintId, LintLevel, usize)> {
+        diagnostics
+            .iter()
+            .filter_map(|diagnostic| match &diagnostic.error {
+                CompilationError::Lint(line, lint) => Some((lint.id, lint.level, *line)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_lints() {
+        let (mut ciphel, tid) = new_ciphel();
+        let diagnostics = ciphel
+            .compile(
+                tid,
+                r##"
+        fn compute(x : i64) -> i64 {
+            let unused = 1;
+            let _ignored = 2;
+            let y = x + 1;
+            if y > 2 {
+                let y = 3;
+                return y;
+            }
+            return y;
+            let z = 4;
+        }
+        close(7);
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        assert_eq!(
+            lints_of(&diagnostics),
+            vec![
+                (LintId::UnusedVariable, LintLevel::Warn, 3),
+                (LintId::ShadowedVariable, LintLevel::Warn, 7),
+                (LintId::UnreachableCode, LintLevel::Warn, 11),
+                (LintId::UnusedError, LintLevel::Warn, 13),
+            ]
+        );
+        assert!(!diagnostics.iter().any(crate::Diagnostic::is_error));
+    }
+
+    #[test]
+    fn valid_lint_levels() {
+        let (mut ciphel, tid) = new_ciphel();
+        let diagnostics = ciphel
+            .compile(
+                tid.clone(),
+                r##"
+        @allow(unused_variable)
+        fn compute() -> i64 {
+            let unused = 1;
+            2
+        }
+            "##,
+                0,
+            )
+            .expect("Compilation should have succeeded");
+        assert_eq!(lints_of(&diagnostics), vec![]);
+
+        let res = ciphel.compile(
+            tid.clone(),
+            r##"
+        @deny(unused_variable)
+        fn other() -> i64 {
+            let unused = 1;
+            2
+        }
+            "##,
+            0,
+        );
+        let diagnostics = res.expect_err("Compilation should have failed");
+        assert_eq!(
+            lints_of(&diagnostics),
+            vec![(LintId::UnusedVariable, LintLevel::Deny, 4)]
+        );
+
+        ciphel.set_lint_level(LintId::UnusedError, LintLevel::Deny);
+        let res = ciphel.compile(tid.clone(), "close(7);", 0);
+        let diagnostics = res.expect_err("Compilation should have failed");
+        assert_eq!(
+            lints_of(&diagnostics),
+            vec![(LintId::UnusedError, LintLevel::Deny, 1)]
+        );
+        let res = ciphel.compile(tid, "let err = close(7);", 0);
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    #[test]
+    fn valid_lint_module() {
+        let (mut ciphel, _) = new_ciphel();
+        let diagnostics = ciphel
+            .import(
+                DefaultProcessID::default(),
+                r##"
+        module Lib {
+            struct Hidden {
+                x : i64,
+            }
+
+            struct Inner {
+                x : i64,
+            }
+
+            fn unused() -> i64 {
+                1
+            }
+
+            pub fn api() -> Inner {
+                Inner { x : 2 }
+            }
+        }
+            "##,
+                0,
+            )
+            .expect("Import should have succeeded");
+        assert_eq!(
+            lints_of(&diagnostics),
+            vec![
+                (LintId::UnusedItem, LintLevel::Warn, 3),
+                (LintId::UnusedItem, LintLevel::Warn, 11),
+            ]
+        );
+
+        ciphel.set_lint_level(LintId::UnusedItem, LintLevel::Deny);
+        let res = ciphel.import(
+            DefaultProcessID::default(),
+            "module Other { fn unused() -> i64 { 1 } }",
+            0,
+        );
+        assert!(res.is_err(), "{:?}", res);
+        assert!(ciphel.runtime.modules[&DefaultProcessID::default()]
+            .iter()
+            .all(|module| module.name() != "Other"));
+    }
+}
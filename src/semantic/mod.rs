@@ -1,15 +1,17 @@
 use std::collections::HashSet;
 
-use crate::vm::CodeGenerationError;
+use crate::{ast::utils::io::SourceSpan, vm::CodeGenerationError};
 
 use self::scope::{static_types::StaticType, user_types::UserType};
 
 use scope::{
     static_types::NumberType,
-    user_types::{Enum, Struct, Union},
+    user_types::{Enum, Interface, Struct, Union},
 };
 use thiserror::Error;
+pub mod lint;
 pub mod scope;
+pub mod symbols;
 
 #[derive(Debug, Clone, Error)]
 pub enum SemanticError {
@@ -38,24 +40,49 @@ pub enum SemanticError {
     ExpectedEnum,
     #[error("expected a struct")]
     ExpectedStruct,
+    #[error("expected an interface")]
+    ExpectedInterface,
     #[error("expected to be inside a loop")]
     ExpectedLoop,
     #[error("expected to be inside a closure")]
     ExpectedClosure,
     #[error("expected to be inside a functio, a closure or a lambda")]
     ExpectedFunctionClosureLambda,
+    #[error("expected an error")]
+    ExpectedError,
     #[error("expected something assignable")]
     ExpectedLeftExpression,
+    #[error("expected a value known at compile time")]
+    ExpectedConstant,
+    #[error("the constant overflows or divides by zero")]
+    ConstantOverflow,
+    #[error("expected a field name")]
+    ExpectedFieldName,
 
     #[error("unknown variable : {0}")]
     UnknownVar(String),
     #[error("unknown type : {0}")]
     UnknownType(String),
-    #[error("unknown field")]
-    UnknownField,
+    #[error("unknown field : {0}")]
+    UnknownField(String),
+    #[error("unknown method : {0}")]
+    UnknownMethod(String),
+    #[error("unknown module item : {0}")]
+    UnknownItem(String),
+    #[error("unknown module : {0}")]
+    UnknownModule(String),
+    #[error("{0} is private")]
+    PrivateItem(String),
 
-    #[error("incorrect function arguments")]
-    IncorrectArguments,
+    #[error("expected {expected} arguments, found {found}")]
+    IncorrectArgumentCount { expected: usize, found: usize },
+    #[error("invalid argument {position} : {error}")]
+    InvalidArgument {
+        position: usize,
+        error: Box<SemanticError>,
+    },
+    #[error("invalid field {field} : {error}")]
+    InvalidField { field: String, error: Box<SemanticError> },
     #[error("invalid arguments for struct {0}")]
     IncorrectStruct(String),
     #[error("invalid arguments for union {0}")]
@@ -64,9 +91,19 @@ pub enum SemanticError {
     IncorrectVariant(String),
     #[error("invalid pattern")]
     InvalidPattern,
+    #[error("invalid type arguments for {0}")]
+    IncorrectGenerics(String),
+    #[error("{0} is already defined")]
+    AlreadyDefined(String),
+    #[error("invalid implementation of the interface {0}")]
+    IncorrectImplementation(String),
+    #[error("impl blocks are only allowed at top level or in modules")]
+    NestedImpl,
 
     #[error("incompatible types")]
     IncompatibleTypes,
+    #[error("mismatched types : expected {expected}, found {found}")]
+    MismatchedTypes { expected: NamedType, found: NamedType },
     #[error("incompatible operation")]
     IncompatibleOperation,
     #[error("incompatible operands")]
@@ -76,11 +113,149 @@ pub enum SemanticError {
     ConcurrencyError,
     #[error("unexpected error")]
     Default,
+
+    #[error("{error}")]
+    Spanned {
+        span: SourceSpan,
+        error: Box<SemanticError>,
+        notes: Vec<(SourceSpan, String)>, // related locations
+    },
 }
 
+impl SemanticError {
+    /*
+     * @desc Locate the error, the innermost location is kept
+     */
+    pub fn at(self, span: SourceSpan) -> Self {
+        match self {
+            SemanticError::Spanned { .. } => self,
+            _ if !span.is_known() => self,
+            error => SemanticError::Spanned {
+                span,
+                error: Box::new(error),
+                notes: Vec::default(),
+            },
+        }
+    }
+
+    pub fn with_note(mut self, span: SourceSpan, note: &str) -> Self {
+        if let SemanticError::Spanned { notes, .. } = &mut self {
+            if span.is_known() {
+                notes.push((span, note.to_string()));
+            }
+        }
+        self
+    }
+
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            SemanticError::Spanned { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
+    /*
+     * @desc Error without its location
+     */
+    pub fn into_inner(self) -> Self {
+        match self {
+            SemanticError::Spanned { error, .. } => *error,
+            error => error,
+        }
+    }
+    /*
+     * @desc Mismatch between the type expected at a location and the type found there
+     */
+    pub fn mismatched(
+        expected: &EType,
+        found: &EType,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Self {
+        SemanticError::MismatchedTypes {
+            expected: NamedType::new(expected, scope_manager, scope_id),
+            found: NamedType::new(found, scope_manager, scope_id),
+        }
+    }
+
+    /*
+     * @desc Mismatch between the kind of types expected at a location, such as any vector, and the type found there
+     */
+    pub fn mismatched_kind(
+        expected: &str,
+        found: &EType,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Self {
+        SemanticError::MismatchedTypes {
+            expected: NamedType {
+                etype: EType::Static(StaticType::Any),
+                name: expected.to_string(),
+            },
+            found: NamedType::new(found, scope_manager, scope_id),
+        }
+    }
+
+    /*
+     * @desc Mismatch between the type expected at a location and the kind of value found there, such as an array
+     */
+    pub fn unexpected_kind(
+        expected: &EType,
+        found: &str,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Self {
+        SemanticError::MismatchedTypes {
+            expected: NamedType::new(expected, scope_manager, scope_id),
+            found: NamedType {
+                etype: EType::Static(StaticType::Any),
+                name: found.to_string(),
+            },
+        }
+    }
+}
+
+/*
+ * @desc Type carried by an error along with its name at the location of the error
+ */
 #[derive(Debug, Clone, PartialEq)]
+pub struct NamedType {
+    pub etype: EType,
+    pub name: String,
+}
+
+impl NamedType {
+    pub fn new(
+        etype: &EType,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Self {
+        Self {
+            etype: etype.clone(),
+            name: etype
+                .name(scope_manager, scope_id)
+                .unwrap_or_else(|_| format!("{:?}", etype)),
+        }
+    }
+}
+
+impl std::fmt::Display for NamedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Metadata {
     pub info: Info,
+    pub span: SourceSpan,
+}
+
+// the location of a node does not take part in its equality
+impl PartialEq for Metadata {
+    fn eq(&self, other: &Self) -> bool {
+        self.info == other.info
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -117,6 +292,7 @@ impl Default for Metadata {
     fn default() -> Self {
         Self {
             info: Info::Unresolved,
+            span: SourceSpan::default(),
         }
     }
 }
@@ -155,13 +331,26 @@ pub trait ResolveNumber {
     fn resolve_number(&mut self, to: NumberType) -> Result<(), SemanticError>;
 }
 
+pub trait ConstEval {
+    /*
+     * @desc Value known at compile time, None when it depends on the runtime
+     * An operation on constants that would fail at runtime is an error
+     */
+    fn const_eval(&self) -> Result<Option<crate::ast::expressions::data::Data>, SemanticError>;
+}
+
 pub trait ResolveCore {
+    /*
+     * @desc Resolve a call to a core function
+     * The receiver of a method call is handed over already resolved as the first parameter
+     */
     fn resolve<E: crate::vm::external::Engine>(
         &mut self,
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<crate::ast::expressions::Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError>
     where
         Self: Sized;
@@ -218,13 +407,17 @@ pub trait SizeOf {
 }
 
 impl CompatibleWith for EType {
+    /*
+     * @desc Check that a type (other) is compatible with an expected type (self)
+     * A mismatch is reported on the outermost types
+     */
     fn compatible_with(
         &self,
         other: &Self,
         scope_manager: &crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<(), SemanticError> {
-        match (self, other) {
+        let res = match (self, other) {
             (EType::Static(x), EType::Static(y)) => x.compatible_with(y, scope_manager, scope_id),
             (
                 EType::User {
@@ -239,6 +432,15 @@ impl CompatibleWith for EType {
                 .then(|| ())
                 .ok_or(SemanticError::IncompatibleTypes),
             _ => Err(SemanticError::IncompatibleTypes),
+        };
+        match res {
+            Err(SemanticError::IncompatibleTypes | SemanticError::MismatchedTypes { .. }) => {
+                Err(SemanticError::MismatchedTypes {
+                    expected: NamedType::new(self, scope_manager, scope_id),
+                    found: NamedType::new(other, scope_manager, scope_id),
+                })
+            }
+            res => res,
         }
     }
 }
@@ -253,13 +455,16 @@ impl SizeOf for EType {
 }
 
 impl MergeType for EType {
+    /*
+     * @desc Merge two types into a common one, a mismatch is reported on the outermost types
+     */
     fn merge(
         &self,
         other: &Self,
         scope_manager: &crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<EType, SemanticError> {
-        match (self, other) {
+        let res = match (self, other) {
             (EType::Static(x), EType::Static(y)) => x.merge(y, scope_manager, scope_id),
             (
                 EType::User {
@@ -272,14 +477,24 @@ impl MergeType for EType {
                 },
             ) => {
                 if *id_x != *id_y || *size_x != *size_y {
-                    return Err(SemanticError::IncompatibleTypes);
+                    Err(SemanticError::IncompatibleTypes)
+                } else {
+                    Ok(EType::User {
+                        id: *id_x,
+                        size: *size_x,
+                    })
                 }
-                return Ok(EType::User {
-                    id: *id_x,
-                    size: *size_x,
-                });
             }
             _ => Err(SemanticError::IncompatibleTypes),
+        };
+        match res {
+            Err(SemanticError::IncompatibleTypes) => Err(SemanticError::mismatched(
+                self,
+                other,
+                scope_manager,
+                scope_id,
+            )),
+            res => res,
         }
     }
 }
@@ -287,7 +502,7 @@ impl MergeType for EType {
 impl EType {
     pub fn name(
         &self,
-        scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
     ) -> Result<String, CodeGenerationError> {
         match self {
@@ -362,6 +577,10 @@ impl EType {
                 StaticType::Unit => Ok("Unit".to_string()),
                 StaticType::Any => Ok("Any".to_string()),
                 StaticType::Error => Ok("Error".to_string()),
+                StaticType::TypedError(error_type) => Ok(format!(
+                    "Error[{0}]",
+                    error_type.0.name(scope_manager, scope_id)?
+                )),
                 StaticType::Address(addr_type) => {
                     Ok(format!("&{0}", addr_type.0.name(scope_manager, scope_id)?))
                 }
@@ -370,6 +589,13 @@ impl EType {
                     map_type.keys_type.name(scope_manager, scope_id)?,
                     map_type.values_type.name(scope_manager, scope_id)?,
                 )),
+                StaticType::Range(range_type) => Ok(format!(
+                    "Range[{0}]",
+                    EType::Static(StaticType::Primitive(
+                        scope::static_types::PrimitiveType::Number(range_type.num)
+                    ))
+                    .name(scope_manager, scope_id)?
+                )),
             },
             EType::User { id, size } => {
                 let Ok(utype) = scope_manager.find_type_by_id(*id, scope_id) else {
@@ -379,6 +605,7 @@ impl EType {
                     UserType::Struct(Struct { id, fields }) => Ok(id),
                     UserType::Enum(Enum { id, values }) => Ok(id),
                     UserType::Union(Union { id, variants }) => Ok(id),
+                    UserType::Interface(Interface { id, methods }) => Ok(id),
                 }
             }
         }
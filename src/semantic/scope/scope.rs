@@ -6,12 +6,27 @@ use std::{
 use ulid::Ulid;
 
 use crate::{
-    ast::modules::Module,
-    semantic::{EType, SemanticError, SizeOf},
-    vm::{allocator::MemoryAddress, CodeGenerationError},
+    ast::utils::io::SourceSpan,
+    ast::{
+        expressions::data::Data,
+        modules::Module,
+        statements::{
+            annotation::{Annotation, Annotations},
+            definition::{FnDef, TypeDef},
+        },
+        utils::lexem,
+    },
+    semantic::{
+        symbols::{Symbol, Symbols},
+        EType, SemanticError, SizeOf,
+    },
+    vm::{allocator::MemoryAddress, runtime::RUNTIME_ERROR_VARIANTS, CodeGenerationError},
 };
 
-use super::{static_types::POINTER_SIZE, user_types::UserType};
+use super::{
+    static_types::POINTER_SIZE,
+    user_types::{Enum, UserType},
+};
 
 #[derive(Debug, Clone, Default, PartialEq, Copy)]
 pub enum VariableAddress {
@@ -97,6 +112,7 @@ pub struct VariableInfo {
     pub marked_as_closed_var: ClosedMarker,
     pub address: VariableAddress,
     pub state: VariableState,
+    pub span: SourceSpan, // location of the statement declaring the variable
 }
 
 #[derive(Debug, Clone)]
@@ -112,6 +128,47 @@ pub struct TypeInfo {
     pub count: usize,
     pub def: UserType,
     pub scope: Option<u128>,
+    pub span: SourceSpan, // location of the statement declaring the type
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstantInfo {
+    pub name: String,
+    pub value: Data,
+    pub scope: Option<u128>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportInfo {
+    pub alias: String,
+    pub target: Vec<String>, // full path of the imported item
+    pub scope: Option<u128>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenericFnInstance {
+    pub template: String,
+    pub def: FnDef,
+    pub is_generated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenericTypeInstance {
+    pub template: String,
+    pub args: Vec<EType>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MethodReceiver {
+    None,    // static method
+    Value,   // self
+    Address, // &self
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodInfo {
+    pub function: String, // name of the function implementing the method
+    pub receiver: MethodReceiver,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -160,16 +217,28 @@ pub struct TransactionStore {
     pub created_scopes: HashSet<u128>,
     pub created_vars: HashSet<u64>,
     pub created_types: HashSet<u64>,
+    pub created_generics: HashSet<String>,
+    pub created_methods: HashSet<(u64, String)>,
+    pub created_interface_impls: HashSet<(u64, u64)>,
+    pub created_constants: HashSet<(String, Option<u128>)>,
+    pub created_imports: HashSet<(String, Option<u128>)>,
     pub previous_global_top: usize,
+    pub previous_modules: usize, // number of modules linked before the transaction
     pub is_open: bool,
 }
 
 impl TransactionStore {
-    pub fn open(&mut self, previous_global_top: usize) {
+    pub fn open(&mut self, previous_global_top: usize, previous_modules: usize) {
         self.created_scopes.clear();
         self.created_vars.clear();
         self.created_types.clear();
+        self.created_generics.clear();
+        self.created_methods.clear();
+        self.created_interface_impls.clear();
+        self.created_constants.clear();
+        self.created_imports.clear();
         self.previous_global_top = previous_global_top;
+        self.previous_modules = previous_modules;
         self.is_open = true;
     }
 }
@@ -182,38 +251,89 @@ pub struct ScopeManager {
     types: Vec<TypeInfo>,
     scope_branches: HashMap<u128, Vec<u128>>, // parent scope of a given scope (key)
 
+    generic_fns: HashMap<String, FnDef>,
+    generic_fn_instances: Vec<GenericFnInstance>,
+    generic_types: HashMap<String, TypeDef>,
+    generic_type_instances: HashMap<u64, GenericTypeInstance>, // type id of the instance
+    type_params: HashMap<u128, Vec<(String, EType)>>,          // bound type parameters of a scope
+    methods: HashMap<(u64, String), MethodInfo>,               // type id and method name
+    interface_impls: HashSet<(u64, u64)>,                      // interface id and type id
+    constants: Vec<ConstantInfo>,
+    imports: Vec<ImportInfo>,
+    used_vars: HashSet<u64>,  // variables read at least once
+    used_types: HashSet<u64>, // types named at least once
+
     pub allocating_scope: HashMap<u128, FrameMapping>,
 
     pub scope_types: HashMap<u128, Vec<EType>>,
+    return_types: HashMap<u128, EType>, // declared return type of a function, closure or lambda
     pub scope_lookup: HashMap<u128, HashSet<u64>>,
     pub scope_states: HashMap<u128, ScopeState>,
     pub global_mapping: GlobalMapping,
 
     pub transaction_store: TransactionStore,
+
+    location: SourceSpan,          // location of the statement being resolved
+    annotations: Annotations,      // annotations of the statement being resolved or generated
+    module_prefix: Option<String>, // mangling prefix of the types of the module being resolved
+    symbols: Option<Symbols>,      // recorded only for the editors
 }
 
 impl Default for ScopeManager {
     fn default() -> Self {
-        Self {
+        let mut scope_manager = Self {
             scope_branches: HashMap::default(),
 
             types: Vec::default(),
             vars: HashMap::default(),
+
+            generic_fns: HashMap::default(),
+            generic_fn_instances: Vec::default(),
+            generic_types: HashMap::default(),
+            generic_type_instances: HashMap::default(),
+            type_params: HashMap::default(),
+            methods: HashMap::default(),
+            interface_impls: HashSet::default(),
+            constants: Vec::default(),
+            imports: Vec::default(),
+            used_vars: HashSet::default(),
+            used_types: HashSet::default(),
+
             allocating_scope: HashMap::default(),
 
             scope_types: HashMap::default(),
+            return_types: HashMap::default(),
             scope_lookup: HashMap::default(),
             scope_states: HashMap::default(),
             global_mapping: GlobalMapping::default(),
             modules: Vec::default(),
             transaction_store: TransactionStore::default(),
-        }
+            location: SourceSpan::default(),
+            annotations: Annotations::default(),
+            module_prefix: None,
+            symbols: None,
+        };
+        // language level enum of the errors caught by a try
+        let _ = scope_manager.register_type(
+            lexem::RUNTIME_ERROR,
+            UserType::Enum(Enum {
+                id: lexem::RUNTIME_ERROR.to_string(),
+                values: RUNTIME_ERROR_VARIANTS
+                    .iter()
+                    .enumerate()
+                    .map(|(code, name)| (name.to_string(), code as u64))
+                    .collect(),
+            }),
+            None,
+        );
+        scope_manager
     }
 }
 
 impl ScopeManager {
     pub fn open_transaction(&mut self) {
-        self.transaction_store.open(self.global_mapping.top);
+        self.transaction_store
+            .open(self.global_mapping.top, self.modules.len());
     }
     pub fn reject_transaction(&mut self) {
         self.global_mapping.top = self.transaction_store.previous_global_top;
@@ -224,14 +344,49 @@ impl ScopeManager {
         }
 
         // Remove created types
-        let types_to_keep: Vec<TypeInfo> = self
-            .types
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| !self.transaction_store.created_types.contains(&(*i as u64)))
-            .map(|(_, t)| t.clone())
-            .collect();
-        self.types = types_to_keep;
+        let created_types = &self.transaction_store.created_types;
+        self.types.retain(|t| !created_types.contains(&t.id));
+
+        // Remove created generic templates and instances
+        for name in self.transaction_store.created_generics.iter() {
+            self.generic_fns.remove(name);
+            self.generic_types.remove(name);
+        }
+        let created_vars = &self.transaction_store.created_vars;
+        self.generic_fn_instances.retain(|instance| {
+            instance
+                .def
+                .id
+                .as_ref()
+                .filter(|(id, _, _)| created_vars.contains(id))
+                .is_none()
+        });
+        let created_types = &self.transaction_store.created_types;
+        self.generic_type_instances
+            .retain(|id, _| !created_types.contains(id));
+
+        // Remove created methods
+        for method in self.transaction_store.created_methods.iter() {
+            self.methods.remove(method);
+        }
+        for implementation in self.transaction_store.created_interface_impls.iter() {
+            self.interface_impls.remove(implementation);
+        }
+
+        // Remove created constants
+        let created_constants = &self.transaction_store.created_constants;
+        self.constants.retain(|constant| {
+            !created_constants.contains(&(constant.name.clone(), constant.scope))
+        });
+
+        // Remove the modules linked during the transaction
+        self.modules
+            .truncate(self.transaction_store.previous_modules);
+
+        // Remove created imports
+        let created_imports = &self.transaction_store.created_imports;
+        self.imports
+            .retain(|import| !created_imports.contains(&(import.alias.clone(), import.scope)));
 
         // Remove created scopes and clean up related mappings
         for scope_id in self.transaction_store.created_scopes.iter() {
@@ -243,12 +398,16 @@ impl ScopeManager {
 
             // Remove from scope types
             self.scope_types.remove(scope_id);
+            self.return_types.remove(scope_id);
 
             // Remove from scope lookup
             self.scope_lookup.remove(scope_id);
 
             // Remove from scope states
             self.scope_states.remove(scope_id);
+
+            // Remove from type parameters
+            self.type_params.remove(scope_id);
         }
         self.transaction_store.is_open = false;
     }
@@ -378,6 +537,7 @@ impl ScopeManager {
                     scope,
                     address: VariableAddress::default(),
                     state: scope.map_or(VariableState::Global, |_| VariableState::Local),
+                    span: self.location,
                 },
             );
         }
@@ -416,6 +576,7 @@ impl ScopeManager {
                 scope,
                 address: VariableAddress::default(),
                 state: VariableState::Parameter,
+                span: self.location,
             },
         );
         if self.transaction_store.is_open {
@@ -452,6 +613,7 @@ impl ScopeManager {
                 scope: Some(scope),
                 address: VariableAddress::Local(0),
                 state: VariableState::Function,
+                span: self.location,
             },
         );
 
@@ -482,6 +644,7 @@ impl ScopeManager {
             count,
             def: ctype,
             scope,
+            span: self.location,
         });
 
         if self.transaction_store.is_open {
@@ -497,13 +660,26 @@ impl ScopeManager {
         scope: Option<u128>,
     ) -> Result<Variable, SemanticError> {
         if let Some(path) = path {
-            return self
-                .modules
-                .iter()
-                .find_map(|module| module.find_var(path, name))
+            let module = self.find_module(path, scope)?;
+            module.check_item(name)?;
+            return module
+                .find_var(name)
                 .ok_or(SemanticError::UnknownVar(name.to_string()));
         }
 
+        self.find_local_var_by_name(name, scope).or_else(|err| {
+            match self.find_import(name, scope) {
+                Some((name, path)) => self.find_var_by_name(name, Some(path), scope),
+                None => Err(err),
+            }
+        })
+    }
+
+    fn find_local_var_by_name(
+        &self,
+        name: &str,
+        scope: Option<u128>,
+    ) -> Result<Variable, SemanticError> {
         match scope {
             Some(scope) => {
                 let Some(branch) = self.scope_branches.get(&scope) else {
@@ -519,10 +695,17 @@ impl ScopeManager {
                     })
                     .collect();
 
+                // a variable of an inner scope shadows the variables of the same count
+                let depth = |v: &VariableInfo| {
+                    v.scope
+                        .and_then(|scope| branch.iter().position(|id| *id == scope))
+                        .map_or(0, |position| position + 1)
+                };
                 buffer.sort_by(|v1, v2| {
                     v1.count
                         .partial_cmp(&v2.count)
                         .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(depth(v1).cmp(&depth(v2)))
                 });
 
                 let Some(variable) = buffer.last() else {
@@ -591,6 +774,95 @@ impl ScopeManager {
         }
     }
 
+    /*
+     * @desc Record the use of a variable, the unused variables are reported by the lints
+     */
+    pub fn signal_variable_use(&mut self, id: u64) {
+        self.used_vars.insert(id);
+    }
+
+    pub fn is_variable_used(&self, id: u64) -> bool {
+        self.used_vars.contains(&id)
+    }
+
+    pub fn signal_type_use(&mut self, id: u64) {
+        self.used_types.insert(id);
+    }
+
+    pub fn is_type_used(&self, id: u64) -> bool {
+        self.used_types.contains(&id)
+    }
+
+    /*
+     * @desc Resolve a statement at its location
+     * The variables and types it registers are declared there
+     * An unknown location keeps the location of the enclosing statement
+     */
+    pub fn at<T>(&mut self, span: SourceSpan, resolve: impl FnOnce(&mut Self) -> T) -> T {
+        let enclosing = self.location;
+        if span.is_known() {
+            self.location = span;
+        }
+        let res = resolve(self);
+        self.location = enclosing;
+        res
+    }
+
+    /*
+     * @desc Resolve or generate an annotated statement
+     * Its annotations are readable from the nodes it contains, the innermost annotated statement prevails
+     */
+    pub fn annotated<T>(
+        &mut self,
+        annotations: &Annotations,
+        resolve: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let enclosing = std::mem::replace(&mut self.annotations, annotations.clone());
+        let res = resolve(self);
+        self.annotations = enclosing;
+        res
+    }
+
+    /*
+     * @desc Annotation of the statement being resolved or generated
+     */
+    pub fn annotation(&self, name: &str) -> Option<&Annotation> {
+        self.annotations.get(name)
+    }
+
+    /*
+     * @desc Resolve the items of a module, a type named within the module is first looked up under its mangled name
+     */
+    pub fn within_module<T>(&mut self, prefix: &str, resolve: impl FnOnce(&mut Self) -> T) -> T {
+        let enclosing = self.module_prefix.replace(prefix.to_string());
+        let res = resolve(self);
+        self.module_prefix = enclosing;
+        res
+    }
+
+    /*
+     * @desc Record the types and the references of the nodes resolved from now on
+     */
+    pub fn record_symbols(&mut self) {
+        self.symbols = Some(Symbols::default());
+    }
+
+    pub fn symbols(&self) -> Option<&Symbols> {
+        self.symbols.as_ref()
+    }
+
+    pub fn signal_signature(&mut self, span: SourceSpan, signature: &EType) {
+        if let Some(symbols) = self.symbols.as_mut() {
+            symbols.push_signature(span, signature.clone());
+        }
+    }
+
+    pub fn signal_reference(&mut self, span: SourceSpan, symbol: Symbol) {
+        if let Some(symbols) = self.symbols.as_mut() {
+            symbols.push_reference(span, symbol);
+        }
+    }
+
     pub fn mark_as_closed_var(
         &mut self,
         scope_id: u128,
@@ -696,6 +968,139 @@ impl ScopeManager {
         Ok(var.address.clone())
     }
 
+    pub fn register_constant(
+        &mut self,
+        name: &str,
+        value: Data,
+        scope: Option<u128>,
+    ) -> Result<(), SemanticError> {
+        if self
+            .constants
+            .iter()
+            .any(|constant| constant.name == name && constant.scope == scope)
+        {
+            return Err(SemanticError::AlreadyDefined(name.to_string()));
+        }
+        self.constants.push(ConstantInfo {
+            name: name.to_string(),
+            value,
+            scope,
+        });
+
+        if self.transaction_store.is_open {
+            self.transaction_store
+                .created_constants
+                .insert((name.to_string(), scope));
+        }
+        Ok(())
+    }
+
+    pub fn find_constant_by_name(
+        &self,
+        path: Option<&[String]>,
+        name: &str,
+        scope: Option<u128>,
+    ) -> Option<Data> {
+        if let Some(path) = path {
+            let module = self.find_module(path, scope).ok()?;
+            module.check_item(name).ok()?;
+            return module.find_constant(name);
+        }
+
+        let branch = scope.and_then(|scope| self.scope_branches.get(&scope));
+        self.constants
+            .iter()
+            .rev()
+            .filter(|constant| constant.name == name)
+            .find(|constant| match (constant.scope, branch) {
+                (None, _) => true,
+                (Some(scope), Some(branch)) => branch.contains(&scope),
+                (Some(_), None) => false,
+            })
+            .map(|constant| constant.value.clone())
+            .or_else(|| {
+                let (name, path) = self.find_import(name, scope)?;
+                self.find_constant_by_name(Some(path), name, scope)
+            })
+    }
+
+    pub fn register_import(
+        &mut self,
+        alias: &str,
+        target: Vec<String>,
+        scope: Option<u128>,
+    ) -> Result<(), SemanticError> {
+        if self
+            .imports
+            .iter()
+            .any(|import| import.alias == alias && import.scope == scope)
+        {
+            return Err(SemanticError::AlreadyDefined(alias.to_string()));
+        }
+        // the imported item is either a module or an item of a module
+        match target.split_last() {
+            Some((name, [])) => {
+                let _ = self.find_module(std::slice::from_ref(name), scope)?;
+            }
+            Some((name, path)) => self.find_module(path, scope)?.check_item(name)?,
+            None => return Err(SemanticError::UnknownItem(alias.to_string())),
+        }
+        self.imports.push(ImportInfo {
+            alias: alias.to_string(),
+            target,
+            scope,
+        });
+
+        if self.transaction_store.is_open {
+            self.transaction_store
+                .created_imports
+                .insert((alias.to_string(), scope));
+        }
+        Ok(())
+    }
+
+    /*
+     * @desc Item imported under the given alias, split into its name and the path of its module
+     */
+    fn find_import(&self, alias: &str, scope: Option<u128>) -> Option<(&str, &[String])> {
+        let branch = scope.and_then(|scope| self.scope_branches.get(&scope));
+        let import = self
+            .imports
+            .iter()
+            .rev()
+            .filter(|import| import.alias == alias)
+            .find(|import| match (import.scope, branch) {
+                (None, _) => true,
+                (Some(scope), Some(branch)) => branch.contains(&scope),
+                (Some(_), None) => false,
+            })?;
+        let (name, path) = import.target.split_last()?;
+        (!path.is_empty()).then_some((name.as_str(), path))
+    }
+
+    /*
+     * @desc Module reached by a path, the first segment of the path can be an imported module
+     */
+    pub fn find_module(
+        &self,
+        path: &[String],
+        scope: Option<u128>,
+    ) -> Result<&Module, SemanticError> {
+        let Some((first, rest)) = path.split_first() else {
+            return Err(SemanticError::UnknownItem(String::default()));
+        };
+        let mut expanded = path.to_vec();
+        if let Some((name, module_path)) = self.find_import(first, scope) {
+            expanded = module_path.to_vec();
+            expanded.push(name.to_string());
+            expanded.extend_from_slice(rest);
+        }
+        self.modules
+            .iter()
+            .find_map(|module| module.find_module(&expanded))
+            .unwrap_or(Err(SemanticError::UnknownItem(path.join(lexem::SEP))))
+    }
+
     pub fn find_type_by_name(
         &self,
         path: Option<&[String]>,
@@ -703,13 +1108,31 @@ impl ScopeManager {
         scope: Option<u128>,
     ) -> Result<Type, SemanticError> {
         if let Some(path) = path {
-            return self
-                .modules
-                .iter()
-                .find_map(|module| module.find_type(path, name))
+            let module = self.find_module(path, scope)?;
+            module.check_item(name)?;
+            return module
+                .find_type(name)
                 .ok_or(SemanticError::UnknownType(name.to_string()));
         }
 
+        if let Some(prefix) = &self.module_prefix {
+            if let Ok(found) = self.find_local_type_by_name(&format!("{prefix}{name}"), scope) {
+                return Ok(found);
+            }
+        }
+        self.find_local_type_by_name(name, scope).or_else(|err| {
+            match self.find_import(name, scope) {
+                Some((name, path)) => self.find_type_by_name(Some(path), name, scope),
+                None => Err(err),
+            }
+        })
+    }
+
+    fn find_local_type_by_name(
+        &self,
+        name: &str,
+        scope: Option<u128>,
+    ) -> Result<Type, SemanticError> {
         match scope {
             Some(scope) => {
                 let Some(branch) = self.scope_branches.get(&scope) else {
@@ -752,6 +1175,24 @@ impl ScopeManager {
         }
     }
 
+    /*
+     * @desc Type of the error bound by a try else branch
+     * The builtin enum is registered first so a user type of the same name cannot shadow it
+     */
+    pub fn runtime_error_type(&self) -> Result<EType, SemanticError> {
+        let Some(ctype) = self
+            .types
+            .iter()
+            .find(|v| v.name == lexem::RUNTIME_ERROR && v.scope.is_none())
+        else {
+            return Err(SemanticError::UnknownType(lexem::RUNTIME_ERROR.to_string()));
+        };
+        Ok(EType::User {
+            id: ctype.id,
+            size: ctype.def.size_of(),
+        })
+    }
+
     pub fn find_type_by_id(&self, id: u64, scope: Option<u128>) -> Result<UserType, SemanticError> {
         let ctype = self.types.iter().find(|var| var.id == id);
         match ctype {
@@ -760,6 +1201,196 @@ impl ScopeManager {
         }
     }
 
+    pub fn find_type_info_by_id(&self, id: u64) -> Option<&TypeInfo> {
+        self.types.iter().find(|info| info.id == id)
+    }
+
+    pub fn register_generic_fn(&mut self, def: FnDef) -> Result<(), SemanticError> {
+        if self.generic_fns.contains_key(&def.name) {
+            return Err(SemanticError::AlreadyDefined(def.name.clone()));
+        }
+        if self.transaction_store.is_open {
+            self.transaction_store
+                .created_generics
+                .insert(def.name.clone());
+        }
+        self.generic_fns.insert(def.name.clone(), def);
+        Ok(())
+    }
+
+    pub fn find_generic_fn(&self, name: &str) -> Option<&FnDef> {
+        self.generic_fns.get(name)
+    }
+
+    pub fn register_generic_fn_instance(&mut self, template: &str, def: FnDef) {
+        self.generic_fn_instances.push(GenericFnInstance {
+            template: template.to_string(),
+            def,
+            is_generated: false,
+        });
+    }
+
+    /// Mark as generated and return the instances of the given template that were not yet generated.
+    pub fn take_pending_fn_instances(&mut self, template: &str) -> Vec<FnDef> {
+        self.generic_fn_instances
+            .iter_mut()
+            .filter(|instance| !instance.is_generated && instance.template == template)
+            .map(|instance| {
+                instance.is_generated = true;
+                instance.def.clone()
+            })
+            .collect()
+    }
+
+    /// Mark as generated and return the instance registered under the given variable id if it was not yet generated.
+    pub fn take_pending_fn_instance_by_id(&mut self, var_id: u64) -> Option<FnDef> {
+        self.generic_fn_instances
+            .iter_mut()
+            .find(|instance| {
+                !instance.is_generated
+                    && instance
+                        .def
+                        .id
+                        .as_ref()
+                        .filter(|(id, _, _)| *id == var_id)
+                        .is_some()
+            })
+            .map(|instance| {
+                instance.is_generated = true;
+                instance.def.clone()
+            })
+    }
+
+    pub fn register_generic_type(&mut self, name: &str, def: TypeDef) -> Result<(), SemanticError> {
+        if self.generic_types.contains_key(name) {
+            return Err(SemanticError::AlreadyDefined(name.to_string()));
+        }
+        if self.transaction_store.is_open {
+            self.transaction_store
+                .created_generics
+                .insert(name.to_string());
+        }
+        self.generic_types.insert(name.to_string(), def);
+        Ok(())
+    }
+
+    pub fn find_generic_type(&self, name: &str) -> Option<&TypeDef> {
+        self.module_prefix
+            .as_ref()
+            .and_then(|prefix| self.generic_types.get(&format!("{prefix}{name}")))
+            .or_else(|| self.generic_types.get(name))
+    }
+
+    pub fn register_generic_type_instance(
+        &mut self,
+        type_id: u64,
+        template: &str,
+        args: Vec<EType>,
+    ) {
+        self.generic_type_instances.insert(
+            type_id,
+            GenericTypeInstance {
+                template: template.to_string(),
+                args,
+            },
+        );
+    }
+
+    pub fn find_generic_type_instance(&self, type_id: u64) -> Option<&GenericTypeInstance> {
+        self.generic_type_instances.get(&type_id)
+    }
+
+    pub fn register_type_params(&mut self, scope_id: u128, params: Vec<(String, EType)>) {
+        self.type_params.insert(scope_id, params);
+    }
+
+    /*
+     * @desc Bind type parameters in a scope removed once the closure returns
+     */
+    pub fn with_type_params<T>(
+        &mut self,
+        params: Vec<(String, EType)>,
+        f: impl FnOnce(&mut Self, u128) -> T,
+    ) -> Result<T, SemanticError> {
+        let scope_id = self.spawn(None)?;
+        self.register_type_params(scope_id, params);
+        let res = f(self, scope_id);
+
+        self.scope_branches.remove(&scope_id);
+        self.scope_lookup.remove(&scope_id);
+        self.type_params.remove(&scope_id);
+        self.transaction_store.created_scopes.remove(&scope_id);
+        Ok(res)
+    }
+
+    pub fn find_type_param(&self, name: &str, scope: Option<u128>) -> Option<EType> {
+        let branch = self.scope_branches.get(&scope?)?;
+        branch.iter().rev().find_map(|id| {
+            self.type_params
+                .get(id)
+                .and_then(|params| params.iter().find(|(param, _)| param == name))
+                .map(|(_, ctype)| ctype.clone())
+        })
+    }
+
+    pub fn register_method(
+        &mut self,
+        type_id: u64,
+        name: &str,
+        info: MethodInfo,
+    ) -> Result<(), SemanticError> {
+        let key = (type_id, name.to_string());
+        if self.methods.contains_key(&key) {
+            return Err(SemanticError::AlreadyDefined(name.to_string()));
+        }
+        if self.transaction_store.is_open {
+            self.transaction_store.created_methods.insert(key.clone());
+        }
+        self.methods.insert(key, info);
+        Ok(())
+    }
+
+    /*
+     * @desc Find a method of a type, the methods of the type itself come first
+     * A method of an implemented interface is found when a single interface has it
+     */
+    pub fn find_method(&self, type_id: u64, name: &str) -> Option<&MethodInfo> {
+        if let Some(info) = self.methods.get(&(type_id, name.to_string())) {
+            return Some(info);
+        }
+        let mut candidates = self.methods.iter().filter(|((id, method), _)| {
+            *id == type_id
+                && method
+                    .rsplit_once(lexem::SEP)
+                    .is_some_and(|(_, method)| method == name)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some((_, info)), None) => Some(info),
+            _ => None,
+        }
+    }
+
+    pub fn register_interface_impl(
+        &mut self,
+        interface_id: u64,
+        type_id: u64,
+        name: &str,
+    ) -> Result<(), SemanticError> {
+        let key = (interface_id, type_id);
+        if self.interface_impls.contains(&key) {
+            return Err(SemanticError::AlreadyDefined(name.to_string()));
+        }
+        if self.transaction_store.is_open {
+            self.transaction_store.created_interface_impls.insert(key);
+        }
+        self.interface_impls.insert(key);
+        Ok(())
+    }
+
+    pub fn implements(&self, interface_id: u64, type_id: u64) -> bool {
+        self.interface_impls.contains(&(interface_id, type_id))
+    }
+
     pub fn is_scope_in(&self, scope_id: u128, state: ScopeState) -> Option<u128> {
         let Some(branch) = self.scope_branches.get(&scope_id) else {
             return None;
@@ -793,6 +1424,18 @@ impl ScopeManager {
         return None;
     }
 
+    pub fn register_return_type(&mut self, scope_id: u128, return_type: EType) {
+        self.return_types.insert(scope_id, return_type);
+    }
+
+    /*
+     * @desc Declared return type of the function, closure or lambda enclosing a scope
+     */
+    pub fn find_return_type(&self, scope_id: u128) -> Option<&EType> {
+        self.can_return(scope_id)
+            .and_then(|scope_id| self.return_types.get(&scope_id))
+    }
+
     pub fn is_var_global(&self, var_id: u64) -> bool {
         self.vars.get(&var_id).filter(|v| v.is_global).is_some()
     }
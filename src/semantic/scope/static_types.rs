@@ -16,8 +16,10 @@ pub enum StaticType {
     Unit,
     Any,
     Error,
+    TypedError(ErrorType),
     Address(AddrType),
     Map(MapType),
+    Range(RangeType),
 }
 #[derive(Debug, Clone, PartialEq)]
 pub enum PrimitiveType {
@@ -56,6 +58,10 @@ pub struct StringType();
 #[derive(Debug, Clone, PartialEq)]
 pub struct VecType(pub SubType);
 
+// error carrying a payload, the error flag is stored after the payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorType(pub SubType);
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionType {
     pub params: Types,
@@ -88,6 +94,13 @@ pub struct MapType {
     pub values_type: SubType,
 }
 
+// bounds of the range are stored one after the other, the upper bound is excluded unless inclusive
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeType {
+    pub num: NumberType,
+    pub inclusive: bool,
+}
+
 pub const POINTER_SIZE: usize = 8;
 
 impl SizeOf for StaticType {
@@ -102,11 +115,13 @@ impl SizeOf for StaticType {
             StaticType::Unit => 0,
             StaticType::Any => 0,
             StaticType::Error => 1,
+            StaticType::TypedError(value) => value.size_of(),
             StaticType::Address(value) => value.size_of(),
             StaticType::Map(value) => value.size_of(),
             StaticType::String(value) => value.size_of(),
             StaticType::StrSlice(value) => value.size_of(),
             StaticType::Lambda(value) => value.size_of(),
+            StaticType::Range(value) => value.size_of(),
         }
     }
 }
@@ -161,6 +176,12 @@ impl SizeOf for VecType {
     }
 }
 
+impl SizeOf for ErrorType {
+    fn size_of(&self) -> usize {
+        self.0.size_of() + 1
+    }
+}
+
 impl SizeOf for FunctionType {
     fn size_of(&self) -> usize {
         POINTER_SIZE
@@ -197,6 +218,12 @@ impl SizeOf for MapType {
     }
 }
 
+impl SizeOf for RangeType {
+    fn size_of(&self) -> usize {
+        2 * self.num.size_of()
+    }
+}
+
 impl MergeType for StaticType {
     fn merge(
         &self,
@@ -228,6 +255,9 @@ impl CompatibleWith for StaticType {
             (StaticType::Map(x), StaticType::Map(y)) => {
                 (x == y).then(|| ()).ok_or(SemanticError::IncompatibleTypes)
             }
+            (StaticType::Range(x), StaticType::Range(y)) => {
+                (x == y).then(|| ()).ok_or(SemanticError::IncompatibleTypes)
+            }
             (StaticType::Vec(x), StaticType::Vec(y)) => {
                 (x == y).then(|| ()).ok_or(SemanticError::IncompatibleTypes)
             }
@@ -252,6 +282,9 @@ impl CompatibleWith for StaticType {
             (StaticType::Unit, StaticType::Unit) => Ok(()),
             (StaticType::Any, _) => Ok(()),
             (StaticType::Error, _) => Ok(()),
+            (StaticType::TypedError(x), StaticType::TypedError(y)) => {
+                x.0.compatible_with(y.0.as_ref(), scope_manager, scope_id)
+            }
             (StaticType::Address(x), StaticType::Address(y)) => {
                 x.compatible_with(y, scope_manager, scope_id)
             }
@@ -3,13 +3,17 @@ use crate::{
     semantic::{CompatibleWith, EType, SemanticError, SizeOf, TypeOf},
 };
 
-use super::{static_types::POINTER_SIZE, BuildUserType};
+use super::{
+    static_types::{FunctionType, POINTER_SIZE},
+    BuildUserType,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum UserType {
     Struct(Struct),
     Enum(Enum),
     Union(Union),
+    Interface(Interface),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -30,6 +34,12 @@ pub struct Union {
     pub variants: Vec<(ID, Struct)>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interface {
+    pub id: ID,
+    pub methods: Vec<(ID, FunctionType)>,
+}
+
 impl CompatibleWith for UserType {
     fn compatible_with(
         &self,
@@ -45,6 +55,9 @@ impl CompatibleWith for UserType {
             (UserType::Union(x), UserType::Union(y)) => {
                 x.compatible_with(y, scope_manager, scope_id)
             }
+            (UserType::Interface(x), UserType::Interface(y)) => {
+                x.compatible_with(y, scope_manager, scope_id)
+            }
             _ => Err(SemanticError::IncompatibleTypes),
         }
     }
@@ -70,6 +83,11 @@ impl BuildUserType for UserType {
             definition::TypeDef::Enum(value) => {
                 Ok(UserType::Enum(Enum::build(value, scope_manager, scope_id)?))
             }
+            definition::TypeDef::Interface(value) => Ok(UserType::Interface(Interface::build(
+                value,
+                scope_manager,
+                scope_id,
+            )?)),
         }
     }
 }
@@ -80,6 +98,7 @@ impl SizeOf for UserType {
             UserType::Struct(value) => value.size_of(),
             UserType::Enum(value) => value.size_of(),
             UserType::Union(value) => value.size_of(),
+            UserType::Interface(value) => value.size_of(),
         }
     }
 }
@@ -105,6 +124,12 @@ impl SizeOf for Enum {
         POINTER_SIZE
     }
 }
+impl SizeOf for Interface {
+    fn size_of(&self) -> usize {
+        // the address of the data and the index of the vtable
+        2 * POINTER_SIZE
+    }
+}
 
 impl CompatibleWith for Struct {
     fn compatible_with(
@@ -182,6 +207,20 @@ impl CompatibleWith for Enum {
     }
 }
 
+impl CompatibleWith for Interface {
+    fn compatible_with(
+        &self,
+        other: &Self,
+        _scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        _scope_id: Option<u128>,
+    ) -> Result<(), SemanticError> {
+        if self.id != other.id || self.methods != other.methods {
+            return Err(SemanticError::IncompatibleTypes);
+        }
+        Ok(())
+    }
+}
+
 impl Struct {
     pub fn build(
         from: &definition::StructDef,
@@ -244,3 +283,39 @@ impl Enum {
         })
     }
 }
+
+impl Interface {
+    pub fn build(
+        from: &definition::InterfaceDef,
+        scope_manager: &crate::semantic::scope::scope::ScopeManager,
+        scope_id: Option<u128>,
+    ) -> Result<Self, SemanticError> {
+        let mut methods = Vec::with_capacity(from.methods.len());
+        for (id, method) in &from.methods {
+            let mut params = Vec::with_capacity(method.params.len());
+            for param in &method.params {
+                params.push(param.type_of(&scope_manager, scope_id)?);
+            }
+            let ret = method.ret.type_of(&scope_manager, scope_id)?;
+            methods.push((
+                id.clone(),
+                FunctionType {
+                    params,
+                    ret: Box::new(ret),
+                },
+            ));
+        }
+        Ok(Self {
+            id: from.id.clone(),
+            methods,
+        })
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<(usize, &FunctionType)> {
+        self.methods
+            .iter()
+            .enumerate()
+            .find(|(_, (id, _))| id == name)
+            .map(|(idx, (_, method))| (idx, method))
+    }
+}
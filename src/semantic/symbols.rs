@@ -0,0 +1,160 @@
+use crate::{
+    ast::{
+        modules::parse_module,
+        statements::parse_statements_with_recovery,
+        utils::io::{SourceSpan, Span},
+    },
+    vm::external::Engine,
+};
+
+use super::{scope::scope::ScopeManager, EType, Resolve};
+
+/*
+ * @desc Item a name of the source refers to, by its id in the scope manager
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Variable(u64),
+    Type(u64),
+}
+
+/*
+ * @desc Resolved types and references of the nodes of a source, recorded for the editors
+ * The scope manager records them during the resolution once the recording is enabled
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Symbols {
+    signatures: Vec<(SourceSpan, EType)>,
+    references: Vec<(SourceSpan, Symbol)>,
+}
+
+/*
+ * @desc Value of the innermost location containing the offset, the first recorded on a tie
+ */
+fn innermost<T>(located: &[(SourceSpan, T)], offset: usize) -> Option<&T> {
+    located
+        .iter()
+        .filter(|(span, _)| {
+            span.is_known() && span.offset <= offset && offset < span.offset + span.length
+        })
+        .min_by_key(|(span, _)| span.length)
+        .map(|(_, value)| value)
+}
+
+impl Symbols {
+    pub fn push_signature(&mut self, span: SourceSpan, signature: EType) {
+        if span.is_known() {
+            self.signatures.push((span, signature));
+        }
+    }
+
+    pub fn push_reference(&mut self, span: SourceSpan, symbol: Symbol) {
+        if span.is_known() {
+            self.references.push((span, symbol));
+        }
+    }
+
+    /*
+     * @desc Type of the innermost expression at an offset of the source
+     */
+    pub fn signature_at(&self, offset: usize) -> Option<&EType> {
+        innermost(&self.signatures, offset)
+    }
+
+    /*
+     * @desc Item referred to by the innermost name at an offset of the source
+     */
+    pub fn reference_at(&self, offset: usize) -> Option<Symbol> {
+        innermost(&self.references, offset).copied()
+    }
+}
+
+/*
+ * @desc Resolve the statements of a source recording their symbols
+ * The resolution goes on after an error so the valid statements are known
+ */
+pub fn resolve_statements<E: Engine>(source: &str) -> ScopeManager {
+    let mut scope_manager = ScopeManager::default();
+    scope_manager.record_symbols();
+    let (mut statements, _) =
+        parse_statements_with_recovery::<E::PID, E::TID>(Span::new(source), 0);
+    for statement in statements.iter_mut() {
+        let _ = statement.resolve::<E>(&mut scope_manager, None, &None, &mut ());
+    }
+    scope_manager
+}
+
+/*
+ * @desc Resolve the module of a source recording its symbols
+ */
+pub fn resolve_module<E: Engine>(source: &str) -> ScopeManager {
+    let mut scope_manager = ScopeManager::default();
+    scope_manager.record_symbols();
+    if let Ok(mut module) = parse_module::<E::PID, E::TID>(Span::new(source), 0) {
+        let _ = module.resolve::<E>(&mut scope_manager, None, &(), &mut ());
+    }
+    scope_manager
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{p_num, vm::external::test::NoopEngine};
+
+    use super::*;
+
+    fn resolve(source: &str) -> ScopeManager {
+        resolve_statements::<NoopEngine>(source)
+    }
+
+    #[test]
+    fn valid_signature_at() {
+        let source = "let x = 5u64;\nlet y = x + 2;";
+        let scope_manager = resolve(source);
+        let symbols = scope_manager.symbols().unwrap();
+
+        let offset = source.rfind('x').unwrap();
+        assert_eq!(symbols.signature_at(offset), Some(&p_num!(U64)));
+        let offset = source.rfind('2').unwrap();
+        assert_eq!(symbols.signature_at(offset), Some(&p_num!(U64)));
+        assert_eq!(symbols.signature_at(source.find("let").unwrap()), None);
+    }
+
+    #[test]
+    fn valid_reference_at() {
+        let source = "let x = 5u64;\nfn f(a : u64) -> u64 {\n    return a + x;\n}";
+        let scope_manager = resolve(source);
+        let symbols = scope_manager.symbols().unwrap();
+
+        let Some(Symbol::Variable(id)) = symbols.reference_at(source.rfind('x').unwrap()) else {
+            panic!("Expected a variable");
+        };
+        let var = scope_manager.find_var_by_id(id).unwrap();
+        assert_eq!(var.name, "x");
+        assert_eq!(var.span.line, 1);
+
+        let Some(Symbol::Variable(id)) = symbols.reference_at(source.rfind('a').unwrap()) else {
+            panic!("Expected a variable");
+        };
+        let var = scope_manager.find_var_by_id(id).unwrap();
+        assert_eq!(var.name, "a");
+        assert_eq!(var.span.line, 2);
+        assert_eq!(var.ctype, p_num!(U64));
+    }
+
+    #[test]
+    fn valid_method_receiver_resolved_once() {
+        let source = "struct Point {\n    x : i64,\n}\nimpl Point {\n    fn get(self) -> i64 {\n        return self.x;\n    }\n    fn scale(&self, factor : i64) {\n        self.x = self.x * factor;\n    }\n}\nlet p = Point { x : 2 };\np.scale(3);\nlet x = p.get();\nlet text = string(\"ab\");\nlet n = text.len();";
+        let scope_manager = resolve(source);
+        let symbols = scope_manager.symbols().unwrap();
+
+        for receiver in ["p.scale", "p.get", "text.len"] {
+            let offset = source.find(receiver).unwrap();
+            let references = symbols
+                .references
+                .iter()
+                .filter(|(span, _)| span.offset == offset)
+                .count();
+            assert_eq!(references, 1, "{}", receiver);
+        }
+    }
+}
@@ -33,7 +33,7 @@ impl MemoryAddress {
             MemoryAddress::Heap { offset } => MemoryAddress::Heap { offset: offset + n },
             MemoryAddress::Stack { offset } => MemoryAddress::Stack { offset: offset + n },
             MemoryAddress::Global { offset } => MemoryAddress::Global { offset: offset + n },
-            MemoryAddress::Frame { offset } => MemoryAddress::Global { offset: offset + n },
+            MemoryAddress::Frame { offset } => MemoryAddress::Frame { offset: offset + n },
         }
     }
     pub fn sub(self, n: usize) -> MemoryAddress {
@@ -47,7 +47,7 @@ impl MemoryAddress {
             MemoryAddress::Global { offset } => MemoryAddress::Global {
                 offset: offset.checked_sub(n).unwrap_or(0),
             },
-            MemoryAddress::Frame { offset } => MemoryAddress::Global {
+            MemoryAddress::Frame { offset } => MemoryAddress::Frame {
                 offset: offset.checked_sub(n).unwrap_or(0),
             },
         }
@@ -30,6 +30,16 @@ pub struct Stack {
     stack_pointer: usize,
     pub frame_pointer: usize,
     pub return_pointer: usize,
+    depth: usize, // number of opened frames
+}
+
+// pointers of the stack saved when entering a try block
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StackState {
+    stack_pointer: usize,
+    frame_pointer: usize,
+    return_pointer: usize,
+    depth: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,6 +57,7 @@ impl Default for Stack {
             stack_pointer: 0,
             frame_pointer: 0,
             return_pointer: 0,
+            depth: 0,
         }
     }
 }
@@ -66,6 +77,49 @@ impl Stack {
         self.stack_pointer
     }
 
+    pub fn state(&self) -> StackState {
+        StackState {
+            stack_pointer: self.stack_pointer,
+            frame_pointer: self.frame_pointer,
+            return_pointer: self.return_pointer,
+            depth: self.depth,
+        }
+    }
+
+    /*
+     * @desc Restore a saved state of the stack
+     * The frames opened since the state was saved are discarded
+     */
+    pub fn restore(&mut self, state: StackState) {
+        self.stack_pointer = state.stack_pointer;
+        self.frame_pointer = state.frame_pointer;
+        self.return_pointer = state.return_pointer;
+        self.depth = state.depth;
+    }
+
+    /*
+     * @desc Return pointers of the opened frames, from the innermost frame to the outermost
+     * The chain is walked through the frame and return pointers saved by each frame
+     */
+    pub fn return_pointers(&self) -> Vec<usize> {
+        let mut return_pointers = Vec::with_capacity(self.depth);
+        let mut frame_pointer = self.frame_pointer;
+        let mut return_pointer = self.return_pointer;
+        for _ in 0..self.depth {
+            return_pointers.push(return_pointer);
+            if frame_pointer + std::mem::size_of::<Frame>() > STACK_SIZE {
+                break;
+            }
+            let frame = unsafe {
+                *(self.stack[frame_pointer..frame_pointer + std::mem::size_of::<Frame>()].as_ptr()
+                    as *const Frame)
+            };
+            frame_pointer = frame.frame_pointer as usize;
+            return_pointer = frame.return_pointer as usize;
+        }
+        return_pointers
+    }
+
     pub fn open_frame(
         &mut self,
         parameters_size: usize,
@@ -124,6 +178,7 @@ impl Stack {
             .copy_from_slice(&parameters);
 
         self.stack_pointer += parameters_size;
+        self.depth += 1;
 
         Ok(())
     }
@@ -149,6 +204,7 @@ impl Stack {
 
         self.frame_pointer = frame.frame_pointer as usize;
         self.return_pointer = frame.return_pointer as usize;
+        self.depth = self.depth.saturating_sub(1);
 
         // copy the return value back to the stack
         if self.stack_pointer + return_size > STACK_SIZE {
@@ -394,4 +450,32 @@ mod tests {
 
         assert_eq!(stack.top(), 8 + 8);
     }
+
+    #[test]
+    fn valid_return_pointers() {
+        let mut stack = Stack::default();
+        assert_eq!(stack.return_pointers(), Vec::<usize>::new());
+
+        let _ = stack
+            .open_frame(0, 4, None)
+            .expect("Frame creation should have succeeded");
+        let _ = stack.push(8).expect("Push should have succeeded"); /* local */
+        let _ = stack
+            .open_frame(0, 12, Some(1))
+            .expect("Frame creation should have succeeded");
+        assert_eq!(stack.return_pointers(), vec![12, 4]);
+
+        let state = stack.state();
+        let _ = stack
+            .open_frame(0, 20, None)
+            .expect("Frame creation should have succeeded");
+        assert_eq!(stack.return_pointers(), vec![20, 12, 4]);
+        stack.restore(state);
+        assert_eq!(stack.return_pointers(), vec![12, 4]);
+
+        let _ = stack
+            .close_frame(0)
+            .expect("closing the stack frame should have succeeded");
+        assert_eq!(stack.return_pointers(), vec![4]);
+    }
 }
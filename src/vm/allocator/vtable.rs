@@ -1,59 +1,105 @@
-// use crate::{
-//     semantic::ArcMutex,
-//     vm::{asm::Asm, vm::RuntimeError},
-// };
-
-// #[derive(Debug, Clone, Copy)]
-// pub enum VTableError {
-//     AllocError,
-//     FreeError,
-//     AccessError,
-//     Default,
-// }
-
-// #[derive(Debug, Clone)]
-// pub struct VTable {
-//     functions: ArcMutex<Vec<Option<Vec<Asm>>>>,
-// }
-// impl Into<RuntimeError> for VTableError {
-//     fn into(self) -> RuntimeError {
-//         RuntimeError::VTableError(self)
-//     }
-// }
-
-// impl Default for VTable {
-//     fn default() -> Self {
-//         Self {
-//             functions: Default::default(),
-//         }
-//     }
-// }
-
-// impl VTable {
-//     pub fn alloc(&self, function: Vec<Asm>) -> Result<u64, VTableError> {
-//         let mut borrowed = self.functions.as_ref().borrow_mut();
-//         let idx = borrowed.len();
-//         borrowed.push(Some(function));
-//         Ok(idx as u64)
-//     }
-
-//     pub fn read(&self, offset: u64) -> Result<Vec<Asm>, VTableError> {
-//         let borrowed: std::cell::Ref<'_, Vec<Option<Vec<Asm>>>> = self.functions.as_ref().borrow();
-//         match (&borrowed).get(offset as usize) {
-//             Some(Some(f)) => Ok(f.clone()),
-//             _ => Err(VTableError::AccessError),
-//         }
-//     }
-
-//     pub fn free(&self, offset: u64) -> Result<(), VTableError> {
-//         let mut borrowed = self.functions.as_ref().borrow_mut();
-//         match borrowed.get_mut(offset as usize) {
-//             Some(function) => {
-//                 function.as_mut().map(|f| f.clear());
-//                 *function = None;
-//                 Ok(())
-//             }
-//             None => Err(VTableError::FreeError),
-//         }
-//     }
-// }
+use std::collections::HashMap;
+
+use thiserror::Error;
+use ulid::Ulid;
+
+#[derive(Debug, Clone, Error)]
+pub enum VTableError {
+    #[error("AccessError")]
+    AccessError,
+    #[error("Default")]
+    Default,
+}
+
+/*
+ * @desc Tables of the functions implementing the interfaces
+ * A vtable is allocated for each implementation of an interface by a type
+ * It holds the labels of the functions in the order of the methods of the interface
+ */
+#[derive(Debug, Clone, Default)]
+pub struct VTable {
+    indexes: HashMap<(u64, u64), u64>, // interface id and type id
+    functions: Vec<Option<Vec<Ulid>>>,
+}
+
+impl VTable {
+    // bit of the vtable word of an interface value that owns the heap copy of its data
+    pub const OWNED: u64 = 1 << 63;
+
+    /*
+     * @desc Index of the vtable held by the vtable word of an interface value, and whether the value owns its data
+     */
+    pub fn split(word: u64) -> (u64, bool) {
+        (word & !VTable::OWNED, word & VTable::OWNED != 0)
+    }
+
+    /*
+     * @desc Index of the vtable of an implementation
+     * The vtable is reserved if the implementation has not been generated yet
+     */
+    pub fn index(&mut self, interface_id: u64, type_id: u64) -> u64 {
+        if let Some(idx) = self.indexes.get(&(interface_id, type_id)) {
+            return *idx;
+        }
+        let idx = self.functions.len() as u64;
+        self.functions.push(None);
+        self.indexes.insert((interface_id, type_id), idx);
+        idx
+    }
+
+    pub fn alloc(&mut self, interface_id: u64, type_id: u64, functions: Vec<Ulid>) -> u64 {
+        let idx = self.index(interface_id, type_id);
+        self.functions[idx as usize] = Some(functions);
+        idx
+    }
+
+    pub fn read(&self, idx: u64, method: usize) -> Result<Ulid, VTableError> {
+        match self.functions.get(idx as usize) {
+            Some(Some(functions)) => functions
+                .get(method)
+                .cloned()
+                .ok_or(VTableError::AccessError),
+            _ => Err(VTableError::AccessError),
+        }
+    }
+
+    /*
+     * @desc Vtables by index with their interface and type, a reserved vtable has no functions yet
+     */
+    pub fn entries(&self) -> Vec<(u64, u64, u64, Option<&[Ulid]>)> {
+        let mut entries: Vec<_> = self
+            .indexes
+            .iter()
+            .map(|(&(interface_id, type_id), &idx)| {
+                let functions = self
+                    .functions
+                    .get(idx as usize)
+                    .and_then(|functions| functions.as_deref());
+                (idx, interface_id, type_id, functions)
+            })
+            .collect();
+        entries.sort_by_key(|(idx, ..)| *idx);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_alloc() {
+        let mut vtable = VTable::default();
+        let reserved = vtable.index(1, 2);
+        assert!(vtable.read(reserved, 0).is_err());
+
+        let label = Ulid::new();
+        let idx = vtable.alloc(1, 2, vec![label]);
+        assert_eq!(idx, reserved);
+        assert_eq!(vtable.read(idx, 0).unwrap(), label);
+        assert!(vtable.read(idx, 1).is_err());
+
+        let other = vtable.alloc(1, 3, vec![]);
+        assert_ne!(other, idx);
+    }
+}
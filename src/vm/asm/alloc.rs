@@ -43,6 +43,21 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Alloc {
     }
 }
 
+impl crate::vm::AsmListing for Alloc {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Alloc::Heap { size } => format!("halloc {size}"),
+            Alloc::Stack { size } => format!("salloc {size}"),
+            Alloc::Global { address, data } => {
+                format!("galloc {} {}", address.name(), crate::vm::listing::hex(data))
+            }
+            Alloc::GlobalFromStack { address, size } => {
+                format!("galloc_from_stack {} {size}", address.name())
+            }
+        }
+    }
+}
+
 impl crate::vm::AsmWeight for Alloc {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -137,6 +152,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Realloc {
         }
     }
 }
+
+impl crate::vm::AsmListing for Realloc {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("realloc {}", crate::vm::listing::option(self.size))
+    }
+}
 impl crate::vm::AsmWeight for Realloc {
     fn weight(&self) -> crate::vm::Weight {
         if self.size.unwrap_or(0) > ALLOC_SIZE_THRESHOLD {
@@ -183,6 +204,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Free {
         stdio.push_asm(engine, pid, "free");
     }
 }
+
+impl crate::vm::AsmListing for Free {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        "free".to_string()
+    }
+}
 impl crate::vm::AsmWeight for Free {}
 
 impl<E: crate::vm::external::Engine> Executable<E> for Free {
@@ -223,6 +250,15 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Access {
         }
     }
 }
+
+impl crate::vm::AsmListing for Access {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Access::Static { address, size } => format!("load {} {size}", address.name()),
+            Access::Runtime { size } => format!("load {}", crate::vm::listing::option(*size)),
+        }
+    }
+}
 impl crate::vm::AsmWeight for Access {
     fn weight(&self) -> crate::vm::Weight {
         match self {
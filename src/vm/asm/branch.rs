@@ -1,7 +1,10 @@
 use super::operation::{OpPrimitive, PopNum};
 
+use crate::semantic::scope::static_types::POINTER_SIZE;
 use crate::vm::{
-    asm::operation::GetNumFrom, runtime::RuntimeError, scheduler::Executable, stdio::StdIO,
+    allocator::{vtable::VTable, MemoryAddress},
+    asm::operation::GetNumFrom, core::string::STRING_HEADER,
+    runtime::RuntimeError, scheduler::Executable, stdio::StdIO,
 };
 
 use ulid::Ulid;
@@ -23,6 +26,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Label {
         stdio.push_asm_label(engine, pid,  &self.name);
     }
 }
+
+impl crate::vm::AsmListing for Label {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("{} :", labels.name(&self.id))
+    }
+}
 impl crate::vm::AsmWeight for Label {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::ZERO
@@ -52,6 +61,7 @@ pub enum Call {
     // Stack,
     Function { param_size: usize },
     Closure { param_size: usize },
+    Dynamic { method: usize, param_size: usize },
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Call {
@@ -66,6 +76,22 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Call {
             }
             Call::Function { param_size } => stdio.push_asm(engine, pid, "call"),
             Call::Closure { param_size } => stdio.push_asm(engine, pid, "call"),
+            Call::Dynamic { method, param_size } => {
+                stdio.push_asm(engine, pid, &format!("call_dyn {method}"))
+            }
+        }
+    }
+}
+
+impl crate::vm::AsmListing for Call {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Call::From { label, param_size } => {
+                format!("call {} {param_size}", labels.name(label))
+            }
+            Call::Function { param_size } => format!("call_fn {param_size}"),
+            Call::Closure { param_size } => format!("call_closure {param_size}"),
+            Call::Dynamic { method, param_size } => format!("call_dyn {method} {param_size}"),
         }
     }
 }
@@ -104,6 +130,18 @@ impl<E: crate::vm::external::Engine> Executable<E> for Call {
                     OpPrimitive::get_num_from::<u64>(address, stack, heap)? as usize;
                 (Some(address.into(stack)), param_size, function_offset)
             }
+            Call::Dynamic { method, param_size } => {
+                // the vtable index sits between the address of the data and the other arguments
+                let args = stack.pop(param_size - POINTER_SIZE)?.to_owned();
+                let (vtable_idx, _) = VTable::split(OpPrimitive::pop_num::<u64>(stack)?);
+                let _ = stack.push_with(&args)?;
+
+                let label = program.vtable.read(vtable_idx, method)?;
+                let Some(function_offset) = program.get_cursor_from_label(&label) else {
+                    return Err(RuntimeError::CodeSegmentation);
+                };
+                (Some(function_offset as u64), param_size, function_offset)
+            }
         };
 
         let _ = stack.open_frame(param_size, scheduler.cursor.get() + 1, caller_data)?;
@@ -132,6 +170,15 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Goto {
         }
     }
 }
+
+impl crate::vm::AsmListing for Goto {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self.label {
+            Some(label) => format!("goto {}", labels.name(&label)),
+            None => "goto".to_string(),
+        }
+    }
+}
 impl crate::vm::AsmWeight for Goto {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::ZERO
@@ -181,6 +228,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for BranchIf {
     }
 }
 
+impl crate::vm::AsmListing for BranchIf {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("else_goto {}", labels.name(&self.else_label))
+    }
+}
+
 impl crate::vm::AsmWeight for BranchIf {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::ZERO
@@ -217,6 +270,8 @@ impl<E: crate::vm::external::Engine> Executable<E> for BranchIf {
 pub enum BranchTry {
     StartTry { else_label: Ulid },
     EndTry,
+    Raise { for_string: bool },
+    Message,
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for BranchTry {
@@ -230,6 +285,22 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for BranchTry {
                 stdio.push_asm(engine, pid, &format!("try_else {label}"));
             }
             BranchTry::EndTry => stdio.push_asm(engine, pid, &format!("try_end")),
+            BranchTry::Raise { .. } => stdio.push_asm(engine, pid, &format!("raise")),
+            BranchTry::Message => stdio.push_asm(engine, pid, &format!("try_message")),
+        }
+    }
+}
+
+impl crate::vm::AsmListing for BranchTry {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            BranchTry::StartTry { else_label } => {
+                format!("try_else {}", labels.name(else_label))
+            }
+            BranchTry::EndTry => "try_end".to_string(),
+            BranchTry::Raise { for_string: false } => "raise".to_string(),
+            BranchTry::Raise { for_string: true } => "raise_string".to_string(),
+            BranchTry::Message => "try_message".to_string(),
         }
     }
 }
@@ -252,11 +323,34 @@ impl<E: crate::vm::external::Engine> Executable<E> for BranchTry {
     ) -> Result<(), RuntimeError> {
         match self {
             BranchTry::StartTry { else_label } => {
-                scheduler.push_catch(else_label);
+                scheduler.push_catch(else_label, stack.state());
             }
             BranchTry::EndTry => {
                 scheduler.pop_catch();
             }
+            BranchTry::Raise { for_string } => {
+                let address: MemoryAddress = OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                let address = if *for_string {
+                    address.add(POINTER_SIZE)
+                } else {
+                    address
+                };
+                let message = OpPrimitive::get_string_from(address, stack, heap)?;
+                // unwind to the innermost try
+                return Err(RuntimeError::Raised(message));
+            }
+            BranchTry::Message => {
+                // the message is copied into a new string, an error value caught by a try has none
+                let message = scheduler.take_caught().unwrap_or_default();
+                let len = message.len();
+                let address = heap.alloc(len + STRING_HEADER)?;
+                let _ = heap.write(address, &(len as u64).to_le_bytes())?;
+                let _ = heap.write(address.add(8), &(len as u64).to_le_bytes())?;
+                let _ = heap.write(address.add(STRING_HEADER), message.as_bytes())?;
+
+                let address: u64 = address.into(stack);
+                stack.push_with(&address.to_le_bytes())?;
+            }
         }
         scheduler.next();
         Ok(())
@@ -273,6 +367,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Return {
         stdio.push_asm(engine, pid, &format!("return {0}", self.size))
     }
 }
+
+impl crate::vm::AsmListing for Return {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("return {}", self.size)
+    }
+}
 impl crate::vm::AsmWeight for Return {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::MEDIUM
@@ -287,6 +387,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for CloseFrame {
         stdio.push_asm(engine, pid, "return")
     }
 }
+
+impl crate::vm::AsmListing for CloseFrame {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        "close_frame".to_string()
+    }
+}
 impl crate::vm::AsmWeight for CloseFrame {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::ZERO
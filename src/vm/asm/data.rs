@@ -31,6 +31,14 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Data {
         }
     }
 }
+
+impl crate::vm::AsmListing for Data {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Data::Serialized { data } => format!("dmp {}", crate::vm::listing::hex(data)),
+        }
+    }
+}
 impl crate::vm::AsmWeight for Data {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -19,6 +19,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Locate {
         stdio.push_asm(engine, pid, &format!("addr {}", self.address.name()));
     }
 }
+
+impl crate::vm::AsmListing for Locate {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("addr {}", self.address.name())
+    }
+}
 impl crate::vm::AsmWeight for Locate {}
 
 impl<E: crate::vm::external::Engine> Executable<E> for Locate {
@@ -51,6 +57,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for LocateOffsetFromS
         stdio.push_asm(engine, pid, &format!("addr SP[-{}]", self.offset));
     }
 }
+
+impl crate::vm::AsmListing for LocateOffsetFromStackPointer {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("addr SP[-{}]", self.offset)
+    }
+}
 impl crate::vm::AsmWeight for LocateOffsetFromStackPointer {}
 impl<E: crate::vm::external::Engine> Executable<E> for LocateOffsetFromStackPointer {
     fn execute<P: crate::vm::scheduler::SchedulingPolicy>(
@@ -87,6 +99,12 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for LocateOffset {
         stdio.push_asm(engine, pid, &format!("offset {}", self.offset));
     }
 }
+
+impl crate::vm::AsmListing for LocateOffset {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        format!("offset {}", self.offset)
+    }
+}
 impl crate::vm::AsmWeight for LocateOffset {}
 
 impl<E: crate::vm::external::Engine> Executable<E> for LocateOffset {
@@ -131,6 +149,19 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for LocateIndex {
         }
     }
 }
+
+impl crate::vm::AsmListing for LocateIndex {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        use crate::vm::listing::option;
+        format!(
+            "addr_at_idx {} {} {} {}",
+            option(self.base_address.map(|address| address.name())),
+            self.size,
+            option(self.len),
+            option(self.offset),
+        )
+    }
+}
 impl crate::vm::AsmWeight for LocateIndex {}
 
 impl<E: crate::vm::external::Engine> Executable<E> for LocateIndex {
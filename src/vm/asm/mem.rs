@@ -33,6 +33,17 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Mem {
         }
     }
 }
+
+impl crate::vm::AsmListing for Mem {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Mem::Dup(n) => format!("dup {n}"),
+            Mem::Label(label) => format!("dmp_label {}", labels.name(label)),
+            Mem::Take { size } => format!("take {size}"),
+            Mem::Store { size, address } => format!("store {} {size}", address.name()),
+        }
+    }
+}
 impl crate::vm::AsmWeight for Mem {
     fn weight(&self) -> crate::vm::Weight {
         match self {
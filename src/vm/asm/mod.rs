@@ -3,7 +3,7 @@ pub mod alloc;
 pub mod branch;
 pub mod data;
 pub mod locate;
-mod math_operation;
+pub(crate) mod math_operation;
 pub mod mem;
 pub mod operation;
 
@@ -275,6 +275,33 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Asm {
         }
     }
 }
+
+impl crate::vm::AsmListing for Asm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            Asm::Core(value) => format!("syscall {}", value.listing(labels)),
+            Asm::Return(value) => value.listing(labels),
+            Asm::CloseFrame(value) => value.listing(labels),
+            Asm::Alloc(value) => value.listing(labels),
+            Asm::Realloc(value) => value.listing(labels),
+            Asm::Free(value) => value.listing(labels),
+            Asm::Mem(value) => value.listing(labels),
+            Asm::Operation(value) => value.listing(labels),
+            Asm::Data(value) => value.listing(labels),
+            Asm::Access(value) => value.listing(labels),
+            Asm::Offset(value) => value.listing(labels),
+            Asm::OffsetIdx(value) => value.listing(labels),
+            Asm::OffsetSP(value) => value.listing(labels),
+            Asm::Locate(value) => value.listing(labels),
+            Asm::If(value) => value.listing(labels),
+            Asm::Label(value) => value.listing(labels),
+            Asm::Call(value) => value.listing(labels),
+            Asm::Goto(value) => value.listing(labels),
+            Asm::Try(value) => value.listing(labels),
+            Asm::Pop(n) => format!("pop {n}"),
+        }
+    }
+}
 impl crate::vm::AsmWeight for Asm {
     fn weight(&self) -> super::Weight {
         match self {
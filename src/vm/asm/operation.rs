@@ -120,6 +120,45 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for Operation {
     }
 }
 
+impl crate::vm::AsmListing for Operation {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let binary = |op: &str, left: &OpPrimitive, right: &OpPrimitive| {
+            format!("{op}_{}_{}", left.name(), right.name())
+        };
+        match &self.kind {
+            OperationKind::Align => "align".to_string(),
+            OperationKind::CastCharToUTF8 => "char_to_utf8".to_string(),
+            OperationKind::Mult(Mult { left, right }) => binary("mult", left, right),
+            OperationKind::Div(Division { left, right }) => binary("div", left, right),
+            OperationKind::Mod(Mod { left, right }) => binary("mod", left, right),
+            OperationKind::Addition(Addition { left, right }) => binary("add", left, right),
+            OperationKind::Substraction(Substraction { left, right }) => {
+                binary("sub", left, right)
+            }
+            OperationKind::ShiftLeft(ShiftLeft { left, right }) => binary("shl", left, right),
+            OperationKind::ShiftRight(ShiftRight { left, right }) => binary("shr", left, right),
+            OperationKind::BitwiseAnd(BitwiseAnd { left, right }) => binary("band", left, right),
+            OperationKind::BitwiseXOR(BitwiseXOR { left, right }) => binary("bxor", left, right),
+            OperationKind::BitwiseOR(BitwiseOR { left, right }) => binary("bor", left, right),
+            OperationKind::Cast(Cast { from, to }) => binary("cast", from, to),
+            OperationKind::Less(Less { left, right }) => binary("le", left, right),
+            OperationKind::LessEqual(LessEqual { left, right }) => binary("leq", left, right),
+            OperationKind::Greater(Greater { left, right }) => binary("ge", left, right),
+            OperationKind::GreaterEqual(GreaterEqual { left, right }) => {
+                binary("geq", left, right)
+            }
+            OperationKind::Equal(Equal { left, right }) => format!("eq {left} {right}"),
+            OperationKind::NotEqual(NotEqual { left, right }) => format!("neq {left} {right}"),
+            OperationKind::StrEqual(_) => "str_eq".to_string(),
+            OperationKind::StrNotEqual(_) => "str_neq".to_string(),
+            OperationKind::LogicalAnd(LogicalAnd()) => "and".to_string(),
+            OperationKind::LogicalOr(LogicalOr()) => "or".to_string(),
+            OperationKind::Minus(Minus { data_type }) => format!("neg_{}", data_type.name()),
+            OperationKind::Not(Not()) => "not".to_string(),
+        }
+    }
+}
+
 impl crate::vm::AsmWeight for Operation {}
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,902 @@
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+use ulid::Ulid;
+
+use crate::semantic::scope::static_types::NumberType;
+
+use super::{
+    allocator::MemoryAddress,
+    asm::{
+        alloc::{Access, Alloc, Free, Realloc},
+        branch::{BranchIf, BranchTry, Call, CloseFrame, Goto, Return},
+        data::Data,
+        locate::{Locate, LocateIndex, LocateOffset, LocateOffsetFromStackPointer},
+        mem::Mem,
+        operation::*,
+        Asm,
+    },
+    core::{
+        alloc::AllocAsm, format::FormatAsm, io::IOAsm, iter::IterAsm, iter::Iterable,
+        map::DerefHashing, map::MapAsm, math::MathAsm, string::StringAsm, thread::ThreadAsm,
+        vector::VectorAsm, CoreAsm,
+    },
+    external::Engine,
+    program::Program,
+    scheduler::{EventConf, EventExclusivity, EventKind},
+};
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum AssemblyError {
+    #[error("line {0} : unknown instruction \"{1}\"")]
+    UnknownInstruction(usize, String),
+
+    #[error("line {0} : invalid operand \"{1}\"")]
+    InvalidOperand(usize, String),
+
+    #[error("line {0} : expected {1} operand(s)")]
+    OperandCount(usize, usize),
+
+    #[error("line {0} : the label \"{1}\" is defined twice")]
+    DuplicateLabel(usize, String),
+
+    #[error("the label \"{0}\" is never defined")]
+    UnknownLabel(String),
+
+    #[error("line {0} : unknown extern function \"{1}\"")]
+    UnknownExtern(usize, String),
+
+    #[error("line {0} : the vtable {1} is out of order")]
+    VTableOrder(usize, u64),
+}
+
+/*
+ * @desc Build a program from its casm, in the format of the static listing
+ * The text after ';' is a comment, the extern functions are rejected
+ */
+pub fn assemble<E: Engine>(source: &str) -> Result<Program<E>, AssemblyError> {
+    assemble_with::<E>(source, |_| None)
+}
+
+/*
+ * @desc Build a program from its casm, the engine parses the text following 'extern'
+ */
+pub fn assemble_with<E: Engine>(
+    source: &str,
+    parse_extern: impl Fn(&str) -> Option<E::Function>,
+) -> Result<Program<E>, AssemblyError> {
+    let mut assembler = Assembler::<E>::default();
+    for (index, line) in source.lines().enumerate() {
+        assembler.line = index + 1;
+        let casm = line.split(';').next().unwrap_or_default().trim();
+        if casm.is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = casm.split_whitespace().collect();
+        match tokens.as_slice() {
+            [".vtable", operands @ ..] => assembler.vtable(operands)?,
+            [name, ":"] => assembler.define(name)?,
+            [name] if name.len() > 1 && name.ends_with(':') && !name.ends_with("::") => {
+                assembler.define(&name[..name.len() - 1])?
+            }
+            ["extern", ..] => {
+                let text = casm["extern".len()..].trim();
+                let function = parse_extern(text).ok_or(AssemblyError::UnknownExtern(
+                    assembler.line,
+                    text.to_string(),
+                ))?;
+                assembler.program.push_extern(function);
+            }
+            [mnemonic, operands @ ..] => {
+                let asm = assembler.asm(mnemonic, operands)?;
+                assembler.program.push(asm);
+            }
+            [] => {}
+        }
+    }
+    assembler.finish()
+}
+
+struct Assembler<E: Engine> {
+    program: Program<E>,
+    labels: HashMap<String, Ulid>,
+    defined: HashSet<String>,
+    line: usize,
+}
+
+impl<E: Engine> Default for Assembler<E> {
+    fn default() -> Self {
+        Self {
+            program: Program::default(),
+            labels: HashMap::default(),
+            defined: HashSet::default(),
+            line: 0,
+        }
+    }
+}
+
+impl<E: Engine> Assembler<E> {
+    fn finish(self) -> Result<Program<E>, AssemblyError> {
+        let mut undefined: Vec<&String> = self
+            .labels
+            .keys()
+            .filter(|name| !self.defined.contains(*name))
+            .collect();
+        undefined.sort();
+        match undefined.first() {
+            Some(name) => Err(AssemblyError::UnknownLabel(name.to_string())),
+            None => Ok(self.program),
+        }
+    }
+
+    /*
+     * @desc The suffix added by the listing to a shared label name is dropped from the label
+     */
+    fn define(&mut self, name: &str) -> Result<(), AssemblyError> {
+        if !self.defined.insert(name.to_string()) {
+            return Err(AssemblyError::DuplicateLabel(self.line, name.to_string()));
+        }
+        let id = self.label(name)?;
+        let label = match name.rsplit_once('#') {
+            Some((label, index)) if index.parse::<usize>().is_ok() => label,
+            _ => name,
+        };
+        self.program.push_label_by_id(id, label.to_string());
+        Ok(())
+    }
+
+    /*
+     * @desc Id of a label, defined before or after its use
+     * A label shown by its id in the listing is missing from the program
+     */
+    fn label(&mut self, name: &str) -> Result<Ulid, AssemblyError> {
+        if let Some(id) = name.strip_prefix('?') {
+            return Ulid::from_string(id).map_err(|_| self.invalid(name));
+        }
+        Ok(*self
+            .labels
+            .entry(name.to_string())
+            .or_insert_with(Ulid::new))
+    }
+
+    fn vtable(&mut self, operands: &[&str]) -> Result<(), AssemblyError> {
+        let [index, interface_id, type_id, functions @ ..] = operands else {
+            return Err(AssemblyError::OperandCount(self.line, 4));
+        };
+        let index = self.number(index)? as u64;
+        let interface_id = self.number(interface_id)? as u64;
+        let type_id = self.number(type_id)? as u64;
+        let reserved = self.program.vtable.index(interface_id, type_id);
+        if reserved != index {
+            return Err(AssemblyError::VTableOrder(self.line, index));
+        }
+        if functions != ["_"] {
+            let functions = functions
+                .iter()
+                .map(|name| self.label(name))
+                .collect::<Result<Vec<Ulid>, AssemblyError>>()?;
+            self.program.vtable.alloc(interface_id, type_id, functions);
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, operand: &str) -> AssemblyError {
+        AssemblyError::InvalidOperand(self.line, operand.to_string())
+    }
+
+    fn number(&self, operand: &str) -> Result<usize, AssemblyError> {
+        operand.parse::<usize>().map_err(|_| self.invalid(operand))
+    }
+
+    fn option<T>(
+        &self,
+        operand: &str,
+        parse: impl Fn(&Self, &str) -> Result<T, AssemblyError>,
+    ) -> Result<Option<T>, AssemblyError> {
+        match operand {
+            "_" => Ok(None),
+            operand => parse(self, operand).map(Some),
+        }
+    }
+
+    fn address(&self, operand: &str) -> Result<MemoryAddress, AssemblyError> {
+        let (segment, offset) = operand
+            .strip_suffix(']')
+            .and_then(|operand| operand.split_once('['))
+            .ok_or(self.invalid(operand))?;
+        let offset = self.number(offset)?;
+        match segment {
+            "HP" => Ok(MemoryAddress::Heap { offset }),
+            "SP" => Ok(MemoryAddress::Stack { offset }),
+            "FP" => Ok(MemoryAddress::Frame { offset }),
+            "GL" => Ok(MemoryAddress::Global { offset }),
+            _ => Err(self.invalid(operand)),
+        }
+    }
+
+    fn hex(&self, operand: &str) -> Result<Box<[u8]>, AssemblyError> {
+        let digits = operand.strip_prefix("0x").ok_or(self.invalid(operand))?;
+        if digits.len() % 2 != 0 || !digits.is_ascii() {
+            return Err(self.invalid(operand));
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| self.invalid(operand)))
+            .collect()
+    }
+
+    fn primitive(&self, operand: &str) -> Result<OpPrimitive, AssemblyError> {
+        match operand {
+            "bool" => Ok(OpPrimitive::Bool),
+            "char" => Ok(OpPrimitive::Char),
+            "str" => Ok(OpPrimitive::String),
+            operand => self.number_type(operand).map(OpPrimitive::Number),
+        }
+    }
+
+    fn number_type(&self, operand: &str) -> Result<NumberType, AssemblyError> {
+        match operand {
+            "u8" => Ok(NumberType::U8),
+            "u16" => Ok(NumberType::U16),
+            "u32" => Ok(NumberType::U32),
+            "u64" => Ok(NumberType::U64),
+            "u128" => Ok(NumberType::U128),
+            "i8" => Ok(NumberType::I8),
+            "i16" => Ok(NumberType::I16),
+            "i32" => Ok(NumberType::I32),
+            "i64" => Ok(NumberType::I64),
+            "i128" => Ok(NumberType::I128),
+            "f64" => Ok(NumberType::F64),
+            _ => Err(self.invalid(operand)),
+        }
+    }
+
+    fn hashing(&self, operand: &str) -> Result<DerefHashing, AssemblyError> {
+        match operand.split(':').collect::<Vec<&str>>().as_slice() {
+            ["vec", item_size] => Ok(DerefHashing::Vec(self.number(item_size)?)),
+            ["string"] => Ok(DerefHashing::String),
+            ["str_slice"] => Ok(DerefHashing::StrSlice),
+            ["default"] => Ok(DerefHashing::Default),
+            _ => Err(self.invalid(operand)),
+        }
+    }
+
+    fn iterable(&self, operand: &str) -> Result<Iterable, AssemblyError> {
+        match operand.split(':').collect::<Vec<&str>>().as_slice() {
+            ["range", num] => Ok(Iterable::Range {
+                num: self.number_type(num)?,
+                inclusive: false,
+            }),
+            ["range_inclusive", num] => Ok(Iterable::Range {
+                num: self.number_type(num)?,
+                inclusive: true,
+            }),
+            ["vec", item_size] => Ok(Iterable::Vec {
+                item_size: self.number(item_size)?,
+            }),
+            ["slice", len, item_size] => Ok(Iterable::Slice {
+                len: self.number(len)?,
+                item_size: self.number(item_size)?,
+            }),
+            ["string"] => Ok(Iterable::String),
+            ["str_slice"] => Ok(Iterable::StrSlice),
+            ["items", key_size, value_size] => Ok(Iterable::Items {
+                key_size: self.number(key_size)?,
+                value_size: self.number(value_size)?,
+            }),
+            _ => Err(self.invalid(operand)),
+        }
+    }
+
+    fn asm(&mut self, mnemonic: &str, operands: &[&str]) -> Result<Asm, AssemblyError> {
+        let line = self.line;
+        let count = |n: usize| AssemblyError::OperandCount(line, n);
+        let asm = match (mnemonic, operands) {
+            ("syscall", [name, operands @ ..]) => Asm::Core(self.core(name, operands)?),
+            ("syscall", _) => return Err(count(1)),
+            ("pop", [n]) => Asm::Pop(self.number(n)?),
+            ("return", [size]) => Asm::Return(Return {
+                size: self.number(size)?,
+            }),
+            ("close_frame", []) => Asm::CloseFrame(CloseFrame),
+            ("halloc", [size]) => Asm::Alloc(Alloc::Heap {
+                size: self.number(size)?,
+            }),
+            ("salloc", [size]) => Asm::Alloc(Alloc::Stack {
+                size: self.number(size)?,
+            }),
+            ("galloc", [address, data]) => Asm::Alloc(Alloc::Global {
+                address: self.address(address)?,
+                data: self.hex(data)?,
+            }),
+            ("galloc_from_stack", [address, size]) => Asm::Alloc(Alloc::GlobalFromStack {
+                address: self.address(address)?,
+                size: self.number(size)?,
+            }),
+            ("realloc", [size]) => Asm::Realloc(Realloc {
+                size: self.option(size, Self::number)?,
+            }),
+            ("free", []) => Asm::Free(Free()),
+            ("load", [address, size]) => Asm::Access(Access::Static {
+                address: self.address(address)?,
+                size: self.number(size)?,
+            }),
+            ("load", [size]) => Asm::Access(Access::Runtime {
+                size: self.option(size, Self::number)?,
+            }),
+            ("dup", [n]) => Asm::Mem(Mem::Dup(self.number(n)?)),
+            ("dmp_label", [label]) => Asm::Mem(Mem::Label(self.label(label)?)),
+            ("take", [size]) => Asm::Mem(Mem::Take {
+                size: self.number(size)?,
+            }),
+            ("store", [address, size]) => Asm::Mem(Mem::Store {
+                size: self.number(size)?,
+                address: self.address(address)?,
+            }),
+            ("dmp", [data]) => Asm::Data(Data::Serialized {
+                data: self.hex(data)?,
+            }),
+            ("addr", [address]) => match address
+                .strip_prefix("SP[-")
+                .and_then(|offset| offset.strip_suffix(']'))
+            {
+                Some(offset) => Asm::OffsetSP(LocateOffsetFromStackPointer {
+                    offset: self.number(offset)?,
+                }),
+                None => Asm::Locate(Locate {
+                    address: self.address(address)?,
+                }),
+            },
+            ("offset", [offset]) => Asm::Offset(LocateOffset {
+                offset: self.number(offset)?,
+            }),
+            ("addr_at_idx", [base_address, size, len, offset]) => Asm::OffsetIdx(LocateIndex {
+                base_address: self.option(base_address, Self::address)?,
+                size: self.number(size)?,
+                len: self.option(len, Self::number)?,
+                offset: self.option(offset, Self::number)?,
+            }),
+            ("else_goto", [label]) => Asm::If(BranchIf {
+                else_label: self.label(label)?,
+            }),
+            ("try_else", [label]) => Asm::Try(BranchTry::StartTry {
+                else_label: self.label(label)?,
+            }),
+            ("try_end", []) => Asm::Try(BranchTry::EndTry),
+            ("raise", []) => Asm::Try(BranchTry::Raise { for_string: false }),
+            ("raise_string", []) => Asm::Try(BranchTry::Raise { for_string: true }),
+            ("try_message", []) => Asm::Try(BranchTry::Message),
+            ("call", [label, param_size]) => Asm::Call(Call::From {
+                label: self.label(label)?,
+                param_size: self.number(param_size)?,
+            }),
+            ("call_fn", [param_size]) => Asm::Call(Call::Function {
+                param_size: self.number(param_size)?,
+            }),
+            ("call_closure", [param_size]) => Asm::Call(Call::Closure {
+                param_size: self.number(param_size)?,
+            }),
+            ("call_dyn", [method, param_size]) => Asm::Call(Call::Dynamic {
+                method: self.number(method)?,
+                param_size: self.number(param_size)?,
+            }),
+            ("goto", [label]) => Asm::Goto(Goto {
+                label: Some(self.label(label)?),
+            }),
+            ("goto", []) => Asm::Goto(Goto { label: None }),
+            (mnemonic, operands) => Asm::Operation(Operation {
+                kind: self.operation(mnemonic, operands)?,
+            }),
+        };
+        Ok(asm)
+    }
+
+    fn operation(&self, mnemonic: &str, operands: &[&str]) -> Result<OperationKind, AssemblyError> {
+        let unknown = || AssemblyError::UnknownInstruction(self.line, mnemonic.to_string());
+        let kind = match (mnemonic, operands) {
+            ("align", []) => OperationKind::Align,
+            ("char_to_utf8", []) => OperationKind::CastCharToUTF8,
+            ("str_eq", []) => OperationKind::StrEqual(StrEqual),
+            ("str_neq", []) => OperationKind::StrNotEqual(StrNotEqual),
+            ("and", []) => OperationKind::LogicalAnd(LogicalAnd()),
+            ("or", []) => OperationKind::LogicalOr(LogicalOr()),
+            ("not", []) => OperationKind::Not(Not()),
+            ("eq", [left, right]) => OperationKind::Equal(Equal {
+                left: self.number(left)?,
+                right: self.number(right)?,
+            }),
+            ("neq", [left, right]) => OperationKind::NotEqual(NotEqual {
+                left: self.number(left)?,
+                right: self.number(right)?,
+            }),
+            (mnemonic, []) => match mnemonic.split('_').collect::<Vec<&str>>().as_slice() {
+                ["neg", data_type] => OperationKind::Minus(Minus {
+                    data_type: self.primitive(data_type)?,
+                }),
+                [op, left, right] => {
+                    let left = self.primitive(left)?;
+                    let right = self.primitive(right)?;
+                    match *op {
+                        "mult" => OperationKind::Mult(Mult { left, right }),
+                        "div" => OperationKind::Div(Division { left, right }),
+                        "mod" => OperationKind::Mod(Mod { left, right }),
+                        "add" => OperationKind::Addition(Addition { left, right }),
+                        "sub" => OperationKind::Substraction(Substraction { left, right }),
+                        "shl" => OperationKind::ShiftLeft(ShiftLeft { left, right }),
+                        "shr" => OperationKind::ShiftRight(ShiftRight { left, right }),
+                        "band" => OperationKind::BitwiseAnd(BitwiseAnd { left, right }),
+                        "bxor" => OperationKind::BitwiseXOR(BitwiseXOR { left, right }),
+                        "bor" => OperationKind::BitwiseOR(BitwiseOR { left, right }),
+                        "cast" => OperationKind::Cast(Cast {
+                            from: left,
+                            to: right,
+                        }),
+                        "le" => OperationKind::Less(Less { left, right }),
+                        "leq" => OperationKind::LessEqual(LessEqual { left, right }),
+                        "ge" => OperationKind::Greater(Greater { left, right }),
+                        "geq" => OperationKind::GreaterEqual(GreaterEqual { left, right }),
+                        _ => return Err(unknown()),
+                    }
+                }
+                _ => return Err(unknown()),
+            },
+            _ => return Err(unknown()),
+        };
+        Ok(kind)
+    }
+
+    fn core(&self, name: &str, operands: &[&str]) -> Result<CoreAsm, AssemblyError> {
+        let unknown = || AssemblyError::UnknownInstruction(self.line, name.to_string());
+        let count = |n: usize| AssemblyError::OperandCount(self.line, n);
+        let Some((group, function)) = name.split_once('.') else {
+            return match (name, operands) {
+                ("assert_true", []) => Ok(CoreAsm::AssertBool),
+                ("assert_ok", []) => Ok(CoreAsm::AssertErr),
+                ("error", []) => Ok(CoreAsm::Error),
+                ("ok", []) => Ok(CoreAsm::Ok),
+                ("assert_true" | "assert_ok" | "error" | "ok", _) => Err(count(0)),
+                _ => Err(unknown()),
+            };
+        };
+        let core = match group {
+            "vec" => CoreAsm::Vec(match (function, operands) {
+                ("new", [item_size]) => VectorAsm::Vec {
+                    item_size: self.number(item_size)?,
+                },
+                ("with_capacity", [item_size]) => VectorAsm::VecWithCapacity {
+                    item_size: self.number(item_size)?,
+                },
+                ("push", [item_size]) => VectorAsm::Push {
+                    item_size: self.number(item_size)?,
+                },
+                ("pop", [item_size]) => VectorAsm::Pop {
+                    item_size: self.number(item_size)?,
+                },
+                ("extend", [item_size, len]) => VectorAsm::Extend {
+                    item_size: self.number(item_size)?,
+                    len: self.number(len)?,
+                },
+                ("delete", [item_size]) => VectorAsm::Delete {
+                    item_size: self.number(item_size)?,
+                },
+                ("clear", [item_size]) => VectorAsm::Clear {
+                    item_size: self.number(item_size)?,
+                },
+                _ => return Err(unknown()),
+            }),
+            "map" => CoreAsm::Map(self.map(function, operands).ok_or_else(unknown)??),
+            "str" => CoreAsm::String(match (function, operands) {
+                ("string", []) => StringAsm::String {},
+                ("append", []) => StringAsm::Append {},
+                ("char_at_str_slice", []) => StringAsm::CharAtStrslice,
+                ("char_at_string", []) => StringAsm::CharAtString,
+                ("to_const_str", []) => StringAsm::ToConstStr,
+                _ => return Err(unknown()),
+            }),
+            "alloc" => CoreAsm::Alloc(match (function, operands) {
+                ("len", []) => AllocAsm::Len,
+                ("cap", []) => AllocAsm::Cap,
+                ("free", []) => AllocAsm::Free,
+                ("malloc", []) => AllocAsm::Alloc,
+                ("memcpy", []) => AllocAsm::MemCopy,
+                ("free_interface", []) => AllocAsm::FreeInterface,
+                ("free_interface_vec", []) => AllocAsm::FreeInterfaceVec,
+                _ => return Err(unknown()),
+            }),
+            "thread" => CoreAsm::Thread(match (function, operands) {
+                ("spawn", []) => ThreadAsm::Spawn,
+                ("close", []) => ThreadAsm::Close,
+                ("exit", []) => ThreadAsm::Exit,
+                ("wait", []) => ThreadAsm::Wait,
+                ("wake", []) => ThreadAsm::Wake,
+                ("sleep", []) => ThreadAsm::Sleep,
+                ("join", []) => ThreadAsm::Join,
+                ("event", [kind, exclu]) => ThreadAsm::EventRegistration(EventConf {
+                    kind: match *kind {
+                        "once" => EventKind::Once,
+                        "repeatable" => EventKind::Repetable,
+                        _ => return Err(self.invalid(kind)),
+                    },
+                    exclu: match *exclu {
+                        "per_tid" => EventExclusivity::PerTID,
+                        "per_pid" => EventExclusivity::PerPID,
+                        _ => return Err(self.invalid(exclu)),
+                    },
+                }),
+                _ => return Err(unknown()),
+            }),
+            "io" => CoreAsm::IO(match (function, operands) {
+                ("print_str", []) => IOAsm::PrintStr,
+                ("print_string", []) => IOAsm::PrintString,
+                ("println_str", []) => IOAsm::PrintlnStr,
+                ("println_string", []) => IOAsm::PrintlnString,
+                ("flush", []) => IOAsm::Flush,
+                ("flushln", []) => IOAsm::Flushln,
+                ("scan", []) => IOAsm::Scan,
+                ("rscan", []) => IOAsm::RequestScan,
+                _ => return Err(unknown()),
+            }),
+            "math" if operands.is_empty() => {
+                CoreAsm::Math(self.math(function).ok_or_else(unknown)?)
+            }
+            "fmt" => CoreAsm::Format(self.format(function, operands).ok_or_else(unknown)??),
+            "iter" => CoreAsm::Iter(match (function, operands) {
+                ("items", [key_size, value_size]) => IterAsm::MapItems {
+                    key_size: self.number(key_size)?,
+                    value_size: self.number(value_size)?,
+                },
+                ("values", [key_size, value_size]) => IterAsm::MapValues {
+                    key_size: self.number(key_size)?,
+                    value_size: self.number(value_size)?,
+                },
+                ("keys", [key_size, value_size]) => IterAsm::MapKeys {
+                    key_size: self.number(key_size)?,
+                    value_size: self.number(value_size)?,
+                },
+                ("next", [iterable]) => IterAsm::Next(self.iterable(iterable)?),
+                ("contains", [iterable, ref_access]) => IterAsm::Contains {
+                    iterable: self.iterable(iterable)?,
+                    ref_access: self.hashing(ref_access)?,
+                },
+                _ => return Err(unknown()),
+            }),
+            _ => return Err(unknown()),
+        };
+        Ok(core)
+    }
+
+    fn map(&self, function: &str, operands: &[&str]) -> Option<Result<MapAsm, AssemblyError>> {
+        let sizes = |item_size: &str, key_size: &str| -> Result<(usize, usize), AssemblyError> {
+            Ok((self.number(item_size)?, self.number(key_size)?))
+        };
+        let map = match (function, operands) {
+            ("new", [item_size, key_size]) => {
+                sizes(item_size, key_size).map(|(item_size, key_size)| MapAsm::Map {
+                    item_size,
+                    key_size,
+                })
+            }
+            ("with_capacity", [item_size, key_size]) => {
+                sizes(item_size, key_size).map(|(item_size, key_size)| MapAsm::MapWithCapacity {
+                    item_size,
+                    key_size,
+                })
+            }
+            ("clear", [item_size, key_size]) => {
+                sizes(item_size, key_size).map(|(item_size, key_size)| MapAsm::Clear {
+                    item_size,
+                    key_size,
+                })
+            }
+            ("free", [item_size, key_size]) => {
+                sizes(item_size, key_size).map(|(item_size, key_size)| MapAsm::MapFree {
+                    item_size,
+                    key_size,
+                })
+            }
+            ("free_interfaces", [item_size, key_size]) => {
+                sizes(item_size, key_size).map(|(item_size, key_size)| {
+                    MapAsm::MapFreeInterfaces {
+                        item_size,
+                        key_size,
+                    }
+                })
+            }
+            (function, [item_size, key_size, ref_access]) => {
+                let res = sizes(item_size, key_size).and_then(|(item_size, key_size)| {
+                    let ref_access = self.hashing(ref_access)?;
+                    Ok((item_size, key_size, ref_access))
+                });
+                match function {
+                    "insert" => res.map(|(item_size, key_size, ref_access)| MapAsm::Insert {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    }),
+                    "get" => res.map(|(item_size, key_size, ref_access)| MapAsm::Get {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    }),
+                    "contains_key" => {
+                        res.map(|(item_size, key_size, ref_access)| MapAsm::Contains {
+                            item_size,
+                            key_size,
+                            ref_access,
+                        })
+                    }
+                    "del_key" => res.map(|(item_size, key_size, ref_access)| MapAsm::DelKey {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    }),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some(map)
+    }
+
+    fn math(&self, function: &str) -> Option<MathAsm> {
+        let math = match function {
+            "ceil" => MathAsm::Ceil,
+            "floor" => MathAsm::Floor,
+            "abs" => MathAsm::Abs,
+            "exp" => MathAsm::Exp,
+            "ln" => MathAsm::Ln,
+            "log" => MathAsm::Log,
+            "log10" => MathAsm::Log10,
+            "pow" => MathAsm::Pow,
+            "sqrt" => MathAsm::Sqrt,
+            "acos" => MathAsm::Acos,
+            "asin" => MathAsm::Asin,
+            "atan" => MathAsm::Atan,
+            "atan2" => MathAsm::Atan2,
+            "cos" => MathAsm::Cos,
+            "sin" => MathAsm::Sin,
+            "tan" => MathAsm::Tan,
+            "hypot" => MathAsm::Hypot,
+            "deg" => MathAsm::Deg,
+            "rad" => MathAsm::Rad,
+            "cosh" => MathAsm::CosH,
+            "sinh" => MathAsm::SinH,
+            "tanh" => MathAsm::TanH,
+            "acosh" => MathAsm::ACosH,
+            "asinh" => MathAsm::ASinH,
+            "atanh" => MathAsm::ATanH,
+            "pi" => MathAsm::Pi,
+            "e" => MathAsm::E,
+            "inf" => MathAsm::Inf,
+            "ninf" => MathAsm::NInf,
+            "isnan" => MathAsm::IsNaN,
+            "isinf" => MathAsm::IsInf,
+            _ => return None,
+        };
+        Some(math)
+    }
+
+    fn format(
+        &self,
+        function: &str,
+        operands: &[&str],
+    ) -> Option<Result<FormatAsm, AssemblyError>> {
+        let format = match (function, operands) {
+            ("pushstr", [data]) => return Some(self.hex(data).map(FormatAsm::PushStr)),
+            ("pushstr_before", [data]) => {
+                return Some(self.hex(data).map(FormatAsm::PushStrBefore))
+            }
+            ("insert", [size]) => return Some(self.number(size).map(FormatAsm::InsertBefore)),
+            ("merge", [count]) => return Some(self.number(count).map(FormatAsm::Merge)),
+            ("wrap", [left, right]) => {
+                return Some(self.hex(left).and_then(|left| {
+                    Ok(FormatAsm::Wrap {
+                        left,
+                        right: self.hex(right)?,
+                    })
+                }))
+            }
+            (_, [_, ..]) => return None,
+            ("u128toa", []) => FormatAsm::U128TOA,
+            ("atou128", []) => FormatAsm::ATOU128,
+            ("u64toa", []) => FormatAsm::U64TOA,
+            ("u64toh", []) => FormatAsm::U64TOH,
+            ("atou64", []) => FormatAsm::ATOU64,
+            ("u32toa", []) => FormatAsm::U32TOA,
+            ("atou32", []) => FormatAsm::ATOU32,
+            ("u16toa", []) => FormatAsm::U16TOA,
+            ("atou16", []) => FormatAsm::ATOU16,
+            ("u8toa", []) => FormatAsm::U8TOA,
+            ("atou8", []) => FormatAsm::ATOU8,
+            ("i128toa", []) => FormatAsm::I128TOA,
+            ("atoi128", []) => FormatAsm::ATOI128,
+            ("i64toa", []) => FormatAsm::I64TOA,
+            ("atoi64", []) => FormatAsm::ATOI64,
+            ("i32toa", []) => FormatAsm::I32TOA,
+            ("atoi32", []) => FormatAsm::ATOI32,
+            ("i16toa", []) => FormatAsm::I16TOA,
+            ("atoi16", []) => FormatAsm::ATOI16,
+            ("i8toa", []) => FormatAsm::I8TOA,
+            ("atoi8", []) => FormatAsm::ATOI8,
+            ("ftoa", []) => FormatAsm::FTOA,
+            ("atof", []) => FormatAsm::ATOF,
+            ("btoa", []) => FormatAsm::BTOA,
+            ("atob", []) => FormatAsm::ATOB,
+            ("ctoa", []) => FormatAsm::CTOA,
+            ("atoc", []) => FormatAsm::ATOC,
+            ("stoa", []) => FormatAsm::STOA,
+            ("etoa", []) => FormatAsm::ETOA,
+            ("printfstart", []) => FormatAsm::PrintfStart,
+            ("formatstart", []) => FormatAsm::FormatStart,
+            ("push", []) => FormatAsm::Push,
+            ("printfend", []) => FormatAsm::PrintfEnd,
+            ("formatend", []) => FormatAsm::FormatEnd,
+            _ => return None,
+        };
+        Some(Ok(format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::statements::parse_statements,
+        semantic::{scope::scope::ScopeManager, Resolve},
+        vm::{
+            allocator::{heap::Heap, stack::Stack},
+            asm::operation::GetNumFrom,
+            external::test::{
+                DefaultProcessID, DefaultThreadID, ExternFuncTest, ExternFuncTestEngine, NoopEngine,
+            },
+            listing::listing,
+            runtime::Runtime,
+            scheduler::ToCompletion,
+            stdio::StdIO,
+            CodeGenerationContext, GenerateCode,
+        },
+    };
+
+    use super::*;
+
+    fn compile(input: &str) -> Program<NoopEngine> {
+        let mut statements = parse_statements::<DefaultProcessID, DefaultThreadID>(input.into(), 0)
+            .expect("Parsing should have succeeded");
+        let mut scope_manager = ScopeManager::default();
+        let mut program = Program::<NoopEngine>::default();
+        for statement in statements.iter_mut() {
+            statement
+                .resolve::<NoopEngine>(&mut scope_manager, None, &None, &mut ())
+                .expect("Resolution should have succeeded");
+        }
+        for statement in statements {
+            statement
+                .gencode::<NoopEngine>(
+                    &mut scope_manager,
+                    None,
+                    &mut program,
+                    &CodeGenerationContext::default(),
+                )
+                .expect("Code generation should have succeeded");
+        }
+        program
+    }
+
+    fn run(program: Program<NoopEngine>) -> Stack {
+        let mut engine = NoopEngine {};
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime: Runtime<NoopEngine, ToCompletion> = Runtime::default();
+        let tid = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+        runtime
+            .context_of(&tid)
+            .expect("Thread should have been found")
+            .program = program;
+        runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+        let (thread, _) = runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+        thread.stack.clone()
+    }
+
+    #[test]
+    fn valid_round_trip_of_compiled_program() {
+        let program = compile(
+            r##"
+        fn fibonacci(n:u64) -> u64 {
+            if n < 2 {
+                return n;
+            }
+            return fibonacci(n - 1) + fibonacci(n - 2);
+        }
+        let res = fibonacci(10);
+        let text = format("res = {res}");
+        let arr : Vec[u32] = vec(5);
+        arr = push(arr, 12);
+        for i in 0..len(arr) {
+            arr[i] = 1;
+        }
+        "##,
+        );
+        let text = listing(&program);
+        let assembled = assemble::<NoopEngine>(&text).expect("the assembly should have succeeded");
+        assert_eq!(listing(&assembled), text);
+        assert_eq!(assembled.len(), program.len());
+    }
+
+    #[test]
+    fn valid_run_of_hand_written_program() {
+        let program = assemble::<NoopEngine>(
+            r##"
+            ; GL[0] = 2 + 3 when 1 < 2
+                dmp 0x01
+                dmp 0x02
+                le_u8_u8
+                else_goto end
+                dmp 0x0200000000000000
+                dmp 0x0300000000000000
+                add_u64_u64
+                store GL[0] 8
+            end:
+                goto exit
+                dmp 0x0100000000000000 ; never run
+                store GL[0] 8
+            exit :
+            "##,
+        )
+        .expect("the assembly should have succeeded");
+
+        let stack = run(program);
+        let res = OpPrimitive::get_num_from::<u64>(
+            MemoryAddress::Global { offset: 0 },
+            &stack,
+            &Heap::new(),
+        )
+        .expect("Deserialization should have succeeded");
+        assert_eq!(res, 5);
+    }
+
+    #[test]
+    fn valid_assemble_extern() {
+        let program = assemble_with::<ExternFuncTestEngine>("    extern TEST_ADDER", |text| {
+            (text == "TEST_ADDER").then_some(ExternFuncTest::TEST_ADDER)
+        })
+        .expect("the assembly should have succeeded");
+        assert_eq!(program.len(), 1);
+        assert_eq!(
+            assemble::<ExternFuncTestEngine>("extern TEST_ADDER").err(),
+            Some(AssemblyError::UnknownExtern(1, "TEST_ADDER".to_string()))
+        );
+    }
+
+    #[test]
+    fn robustness_assemble() {
+        assert_eq!(
+            assemble::<NoopEngine>("pop 8\njump end").err(),
+            Some(AssemblyError::UnknownInstruction(2, "jump".to_string()))
+        );
+        assert_eq!(
+            assemble::<NoopEngine>("store XP[0] 8").err(),
+            Some(AssemblyError::InvalidOperand(1, "XP[0]".to_string()))
+        );
+        assert_eq!(
+            assemble::<NoopEngine>("goto end").err(),
+            Some(AssemblyError::UnknownLabel("end".to_string()))
+        );
+        assert_eq!(
+            assemble::<NoopEngine>("end :\nend :").err(),
+            Some(AssemblyError::DuplicateLabel(2, "end".to_string()))
+        );
+        assert_eq!(
+            assemble::<NoopEngine>("syscall vec.push").err(),
+            Some(AssemblyError::UnknownInstruction(1, "vec.push".to_string()))
+        );
+        assert_eq!(
+            assemble::<NoopEngine>(".vtable 1 0 0 _").err(),
+            Some(AssemblyError::VTableOrder(1, 1))
+        );
+    }
+}
@@ -0,0 +1,1509 @@
+use thiserror::Error;
+use ulid::Ulid;
+
+use crate::semantic::scope::static_types::NumberType;
+
+use super::{
+    allocator::MemoryAddress,
+    asm::{
+        alloc::{Access, Alloc, Free, Realloc},
+        branch::{BranchIf, BranchTry, Call, CloseFrame, Goto, Label, Return},
+        data::Data,
+        locate::{Locate, LocateIndex, LocateOffset, LocateOffsetFromStackPointer},
+        mem::Mem,
+        operation::*,
+        Asm,
+    },
+    core::{
+        alloc::AllocAsm, format::FormatAsm, io::IOAsm, iter::IterAsm, iter::Iterable,
+        map::DerefHashing, map::MapAsm, math::MathAsm, string::StringAsm, thread::ThreadAsm,
+        vector::VectorAsm, CoreAsm,
+    },
+    external::{Engine, ExternCodec},
+    program::{Instruction, Program},
+    scheduler::{EventConf, EventExclusivity, EventKind},
+    source_map::SourceLocation,
+};
+
+pub const MAGIC: [u8; 4] = *b"CIPH";
+pub const VERSION: u16 = 2;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BytecodeError {
+    #[error("the bytes do not hold a ciphel program")]
+    InvalidMagic,
+
+    #[error("the bytecode version {0} is not supported")]
+    UnsupportedVersion(u16),
+
+    #[error("unexpected end of the bytecode")]
+    UnexpectedEnd,
+
+    #[error("byte {0} : invalid {1} tag {2}")]
+    InvalidTag(usize, &'static str, u8),
+
+    #[error("byte {0} : invalid extern function")]
+    InvalidExtern(usize),
+
+    #[error("byte {0} : invalid utf8 string")]
+    InvalidString(usize),
+
+    #[error("the vtable {0} is out of order")]
+    VTableOrder(u64),
+
+    #[error("{0} bytes follow the program")]
+    TrailingBytes(usize),
+}
+
+const NUMBER_TYPES: [NumberType; 11] = [
+    NumberType::U8,
+    NumberType::U16,
+    NumberType::U32,
+    NumberType::U64,
+    NumberType::U128,
+    NumberType::I8,
+    NumberType::I16,
+    NumberType::I32,
+    NumberType::I64,
+    NumberType::I128,
+    NumberType::F64,
+];
+
+const STRING_ASM: [StringAsm; 5] = [
+    StringAsm::String {},
+    StringAsm::Append {},
+    StringAsm::CharAtStrslice,
+    StringAsm::CharAtString,
+    StringAsm::ToConstStr,
+];
+
+const ALLOC_ASM: [AllocAsm; 7] = [
+    AllocAsm::Len,
+    AllocAsm::Cap,
+    AllocAsm::Free,
+    AllocAsm::Alloc,
+    AllocAsm::MemCopy,
+    AllocAsm::FreeInterface,
+    AllocAsm::FreeInterfaceVec,
+];
+
+const IO_ASM: [IOAsm; 8] = [
+    IOAsm::PrintStr,
+    IOAsm::PrintString,
+    IOAsm::PrintlnStr,
+    IOAsm::PrintlnString,
+    IOAsm::Flush,
+    IOAsm::Flushln,
+    IOAsm::Scan,
+    IOAsm::RequestScan,
+];
+
+// the event registration is encoded after the other thread instructions
+const THREAD_ASM: [ThreadAsm; 7] = [
+    ThreadAsm::Spawn,
+    ThreadAsm::Close,
+    ThreadAsm::Exit,
+    ThreadAsm::Wait,
+    ThreadAsm::Wake,
+    ThreadAsm::Sleep,
+    ThreadAsm::Join,
+];
+
+const MATH_ASM: [MathAsm; 31] = [
+    MathAsm::Ceil,
+    MathAsm::Floor,
+    MathAsm::Abs,
+    MathAsm::Exp,
+    MathAsm::Ln,
+    MathAsm::Log,
+    MathAsm::Log10,
+    MathAsm::Pow,
+    MathAsm::Sqrt,
+    MathAsm::Acos,
+    MathAsm::Asin,
+    MathAsm::Atan,
+    MathAsm::Atan2,
+    MathAsm::Cos,
+    MathAsm::Sin,
+    MathAsm::Tan,
+    MathAsm::Hypot,
+    MathAsm::Deg,
+    MathAsm::Rad,
+    MathAsm::CosH,
+    MathAsm::SinH,
+    MathAsm::TanH,
+    MathAsm::ACosH,
+    MathAsm::ASinH,
+    MathAsm::ATanH,
+    MathAsm::Pi,
+    MathAsm::E,
+    MathAsm::Inf,
+    MathAsm::NInf,
+    MathAsm::IsNaN,
+    MathAsm::IsInf,
+];
+
+// the format instructions holding data are encoded after these ones
+const FORMAT_ASM: [FormatAsm; 34] = [
+    FormatAsm::U128TOA,
+    FormatAsm::ATOU128,
+    FormatAsm::U64TOA,
+    FormatAsm::U64TOH,
+    FormatAsm::ATOU64,
+    FormatAsm::U32TOA,
+    FormatAsm::ATOU32,
+    FormatAsm::U16TOA,
+    FormatAsm::ATOU16,
+    FormatAsm::U8TOA,
+    FormatAsm::ATOU8,
+    FormatAsm::I128TOA,
+    FormatAsm::ATOI128,
+    FormatAsm::I64TOA,
+    FormatAsm::ATOI64,
+    FormatAsm::I32TOA,
+    FormatAsm::ATOI32,
+    FormatAsm::I16TOA,
+    FormatAsm::ATOI16,
+    FormatAsm::I8TOA,
+    FormatAsm::ATOI8,
+    FormatAsm::FTOA,
+    FormatAsm::ATOF,
+    FormatAsm::BTOA,
+    FormatAsm::ATOB,
+    FormatAsm::CTOA,
+    FormatAsm::ATOC,
+    FormatAsm::STOA,
+    FormatAsm::ETOA,
+    FormatAsm::PrintfStart,
+    FormatAsm::FormatStart,
+    FormatAsm::Push,
+    FormatAsm::PrintfEnd,
+    FormatAsm::FormatEnd,
+];
+
+/*
+ * @desc Versioned binary encoding of a program : its instructions, its labels, its vtables
+ * and its source map
+ * The extern functions are encoded by the engine
+ * The numbers are little endian and the sizes are encoded on 8 bytes
+ */
+pub fn encode<E: Engine + ExternCodec>(program: &Program<E>) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.bytes.extend_from_slice(&MAGIC);
+    encoder.bytes.extend_from_slice(&VERSION.to_le_bytes());
+
+    encoder.size(program.instructions.len());
+    for instruction in &program.instructions {
+        match instruction {
+            Instruction::Asm(asm) => {
+                encoder.tag(0);
+                encoder.asm(asm);
+            }
+            Instruction::Extern(function) => {
+                encoder.tag(1);
+                encoder.data(&E::encode_function(function));
+            }
+        }
+    }
+
+    let mut labels: Vec<_> = program.labels.iter().collect();
+    labels.sort_by_key(|(id, (index, _))| (*index, **id));
+    encoder.size(labels.len());
+    for (id, (index, name)) in labels {
+        encoder.ulid(id);
+        encoder.size(*index);
+        encoder.data(name.as_bytes());
+    }
+
+    let entries = program.vtable.entries();
+    encoder.size(entries.len());
+    for (_, interface_id, type_id, functions) in entries {
+        encoder.bytes.extend_from_slice(&interface_id.to_le_bytes());
+        encoder.bytes.extend_from_slice(&type_id.to_le_bytes());
+        match functions {
+            Some(functions) => {
+                encoder.tag(1);
+                encoder.size(functions.len());
+                for function in functions {
+                    encoder.ulid(function);
+                }
+            }
+            None => encoder.tag(0),
+        }
+    }
+
+    let entries = program.source_map.entries();
+    encoder.size(entries.len());
+    for (index, location) in entries {
+        encoder.size(*index);
+        match location {
+            Some(SourceLocation {
+                line,
+                column,
+                function,
+            }) => {
+                encoder.tag(1);
+                encoder.size(*line);
+                encoder.size(*column);
+                match function {
+                    Some(function) => {
+                        encoder.tag(1);
+                        encoder.ulid(function);
+                    }
+                    None => encoder.tag(0),
+                }
+            }
+            None => encoder.tag(0),
+        }
+    }
+    encoder.bytes
+}
+
+/*
+ * @desc Program encoded by encode, the program can be loaded in a thread context as is
+ */
+pub fn decode<E: Engine + ExternCodec>(bytes: &[u8]) -> Result<Program<E>, BytecodeError> {
+    let mut decoder = Decoder { bytes, cursor: 0 };
+    if decoder.take(MAGIC.len())? != MAGIC {
+        return Err(BytecodeError::InvalidMagic);
+    }
+    let version = u16::from_le_bytes([decoder.tag()?, decoder.tag()?]);
+    if version != VERSION {
+        return Err(BytecodeError::UnsupportedVersion(version));
+    }
+
+    let mut program = Program::<E>::default();
+    for _ in 0..decoder.size()? {
+        let instruction = match decoder.tag()? {
+            0 => Instruction::Asm(decoder.asm()?),
+            1 => {
+                let start = decoder.cursor;
+                let function = E::decode_function(decoder.data()?)
+                    .ok_or(BytecodeError::InvalidExtern(start))?;
+                Instruction::Extern(function)
+            }
+            tag => return Err(decoder.invalid("instruction", tag)),
+        };
+        program.instructions.push(instruction);
+    }
+
+    for _ in 0..decoder.size()? {
+        let id = decoder.ulid()?;
+        let index = decoder.size()?;
+        let name = decoder.string()?;
+        program.labels.insert(id, (index, name.into()));
+    }
+
+    for index in 0..decoder.u64()? {
+        let interface_id = decoder.u64()?;
+        let type_id = decoder.u64()?;
+        if program.vtable.index(interface_id, type_id) != index {
+            return Err(BytecodeError::VTableOrder(index));
+        }
+        match decoder.tag()? {
+            0 => {}
+            1 => {
+                let functions = (0..decoder.size()?)
+                    .map(|_| decoder.ulid())
+                    .collect::<Result<Vec<Ulid>, BytecodeError>>()?;
+                program.vtable.alloc(interface_id, type_id, functions);
+            }
+            tag => return Err(decoder.invalid("vtable", tag)),
+        }
+    }
+
+    for _ in 0..decoder.size()? {
+        let index = decoder.size()?;
+        let location = match decoder.bool()? {
+            true => Some(SourceLocation {
+                line: decoder.size()?,
+                column: decoder.size()?,
+                function: match decoder.bool()? {
+                    true => Some(decoder.ulid()?),
+                    false => None,
+                },
+            }),
+            false => None,
+        };
+        program.source_map.push(index, location);
+    }
+
+    match bytes.len() - decoder.cursor {
+        0 => Ok(program),
+        trailing => Err(BytecodeError::TrailingBytes(trailing)),
+    }
+}
+
+fn position<T: PartialEq>(table: &[T], value: &T) -> u8 {
+    table
+        .iter()
+        .position(|item| item == value)
+        .expect("the encoding tables should be exhaustive") as u8
+}
+
+#[derive(Default)]
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn tag(&mut self, tag: u8) {
+        self.bytes.push(tag);
+    }
+
+    fn size(&mut self, size: usize) {
+        self.bytes.extend_from_slice(&(size as u64).to_le_bytes());
+    }
+
+    fn data(&mut self, data: &[u8]) {
+        self.size(data.len());
+        self.bytes.extend_from_slice(data);
+    }
+
+    fn ulid(&mut self, id: &Ulid) {
+        self.bytes.extend_from_slice(&id.0.to_le_bytes());
+    }
+
+    fn option_size(&mut self, size: &Option<usize>) {
+        match size {
+            Some(size) => {
+                self.tag(1);
+                self.size(*size);
+            }
+            None => self.tag(0),
+        }
+    }
+
+    fn address(&mut self, address: &MemoryAddress) {
+        let (tag, offset) = match address {
+            MemoryAddress::Heap { offset } => (0, offset),
+            MemoryAddress::Stack { offset } => (1, offset),
+            MemoryAddress::Frame { offset } => (2, offset),
+            MemoryAddress::Global { offset } => (3, offset),
+        };
+        self.tag(tag);
+        self.size(*offset);
+    }
+
+    fn number_type(&mut self, number: &NumberType) {
+        self.tag(position(&NUMBER_TYPES, number));
+    }
+
+    fn primitive(&mut self, primitive: &OpPrimitive) {
+        match primitive {
+            OpPrimitive::Number(number) => {
+                self.tag(0);
+                self.number_type(number);
+            }
+            OpPrimitive::Bool => self.tag(1),
+            OpPrimitive::Char => self.tag(2),
+            OpPrimitive::String => self.tag(3),
+        }
+    }
+
+    fn binary(&mut self, tag: u8, left: &OpPrimitive, right: &OpPrimitive) {
+        self.tag(tag);
+        self.primitive(left);
+        self.primitive(right);
+    }
+
+    fn hashing(&mut self, hashing: &DerefHashing) {
+        match hashing {
+            DerefHashing::Vec(item_size) => {
+                self.tag(0);
+                self.size(*item_size);
+            }
+            DerefHashing::String => self.tag(1),
+            DerefHashing::StrSlice => self.tag(2),
+            DerefHashing::Default => self.tag(3),
+        }
+    }
+
+    fn iterable(&mut self, iterable: &Iterable) {
+        match iterable {
+            Iterable::Range { num, inclusive } => {
+                self.tag(0);
+                self.number_type(num);
+                self.tag(*inclusive as u8);
+            }
+            Iterable::Vec { item_size } => {
+                self.tag(1);
+                self.size(*item_size);
+            }
+            Iterable::Slice { len, item_size } => {
+                self.tag(2);
+                self.size(*len);
+                self.size(*item_size);
+            }
+            Iterable::String => self.tag(3),
+            Iterable::StrSlice => self.tag(4),
+            Iterable::Items {
+                key_size,
+                value_size,
+            } => {
+                self.tag(5);
+                self.size(*key_size);
+                self.size(*value_size);
+            }
+        }
+    }
+
+    fn asm(&mut self, asm: &Asm) {
+        match asm {
+            Asm::Core(core) => {
+                self.tag(0);
+                self.core(core);
+            }
+            Asm::Return(Return { size }) => {
+                self.tag(1);
+                self.size(*size);
+            }
+            Asm::CloseFrame(CloseFrame) => self.tag(2),
+            Asm::Alloc(alloc) => {
+                self.tag(3);
+                match alloc {
+                    Alloc::Heap { size } => {
+                        self.tag(0);
+                        self.size(*size);
+                    }
+                    Alloc::Stack { size } => {
+                        self.tag(1);
+                        self.size(*size);
+                    }
+                    Alloc::Global { address, data } => {
+                        self.tag(2);
+                        self.address(address);
+                        self.data(data);
+                    }
+                    Alloc::GlobalFromStack { address, size } => {
+                        self.tag(3);
+                        self.address(address);
+                        self.size(*size);
+                    }
+                }
+            }
+            Asm::Realloc(Realloc { size }) => {
+                self.tag(4);
+                self.option_size(size);
+            }
+            Asm::Free(Free()) => self.tag(5),
+            Asm::Mem(mem) => {
+                self.tag(6);
+                match mem {
+                    Mem::Dup(size) => {
+                        self.tag(0);
+                        self.size(*size);
+                    }
+                    Mem::Label(label) => {
+                        self.tag(1);
+                        self.ulid(label);
+                    }
+                    Mem::Store { size, address } => {
+                        self.tag(2);
+                        self.size(*size);
+                        self.address(address);
+                    }
+                    Mem::Take { size } => {
+                        self.tag(3);
+                        self.size(*size);
+                    }
+                }
+            }
+            Asm::Operation(Operation { kind }) => {
+                self.tag(7);
+                self.operation(kind);
+            }
+            Asm::Data(Data::Serialized { data }) => {
+                self.tag(8);
+                self.data(data);
+            }
+            Asm::Access(access) => {
+                self.tag(9);
+                match access {
+                    Access::Static { address, size } => {
+                        self.tag(0);
+                        self.address(address);
+                        self.size(*size);
+                    }
+                    Access::Runtime { size } => {
+                        self.tag(1);
+                        self.option_size(size);
+                    }
+                }
+            }
+            Asm::Locate(Locate { address }) => {
+                self.tag(10);
+                self.address(address);
+            }
+            Asm::OffsetIdx(LocateIndex {
+                len,
+                size,
+                base_address,
+                offset,
+            }) => {
+                self.tag(11);
+                self.option_size(len);
+                self.size(*size);
+                match base_address {
+                    Some(address) => {
+                        self.tag(1);
+                        self.address(address);
+                    }
+                    None => self.tag(0),
+                }
+                self.option_size(offset);
+            }
+            Asm::OffsetSP(LocateOffsetFromStackPointer { offset }) => {
+                self.tag(12);
+                self.size(*offset);
+            }
+            Asm::Offset(LocateOffset { offset }) => {
+                self.tag(13);
+                self.size(*offset);
+            }
+            Asm::If(BranchIf { else_label }) => {
+                self.tag(14);
+                self.ulid(else_label);
+            }
+            Asm::Try(branch) => {
+                self.tag(15);
+                match branch {
+                    BranchTry::StartTry { else_label } => {
+                        self.tag(0);
+                        self.ulid(else_label);
+                    }
+                    BranchTry::EndTry => self.tag(1),
+                    BranchTry::Raise { for_string } => {
+                        self.tag(2);
+                        self.tag(*for_string as u8);
+                    }
+                    BranchTry::Message => self.tag(3),
+                }
+            }
+            Asm::Label(Label { id, name }) => {
+                self.tag(16);
+                self.ulid(id);
+                self.data(name.as_bytes());
+            }
+            Asm::Call(call) => {
+                self.tag(17);
+                match call {
+                    Call::From { label, param_size } => {
+                        self.tag(0);
+                        self.ulid(label);
+                        self.size(*param_size);
+                    }
+                    Call::Function { param_size } => {
+                        self.tag(1);
+                        self.size(*param_size);
+                    }
+                    Call::Closure { param_size } => {
+                        self.tag(2);
+                        self.size(*param_size);
+                    }
+                    Call::Dynamic { method, param_size } => {
+                        self.tag(3);
+                        self.size(*method);
+                        self.size(*param_size);
+                    }
+                }
+            }
+            Asm::Goto(Goto { label }) => {
+                self.tag(18);
+                match label {
+                    Some(label) => {
+                        self.tag(1);
+                        self.ulid(label);
+                    }
+                    None => self.tag(0),
+                }
+            }
+            Asm::Pop(size) => {
+                self.tag(19);
+                self.size(*size);
+            }
+        }
+    }
+
+    fn operation(&mut self, kind: &OperationKind) {
+        match kind {
+            OperationKind::Align => self.tag(0),
+            OperationKind::CastCharToUTF8 => self.tag(1),
+            OperationKind::Mult(Mult { left, right }) => self.binary(2, left, right),
+            OperationKind::Div(Division { left, right }) => self.binary(3, left, right),
+            OperationKind::Mod(Mod { left, right }) => self.binary(4, left, right),
+            OperationKind::Addition(Addition { left, right }) => self.binary(5, left, right),
+            OperationKind::Substraction(Substraction { left, right }) => {
+                self.binary(6, left, right)
+            }
+            OperationKind::ShiftLeft(ShiftLeft { left, right }) => self.binary(7, left, right),
+            OperationKind::ShiftRight(ShiftRight { left, right }) => self.binary(8, left, right),
+            OperationKind::BitwiseAnd(BitwiseAnd { left, right }) => self.binary(9, left, right),
+            OperationKind::BitwiseXOR(BitwiseXOR { left, right }) => self.binary(10, left, right),
+            OperationKind::BitwiseOR(BitwiseOR { left, right }) => self.binary(11, left, right),
+            OperationKind::Cast(Cast { from, to }) => self.binary(12, from, to),
+            OperationKind::Less(Less { left, right }) => self.binary(13, left, right),
+            OperationKind::LessEqual(LessEqual { left, right }) => self.binary(14, left, right),
+            OperationKind::Greater(Greater { left, right }) => self.binary(15, left, right),
+            OperationKind::GreaterEqual(GreaterEqual { left, right }) => {
+                self.binary(16, left, right)
+            }
+            OperationKind::Equal(Equal { left, right }) => {
+                self.tag(17);
+                self.size(*left);
+                self.size(*right);
+            }
+            OperationKind::StrEqual(StrEqual) => self.tag(18),
+            OperationKind::NotEqual(NotEqual { left, right }) => {
+                self.tag(19);
+                self.size(*left);
+                self.size(*right);
+            }
+            OperationKind::StrNotEqual(StrNotEqual) => self.tag(20),
+            OperationKind::LogicalAnd(LogicalAnd()) => self.tag(21),
+            OperationKind::LogicalOr(LogicalOr()) => self.tag(22),
+            OperationKind::Minus(Minus { data_type }) => {
+                self.tag(23);
+                self.primitive(data_type);
+            }
+            OperationKind::Not(Not()) => self.tag(24),
+        }
+    }
+
+    fn core(&mut self, core: &CoreAsm) {
+        match core {
+            CoreAsm::Vec(vector) => {
+                self.tag(0);
+                let (tag, item_size) = match vector {
+                    VectorAsm::Vec { item_size } => (0, item_size),
+                    VectorAsm::VecWithCapacity { item_size } => (1, item_size),
+                    VectorAsm::Push { item_size } => (2, item_size),
+                    VectorAsm::Pop { item_size } => (3, item_size),
+                    VectorAsm::Extend { item_size, .. } => (4, item_size),
+                    VectorAsm::Delete { item_size } => (5, item_size),
+                    VectorAsm::Clear { item_size } => (6, item_size),
+                };
+                self.tag(tag);
+                self.size(*item_size);
+                if let VectorAsm::Extend { len, .. } = vector {
+                    self.size(*len);
+                }
+            }
+            CoreAsm::Map(map) => {
+                self.tag(1);
+                let (tag, item_size, key_size, ref_access) = match map {
+                    MapAsm::Map {
+                        item_size,
+                        key_size,
+                    } => (0, item_size, key_size, None),
+                    MapAsm::MapWithCapacity {
+                        item_size,
+                        key_size,
+                    } => (1, item_size, key_size, None),
+                    MapAsm::Insert {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    } => (2, item_size, key_size, Some(ref_access)),
+                    MapAsm::Get {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    } => (3, item_size, key_size, Some(ref_access)),
+                    MapAsm::Contains {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    } => (4, item_size, key_size, Some(ref_access)),
+                    MapAsm::DelKey {
+                        item_size,
+                        key_size,
+                        ref_access,
+                    } => (5, item_size, key_size, Some(ref_access)),
+                    MapAsm::Clear {
+                        item_size,
+                        key_size,
+                    } => (6, item_size, key_size, None),
+                    MapAsm::MapFree {
+                        item_size,
+                        key_size,
+                    } => (7, item_size, key_size, None),
+                    MapAsm::MapFreeInterfaces {
+                        item_size,
+                        key_size,
+                    } => (8, item_size, key_size, None),
+                };
+                self.tag(tag);
+                self.size(*item_size);
+                self.size(*key_size);
+                if let Some(ref_access) = ref_access {
+                    self.hashing(ref_access);
+                }
+            }
+            CoreAsm::String(string) => {
+                self.tag(2);
+                self.tag(position(&STRING_ASM, string));
+            }
+            CoreAsm::Alloc(alloc) => {
+                self.tag(3);
+                self.tag(position(&ALLOC_ASM, alloc));
+            }
+            CoreAsm::Thread(ThreadAsm::EventRegistration(EventConf { kind, exclu })) => {
+                self.tag(4);
+                self.tag(THREAD_ASM.len() as u8);
+                self.tag(match kind {
+                    EventKind::Once => 0,
+                    EventKind::Repetable => 1,
+                });
+                self.tag(match exclu {
+                    EventExclusivity::PerTID => 0,
+                    EventExclusivity::PerPID => 1,
+                });
+            }
+            CoreAsm::Thread(thread) => {
+                self.tag(4);
+                self.tag(position(&THREAD_ASM, thread));
+            }
+            CoreAsm::IO(io) => {
+                self.tag(5);
+                self.tag(position(&IO_ASM, io));
+            }
+            CoreAsm::Math(math) => {
+                self.tag(6);
+                self.tag(position(&MATH_ASM, math));
+            }
+            CoreAsm::Format(format) => {
+                self.tag(7);
+                self.format(format);
+            }
+            CoreAsm::AssertBool => self.tag(8),
+            CoreAsm::AssertErr => self.tag(9),
+            CoreAsm::Error => self.tag(10),
+            CoreAsm::Ok => self.tag(11),
+            CoreAsm::Iter(iter) => {
+                self.tag(12);
+                match iter {
+                    IterAsm::MapItems {
+                        key_size,
+                        value_size,
+                    } => {
+                        self.tag(0);
+                        self.size(*key_size);
+                        self.size(*value_size);
+                    }
+                    IterAsm::MapValues {
+                        key_size,
+                        value_size,
+                    } => {
+                        self.tag(1);
+                        self.size(*key_size);
+                        self.size(*value_size);
+                    }
+                    IterAsm::MapKeys {
+                        key_size,
+                        value_size,
+                    } => {
+                        self.tag(2);
+                        self.size(*key_size);
+                        self.size(*value_size);
+                    }
+                    IterAsm::Next(iterable) => {
+                        self.tag(3);
+                        self.iterable(iterable);
+                    }
+                    IterAsm::Contains {
+                        iterable,
+                        ref_access,
+                    } => {
+                        self.tag(4);
+                        self.iterable(iterable);
+                        self.hashing(ref_access);
+                    }
+                }
+            }
+        }
+    }
+
+    fn format(&mut self, format: &FormatAsm) {
+        let base = FORMAT_ASM.len() as u8;
+        match format {
+            FormatAsm::PushStr(data) => {
+                self.tag(base);
+                self.data(data);
+            }
+            FormatAsm::PushStrBefore(data) => {
+                self.tag(base + 1);
+                self.data(data);
+            }
+            FormatAsm::InsertBefore(size) => {
+                self.tag(base + 2);
+                self.size(*size);
+            }
+            FormatAsm::Merge(count) => {
+                self.tag(base + 3);
+                self.size(*count);
+            }
+            FormatAsm::Wrap { left, right } => {
+                self.tag(base + 4);
+                self.data(left);
+                self.data(right);
+            }
+            format => self.tag(position(&FORMAT_ASM, format)),
+        }
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn invalid(&self, kind: &'static str, tag: u8) -> BytecodeError {
+        BytecodeError::InvalidTag(self.cursor - 1, kind, tag)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BytecodeError> {
+        let end = self
+            .cursor
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(BytecodeError::UnexpectedEnd)?;
+        let bytes = &self.bytes[self.cursor..end];
+        self.cursor = end;
+        Ok(bytes)
+    }
+
+    fn tag(&mut self) -> Result<u8, BytecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, BytecodeError> {
+        match self.tag()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(self.invalid("bool", tag)),
+        }
+    }
+
+    fn u64(&mut self) -> Result<u64, BytecodeError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    fn size(&mut self) -> Result<usize, BytecodeError> {
+        Ok(self.u64()? as usize)
+    }
+
+    fn data(&mut self) -> Result<&'a [u8], BytecodeError> {
+        let len = self.size()?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String, BytecodeError> {
+        let start = self.cursor;
+        let data = self.data()?;
+        String::from_utf8(data.to_vec()).map_err(|_| BytecodeError::InvalidString(start))
+    }
+
+    fn ulid(&mut self) -> Result<Ulid, BytecodeError> {
+        let bytes = self.take(16)?;
+        Ok(Ulid(u128::from_le_bytes(
+            bytes.try_into().unwrap_or_default(),
+        )))
+    }
+
+    fn option_size(&mut self) -> Result<Option<usize>, BytecodeError> {
+        match self.bool()? {
+            true => self.size().map(Some),
+            false => Ok(None),
+        }
+    }
+
+    fn table<T: Clone>(&mut self, kind: &'static str, table: &[T]) -> Result<T, BytecodeError> {
+        let tag = self.tag()?;
+        table
+            .get(tag as usize)
+            .cloned()
+            .ok_or(self.invalid(kind, tag))
+    }
+
+    fn address(&mut self) -> Result<MemoryAddress, BytecodeError> {
+        let start = self.cursor;
+        let tag = self.tag()?;
+        let offset = self.size()?;
+        match tag {
+            0 => Ok(MemoryAddress::Heap { offset }),
+            1 => Ok(MemoryAddress::Stack { offset }),
+            2 => Ok(MemoryAddress::Frame { offset }),
+            3 => Ok(MemoryAddress::Global { offset }),
+            tag => Err(BytecodeError::InvalidTag(start, "address", tag)),
+        }
+    }
+
+    fn primitive(&mut self) -> Result<OpPrimitive, BytecodeError> {
+        match self.tag()? {
+            0 => Ok(OpPrimitive::Number(self.table("number", &NUMBER_TYPES)?)),
+            1 => Ok(OpPrimitive::Bool),
+            2 => Ok(OpPrimitive::Char),
+            3 => Ok(OpPrimitive::String),
+            tag => Err(self.invalid("primitive", tag)),
+        }
+    }
+
+    fn hashing(&mut self) -> Result<DerefHashing, BytecodeError> {
+        match self.tag()? {
+            0 => Ok(DerefHashing::Vec(self.size()?)),
+            1 => Ok(DerefHashing::String),
+            2 => Ok(DerefHashing::StrSlice),
+            3 => Ok(DerefHashing::Default),
+            tag => Err(self.invalid("hashing", tag)),
+        }
+    }
+
+    fn iterable(&mut self) -> Result<Iterable, BytecodeError> {
+        match self.tag()? {
+            0 => Ok(Iterable::Range {
+                num: self.table("number", &NUMBER_TYPES)?,
+                inclusive: self.bool()?,
+            }),
+            1 => Ok(Iterable::Vec {
+                item_size: self.size()?,
+            }),
+            2 => Ok(Iterable::Slice {
+                len: self.size()?,
+                item_size: self.size()?,
+            }),
+            3 => Ok(Iterable::String),
+            4 => Ok(Iterable::StrSlice),
+            5 => Ok(Iterable::Items {
+                key_size: self.size()?,
+                value_size: self.size()?,
+            }),
+            tag => Err(self.invalid("iterable", tag)),
+        }
+    }
+
+    fn asm(&mut self) -> Result<Asm, BytecodeError> {
+        let asm = match self.tag()? {
+            0 => Asm::Core(self.core()?),
+            1 => Asm::Return(Return { size: self.size()? }),
+            2 => Asm::CloseFrame(CloseFrame),
+            3 => Asm::Alloc(match self.tag()? {
+                0 => Alloc::Heap { size: self.size()? },
+                1 => Alloc::Stack { size: self.size()? },
+                2 => Alloc::Global {
+                    address: self.address()?,
+                    data: self.data()?.into(),
+                },
+                3 => Alloc::GlobalFromStack {
+                    address: self.address()?,
+                    size: self.size()?,
+                },
+                tag => return Err(self.invalid("alloc", tag)),
+            }),
+            4 => Asm::Realloc(Realloc {
+                size: self.option_size()?,
+            }),
+            5 => Asm::Free(Free()),
+            6 => Asm::Mem(match self.tag()? {
+                0 => Mem::Dup(self.size()?),
+                1 => Mem::Label(self.ulid()?),
+                2 => Mem::Store {
+                    size: self.size()?,
+                    address: self.address()?,
+                },
+                3 => Mem::Take { size: self.size()? },
+                tag => return Err(self.invalid("mem", tag)),
+            }),
+            7 => Asm::Operation(Operation {
+                kind: self.operation()?,
+            }),
+            8 => Asm::Data(Data::Serialized {
+                data: self.data()?.into(),
+            }),
+            9 => Asm::Access(match self.tag()? {
+                0 => Access::Static {
+                    address: self.address()?,
+                    size: self.size()?,
+                },
+                1 => Access::Runtime {
+                    size: self.option_size()?,
+                },
+                tag => return Err(self.invalid("access", tag)),
+            }),
+            10 => Asm::Locate(Locate {
+                address: self.address()?,
+            }),
+            11 => Asm::OffsetIdx(LocateIndex {
+                len: self.option_size()?,
+                size: self.size()?,
+                base_address: match self.bool()? {
+                    true => Some(self.address()?),
+                    false => None,
+                },
+                offset: self.option_size()?,
+            }),
+            12 => Asm::OffsetSP(LocateOffsetFromStackPointer {
+                offset: self.size()?,
+            }),
+            13 => Asm::Offset(LocateOffset {
+                offset: self.size()?,
+            }),
+            14 => Asm::If(BranchIf {
+                else_label: self.ulid()?,
+            }),
+            15 => Asm::Try(match self.tag()? {
+                0 => BranchTry::StartTry {
+                    else_label: self.ulid()?,
+                },
+                1 => BranchTry::EndTry,
+                2 => BranchTry::Raise {
+                    for_string: self.bool()?,
+                },
+                3 => BranchTry::Message,
+                tag => return Err(self.invalid("try", tag)),
+            }),
+            16 => Asm::Label(Label {
+                id: self.ulid()?,
+                name: self.string()?,
+            }),
+            17 => Asm::Call(match self.tag()? {
+                0 => Call::From {
+                    label: self.ulid()?,
+                    param_size: self.size()?,
+                },
+                1 => Call::Function {
+                    param_size: self.size()?,
+                },
+                2 => Call::Closure {
+                    param_size: self.size()?,
+                },
+                3 => Call::Dynamic {
+                    method: self.size()?,
+                    param_size: self.size()?,
+                },
+                tag => return Err(self.invalid("call", tag)),
+            }),
+            18 => Asm::Goto(Goto {
+                label: match self.bool()? {
+                    true => Some(self.ulid()?),
+                    false => None,
+                },
+            }),
+            19 => Asm::Pop(self.size()?),
+            tag => return Err(self.invalid("asm", tag)),
+        };
+        Ok(asm)
+    }
+
+    fn operation(&mut self) -> Result<OperationKind, BytecodeError> {
+        let tag = self.tag()?;
+        let kind = match tag {
+            0 => OperationKind::Align,
+            1 => OperationKind::CastCharToUTF8,
+            17 => OperationKind::Equal(Equal {
+                left: self.size()?,
+                right: self.size()?,
+            }),
+            18 => OperationKind::StrEqual(StrEqual),
+            19 => OperationKind::NotEqual(NotEqual {
+                left: self.size()?,
+                right: self.size()?,
+            }),
+            20 => OperationKind::StrNotEqual(StrNotEqual),
+            21 => OperationKind::LogicalAnd(LogicalAnd()),
+            22 => OperationKind::LogicalOr(LogicalOr()),
+            23 => OperationKind::Minus(Minus {
+                data_type: self.primitive()?,
+            }),
+            24 => OperationKind::Not(Not()),
+            2..=16 => {
+                let left = self.primitive()?;
+                let right = self.primitive()?;
+                match tag {
+                    2 => OperationKind::Mult(Mult { left, right }),
+                    3 => OperationKind::Div(Division { left, right }),
+                    4 => OperationKind::Mod(Mod { left, right }),
+                    5 => OperationKind::Addition(Addition { left, right }),
+                    6 => OperationKind::Substraction(Substraction { left, right }),
+                    7 => OperationKind::ShiftLeft(ShiftLeft { left, right }),
+                    8 => OperationKind::ShiftRight(ShiftRight { left, right }),
+                    9 => OperationKind::BitwiseAnd(BitwiseAnd { left, right }),
+                    10 => OperationKind::BitwiseXOR(BitwiseXOR { left, right }),
+                    11 => OperationKind::BitwiseOR(BitwiseOR { left, right }),
+                    12 => OperationKind::Cast(Cast {
+                        from: left,
+                        to: right,
+                    }),
+                    13 => OperationKind::Less(Less { left, right }),
+                    14 => OperationKind::LessEqual(LessEqual { left, right }),
+                    15 => OperationKind::Greater(Greater { left, right }),
+                    _ => OperationKind::GreaterEqual(GreaterEqual { left, right }),
+                }
+            }
+            tag => return Err(self.invalid("operation", tag)),
+        };
+        Ok(kind)
+    }
+
+    fn core(&mut self) -> Result<CoreAsm, BytecodeError> {
+        let core = match self.tag()? {
+            0 => {
+                let start = self.cursor;
+                let tag = self.tag()?;
+                let item_size = self.size()?;
+                CoreAsm::Vec(match tag {
+                    0 => VectorAsm::Vec { item_size },
+                    1 => VectorAsm::VecWithCapacity { item_size },
+                    2 => VectorAsm::Push { item_size },
+                    3 => VectorAsm::Pop { item_size },
+                    4 => VectorAsm::Extend {
+                        item_size,
+                        len: self.size()?,
+                    },
+                    5 => VectorAsm::Delete { item_size },
+                    6 => VectorAsm::Clear { item_size },
+                    tag => return Err(BytecodeError::InvalidTag(start, "vec", tag)),
+                })
+            }
+            1 => {
+                let start = self.cursor;
+                let tag = self.tag()?;
+                let item_size = self.size()?;
+                let key_size = self.size()?;
+                CoreAsm::Map(match tag {
+                    0 => MapAsm::Map {
+                        item_size,
+                        key_size,
+                    },
+                    1 => MapAsm::MapWithCapacity {
+                        item_size,
+                        key_size,
+                    },
+                    2 => MapAsm::Insert {
+                        item_size,
+                        key_size,
+                        ref_access: self.hashing()?,
+                    },
+                    3 => MapAsm::Get {
+                        item_size,
+                        key_size,
+                        ref_access: self.hashing()?,
+                    },
+                    4 => MapAsm::Contains {
+                        item_size,
+                        key_size,
+                        ref_access: self.hashing()?,
+                    },
+                    5 => MapAsm::DelKey {
+                        item_size,
+                        key_size,
+                        ref_access: self.hashing()?,
+                    },
+                    6 => MapAsm::Clear {
+                        item_size,
+                        key_size,
+                    },
+                    7 => MapAsm::MapFree {
+                        item_size,
+                        key_size,
+                    },
+                    8 => MapAsm::MapFreeInterfaces {
+                        item_size,
+                        key_size,
+                    },
+                    tag => return Err(BytecodeError::InvalidTag(start, "map", tag)),
+                })
+            }
+            2 => CoreAsm::String(self.table("string", &STRING_ASM)?),
+            3 => CoreAsm::Alloc(self.table("alloc", &ALLOC_ASM)?),
+            4 => match self.tag()? {
+                tag if tag as usize == THREAD_ASM.len() => {
+                    CoreAsm::Thread(ThreadAsm::EventRegistration(EventConf {
+                        kind: match self.tag()? {
+                            0 => EventKind::Once,
+                            1 => EventKind::Repetable,
+                            tag => return Err(self.invalid("event kind", tag)),
+                        },
+                        exclu: match self.tag()? {
+                            0 => EventExclusivity::PerTID,
+                            1 => EventExclusivity::PerPID,
+                            tag => return Err(self.invalid("event exclusivity", tag)),
+                        },
+                    }))
+                }
+                tag => CoreAsm::Thread(
+                    THREAD_ASM
+                        .get(tag as usize)
+                        .cloned()
+                        .ok_or(self.invalid("thread", tag))?,
+                ),
+            },
+            5 => CoreAsm::IO(self.table("io", &IO_ASM)?),
+            6 => CoreAsm::Math(self.table("math", &MATH_ASM)?),
+            7 => CoreAsm::Format(self.format()?),
+            8 => CoreAsm::AssertBool,
+            9 => CoreAsm::AssertErr,
+            10 => CoreAsm::Error,
+            11 => CoreAsm::Ok,
+            12 => CoreAsm::Iter(match self.tag()? {
+                0 => IterAsm::MapItems {
+                    key_size: self.size()?,
+                    value_size: self.size()?,
+                },
+                1 => IterAsm::MapValues {
+                    key_size: self.size()?,
+                    value_size: self.size()?,
+                },
+                2 => IterAsm::MapKeys {
+                    key_size: self.size()?,
+                    value_size: self.size()?,
+                },
+                3 => IterAsm::Next(self.iterable()?),
+                4 => IterAsm::Contains {
+                    iterable: self.iterable()?,
+                    ref_access: self.hashing()?,
+                },
+                tag => return Err(self.invalid("iter", tag)),
+            }),
+            tag => return Err(self.invalid("core", tag)),
+        };
+        Ok(core)
+    }
+
+    fn format(&mut self) -> Result<FormatAsm, BytecodeError> {
+        let tag = self.tag()?;
+        if let Some(format) = FORMAT_ASM.get(tag as usize) {
+            return Ok(format.clone());
+        }
+        let format = match tag as usize - FORMAT_ASM.len() {
+            0 => FormatAsm::PushStr(self.data()?.into()),
+            1 => FormatAsm::PushStrBefore(self.data()?.into()),
+            2 => FormatAsm::InsertBefore(self.size()?),
+            3 => FormatAsm::Merge(self.size()?),
+            4 => FormatAsm::Wrap {
+                left: self.data()?.into(),
+                right: self.data()?.into(),
+            },
+            _ => return Err(self.invalid("format", tag)),
+        };
+        Ok(format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::statements::parse_statements,
+        semantic::{scope::scope::ScopeManager, Resolve},
+        test_extract_variable,
+        vm::{
+            allocator::heap::Heap,
+            assembler::assemble,
+            external::test::{
+                DefaultProcessID, DefaultThreadID, ExternFuncTest, ExternFuncTestEngine, NoopEngine,
+            },
+            listing::listing,
+            runtime::Runtime,
+            scheduler::ToCompletion,
+            stdio::StdIO,
+            CodeGenerationContext, GenerateCode,
+        },
+    };
+
+    use super::*;
+
+    fn compile(input: &str) -> (Program<NoopEngine>, ScopeManager) {
+        let mut statements = parse_statements::<DefaultProcessID, DefaultThreadID>(input.into(), 0)
+            .expect("Parsing should have succeeded");
+        let mut scope_manager = ScopeManager::default();
+        let mut program = Program::<NoopEngine>::default();
+        for statement in statements.iter_mut() {
+            statement
+                .resolve::<NoopEngine>(&mut scope_manager, None, &None, &mut ())
+                .expect("Resolution should have succeeded");
+        }
+        for statement in statements {
+            statement
+                .gencode::<NoopEngine>(
+                    &mut scope_manager,
+                    None,
+                    &mut program,
+                    &CodeGenerationContext::default(),
+                )
+                .expect("Code generation should have succeeded");
+        }
+        (program, scope_manager)
+    }
+
+    #[test]
+    fn valid_round_trip_of_every_kind_of_instruction() {
+        let program = assemble::<NoopEngine>(
+            r##"
+            start :
+                galloc GL[0] 0x0102
+                galloc_from_stack GL[8] 8
+                halloc 16
+                salloc 8
+                realloc _
+                realloc 32
+                free
+                load HP[4] 8
+                load _
+                load 16
+                dup 8
+                dmp_label start
+                take 8
+                store FP[8] 4
+                addr SP[2]
+                addr SP[-4]
+                offset 8
+                addr_at_idx SP[0] 8 _ 4
+                addr_at_idx _ 8 3 _
+                else_goto start
+                try_else end
+                try_end
+                raise
+                raise_string
+                call start 8
+                call_fn 8
+                call_closure 16
+                call_dyn 1 8
+                goto start
+                goto
+                return 8
+                close_frame
+                pop 8
+                align
+                char_to_utf8
+                add_u64_u64
+                cast_i32_f64
+                geq_char_char
+                eq 8 8
+                neq 1 1
+                str_eq
+                str_neq
+                and
+                or
+                not
+                neg_f64
+                syscall assert_true
+                syscall ok
+                syscall vec.extend 8 3
+                syscall map.insert 8 8 vec:4
+                syscall map.free 8 16
+                syscall str.to_const_str
+                syscall alloc.memcpy
+                syscall thread.join
+                syscall thread.event repeatable per_pid
+                syscall io.rscan
+                syscall math.atan2
+                syscall fmt.etoa
+                syscall fmt.wrap 0x28 0x29
+                syscall fmt.merge 3
+                syscall iter.keys 8 16
+                syscall iter.next range_inclusive:u8
+                syscall iter.contains items:8:16 str_slice
+            end :
+            .vtable 0 1 2 start end
+            .vtable 1 1 3 _
+            "##,
+        )
+        .expect("the assembly should have succeeded");
+
+        let bytes = encode(&program);
+        assert!(bytes.starts_with(&MAGIC));
+        let decoded = decode::<NoopEngine>(&bytes).expect("the decoding should have succeeded");
+        assert_eq!(listing(&decoded), listing(&program));
+        assert_eq!(decoded.labels, program.labels);
+        assert_eq!(encode(&decoded), bytes);
+    }
+
+    #[test]
+    fn valid_run_of_decoded_program() {
+        let (program, scope_manager) = compile(
+            r##"
+        fn fibonacci(n:u64) -> u64 {
+            if n < 2 {
+                return n;
+            }
+            return fibonacci(n - 1) + fibonacci(n - 2);
+        }
+        let res = fibonacci(10);
+        "##,
+        );
+        let decoded =
+            decode::<NoopEngine>(&encode(&program)).expect("the decoding should have succeeded");
+        assert!(!program.source_map.entries().is_empty());
+        assert_eq!(decoded.source_map.entries(), program.source_map.entries());
+
+        let mut engine = NoopEngine {};
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime: Runtime<NoopEngine, ToCompletion> = Runtime::default();
+        let tid = runtime
+            .spawn_with_program(DefaultProcessID::default(), decoded, &mut engine)
+            .expect("Spawning should have succeeded");
+        runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        let (thread, _) = runtime
+            .thread_with_context_of(&tid)
+            .expect("Thread should have been found");
+        let res = test_extract_variable::<u64>("res", &scope_manager, &thread.stack, &heap)
+            .expect("Deserialization should have succeeded");
+        assert_eq!(res, 55);
+    }
+
+    #[test]
+    fn valid_round_trip_of_extern_function() {
+        let mut program = Program::<ExternFuncTestEngine>::default();
+        program.push_extern(ExternFuncTest::TEST_ADDER);
+        let decoded = decode::<ExternFuncTestEngine>(&encode(&program))
+            .expect("the decoding should have succeeded");
+        assert!(matches!(
+            decoded.instructions.as_slice(),
+            [Instruction::Extern(ExternFuncTest::TEST_ADDER)]
+        ));
+    }
+
+    #[test]
+    fn robustness_decode() {
+        let program = assemble::<NoopEngine>("pop 8").expect("the assembly should have succeeded");
+        let bytes = encode(&program);
+
+        assert_eq!(
+            decode::<NoopEngine>(b"CASM").err(),
+            Some(BytecodeError::InvalidMagic)
+        );
+
+        let mut version = bytes.clone();
+        version[4] = 0xff;
+        assert_eq!(
+            decode::<NoopEngine>(&version).err(),
+            Some(BytecodeError::UnsupportedVersion(0xff))
+        );
+
+        assert_eq!(
+            decode::<NoopEngine>(&bytes[..bytes.len() - 1]).err(),
+            Some(BytecodeError::UnexpectedEnd)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            decode::<NoopEngine>(&trailing).err(),
+            Some(BytecodeError::TrailingBytes(1))
+        );
+
+        // the asm tag of the first instruction
+        let mut tag = bytes.clone();
+        tag[15] = 0xff;
+        assert_eq!(
+            decode::<NoopEngine>(&tag).err(),
+            Some(BytecodeError::InvalidTag(15, "asm", 0xff))
+        );
+    }
+}
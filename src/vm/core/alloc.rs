@@ -4,7 +4,7 @@ use crate::{
     err_tuple,
     semantic::ResolveCore,
     vm::{
-        allocator::MemoryAddress,
+        allocator::{vtable::VTable, MemoryAddress},
         asm::operation::{GetNumFrom, PopNum},
         runtime::RuntimeError,
         scheduler::Executable,
@@ -19,19 +19,37 @@ use crate::{
     ast::expressions::Expression,
     e_static, p_num,
     semantic::{
-        scope::static_types::{AddrType, StaticType},
+        scope::{
+            static_types::{AddrType, MapType, StaticType, VecType, POINTER_SIZE},
+            user_types::UserType,
+        },
         EType, Resolve, SemanticError, SizeOf, TypeOf,
     },
     vm::asm::{data::Data, operation::OpPrimitive, Asm},
 };
 
-use super::{lexem, PathFinder, ERROR_SLICE, OK_SLICE};
+use super::{lexem, vector::VEC_HEADER, PathFinder, ERROR_SLICE, OK_SLICE};
+
+/*
+ * @desc What a call to free releases along with the freed value
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum FreeKind {
+    #[default]
+    Block,
+    // the data owned by an interface value
+    Interface,
+    // the data owned by the interface values of a vector
+    InterfaceVec,
+    // the data owned by the interface values of a map
+    InterfaceMap { key_size: usize },
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AllocFn {
     Len,
     Cap,
-    Free,
+    Free { kind: FreeKind },
     Alloc,
     SizeOf { size: usize },
     MemCopy,
@@ -44,6 +62,8 @@ pub enum AllocAsm {
     Free,
     Alloc,
     MemCopy,
+    FreeInterface,
+    FreeInterfaceVec,
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for AllocAsm {
@@ -60,10 +80,27 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for AllocAsm {
             AllocAsm::Free => stdio.push_asm_lib(engine, pid, "free"),
             AllocAsm::Alloc => stdio.push_asm_lib(engine, pid, "malloc"),
             AllocAsm::MemCopy => stdio.push_asm_lib(engine, pid, "memcpy"),
+            AllocAsm::FreeInterface => stdio.push_asm_lib(engine, pid, "free_interface"),
+            AllocAsm::FreeInterfaceVec => stdio.push_asm_lib(engine, pid, "free_interface_vec"),
         }
     }
 }
 
+impl crate::vm::AsmListing for AllocAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let name = match self {
+            AllocAsm::Len => "alloc.len",
+            AllocAsm::Cap => "alloc.cap",
+            AllocAsm::Free => "alloc.free",
+            AllocAsm::Alloc => "alloc.malloc",
+            AllocAsm::MemCopy => "alloc.memcpy",
+            AllocAsm::FreeInterface => "alloc.free_interface",
+            AllocAsm::FreeInterfaceVec => "alloc.free_interface_vec",
+        };
+        name.to_string()
+    }
+}
+
 impl crate::vm::AsmWeight for AllocAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -72,6 +109,8 @@ impl crate::vm::AsmWeight for AllocAsm {
             AllocAsm::Free => crate::vm::Weight::MEDIUM,
             AllocAsm::Alloc => crate::vm::Weight::HIGH,
             AllocAsm::MemCopy => crate::vm::Weight::HIGH,
+            AllocAsm::FreeInterface => crate::vm::Weight::MEDIUM,
+            AllocAsm::FreeInterfaceVec => crate::vm::Weight::HIGH,
         }
     }
 }
@@ -85,7 +124,9 @@ impl PathFinder for AllocFn {
             return match name {
                 lexem::LEN => Some(AllocFn::Len),
                 lexem::CAP => Some(AllocFn::Cap),
-                lexem::FREE => Some(AllocFn::Free),
+                lexem::FREE => Some(AllocFn::Free {
+                    kind: FreeKind::Block,
+                }),
                 lexem::ALLOC => Some(AllocFn::Alloc),
                 lexem::MEMCPY => Some(AllocFn::MemCopy),
                 lexem::SIZEOF => Some(AllocFn::SizeOf { size: 0 }),
@@ -103,30 +144,64 @@ impl ResolveCore for AllocFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
-            AllocFn::Free => {
+            AllocFn::Free { kind } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
 
                 let address = &mut parameters[0];
 
-                let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                let address_type = address.type_of(&scope_manager, scope_id)?;
-                match &address_type {
-                    EType::Static(StaticType::Address(_)) => {}
-                    EType::Static(StaticType::Vec(_)) => {}
-                    EType::Static(StaticType::String(_)) => {}
-                    EType::Static(StaticType::Map(_)) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                if !resolved_receiver {
+                    let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
                 }
+                let address_type = address.type_of(&scope_manager, scope_id)?;
+                *kind = match &address_type {
+                    EType::Static(StaticType::Address(_)) => FreeKind::Block,
+                    EType::Static(StaticType::Vec(VecType(item)))
+                        if is_interface(item, scope_manager, scope_id) =>
+                    {
+                        FreeKind::InterfaceVec
+                    }
+                    EType::Static(StaticType::Vec(_)) => FreeKind::Block,
+                    EType::Static(StaticType::String(_)) => FreeKind::Block,
+                    EType::Static(StaticType::Map(MapType {
+                        keys_type,
+                        values_type,
+                    })) if is_interface(values_type, scope_manager, scope_id) => {
+                        FreeKind::InterfaceMap {
+                            key_size: keys_type.size_of(),
+                        }
+                    }
+                    EType::Static(StaticType::Map(_)) => FreeKind::Block,
+                    // an interface value cast from a value owns the heap copy of its data
+                    interface if is_interface(interface, scope_manager, scope_id) => {
+                        FreeKind::Interface
+                    }
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address, a Vec, a String, a Map or an interface",
+                            &address_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(address.span()))
+                    }
+                };
 
                 Ok(EType::Static(StaticType::Error))
             }
             AllocFn::Alloc => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
 
                 let size = &mut parameters[0];
@@ -134,8 +209,15 @@ impl ResolveCore for AllocFn {
                 let _ =
                     size.resolve::<E>(scope_manager, scope_id, &Some(p_num!(U64)), &mut None)?;
 
-                let p_num!(U64) = size.type_of(&scope_manager, scope_id)? else {
-                    return Err(SemanticError::IncorrectArguments);
+                let size_type = size.type_of(&scope_manager, scope_id)?;
+                let p_num!(U64) = size_type else {
+                    return Err(SemanticError::mismatched(
+                        &p_num!(U64),
+                        &size_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(size.span()));
                 };
 
                 let inner = match context {
@@ -149,50 +231,75 @@ impl ResolveCore for AllocFn {
             }
             AllocFn::Len => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let address = &mut parameters[0];
 
-                let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                if !resolved_receiver {
+                    let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                }
                 let address_type = address.type_of(&scope_manager, scope_id)?;
                 match &address_type {
-                    EType::Static(value) => match value {
-                        StaticType::String(_) => {}
-                        StaticType::Vec(_) => {}
-                        StaticType::Map(_) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(
+                        StaticType::String(_) | StaticType::Vec(_) | StaticType::Map(_),
+                    ) => {}
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a String, a Vec or a Map",
+                            &address_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(address.span()))
+                    }
                 }
                 Ok(p_num!(U64))
             }
             AllocFn::Cap => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let address = &mut parameters[0];
 
-                let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                if !resolved_receiver {
+                    let _ = address.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                }
                 let address_type = address.type_of(&scope_manager, scope_id)?;
 
                 match &address_type {
-                    EType::Static(value) => match value {
-                        StaticType::String(_) => {}
-                        StaticType::Vec(_) => {}
-                        StaticType::Map(_) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(
+                        StaticType::String(_) | StaticType::Vec(_) | StaticType::Map(_),
+                    ) => {}
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a String, a Vec or a Map",
+                            &address_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(address.span()))
+                    }
                 }
                 Ok(p_num!(U64))
             }
             AllocFn::SizeOf { size } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let param = &mut parameters[0];
 
-                let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                if !resolved_receiver {
+                    let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                }
                 let param_type = param.type_of(&scope_manager, scope_id)?;
 
                 *size = param_type.size_of();
@@ -201,7 +308,10 @@ impl ResolveCore for AllocFn {
             }
             AllocFn::MemCopy => {
                 if parameters.len() != 3 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 3,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, rest) = parameters.split_at_mut(1);
                 let (second_part, third_part) = rest.split_at_mut(1);
@@ -211,39 +321,62 @@ impl ResolveCore for AllocFn {
                 let src = &mut second_part[0];
                 let size = &mut third_part[0];
 
-                let _ = dest.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                if !resolved_receiver {
+                    let _ = dest.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+                }
                 let _ = src.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
                 let dest_type = dest.type_of(&scope_manager, scope_id)?;
                 let src_type = src.type_of(&scope_manager, scope_id)?;
 
                 match &dest_type {
-                    EType::Static(value) => match value {
-                        StaticType::Address(_) => {}
-                        StaticType::String(_) => {}
-                        StaticType::StrSlice(_) => {}
-                        StaticType::Slice(_) => {}
-                        StaticType::Vec(_) => {}
-                        StaticType::Map(_) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(
+                        StaticType::Address(_)
+                        | StaticType::String(_)
+                        | StaticType::StrSlice(_)
+                        | StaticType::Slice(_)
+                        | StaticType::Vec(_)
+                        | StaticType::Map(_),
+                    ) => {}
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address, a slice, a Vec, a String or a Map",
+                            &dest_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(dest.span()))
+                    }
                 }
                 match &src_type {
-                    EType::Static(value) => match value {
-                        StaticType::Address(_) => {}
-                        StaticType::String(_) => {}
-                        StaticType::StrSlice(_) => {}
-                        StaticType::Slice(_) => {}
-                        StaticType::Vec(_) => {}
-                        StaticType::Map(_) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(
+                        StaticType::Address(_)
+                        | StaticType::String(_)
+                        | StaticType::StrSlice(_)
+                        | StaticType::Slice(_)
+                        | StaticType::Vec(_)
+                        | StaticType::Map(_),
+                    ) => {}
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address, a slice, a Vec, a String or a Map",
+                            &src_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(src.span()))
+                    }
                 }
                 let _ =
                     size.resolve::<E>(scope_manager, scope_id, &Some(p_num!(U64)), &mut None)?;
-                let p_num!(U64) = size.type_of(&scope_manager, scope_id)? else {
-                    return Err(SemanticError::IncorrectArguments);
+                let size_type = size.type_of(&scope_manager, scope_id)?;
+                let p_num!(U64) = size_type else {
+                    return Err(SemanticError::mismatched(
+                        &p_num!(U64),
+                        &size_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(size.span()));
                 };
 
                 Ok(EType::Static(StaticType::Error))
@@ -261,7 +394,17 @@ impl GenerateCode for AllocFn {
         context: &crate::vm::CodeGenerationContext,
     ) -> Result<(), crate::vm::CodeGenerationError> {
         match self {
-            AllocFn::Free => instructions.push(Asm::Core(super::CoreAsm::Alloc(AllocAsm::Free))),
+            AllocFn::Free { kind } => instructions.push(Asm::Core(match kind {
+                FreeKind::Block => super::CoreAsm::Alloc(AllocAsm::Free),
+                FreeKind::Interface => super::CoreAsm::Alloc(AllocAsm::FreeInterface),
+                FreeKind::InterfaceVec => super::CoreAsm::Alloc(AllocAsm::FreeInterfaceVec),
+                FreeKind::InterfaceMap { key_size } => {
+                    super::CoreAsm::Map(super::map::MapAsm::MapFreeInterfaces {
+                        item_size: 2 * POINTER_SIZE,
+                        key_size: *key_size,
+                    })
+                }
+            })),
             AllocFn::Alloc => instructions.push(Asm::Core(super::CoreAsm::Alloc(AllocAsm::Alloc))),
             AllocFn::Len => instructions.push(Asm::Core(super::CoreAsm::Alloc(AllocAsm::Len))),
             AllocFn::Cap => instructions.push(Asm::Core(super::CoreAsm::Alloc(AllocAsm::Cap))),
@@ -313,6 +456,38 @@ impl<E: crate::vm::external::Engine> Executable<E> for AllocAsm {
                     stack.push_with(&ERROR_SLICE)?;
                 }
             }
+            AllocAsm::FreeInterface => {
+                let word = OpPrimitive::pop_num::<u64>(stack)?;
+                let address = OpPrimitive::pop_num::<u64>(stack)?;
+
+                // the data borrowed by the interface value is not freed
+                if release_interface(address, word, heap).unwrap_or(false) {
+                    stack.push_with(&OK_SLICE)?;
+                } else {
+                    stack.push_with(&ERROR_SLICE)?;
+                }
+            }
+            AllocAsm::FreeInterfaceVec => {
+                let address: MemoryAddress = OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                let len = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)? as usize;
+                let items = heap.read(address.add(VEC_HEADER), len * 2 * POINTER_SIZE)?;
+
+                let mut res = Ok(());
+                for item in items.chunks_exact(2 * POINTER_SIZE) {
+                    let (address, word) = item.split_at(POINTER_SIZE);
+                    if let Err(err) =
+                        release_interface(u64_of(address)?, u64_of(word)?, heap)
+                    {
+                        res = Err(err);
+                    }
+                }
+
+                if heap.free(address).is_ok() && res.is_ok() {
+                    stack.push_with(&OK_SLICE)?;
+                } else {
+                    stack.push_with(&ERROR_SLICE)?;
+                }
+            }
             AllocAsm::Alloc => {
                 let size = OpPrimitive::pop_num::<u64>(stack)? as usize;
                 if let Ok(address) = heap.alloc(size) {
@@ -367,6 +542,49 @@ impl<E: crate::vm::external::Engine> Executable<E> for AllocAsm {
     }
 }
 
+/*
+ * @desc Whether a type is an interface, the values of which can own a heap copy of their data
+ */
+pub fn is_interface(
+    ctype: &EType,
+    scope_manager: &crate::semantic::scope::scope::ScopeManager,
+    scope_id: Option<u128>,
+) -> bool {
+    match ctype {
+        EType::User { id, .. } => matches!(
+            scope_manager.find_type_by_id(*id, scope_id),
+            Ok(UserType::Interface(_))
+        ),
+        _ => false,
+    }
+}
+
+/*
+ * @desc Free the data owned by an interface value given the address of its data and its vtable word
+ * Returns false when the interface value borrows its data
+ */
+pub fn release_interface(
+    address: u64,
+    word: u64,
+    heap: &mut crate::vm::allocator::heap::Heap,
+) -> Result<bool, RuntimeError> {
+    let (_, owned) = VTable::split(word);
+    if !owned {
+        return Ok(false);
+    }
+    let address: MemoryAddress = address.try_into()?;
+    heap.free(address)?;
+    Ok(true)
+}
+
+fn u64_of(bytes: &[u8]) -> Result<u64, RuntimeError> {
+    Ok(u64::from_le_bytes(
+        bytes
+            .try_into()
+            .map_err(|_| RuntimeError::Deserialization)?,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -65,29 +65,54 @@ impl ResolveCore for FormatFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        _resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
             FormatFn::ITOA { number_type } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::Primitive(PrimitiveType::Number(nt))) => {
-                        *number_type = nt;
+                        *number_type = nt.clone();
+                    }
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a number",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
                     }
-                    _ => return Err(SemanticError::IncorrectArguments),
                 }
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             FormatFn::ATOI { number_type } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::StrSlice(StrSliceType())) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::StrSlice(StrSliceType())),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
 
                 match context {
@@ -95,7 +120,14 @@ impl ResolveCore for FormatFn {
                         *number_type = nt.clone();
                     }
                     None => *number_type = NumberType::I64,
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    Some(other) => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a number",
+                            other,
+                            scope_manager,
+                            scope_id,
+                        ));
+                    }
                 }
                 Ok(EType::Static(StaticType::Primitive(PrimitiveType::Number(
                     number_type.to_owned(),
@@ -103,25 +135,51 @@ impl ResolveCore for FormatFn {
             }
             FormatFn::FTOA => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::Primitive(PrimitiveType::Number(
                         NumberType::F64,
                     ))) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::Primitive(PrimitiveType::Number(
+                                NumberType::F64,
+                            ))),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             FormatFn::ATOF => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::StrSlice(StrSliceType())) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::StrSlice(StrSliceType())),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::Primitive(PrimitiveType::Number(
                     NumberType::F64,
@@ -129,45 +187,93 @@ impl ResolveCore for FormatFn {
             }
             FormatFn::BTOA => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::Primitive(PrimitiveType::Bool)) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::Primitive(PrimitiveType::Bool)),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             FormatFn::ATOB => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::StrSlice(StrSliceType())) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::StrSlice(StrSliceType())),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::Primitive(PrimitiveType::Bool)))
             }
             FormatFn::CTOA => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::Primitive(PrimitiveType::Char)) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::Primitive(PrimitiveType::Char)),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             FormatFn::ATOC => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let _ = parameters[0].resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                match parameters[0].type_of(scope_manager, scope_id)? {
+                let param_type = parameters[0].type_of(scope_manager, scope_id)?;
+                match &param_type {
                     EType::Static(StaticType::StrSlice(StrSliceType())) => {}
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(SemanticError::mismatched(
+                            &EType::Static(StaticType::StrSlice(StrSliceType())),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(parameters[0].span()));
+                    }
                 }
                 Ok(EType::Static(StaticType::Primitive(PrimitiveType::Char)))
             }
@@ -358,10 +464,13 @@ pub mod type_printer {
     use crate::{
         semantic::{
             scope::{
-                static_types::{NumberType, PrimitiveType, StaticType, POINTER_SIZE},
-                user_types::{Enum, Struct, Union, UserType},
+                static_types::{
+                    ErrorType, NumberType, PrimitiveType, RangeType, StaticType, TupleType,
+                    POINTER_SIZE,
+                },
+                user_types::{Enum, Interface, Struct, Union, UserType},
             },
-            SizeOf,
+            EType, SizeOf,
         },
         vm::{
             asm::{
@@ -378,12 +487,20 @@ pub mod type_printer {
 
     use super::FormatAsm;
 
+    /*
+     * @desc Name of a user type as written in the source, the types of a module are mangled as $Module::name
+     */
+    fn type_name(id: &str) -> &str {
+        id.strip_prefix('$').unwrap_or(id)
+    }
+
     fn build_struct<E: crate::vm::external::Engine>(
         Struct { id, fields }: &Struct,
         scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
         scope_id: Option<u128>,
         instructions: &mut crate::vm::program::Program<E>,
     ) -> Result<(), crate::vm::CodeGenerationError> {
+        let id = type_name(id);
         match fields.len() {
             0 => instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::PushStr(
                 format!("{} {{}}", id).as_bytes().into(),
@@ -577,6 +694,32 @@ pub mod type_printer {
                     StaticType::Error => {
                         instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::ETOA)))
                     }
+                    StaticType::Range(RangeType { num, inclusive }) => {
+                        let bound =
+                            EType::Static(StaticType::Primitive(PrimitiveType::Number(*num)));
+                        let _ = build(&bound, scope_manager, scope_id, instructions)?;
+                        instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::InsertBefore(
+                            num.size_of(),
+                        ))));
+                        let _ = build(&bound, scope_manager, scope_id, instructions)?;
+                        let separator = if *inclusive { "..=" } else { ".." };
+                        instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::PushStr(
+                            separator.as_bytes().into(),
+                        ))));
+                        instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::Merge(2))));
+                    }
+                    StaticType::TypedError(ErrorType(payload)) => {
+                        // same layout as a tuple of the payload and the error flag
+                        let _ = build(
+                            &EType::Static(StaticType::Tuple(TupleType(vec![
+                                payload.as_ref().clone(),
+                                EType::Static(StaticType::Error),
+                            ]))),
+                            scope_manager,
+                            scope_id,
+                            instructions,
+                        )?;
+                    }
                 }
             }
             crate::semantic::EType::User { id, size } => {
@@ -594,7 +737,7 @@ pub mod type_printer {
                             data: 0u64.to_le_bytes().into(),
                         }));
                         instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::PushStr(
-                            format!("{0}::", id).as_bytes().into(),
+                            format!("{0}::", type_name(&id)).as_bytes().into(),
                         ))));
                         instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::InsertBefore(
                             POINTER_SIZE,
@@ -662,9 +805,19 @@ pub mod type_printer {
                         instructions
                             .push_label_by_id(end_label, format!("end_format_union").into());
                         instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::PushStrBefore(
-                            format!("{0}::", id).as_bytes().into(),
+                            format!("{0}::", type_name(&id)).as_bytes().into(),
                         ))));
                     }
+                    UserType::Interface(Interface { id, .. }) => {
+                        instructions.push(Asm::Pop(2 * POINTER_SIZE));
+
+                        instructions.push(Asm::Core(CoreAsm::Format(FormatAsm::PushStr(
+                            id.as_bytes().into(),
+                        ))));
+                        instructions.push(Asm::Data(Data::Serialized {
+                            data: 0u64.to_le_bytes().into(),
+                        }));
+                    }
                 }
             }
         }
@@ -718,6 +871,53 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for FormatAsm {
     }
 }
 
+impl crate::vm::AsmListing for FormatAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        use crate::vm::listing::hex;
+        match self {
+            FormatAsm::U128TOA => "fmt.u128toa".to_string(),
+            FormatAsm::ATOU128 => "fmt.atou128".to_string(),
+            FormatAsm::U64TOA => "fmt.u64toa".to_string(),
+            FormatAsm::U64TOH => "fmt.u64toh".to_string(),
+            FormatAsm::ATOU64 => "fmt.atou64".to_string(),
+            FormatAsm::U32TOA => "fmt.u32toa".to_string(),
+            FormatAsm::ATOU32 => "fmt.atou32".to_string(),
+            FormatAsm::U16TOA => "fmt.u16toa".to_string(),
+            FormatAsm::ATOU16 => "fmt.atou16".to_string(),
+            FormatAsm::U8TOA => "fmt.u8toa".to_string(),
+            FormatAsm::ATOU8 => "fmt.atou8".to_string(),
+            FormatAsm::I128TOA => "fmt.i128toa".to_string(),
+            FormatAsm::ATOI128 => "fmt.atoi128".to_string(),
+            FormatAsm::I64TOA => "fmt.i64toa".to_string(),
+            FormatAsm::ATOI64 => "fmt.atoi64".to_string(),
+            FormatAsm::I32TOA => "fmt.i32toa".to_string(),
+            FormatAsm::ATOI32 => "fmt.atoi32".to_string(),
+            FormatAsm::I16TOA => "fmt.i16toa".to_string(),
+            FormatAsm::ATOI16 => "fmt.atoi16".to_string(),
+            FormatAsm::I8TOA => "fmt.i8toa".to_string(),
+            FormatAsm::ATOI8 => "fmt.atoi8".to_string(),
+            FormatAsm::FTOA => "fmt.ftoa".to_string(),
+            FormatAsm::ATOF => "fmt.atof".to_string(),
+            FormatAsm::BTOA => "fmt.btoa".to_string(),
+            FormatAsm::ATOB => "fmt.atob".to_string(),
+            FormatAsm::CTOA => "fmt.ctoa".to_string(),
+            FormatAsm::ATOC => "fmt.atoc".to_string(),
+            FormatAsm::STOA => "fmt.stoa".to_string(),
+            FormatAsm::ETOA => "fmt.etoa".to_string(),
+            FormatAsm::PrintfStart => "fmt.printfstart".to_string(),
+            FormatAsm::FormatStart => "fmt.formatstart".to_string(),
+            FormatAsm::Push => "fmt.push".to_string(),
+            FormatAsm::PrintfEnd => "fmt.printfend".to_string(),
+            FormatAsm::FormatEnd => "fmt.formatend".to_string(),
+            FormatAsm::PushStr(data) => format!("fmt.pushstr {}", hex(data)),
+            FormatAsm::PushStrBefore(data) => format!("fmt.pushstr_before {}", hex(data)),
+            FormatAsm::InsertBefore(size) => format!("fmt.insert {size}"),
+            FormatAsm::Merge(count) => format!("fmt.merge {count}"),
+            FormatAsm::Wrap { left, right } => format!("fmt.wrap {} {}", hex(left), hex(right)),
+        }
+    }
+}
+
 impl crate::vm::AsmWeight for FormatAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -62,6 +62,22 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for IOAsm {
     }
 }
 
+impl crate::vm::AsmListing for IOAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let name = match self {
+            IOAsm::PrintStr => "io.print_str",
+            IOAsm::PrintString => "io.print_string",
+            IOAsm::PrintlnStr => "io.println_str",
+            IOAsm::PrintlnString => "io.println_string",
+            IOAsm::Flush => "io.flush",
+            IOAsm::Flushln => "io.flushln",
+            IOAsm::Scan => "io.scan",
+            IOAsm::RequestScan => "io.rscan",
+        };
+        name.to_string()
+    }
+}
+
 impl crate::vm::AsmWeight for IOAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -100,11 +116,15 @@ impl ResolveCore for IOFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        _resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
             IOFn::Print { for_string } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let param = parameters.first_mut().unwrap();
                 let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
@@ -115,13 +135,24 @@ impl ResolveCore for IOFn {
                     EType::Static(StaticType::String(_)) => {
                         *for_string = true;
                     }
-                    _ => return Err(SemanticError::IncompatibleTypes),
+                    param_type => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a String or a str",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(param.span()))
+                    }
                 }
                 Ok(e_static!(StaticType::Unit))
             }
             IOFn::Println { for_string } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let param = parameters.first_mut().unwrap();
                 let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
@@ -132,13 +163,24 @@ impl ResolveCore for IOFn {
                     EType::Static(StaticType::String(_)) => {
                         *for_string = true;
                     }
-                    _ => return Err(SemanticError::IncompatibleTypes),
+                    param_type => {
+                        return Err(SemanticError::mismatched_kind(
+                            "a String or a str",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(param.span()))
+                    }
                 }
                 Ok(e_static!(StaticType::Unit))
             }
             IOFn::Scan => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
                 }
                 Ok(e_static!(StaticType::String(StringType())))
             }
@@ -4,16 +4,19 @@ use crate::{
     ast::expressions::Expression,
     e_static,
     semantic::{
-        scope::static_types::{AddrType, MapType, StaticType, TupleType, VecType},
+        scope::static_types::{AddrType, MapType, NumberType, StaticType, TupleType, VecType},
         EType, Resolve, ResolveCore, SemanticError, SizeOf, TypeOf,
     },
     vm::{
-        allocator::{align, MemoryAddress},
+        allocator::{align, heap::Heap, stack::Stack, MemoryAddress},
         asm::{
-            operation::{OpPrimitive, PopNum},
+            operation::{GetNumFrom, OpPrimitive, PopNum},
             Asm,
         },
-        core::{lexem, map::map_layout},
+        core::{
+            lexem,
+            map::{map_layout, retrieve_key, DerefHashing},
+        },
         runtime::RuntimeError,
         scheduler::Executable,
         stdio::StdIO,
@@ -21,7 +24,7 @@ use crate::{
     },
 };
 
-use super::{vector::VEC_HEADER, PathFinder};
+use super::{string::STRING_HEADER, vector::VEC_HEADER, PathFinder};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum IterFn {
@@ -32,9 +35,59 @@ pub enum IterFn {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum IterAsm {
-    MapItems { key_size: usize, value_size: usize },
-    MapValues { key_size: usize, value_size: usize },
-    MapKeys { key_size: usize, value_size: usize },
+    MapItems {
+        key_size: usize,
+        value_size: usize,
+    },
+    MapValues {
+        key_size: usize,
+        value_size: usize,
+    },
+    MapKeys {
+        key_size: usize,
+        value_size: usize,
+    },
+    Next(Iterable),
+    Contains {
+        iterable: Iterable,
+        ref_access: DerefHashing,
+    },
+}
+
+/*
+ * @desc Values a for-in loop can walk through.
+ * The items of a map are walked through the vector built by MapItems.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Iterable {
+    Range { num: NumberType, inclusive: bool },
+    Vec { item_size: usize },
+    Slice { len: usize, item_size: usize },
+    String,
+    StrSlice,
+    Items { key_size: usize, value_size: usize },
+}
+
+impl Iterable {
+    pub fn name(&self) -> String {
+        match self {
+            Iterable::Range { num, inclusive } => {
+                let num = OpPrimitive::Number(*num).name();
+                match inclusive {
+                    true => format!("range_inclusive:{num}"),
+                    false => format!("range:{num}"),
+                }
+            }
+            Iterable::Vec { item_size } => format!("vec:{item_size}"),
+            Iterable::Slice { len, item_size } => format!("slice:{len}:{item_size}"),
+            Iterable::String => "string".to_string(),
+            Iterable::StrSlice => "str_slice".to_string(),
+            Iterable::Items {
+                key_size,
+                value_size,
+            } => format!("items:{key_size}:{value_size}"),
+        }
+    }
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for IterAsm {
@@ -43,13 +96,42 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for IterAsm {
             IterAsm::MapItems { .. } => stdio.push_asm_lib(engine, pid, "items"),
             IterAsm::MapValues { .. } => stdio.push_asm_lib(engine, pid, "values"),
             IterAsm::MapKeys { .. } => stdio.push_asm_lib(engine, pid, "keys"),
+            IterAsm::Next(_) => stdio.push_asm_lib(engine, pid, "next"),
+            IterAsm::Contains { .. } => stdio.push_asm_lib(engine, pid, "contains"),
+        }
+    }
+}
+
+impl crate::vm::AsmListing for IterAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            IterAsm::MapItems {
+                key_size,
+                value_size,
+            } => format!("iter.items {key_size} {value_size}"),
+            IterAsm::MapValues {
+                key_size,
+                value_size,
+            } => format!("iter.values {key_size} {value_size}"),
+            IterAsm::MapKeys {
+                key_size,
+                value_size,
+            } => format!("iter.keys {key_size} {value_size}"),
+            IterAsm::Next(iterable) => format!("iter.next {}", iterable.name()),
+            IterAsm::Contains {
+                iterable,
+                ref_access,
+            } => format!("iter.contains {} {}", iterable.name(), ref_access.name()),
         }
     }
 }
 
 impl crate::vm::AsmWeight for IterAsm {
     fn weight(&self) -> crate::vm::Weight {
-        crate::vm::Weight::HIGH
+        match self {
+            IterAsm::Next(_) => crate::vm::Weight::LOW,
+            _ => crate::vm::Weight::HIGH,
+        }
     }
 }
 
@@ -86,6 +168,7 @@ impl ResolveCore for IterFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        _resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
             IterFn::MapItems {
@@ -93,42 +176,53 @@ impl ResolveCore for IterFn {
                 value_size,
             } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let map = &mut parameters[0];
                 let _ = map.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                let mut map_type = map.type_of(&scope_manager, scope_id)?;
-                match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Address(AddrType(sub)) => map_type = sub.as_ref().clone(),
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
-                }
+                let param_type = map.type_of(&scope_manager, scope_id)?;
+                let map_type = match &param_type {
+                    EType::Static(StaticType::Address(AddrType(sub))) => sub.as_ref().clone(),
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address of a Map",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(map.span()))
+                    }
+                };
 
                 match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Map(MapType {
-                            keys_type,
-                            values_type,
-                        }) => {
-                            *key_size = keys_type.as_ref().size_of();
-                            *value_size = values_type.as_ref().size_of();
-
-                            Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
-                                StaticType::Tuple(TupleType(vec![
-                                    e_static!(StaticType::Address(AddrType(Box::new(
-                                        keys_type.as_ref().clone()
-                                    )))),
-                                    e_static!(StaticType::Address(AddrType(Box::new(
-                                        values_type.as_ref().clone()
-                                    ))))
-                                ]))
-                            ))))))
-                        }
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(StaticType::Map(MapType {
+                        keys_type,
+                        values_type,
+                    })) => {
+                        *key_size = keys_type.as_ref().size_of();
+                        *value_size = values_type.as_ref().size_of();
+
+                        Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
+                            StaticType::Tuple(TupleType(vec![
+                                e_static!(StaticType::Address(AddrType(Box::new(
+                                    keys_type.as_ref().clone()
+                                )))),
+                                e_static!(StaticType::Address(AddrType(Box::new(
+                                    values_type.as_ref().clone()
+                                ))))
+                            ]))
+                        ))))))
+                    }
+                    _ => Err(SemanticError::mismatched_kind(
+                        "an address of a Map",
+                        &param_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(map.span())),
                 }
             }
             IterFn::MapValues {
@@ -136,37 +230,46 @@ impl ResolveCore for IterFn {
                 value_size,
             } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let map = &mut parameters[0];
                 let _ = map.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                let mut map_type = map.type_of(&scope_manager, scope_id)?;
-                match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Address(AddrType(sub)) => map_type = sub.as_ref().clone(),
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
-                }
+                let param_type = map.type_of(&scope_manager, scope_id)?;
+                let map_type = match &param_type {
+                    EType::Static(StaticType::Address(AddrType(sub))) => sub.as_ref().clone(),
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address of a Map",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(map.span()))
+                    }
+                };
 
                 match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Map(MapType {
-                            keys_type,
-                            values_type,
-                        }) => {
-                            *key_size = keys_type.as_ref().size_of();
-                            *value_size = values_type.as_ref().size_of();
-
-                            Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
-                                StaticType::Address(AddrType(Box::new(
-                                    values_type.as_ref().clone()
-                                )))
-                            ))))))
-                        }
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(StaticType::Map(MapType {
+                        keys_type,
+                        values_type,
+                    })) => {
+                        *key_size = keys_type.as_ref().size_of();
+                        *value_size = values_type.as_ref().size_of();
+
+                        Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
+                            StaticType::Address(AddrType(Box::new(values_type.as_ref().clone())))
+                        ))))))
+                    }
+                    _ => Err(SemanticError::mismatched_kind(
+                        "an address of a Map",
+                        &param_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(map.span())),
                 }
             }
             IterFn::MapKeys {
@@ -174,35 +277,46 @@ impl ResolveCore for IterFn {
                 value_size,
             } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let map = &mut parameters[0];
                 let _ = map.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                let mut map_type = map.type_of(&scope_manager, scope_id)?;
-                match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Address(AddrType(sub)) => map_type = sub.as_ref().clone(),
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
-                }
+                let param_type = map.type_of(&scope_manager, scope_id)?;
+                let map_type = match &param_type {
+                    EType::Static(StaticType::Address(AddrType(sub))) => sub.as_ref().clone(),
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "an address of a Map",
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(map.span()))
+                    }
+                };
 
                 match &map_type {
-                    EType::Static(value) => match value {
-                        StaticType::Map(MapType {
-                            keys_type,
-                            values_type,
-                        }) => {
-                            *key_size = keys_type.as_ref().size_of();
-                            *value_size = values_type.as_ref().size_of();
-
-                            Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
-                                StaticType::Address(AddrType(Box::new(keys_type.as_ref().clone())))
-                            ))))))
-                        }
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(StaticType::Map(MapType {
+                        keys_type,
+                        values_type,
+                    })) => {
+                        *key_size = keys_type.as_ref().size_of();
+                        *value_size = values_type.as_ref().size_of();
+
+                        Ok(e_static!(StaticType::Vec(VecType(Box::new(e_static!(
+                            StaticType::Address(AddrType(Box::new(keys_type.as_ref().clone())))
+                        ))))))
+                    }
+                    _ => Err(SemanticError::mismatched_kind(
+                        "an address of a Map",
+                        &param_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(map.span())),
                 }
             }
         }
@@ -367,12 +481,316 @@ impl<E: crate::vm::external::Engine> Executable<E> for IterAsm {
                 let address: u64 = address.into(stack);
                 let _ = stack.push_with(&address.to_le_bytes())?;
             }
+            IterAsm::Next(iterable) => {
+                /* Stack : [iterable][index] -> [item][next index][true] | [false] */
+                let index = OpPrimitive::pop_num::<u64>(stack)?;
+                let next = match iterable {
+                    Iterable::Range { num, inclusive } => {
+                        let size = num.size_of();
+                        let bounds = stack.pop(2 * size)?.to_vec();
+                        range_item(*num, &bounds[..size], &bounds[size..], index, *inclusive)
+                            .map(|item| (item, index + 1))
+                    }
+                    Iterable::Vec { item_size } => {
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        let len = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)?;
+                        if index < len {
+                            let item_address =
+                                address.add(VEC_HEADER + (index as usize) * item_size);
+                            Some((
+                                read_bytes(item_address, *item_size, stack, heap)?,
+                                index + 1,
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                    Iterable::Slice { len, item_size } => {
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        if index < *len as u64 {
+                            let item_address = address.add((index as usize) * item_size);
+                            Some((
+                                read_bytes(item_address, *item_size, stack, heap)?,
+                                index + 1,
+                            ))
+                        } else {
+                            None
+                        }
+                    }
+                    Iterable::String | Iterable::StrSlice => {
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        /* The index is a byte offset, moved forward by one char at a time */
+                        let (len, bytes) = match iterable {
+                            Iterable::String => (
+                                OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)?,
+                                address.add(STRING_HEADER),
+                            ),
+                            _ => (
+                                OpPrimitive::get_num_from::<u64>(address, stack, heap)?,
+                                address.add(8),
+                            ),
+                        };
+                        if index < len {
+                            let bytes = bytes.add(index as usize);
+                            let width = read_bytes(bytes, 1, stack, heap)?
+                                .first()
+                                .map(|first| utf8_width(*first))
+                                .ok_or(RuntimeError::Deserialization)?;
+                            if index + width as u64 > len {
+                                return Err(RuntimeError::Deserialization);
+                            }
+                            let mut item = [0u8; 4];
+                            item[..width].copy_from_slice(&read_bytes(bytes, width, stack, heap)?);
+                            Some((item.to_vec(), index + width as u64))
+                        } else {
+                            None
+                        }
+                    }
+                    Iterable::Items {
+                        key_size,
+                        value_size,
+                    } => {
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        let len = OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)?;
+                        if index < len {
+                            let pair = address.add(VEC_HEADER + (index as usize) * 16);
+                            let key_address: MemoryAddress =
+                                OpPrimitive::get_num_from::<u64>(pair, stack, heap)?.try_into()?;
+                            let value_address: MemoryAddress =
+                                OpPrimitive::get_num_from::<u64>(pair.add(8), stack, heap)?
+                                    .try_into()?;
+                            let mut item = read_bytes(key_address, *key_size, stack, heap)?;
+                            item.extend(read_bytes(value_address, *value_size, stack, heap)?);
+                            Some((item, index + 1))
+                        } else {
+                            None
+                        }
+                    }
+                };
+                match next {
+                    Some((item, next_index)) => {
+                        let _ = stack.push_with(&item)?;
+                        let _ = stack.push_with(&next_index.to_le_bytes())?;
+                        let _ = stack.push_with(&[true as u8])?;
+                    }
+                    None => {
+                        let _ = stack.push_with(&[false as u8])?;
+                    }
+                }
+            }
+            IterAsm::Contains {
+                iterable,
+                ref_access,
+            } => {
+                /* Stack : [iterable][item] -> [bool] */
+                let found = match iterable {
+                    Iterable::Range { num, inclusive } => {
+                        let size = num.size_of();
+                        let (_, item) = retrieve_key(size, *ref_access, stack, heap)?;
+                        let bounds = stack.pop(2 * size)?.to_vec();
+                        range_contains(*num, &bounds[..size], &bounds[size..], &item, *inclusive)
+                    }
+                    Iterable::Vec { item_size } | Iterable::Slice { item_size, .. } => {
+                        let (_, item) = retrieve_key(*item_size, *ref_access, stack, heap)?;
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        let (len, items) = match iterable {
+                            Iterable::Slice { len, .. } => (*len as u64, address),
+                            _ => (
+                                OpPrimitive::get_num_from::<u64>(address.add(8), stack, heap)?,
+                                address.add(VEC_HEADER),
+                            ),
+                        };
+                        let mut found = false;
+                        for idx in 0..len as usize {
+                            let address = items.add(idx * item_size);
+                            if deref_bytes(address, *item_size, *ref_access, stack, heap)? == item {
+                                found = true;
+                                break;
+                            }
+                        }
+                        found
+                    }
+                    Iterable::String | Iterable::StrSlice => {
+                        // chars are looked up as a one char substring
+                        let (_, mut pattern) = retrieve_key(4, *ref_access, stack, heap)?;
+                        if *ref_access == DerefHashing::Default {
+                            let width = pattern.first().map_or(1, |first| utf8_width(*first));
+                            pattern.truncate(width);
+                        }
+                        let address: MemoryAddress =
+                            OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                        let text = match iterable {
+                            Iterable::String => {
+                                pointed_bytes(address, DerefHashing::String, stack, heap)?
+                            }
+                            _ => pointed_bytes(address, DerefHashing::StrSlice, stack, heap)?,
+                        };
+                        pattern.is_empty()
+                            || text.windows(pattern.len()).any(|window| window == pattern)
+                    }
+                    Iterable::Items { .. } => return Err(RuntimeError::UnsupportedOperation),
+                };
+                let _ = stack.push_with(&[found as u8])?;
+            }
         }
         scheduler.next();
         Ok(())
     }
 }
 
+fn read_bytes(
+    address: MemoryAddress,
+    size: usize,
+    stack: &Stack,
+    heap: &Heap,
+) -> Result<Vec<u8>, RuntimeError> {
+    Ok(match address {
+        MemoryAddress::Heap { .. } => heap.read(address, size)?,
+        MemoryAddress::Stack { .. } => stack.read(address, size)?.to_vec(),
+        MemoryAddress::Global { .. } => stack.read_global(address, size)?.to_vec(),
+        MemoryAddress::Frame { .. } => stack.read_in_frame(address, size)?.to_vec(),
+    })
+}
+
+/*
+ * @desc Read the bytes of a value, following the pointer of strings and vectors
+ */
+fn deref_bytes(
+    address: MemoryAddress,
+    size: usize,
+    ref_access: DerefHashing,
+    stack: &Stack,
+    heap: &Heap,
+) -> Result<Vec<u8>, RuntimeError> {
+    match ref_access {
+        DerefHashing::Default => read_bytes(address, size, stack, heap),
+        _ => {
+            let pointer: MemoryAddress =
+                OpPrimitive::get_num_from::<u64>(address, stack, heap)?.try_into()?;
+            pointed_bytes(pointer, ref_access, stack, heap)
+        }
+    }
+}
+
+/*
+ * @desc Read the content of a string, a str slice or a vector
+ */
+fn pointed_bytes(
+    pointer: MemoryAddress,
+    ref_access: DerefHashing,
+    stack: &Stack,
+    heap: &Heap,
+) -> Result<Vec<u8>, RuntimeError> {
+    let (len, data) = match ref_access {
+        DerefHashing::String => (
+            OpPrimitive::get_num_from::<u64>(pointer.add(8), stack, heap)?,
+            pointer.add(STRING_HEADER),
+        ),
+        DerefHashing::Vec(item_size) => (
+            OpPrimitive::get_num_from::<u64>(pointer.add(8), stack, heap)? * item_size as u64,
+            pointer.add(VEC_HEADER),
+        ),
+        DerefHashing::StrSlice => (
+            OpPrimitive::get_num_from::<u64>(pointer, stack, heap)?,
+            pointer.add(8),
+        ),
+        DerefHashing::Default => return Err(RuntimeError::UnsupportedOperation),
+    };
+    read_bytes(data, len as usize, stack, heap)
+}
+
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+fn is_signed(num: NumberType) -> bool {
+    matches!(
+        num,
+        NumberType::I8 | NumberType::I16 | NumberType::I32 | NumberType::I64 | NumberType::I128
+    )
+}
+
+fn widen(bytes: &[u8], signed: bool) -> [u8; 16] {
+    let negative = signed && bytes.last().map_or(false, |byte| byte & 0x80 != 0);
+    let mut buffer = [if negative { 0xFF } else { 0x00 }; 16];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    buffer
+}
+
+/*
+ * @desc Compute the index-th value of a range, None once the upper bound is reached
+ */
+fn range_item(
+    num: NumberType,
+    lower: &[u8],
+    upper: &[u8],
+    index: u64,
+    inclusive: bool,
+) -> Option<Vec<u8>> {
+    let size = lower.len();
+    if is_signed(num) {
+        let lower = i128::from_le_bytes(widen(lower, true));
+        let upper = i128::from_le_bytes(widen(upper, true));
+        let item = lower.checked_add(index as i128)?;
+        let in_range = if inclusive {
+            item <= upper
+        } else {
+            item < upper
+        };
+        in_range.then(|| item.to_le_bytes()[..size].to_vec())
+    } else {
+        let lower = u128::from_le_bytes(widen(lower, false));
+        let upper = u128::from_le_bytes(widen(upper, false));
+        let item = lower.checked_add(index as u128)?;
+        let in_range = if inclusive {
+            item <= upper
+        } else {
+            item < upper
+        };
+        in_range.then(|| item.to_le_bytes()[..size].to_vec())
+    }
+}
+
+fn range_contains(
+    num: NumberType,
+    lower: &[u8],
+    upper: &[u8],
+    item: &[u8],
+    inclusive: bool,
+) -> bool {
+    if is_signed(num) {
+        let lower = i128::from_le_bytes(widen(lower, true));
+        let upper = i128::from_le_bytes(widen(upper, true));
+        let item = i128::from_le_bytes(widen(item, true));
+        lower <= item
+            && if inclusive {
+                item <= upper
+            } else {
+                item < upper
+            }
+    } else {
+        let lower = u128::from_le_bytes(widen(lower, false));
+        let upper = u128::from_le_bytes(widen(upper, false));
+        let item = u128::from_le_bytes(widen(item, false));
+        lower <= item
+            && if inclusive {
+                item <= upper
+            } else {
+                item < upper
+            }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -105,3 +105,28 @@ pub const PI: &str = "pi";
 pub const E: &str = "e";
 pub const INF: &str = "inf";
 pub const NEG_INF: &str = "neg_inf";
+
+/*
+ * @desc Functions of each library of the core, a function is called with or without its library
+ */
+pub const LIBRARIES: [(&str, &[&str]); 9] = [
+    (MEM, &[LEN, CAP, FREE, ALLOC, MEMCPY, SIZEOF]),
+    (VECTOR, &[VEC, PUSH, POP, EXTEND, DELETE, CLEAR_VEC]),
+    (MAP, &[MAP, INSERT, GET, DELKEY, CLEAR_MAP, FREE_MAP]),
+    (STRING, &[STRING, APPEND, CHARAT, TO_CONST_STR]),
+    (THREAD, &[SPAWN, CLOSE, EXIT, WAIT, WAKE, SLEEP, JOIN]),
+    (IO, &[PRINT, PRINTLN, SCAN]),
+    (
+        MATH,
+        &[
+            CEIL, FLOOR, ABS, EXP, LN, LOG, LOG10, POW, SQRT, ACOS, ASIN, ATAN, ATAN2, COS, SIN,
+            TAN, HYPOT, DEG, RAD, COSH, SINH, TANH, ACOSH, ASINH, ATANH, PI, E, INF, NEG_INF,
+            IS_NAN, IS_INF,
+        ],
+    ),
+    (FORMAT, &[ITOA, ATOI, FTOA, ATOF, BTOA, ATOB, CTOA, ATOC]),
+    (ITER, &[ITEMS, KEYS, VALUES]),
+];
+
+// functions of the core called without a library
+pub const BUILTINS: [&str; 3] = [ASSERT, ERROR, OK];
@@ -25,7 +25,12 @@ use std::{
     ops::ControlFlow,
 };
 
-use super::{string::STRING_HEADER, vector::VEC_HEADER, PathFinder};
+use super::{
+    alloc::{is_interface, release_interface},
+    string::STRING_HEADER,
+    vector::VEC_HEADER,
+    PathFinder,
+};
 use crate::vm::allocator::heap::{HeapError, HEAP_SIZE};
 use num_traits::ToBytes;
 use rand::Rng;
@@ -37,6 +42,17 @@ pub enum DerefHashing {
     StrSlice,
     Default,
 }
+
+impl DerefHashing {
+    pub fn name(&self) -> String {
+        match self {
+            DerefHashing::Vec(item_size) => format!("vec:{item_size}"),
+            DerefHashing::String => "string".to_string(),
+            DerefHashing::StrSlice => "str_slice".to_string(),
+            DerefHashing::Default => "default".to_string(),
+        }
+    }
+}
 impl From<&EType> for DerefHashing {
     fn from(value: &EType) -> Self {
         match value {
@@ -82,6 +98,7 @@ pub enum MapFn {
         key_size: usize,
         item_size: usize,
         ref_access: DerefHashing,
+        interfaces: bool, // the values are interface values, the data they own is freed along the map
     },
 }
 
@@ -121,6 +138,7 @@ impl PathFinder for MapFn {
                     item_size: 0,
                     key_size: 0,
                     ref_access: DerefHashing::Default,
+                    interfaces: false,
                 }),
                 _ => None,
             };
@@ -136,17 +154,26 @@ impl ResolveCore for MapFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         fn map_param<E: crate::vm::external::Engine>(
             param: &mut Expression,
+            resolved: bool,
             scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
             scope_id: Option<u128>,
         ) -> Result<MapType, SemanticError> {
-            let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-            let EType::Static(StaticType::Map(map_type)) =
-                param.type_of(scope_manager, scope_id)?
-            else {
-                return Err(SemanticError::IncorrectArguments);
+            if !resolved {
+                let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            }
+            let param_type = param.type_of(scope_manager, scope_id)?;
+            let EType::Static(StaticType::Map(map_type)) = param_type.clone() else {
+                return Err(SemanticError::mismatched_kind(
+                    "a Map",
+                    &param_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(param.span()));
             };
             Ok(map_type)
         }
@@ -168,7 +195,10 @@ impl ResolveCore for MapFn {
                     )));
                 };
                 if parameters.len() > 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
 
                 for param in parameters.iter_mut() {
@@ -178,8 +208,15 @@ impl ResolveCore for MapFn {
                         &Some(crate::p_num!(U64)),
                         &mut None,
                     )?;
-                    let crate::p_num!(U64) = param.type_of(&scope_manager, scope_id)? else {
-                        return Err(SemanticError::IncorrectArguments);
+                    let param_type = param.type_of(&scope_manager, scope_id)?;
+                    let crate::p_num!(U64) = param_type else {
+                        return Err(SemanticError::mismatched(
+                            &crate::p_num!(U64),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(param.span()));
                     };
                 }
 
@@ -195,7 +232,10 @@ impl ResolveCore for MapFn {
                 ref_access,
             } => {
                 if parameters.len() != 3 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 3,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let map = &mut first_part[0];
@@ -204,7 +244,7 @@ impl ResolveCore for MapFn {
                 let key = &mut first_part[0];
                 let item = &mut second_part[0];
 
-                let map_type = map_param::<E>(map, scope_manager, scope_id)?;
+                let map_type = map_param::<E>(map, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = item.resolve::<E>(
                     scope_manager,
@@ -258,13 +298,16 @@ impl ResolveCore for MapFn {
                 ref_access,
             } => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let map = &mut first_part[0];
                 let key = &mut second_part[0];
 
-                let map_type = map_param::<E>(map, scope_manager, scope_id)?;
+                let map_type = map_param::<E>(map, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = key.resolve::<E>(
                     scope_manager,
@@ -292,10 +335,13 @@ impl ResolveCore for MapFn {
                 ref_access,
             } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let map = &mut parameters[0];
-                let map_type = map_param::<E>(map, scope_manager, scope_id)?;
+                let map_type = map_param::<E>(map, resolved_receiver, scope_manager, scope_id)?;
 
                 *item_size = map_type.values_type.size_of();
                 *key_size = map_type.keys_type.size_of();
@@ -306,16 +352,21 @@ impl ResolveCore for MapFn {
                 key_size,
                 item_size,
                 ref_access,
+                interfaces,
             } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let map = &mut parameters[0];
-                let map_type = map_param::<E>(map, scope_manager, scope_id)?;
+                let map_type = map_param::<E>(map, resolved_receiver, scope_manager, scope_id)?;
 
                 *item_size = map_type.values_type.size_of();
                 *key_size = map_type.keys_type.size_of();
                 *ref_access = map_type.keys_type.as_ref().into();
+                *interfaces = is_interface(&map_type.values_type, scope_manager, scope_id);
 
                 Ok(EType::Static(StaticType::Error))
             }
@@ -343,6 +394,11 @@ pub enum MapAsm {
         key_size: usize,
         ref_access: DerefHashing,
     },
+    Contains {
+        item_size: usize,
+        key_size: usize,
+        ref_access: DerefHashing,
+    },
     DelKey {
         item_size: usize,
         key_size: usize,
@@ -356,6 +412,10 @@ pub enum MapAsm {
         item_size: usize,
         key_size: usize,
     },
+    MapFreeInterfaces {
+        item_size: usize,
+        key_size: usize,
+    },
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for MapAsm {
@@ -366,8 +426,57 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for MapAsm {
             MapAsm::Insert { .. } => stdio.push_asm_lib(engine, pid, "insert"),
             MapAsm::DelKey { .. } => stdio.push_asm_lib(engine, pid, "del_key"),
             MapAsm::Get { .. } => stdio.push_asm_lib(engine, pid, "get"),
+            MapAsm::Contains { .. } => stdio.push_asm_lib(engine, pid, "contains_key"),
             MapAsm::Clear { .. } => stdio.push_asm_lib(engine, pid, "clear_map"),
             MapAsm::MapFree { .. } => stdio.push_asm_lib(engine, pid, "free_map"),
+            MapAsm::MapFreeInterfaces { .. } => stdio.push_asm_lib(engine, pid, "free_map"),
+        }
+    }
+}
+
+impl crate::vm::AsmListing for MapAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            MapAsm::Map {
+                item_size,
+                key_size,
+            } => format!("map.new {item_size} {key_size}"),
+            MapAsm::MapWithCapacity {
+                item_size,
+                key_size,
+            } => format!("map.with_capacity {item_size} {key_size}"),
+            MapAsm::Insert {
+                item_size,
+                key_size,
+                ref_access,
+            } => format!("map.insert {item_size} {key_size} {}", ref_access.name()),
+            MapAsm::Get {
+                item_size,
+                key_size,
+                ref_access,
+            } => format!("map.get {item_size} {key_size} {}", ref_access.name()),
+            MapAsm::Contains {
+                item_size,
+                key_size,
+                ref_access,
+            } => format!("map.contains_key {item_size} {key_size} {}", ref_access.name()),
+            MapAsm::DelKey {
+                item_size,
+                key_size,
+                ref_access,
+            } => format!("map.del_key {item_size} {key_size} {}", ref_access.name()),
+            MapAsm::Clear {
+                item_size,
+                key_size,
+            } => format!("map.clear {item_size} {key_size}"),
+            MapAsm::MapFree {
+                item_size,
+                key_size,
+            } => format!("map.free {item_size} {key_size}"),
+            MapAsm::MapFreeInterfaces {
+                item_size,
+                key_size,
+            } => format!("map.free_interfaces {item_size} {key_size}"),
         }
     }
 }
@@ -380,11 +489,13 @@ impl crate::vm::AsmWeight for MapAsm {
             MapAsm::Insert { .. } => crate::vm::Weight::HIGH,
             MapAsm::DelKey { .. } => crate::vm::Weight::HIGH,
             MapAsm::Get { .. } => crate::vm::Weight::MEDIUM,
+            MapAsm::Contains { .. } => crate::vm::Weight::MEDIUM,
             MapAsm::Clear { .. } => crate::vm::Weight::MEDIUM,
             MapAsm::MapFree {
                 item_size,
                 key_size,
             } => crate::vm::Weight::MEDIUM,
+            MapAsm::MapFreeInterfaces { .. } => crate::vm::Weight::HIGH,
         }
     }
 }
@@ -462,12 +573,24 @@ impl GenerateCode for MapFn {
                 key_size,
                 item_size,
                 ref_access,
+                interfaces: false,
             } => {
                 instructions.push(Asm::Core(CoreAsm::Map(MapAsm::MapFree {
                     item_size,
                     key_size,
                 })));
             }
+            MapFn::MapFree {
+                key_size,
+                item_size,
+                interfaces: true,
+                ..
+            } => {
+                instructions.push(Asm::Core(CoreAsm::Map(MapAsm::MapFreeInterfaces {
+                    item_size,
+                    key_size,
+                })));
+            }
         }
         Ok(())
     }
@@ -1316,7 +1439,7 @@ fn gen_seed() -> u32 {
     seed
 }
 
-fn retrieve_key(
+pub fn retrieve_key(
     key_size: usize,
     ref_access: DerefHashing,
     stack: &mut Stack,
@@ -1578,6 +1701,30 @@ impl<E: crate::vm::external::Engine> Executable<E> for MapAsm {
                     }
                 }
             }
+            MapAsm::Contains {
+                item_size,
+                key_size,
+                ref_access,
+            } => {
+                let (_, key_data) = retrieve_key(key_size, ref_access, stack, heap)?;
+                let map_address: MemoryAddress = OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                let map_layout = map_layout(map_address, key_size, item_size, heap)?;
+
+                let hash = hash_of(&key_data, map_layout.hash_seed);
+                let bucket_idx = bucket_idx(hash, map_layout.log_cap) as u64;
+
+                // get address of the bucket
+                let bucket_address = map_layout
+                    .ptr_buckets
+                    .add(bucket_idx as usize * map_layout.bucket_size);
+
+                let bucket_layout = bucket_layout(bucket_address, key_size, item_size, heap)?;
+
+                let found = bucket_layout
+                    .get(top_hash(hash), &key_data, ref_access, stack, heap)?
+                    .is_some();
+                let _ = stack.push_with(&[found as u8])?;
+            }
             MapAsm::Clear {
                 item_size,
                 key_size,
@@ -1603,6 +1750,33 @@ impl<E: crate::vm::external::Engine> Executable<E> for MapAsm {
                 let map_layout = map_layout(map_address, key_size, item_size, heap)?;
                 let res = map_layout.free_buckets(heap);
 
+                if heap.free(map_address).is_ok() && res.is_ok() {
+                    stack.push_with(&OK_SLICE)?;
+                } else {
+                    stack.push_with(&ERROR_SLICE)?;
+                }
+            }
+            MapAsm::MapFreeInterfaces {
+                item_size,
+                key_size,
+            } => {
+                let map_address: MemoryAddress = OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                let map_layout = map_layout(map_address, key_size, item_size, heap)?;
+
+                let mut res = Ok(());
+                for value_address in map_layout.retrieve_vec_values(heap)? {
+                    let address = OpPrimitive::get_num_from::<u64>(value_address, stack, heap)?;
+                    let word = OpPrimitive::get_num_from::<u64>(
+                        value_address.add(POINTER_SIZE),
+                        stack,
+                        heap,
+                    )?;
+                    if let Err(err) = release_interface(address, word, heap) {
+                        res = Err(err);
+                    }
+                }
+                let res = res.and(map_layout.free_buckets(heap));
+
                 if heap.free(map_address).is_ok() && res.is_ok() {
                     stack.push_with(&OK_SLICE)?;
                 } else {
@@ -175,6 +175,45 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for MathAsm {
     }
 }
 
+impl crate::vm::AsmListing for MathAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let name = match self {
+            MathAsm::Ceil => "math.ceil",
+            MathAsm::Floor => "math.floor",
+            MathAsm::Abs => "math.abs",
+            MathAsm::Exp => "math.exp",
+            MathAsm::Ln => "math.ln",
+            MathAsm::Log => "math.log",
+            MathAsm::Log10 => "math.log10",
+            MathAsm::Pow => "math.pow",
+            MathAsm::Sqrt => "math.sqrt",
+            MathAsm::Acos => "math.acos",
+            MathAsm::Asin => "math.asin",
+            MathAsm::Atan => "math.atan",
+            MathAsm::Atan2 => "math.atan2",
+            MathAsm::Cos => "math.cos",
+            MathAsm::Sin => "math.sin",
+            MathAsm::Tan => "math.tan",
+            MathAsm::Hypot => "math.hypot",
+            MathAsm::Deg => "math.deg",
+            MathAsm::Rad => "math.rad",
+            MathAsm::CosH => "math.cosh",
+            MathAsm::SinH => "math.sinh",
+            MathAsm::TanH => "math.tanh",
+            MathAsm::ACosH => "math.acosh",
+            MathAsm::ASinH => "math.asinh",
+            MathAsm::ATanH => "math.atanh",
+            MathAsm::Pi => "math.pi",
+            MathAsm::E => "math.e",
+            MathAsm::Inf => "math.inf",
+            MathAsm::NInf => "math.ninf",
+            MathAsm::IsNaN => "math.isnan",
+            MathAsm::IsInf => "math.isinf",
+        };
+        name.to_string()
+    }
+}
+
 impl crate::vm::AsmWeight for MathAsm {
     fn weight(&self) -> crate::vm::Weight {
         crate::vm::Weight::EXTREME
@@ -188,11 +227,15 @@ impl ResolveCore for MathFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        _resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
             MathFn::Pi | MathFn::E | MathFn::Inf | MathFn::NInf => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
                 }
 
                 Ok(p_num!(F64))
@@ -221,18 +264,23 @@ impl ResolveCore for MathFn {
             | MathFn::ASinH
             | MathFn::ATanH => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let n = &mut parameters[0];
                 let _ = n.resolve::<E>(scope_manager, scope_id, &Some(p_num!(F64)), &mut None)?;
                 let n_type = n.type_of(&scope_manager, scope_id)?;
 
-                match &n_type {
-                    EType::Static(value) => match value {
-                        &StaticType::Primitive(PrimitiveType::Number(NumberType::F64)) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                if n_type != p_num!(F64) {
+                    return Err(SemanticError::mismatched(
+                        &p_num!(F64),
+                        &n_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(n.span()));
                 }
 
                 match self {
@@ -245,7 +293,10 @@ impl ResolveCore for MathFn {
 
             MathFn::Atan2 | MathFn::Hypot | MathFn::Log | MathFn::Pow => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let x = &mut first_part[0];
@@ -257,20 +308,24 @@ impl ResolveCore for MathFn {
                 let x_type = x.type_of(&scope_manager, scope_id)?;
                 let y_type = y.type_of(&scope_manager, scope_id)?;
 
-                match &x_type {
-                    EType::Static(value) => match value {
-                        &StaticType::Primitive(PrimitiveType::Number(NumberType::F64)) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                if x_type != p_num!(F64) {
+                    return Err(SemanticError::mismatched(
+                        &p_num!(F64),
+                        &x_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(x.span()));
                 }
 
-                match &y_type {
-                    EType::Static(value) => match value {
-                        &StaticType::Primitive(PrimitiveType::Number(NumberType::F64)) => {}
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                if y_type != p_num!(F64) {
+                    return Err(SemanticError::mismatched(
+                        &p_num!(F64),
+                        &y_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(y.span()));
                 }
 
                 Ok(p_num!(F64))
@@ -12,13 +12,13 @@ use crate::{
     ast::expressions::Expression,
     e_static,
     semantic::{
-        scope::static_types::{PrimitiveType, StaticType},
-        EType, Resolve, ResolveCore, SemanticError, TypeOf,
+        scope::static_types::{ErrorType, PrimitiveType, StaticType},
+        CompatibleWith, EType, Resolve, ResolveCore, SemanticError, SizeOf, TypeOf,
     },
 };
 
 use super::{
-    asm::{operation::OpPrimitive, Asm},
+    asm::{data::Data, operation::OpPrimitive, Asm},
     runtime::RuntimeError,
     scheduler::Executable,
     stdio::StdIO,
@@ -48,8 +48,8 @@ pub enum Core {
     Format(FormatFn),
     Assert(bool),
     Iter(IterFn),
-    Error,
-    Ok,
+    Error(usize),
+    Ok(usize),
 }
 
 pub trait PathFinder {
@@ -72,8 +72,8 @@ impl PathFinder for Core {
         if path.len() == 0 {
             match name {
                 lexem::ASSERT => return Some(Core::Assert(false)),
-                lexem::ERROR => return Some(Core::Error),
-                lexem::OK => return Some(Core::Ok),
+                lexem::ERROR => return Some(Core::Error(0)),
+                lexem::OK => return Some(Core::Ok(0)),
                 _ => {}
             }
         }
@@ -109,6 +109,42 @@ impl PathFinder for Core {
     }
 }
 
+impl Core {
+    /*
+     * @desc Find the core function called as a method on a receiver of the given type
+     * The receiver is passed as the first argument of the function
+     * The libraries are searched according to the type of the receiver : vector, map and string functions first, then memory functions
+     */
+    pub fn find_method(receiver: &StaticType, name: &str) -> Option<Self> {
+        let libraries: &[&str] = match receiver {
+            StaticType::Vec(_) => &[lexem::VECTOR, lexem::MEM],
+            StaticType::Map(_) => &[lexem::MAP, lexem::MEM],
+            StaticType::String(_) => &[lexem::STRING, lexem::MEM],
+            StaticType::StrSlice(_) => &[lexem::STRING],
+            _ => &[lexem::MEM],
+        };
+        for library in libraries {
+            let path = [library.to_string()];
+            let found = match *library {
+                lexem::VECTOR => VectorFn::find(&path, name).map(Core::Vec),
+                lexem::MAP => MapFn::find(&path, name).map(Core::Map),
+                lexem::STRING => StringFn::find(&path, name).map(Core::String),
+                _ => AllocFn::find(&path, name).map(Core::Alloc),
+            };
+            match found {
+                // constructors do not take a receiver
+                Some(Core::Vec(VectorFn::Vec { .. }))
+                | Some(Core::Map(MapFn::Map { .. }))
+                | Some(Core::String(StringFn::String {}))
+                | Some(Core::Alloc(AllocFn::Alloc)) => return None,
+                Some(_) => return found,
+                None => {}
+            }
+        }
+        None
+    }
+}
+
 impl ResolveCore for Core {
     fn resolve<E: crate::vm::external::Engine>(
         &mut self,
@@ -116,20 +152,24 @@ impl ResolveCore for Core {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
-            Core::Vec(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Map(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::String(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Alloc(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Thread(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::IO(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Math(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Format(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
-            Core::Iter(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters),
+            Core::Vec(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Map(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::String(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Alloc(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Thread(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::IO(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Math(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Format(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
+            Core::Iter(value) => value.resolve::<E>(scope_manager, scope_id, context, parameters, resolved_receiver),
             Core::Assert(expect_err) => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
 
                 let size = &mut parameters[0];
@@ -142,26 +182,74 @@ impl ResolveCore for Core {
                 )?;
                 let size_type = size.type_of(&scope_manager, scope_id)?;
                 match &size_type {
-                    EType::Static(value) => match value {
-                        StaticType::Primitive(PrimitiveType::Bool) => *expect_err = false,
-                        StaticType::Error => *expect_err = true,
-                        _ => return Err(SemanticError::IncorrectArguments),
-                    },
-                    _ => return Err(SemanticError::IncorrectArguments),
+                    EType::Static(StaticType::Primitive(PrimitiveType::Bool)) => {
+                        *expect_err = false
+                    }
+                    EType::Static(StaticType::Error) => *expect_err = true,
+                    _ => {
+                        return Err(SemanticError::mismatched_kind(
+                            "bool or Error",
+                            &size_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(size.span()))
+                    }
                 }
                 Ok(e_static!(StaticType::Error))
             }
-            Core::Error => {
-                if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+            Core::Error(padding) => {
+                let payload_context = match context {
+                    Some(EType::Static(StaticType::TypedError(ErrorType(payload)))) => {
+                        Some(payload.as_ref().clone())
+                    }
+                    _ => None,
+                };
+                match parameters.len() {
+                    0 => match payload_context {
+                        // an error without payload in a typed context carries a zeroed payload
+                        Some(payload) => {
+                            *padding = payload.size_of();
+                            Ok(e_static!(StaticType::TypedError(ErrorType(Box::new(
+                                payload
+                            )))))
+                        }
+                        None => Ok(e_static!(StaticType::Error)),
+                    },
+                    1 => {
+                        let payload = &mut parameters[0];
+                        let _ = payload.resolve::<E>(
+                            scope_manager,
+                            scope_id,
+                            &payload_context,
+                            &mut None,
+                        )?;
+                        let payload_type = payload.type_of(&scope_manager, scope_id)?;
+                        if let Some(expected) = &payload_context {
+                            expected.compatible_with(&payload_type, &scope_manager, scope_id)?;
+                        }
+                        *padding = 0;
+                        Ok(e_static!(StaticType::TypedError(ErrorType(Box::new(
+                            payload_context.unwrap_or(payload_type)
+                        )))))
+                    }
+                    found => Err(SemanticError::IncorrectArgumentCount { expected: 1, found }),
                 }
-                return Ok(e_static!(StaticType::Error));
             }
-            Core::Ok => {
+            Core::Ok(padding) => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
+                }
+                match context {
+                    Some(EType::Static(StaticType::TypedError(error_type))) => {
+                        *padding = error_type.0.size_of();
+                        Ok(e_static!(StaticType::TypedError(error_type.clone())))
+                    }
+                    _ => Ok(e_static!(StaticType::Error)),
                 }
-                return Ok(e_static!(StaticType::Error));
             }
         }
     }
@@ -218,11 +306,21 @@ impl GenerateCode for Core {
                 }
                 Ok(())
             }
-            Core::Error => {
+            Core::Error(padding) => {
+                if *padding > 0 {
+                    instructions.push(Asm::Data(Data::Serialized {
+                        data: vec![0; *padding].into(),
+                    }));
+                }
                 instructions.push(Asm::Core(CoreAsm::Error));
                 Ok(())
             }
-            Core::Ok => {
+            Core::Ok(padding) => {
+                if *padding > 0 {
+                    instructions.push(Asm::Data(Data::Serialized {
+                        data: vec![0; *padding].into(),
+                    }));
+                }
                 instructions.push(Asm::Core(CoreAsm::Ok));
                 Ok(())
             }
@@ -250,6 +348,26 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for CoreAsm {
     }
 }
 
+impl crate::vm::AsmListing for CoreAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            CoreAsm::Vec(value) => value.listing(labels),
+            CoreAsm::Map(value) => value.listing(labels),
+            CoreAsm::String(value) => value.listing(labels),
+            CoreAsm::Alloc(value) => value.listing(labels),
+            CoreAsm::Thread(value) => value.listing(labels),
+            CoreAsm::IO(value) => value.listing(labels),
+            CoreAsm::Math(value) => value.listing(labels),
+            CoreAsm::Format(value) => value.listing(labels),
+            CoreAsm::Iter(value) => value.listing(labels),
+            CoreAsm::AssertBool => "assert_true".to_string(),
+            CoreAsm::AssertErr => "assert_ok".to_string(),
+            CoreAsm::Error => "error".to_string(),
+            CoreAsm::Ok => "ok".to_string(),
+        }
+    }
+}
+
 impl crate::vm::AsmWeight for CoreAsm {
     fn weight(&self) -> super::Weight {
         match self {
@@ -420,6 +538,179 @@ impl<E: crate::vm::external::Engine> Executable<E> for CoreAsm {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        ast::{statements::Statement, TryParse},
+        semantic::{Resolve, SemanticError},
+        test_extract_variable, test_statements,
+    };
+
+    use super::{lexem, Core, PathFinder};
+
+    #[test]
+    fn valid_libraries() {
+        for (library, functions) in lexem::LIBRARIES {
+            for function in functions.iter() {
+                let path = [lexem::CORE.to_string(), library.to_string()];
+                assert!(Core::find(&path, function).is_some(), "{}::{}", library, function);
+            }
+        }
+        for function in lexem::BUILTINS {
+            assert!(Core::find(&[], function).is_some(), "{}", function);
+        }
+    }
+
+    #[test]
+    fn valid_method_syntax() {
+        let mut engine = crate::vm::external::test::NoopEngine {};
+
+        fn assert_fn(
+            scope_manager: &crate::semantic::scope::scope::ScopeManager,
+            stack: &crate::vm::allocator::stack::Stack,
+            heap: &crate::vm::allocator::heap::Heap,
+        ) -> bool {
+            let res = test_extract_variable::<u64>("len1", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 3);
+            let res = test_extract_variable::<i64>("last", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 12);
+            let res = test_extract_variable::<u64>("len2", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            let res = test_extract_variable::<u64>("len3", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 11);
+            let res = test_extract_variable::<u64>("value", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 6);
+            let res = test_extract_variable::<u64>("len4", scope_manager, stack, heap)
+                .expect("Deserialization should have succeeded");
+            assert_eq!(res, 2);
+            true
+        }
+
+        test_statements(
+            r##"
+        let arr : Vec[i64] = vec[1,2];
+        arr = arr.push(12);
+        let len1 = arr.len();
+        let last = arr.pop();
+        let ptr = &arr;
+        let len2 = ptr.len();
+
+        let text = string("Hello");
+        text = text.append(" World");
+        let len3 = text.len();
+
+        let hmap : Map[u64]u64 = map();
+        hmap = hmap.insert(101,5);
+        hmap = hmap.insert(102,6);
+        let (value,err) = hmap.get(102);
+        let len4 = hmap.len();
+        "##,
+            &mut engine,
+            assert_fn,
+        );
+    }
+
+    #[test]
+    fn robustness_method_syntax() {
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let mut statement = Statement::parse(
+            r##"
+            let arr : Vec[i64] = vec[1,2];
+            "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let _ = statement
+            .resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            )
+            .expect("Resolution should have succeeded");
+
+        for input in [
+            // string function on a vector
+            r##"
+            let res = arr.append("a");
+            "##,
+            // constructors are not methods
+            r##"
+            let res : Vec[i64] = arr.vec(2);
+            "##,
+        ] {
+            let mut statement = Statement::parse(input.into()).unwrap().1;
+            let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            );
+            assert!(
+                matches!(res, Err(SemanticError::UnknownMethod(_))),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn robustness_core_arguments() {
+        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let mut statement = Statement::parse(
+            r##"
+            let arr : Vec[i64] = vec[1,2];
+            "##
+            .into(),
+        )
+        .unwrap()
+        .1;
+        let _ = statement
+            .resolve::<crate::vm::external::test::NoopEngine>(
+                &mut scope_manager,
+                None,
+                &None,
+                &mut (),
+            )
+            .expect("Resolution should have succeeded");
+
+        let mut statement = Statement::parse("let res = arr.pop(1);".into()).unwrap().1;
+        let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        match res.map_err(SemanticError::into_inner) {
+            Err(SemanticError::IncorrectArgumentCount { expected, found }) => {
+                assert_eq!((expected, found), (1, 2))
+            }
+            res => panic!("{:?}", res),
+        }
+
+        let mut statement = Statement::parse("let res = itoa(true);".into()).unwrap().1;
+        let res = statement.resolve::<crate::vm::external::test::NoopEngine>(
+            &mut scope_manager,
+            None,
+            &None,
+            &mut (),
+        );
+        let Err(err) = res else {
+            panic!("Resolution should have failed");
+        };
+        assert!(err.span().is_some(), "{:?}", err);
+        match err.into_inner() {
+            SemanticError::MismatchedTypes { expected, found } => {
+                assert_eq!(expected.name, "a number");
+                assert_eq!(found.name, "bool");
+            }
+            err => panic!("{:?}", err),
+        }
+    }
 
     // #[test]
     // fn valid_extern_fn() {
@@ -48,39 +48,60 @@ impl ResolveCore for StringFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         fn string_param<E: crate::vm::external::Engine>(
             param: &mut Expression,
+            resolved: bool,
             scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
             scope_id: Option<u128>,
         ) -> Result<StringType, SemanticError> {
-            let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-            let EType::Static(StaticType::String(string_type)) =
-                param.type_of(scope_manager, scope_id)?
-            else {
-                return Err(SemanticError::IncorrectArguments);
+            if !resolved {
+                let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            }
+            let param_type = param.type_of(scope_manager, scope_id)?;
+            let EType::Static(StaticType::String(string_type)) = param_type.clone() else {
+                return Err(SemanticError::mismatched_kind(
+                    "a String",
+                    &param_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(param.span()));
             };
             Ok(string_type)
         }
 
         fn string_or_slice_param<E: crate::vm::external::Engine>(
             param: &mut Expression,
+            resolved: bool,
             scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
             scope_id: Option<u128>,
         ) -> Result<bool, SemanticError> {
-            let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            if !resolved {
+                let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            }
 
             match param.type_of(scope_manager, scope_id)? {
                 EType::Static(StaticType::String(_)) => Ok(true),
                 EType::Static(StaticType::StrSlice(_)) => Ok(false),
-                _ => Err(SemanticError::IncorrectArguments),
+                param_type => Err(SemanticError::mismatched_kind(
+                    "a String or a str",
+                    &param_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(param.span())),
             }
         }
 
         match self {
             StringFn::String {} => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let str_slice = &mut parameters[0];
                 let _ = str_slice.resolve::<E>(
@@ -90,40 +111,56 @@ impl ResolveCore for StringFn {
                     &mut None,
                 )?;
 
-                let EType::Static(StaticType::StrSlice(StrSliceType())) =
-                    str_slice.type_of(scope_manager, scope_id)?
-                else {
-                    return Err(SemanticError::IncorrectArguments);
+                let str_slice_type = str_slice.type_of(scope_manager, scope_id)?;
+                let EType::Static(StaticType::StrSlice(StrSliceType())) = str_slice_type else {
+                    return Err(SemanticError::mismatched_kind(
+                        "a str",
+                        &str_slice_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(str_slice.span()));
                 };
 
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             StringFn::Append {} => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let string = &mut first_part[0];
                 let const_str = &mut second_part[0];
-                let _ = string_param::<E>(string, scope_manager, scope_id)?;
+                let _ = string_param::<E>(string, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = const_str.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-                let EType::Static(StaticType::StrSlice(_)) =
-                    const_str.type_of(scope_manager, scope_id)?
-                else {
-                    return Err(SemanticError::IncorrectArguments);
+                let const_str_type = const_str.type_of(scope_manager, scope_id)?;
+                let EType::Static(StaticType::StrSlice(_)) = const_str_type.clone() else {
+                    return Err(SemanticError::mismatched_kind(
+                        "a str",
+                        &const_str_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(const_str.span()));
                 };
 
                 Ok(EType::Static(StaticType::String(StringType())))
             }
             StringFn::CharAt { for_string } => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let string = &mut first_part[0];
                 let index = &mut second_part[0];
-                *for_string = string_or_slice_param::<E>(string, scope_manager, scope_id)?;
+                *for_string = string_or_slice_param::<E>(string, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = index.resolve::<E>(
                     scope_manager,
@@ -131,18 +168,28 @@ impl ResolveCore for StringFn {
                     &Some(crate::p_num!(U64)),
                     &mut None,
                 )?;
-                let crate::p_num!(U64) = index.type_of(scope_manager, scope_id)? else {
-                    return Err(SemanticError::IncorrectArguments);
+                let index_type = index.type_of(scope_manager, scope_id)?;
+                let crate::p_num!(U64) = index_type else {
+                    return Err(SemanticError::mismatched(
+                        &crate::p_num!(U64),
+                        &index_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(index.span()));
                 };
 
                 Ok(EType::Static(StaticType::Primitive(PrimitiveType::Char)))
             }
             StringFn::ToConstStr {} => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let string = &mut parameters[0];
-                let _ = string_param::<E>(string, scope_manager, scope_id)?;
+                let _ = string_param::<E>(string, resolved_receiver, scope_manager, scope_id)?;
 
                 Ok(EType::Static(StaticType::StrSlice(StrSliceType())))
             }
@@ -171,6 +218,19 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for StringAsm {
     }
 }
 
+impl crate::vm::AsmListing for StringAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let name = match self {
+            StringAsm::String {} => "str.string",
+            StringAsm::Append {} => "str.append",
+            StringAsm::CharAtStrslice => "str.char_at_str_slice",
+            StringAsm::CharAtString => "str.char_at_string",
+            StringAsm::ToConstStr => "str.to_const_str",
+        };
+        name.to_string()
+    }
+}
+
 impl crate::vm::AsmWeight for StringAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -8,8 +8,11 @@ use crate::vm::core::lexem;
 use crate::vm::core::CoreAsm;
 use crate::vm::core::{ERROR_SLICE, OK_SLICE};
 use crate::vm::external::ExternThreadIdentifier;
+use crate::vm::allocator::MemoryAddress;
 use crate::vm::runtime::RuntimeError;
-use crate::vm::scheduler::Executable;
+use crate::vm::scheduler::{
+    EventCallback, EventConf, EventExclusivity, EventKind, EventManager, Executable,
+};
 use crate::vm::signal::{SignalAction, SignalResult};
 use crate::vm::GenerateCode;
 use crate::{
@@ -40,6 +43,7 @@ pub enum ThreadAsm {
     Wake,
     Sleep,
     Join,
+    EventRegistration(EventConf),
 }
 
 impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for ThreadAsm {
@@ -58,10 +62,37 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for ThreadAsm {
             ThreadAsm::Wake => stdio.push_asm_lib(engine, pid, "wake"),
             ThreadAsm::Sleep => stdio.push_asm_lib(engine, pid, "sleep"),
             ThreadAsm::Join => stdio.push_asm_lib(engine, pid, "join"),
+            ThreadAsm::EventRegistration(_) => stdio.push_asm_lib(engine, pid, "event"),
         }
     }
 }
 
+impl crate::vm::AsmListing for ThreadAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        let name = match self {
+            ThreadAsm::Spawn => "thread.spawn",
+            ThreadAsm::Close => "thread.close",
+            ThreadAsm::Exit => "thread.exit",
+            ThreadAsm::Wait => "thread.wait",
+            ThreadAsm::Wake => "thread.wake",
+            ThreadAsm::Sleep => "thread.sleep",
+            ThreadAsm::Join => "thread.join",
+            ThreadAsm::EventRegistration(EventConf { kind, exclu }) => {
+                let kind = match kind {
+                    EventKind::Once => "once",
+                    EventKind::Repetable => "repeatable",
+                };
+                let exclu = match exclu {
+                    EventExclusivity::PerTID => "per_tid",
+                    EventExclusivity::PerPID => "per_pid",
+                };
+                return format!("thread.event {kind} {exclu}");
+            }
+        };
+        name.to_string()
+    }
+}
+
 impl crate::vm::AsmWeight for ThreadAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
@@ -72,6 +103,7 @@ impl crate::vm::AsmWeight for ThreadAsm {
             ThreadAsm::Wake => crate::vm::Weight::HIGH,
             ThreadAsm::Sleep => crate::vm::Weight::END,
             ThreadAsm::Join => crate::vm::Weight::END,
+            ThreadAsm::EventRegistration(_) => crate::vm::Weight::HIGH,
         }
     }
 }
@@ -103,19 +135,21 @@ fn expect_one_u64<E: crate::vm::external::Engine>(
     scope_id: Option<u128>,
 ) -> Result<(), SemanticError> {
     if params.len() != 1 {
-        return Err(SemanticError::IncorrectArguments);
+        return Err(SemanticError::IncorrectArgumentCount {
+            expected: 1,
+            found: params.len(),
+        });
     }
 
     let size = &mut params[0];
 
     let _ = size.resolve::<E>(scope_manager, scope_id, &Some(p_num!(U64)), &mut None)?;
     let size_type = size.type_of(&scope_manager, scope_id)?;
-    match &size_type {
-        EType::Static(value) => match value {
-            StaticType::Primitive(PrimitiveType::Number(NumberType::U64)) => {}
-            _ => return Err(SemanticError::IncorrectArguments),
-        },
-        _ => return Err(SemanticError::IncorrectArguments),
+    if size_type != p_num!(U64) {
+        return Err(
+            SemanticError::mismatched(&p_num!(U64), &size_type, scope_manager, scope_id)
+                .at(size.span()),
+        );
     }
     Ok(())
 }
@@ -126,17 +160,24 @@ impl ResolveCore for ThreadFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        _resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         match self {
             ThreadFn::Spawn => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
                 }
                 Ok(err_tuple!(p_num!(U64)))
             }
             ThreadFn::Exit => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
                 }
                 Ok(e_static!(StaticType::Unit))
             }
@@ -146,7 +187,10 @@ impl ResolveCore for ThreadFn {
             }
             ThreadFn::Wait => {
                 if parameters.len() != 0 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: parameters.len(),
+                    });
                 }
                 Ok(e_static!(StaticType::Unit))
             }
@@ -545,6 +589,40 @@ impl<E: crate::vm::external::Engine> Executable<E> for ThreadAsm {
                 scheduler.next();
                 Ok(())
             }
+            ThreadAsm::EventRegistration(conf) => {
+                // request the registration of an event defined in the language
+                fn event_callback<E: crate::vm::external::Engine>(
+                    response: crate::vm::signal::SignalResult<E>,
+                    stack: &mut crate::vm::allocator::stack::Stack,
+                ) -> Result<(), RuntimeError> {
+                    match response {
+                        SignalResult::Ok(SignalAction::EventRegistration { .. }) => {}
+                        SignalResult::Error => return Err(RuntimeError::SignalError),
+                        SignalResult::Ok(_) => {} // Unreachable
+                    }
+                    Ok(())
+                }
+                let callback: MemoryAddress = OpPrimitive::pop_num::<u64>(stack)?.try_into()?;
+                let trigger = OpPrimitive::pop_num::<u64>(stack)?;
+                let _ = signal_handler.notify(
+                    crate::vm::signal::Signal::EventRegistration {
+                        tid: context.tid.clone(),
+                        trigger,
+                        callback: EventCallback {
+                            callback,
+                            manager: EventManager::Native,
+                            _phantom: PhantomData::default(),
+                        },
+                        conf: *conf,
+                    },
+                    stack,
+                    engine,
+                    context.tid.clone(),
+                    event_callback::<E>,
+                )?;
+                scheduler.next();
+                Ok(())
+            }
         }
     }
 }
@@ -1227,6 +1305,50 @@ mod tests {
             .expect("Execution should have succeeded");
     }
 
+    #[test]
+    fn valid_trace_of_error_in_event() {
+        let mut engine = crate::vm::external::test::ExternEventTestEngine {};
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        fn divide(a:i64, b:i64) -> i64 {
+            return a / b;
+        }
+        test_event(move () -> {
+            let x = divide(5, 0);
+        });
+        let y = 8;
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        let _ = runtime.trigger(tid_1.clone(), 1);
+
+        let (_, error) = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect_err("Execution should have failed");
+        assert_eq!(
+            error.to_string(),
+            "MathError at line 3 in fn divide\n  called at line 6 in closure\n  called at line 8"
+        );
+    }
+
     #[test]
     fn valid_event_once() {
         let mut engine = crate::vm::external::test::ExternEventTestEngine {};
@@ -1346,4 +1468,233 @@ mod tests {
             .run(&mut heap, &mut stdio, &mut engine)
             .expect("Execution should have succeeded");
     }
+
+    #[test]
+    fn valid_event_def() {
+        let mut engine = crate::vm::external::test::ThreadTestEngine {
+            id_auto_increment: 0,
+        };
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        let x = 1;
+        event repeat on_signal(2) {
+            x = x + 1;
+        }
+        let y = 8;
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        let _ = runtime.trigger(tid_1.clone(), 1);
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+        {
+            let (Thread { stack, .. }, ThreadContext { scope_manager, .. }) = runtime
+                .thread_with_context_of(&tid_1)
+                .expect("Thread should have been found");
+            let x = test_extract_variable::<i64>("x", scope_manager, stack, &heap)
+                .expect("Variable should have been found");
+            assert_eq!(x, 1);
+        }
+
+        for _ in 0..2 {
+            let _ = runtime.trigger(tid_1.clone(), 2);
+            let _ = runtime
+                .run(&mut heap, &mut stdio, &mut engine)
+                .expect("Execution should have succeeded");
+            let _ = runtime
+                .run(&mut heap, &mut stdio, &mut engine)
+                .expect("Execution should have succeeded");
+        }
+        {
+            let (Thread { stack, .. }, ThreadContext { scope_manager, .. }) = runtime
+                .thread_with_context_of(&tid_1)
+                .expect("Thread should have been found");
+            let x = test_extract_variable::<i64>("x", scope_manager, stack, &heap)
+                .expect("Variable should have been found");
+            assert_eq!(x, 3);
+        }
+    }
+
+    #[test]
+    fn valid_event_def_once() {
+        let mut engine = crate::vm::external::test::ThreadTestEngine {
+            id_auto_increment: 0,
+        };
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        let x = 1;
+        event once per_pid on_signal(1) {
+            x = x + 1;
+        }
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        for _ in 0..2 {
+            let _ = runtime.trigger(tid_1.clone(), 1);
+            let _ = runtime
+                .run(&mut heap, &mut stdio, &mut engine)
+                .expect("Execution should have succeeded");
+        }
+        assert!(!runtime.event_queue.current_events.contains_key(&tid_1));
+        assert_eq!(
+            runtime
+                .event_queue
+                .events
+                .get(&tid_1)
+                .map(|e| e.len())
+                .unwrap_or(0),
+            0
+        );
+        {
+            let (Thread { stack, .. }, ThreadContext { scope_manager, .. }) = runtime
+                .thread_with_context_of(&tid_1)
+                .expect("Thread should have been found");
+            let x = test_extract_variable::<i64>("x", scope_manager, stack, &heap)
+                .expect("Variable should have been found");
+            assert_eq!(x, 2);
+        }
+    }
+
+    #[test]
+    fn valid_event_def_label() {
+        let mut engine = crate::vm::external::test::ThreadTestEngine {
+            id_auto_increment: 0,
+        };
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        let x = 0;
+        event on_signal(1) {
+            let y = 5 / x;
+        }
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        let _ = runtime.trigger(tid_1.clone(), 1);
+        let (_, error) = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect_err("Execution should have failed");
+        let location = error.location().expect("the error should have been located");
+        assert_eq!(location.line, 4);
+        assert_eq!(location.function.as_deref(), Some("event on_signal"));
+    }
+
+    #[test]
+    fn valid_uncaught_raise() {
+        let mut engine = crate::vm::external::test::StdoutTestEngine { out: String::new() };
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        let res1 = 1;
+        let message = "something went wrong";
+        fn fail() {
+            raise message;
+        }
+        fail();
+        res1 = 2;
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+        assert_eq!(
+            engine.out,
+            "Error : uncaught raise : something went wrong at line 5 in fn fail\n  called at line 7"
+        );
+        assert!(runtime.context_of(&tid_1).is_err());
+    }
+
+    #[test]
+    fn valid_uncaught_raise_in_event() {
+        let mut engine = crate::vm::external::test::ThreadTestEngine {
+            id_auto_increment: 0,
+        };
+
+        let mut heap = Heap::new();
+        let mut stdio = StdIO::default();
+        let mut runtime = Runtime::default();
+
+        let tid_1 = runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+
+        compile_for(
+            r##"
+        let message = "something went wrong";
+        event on_signal(1) {
+            raise message;
+        }
+        "##,
+            &tid_1,
+            &mut runtime,
+        );
+
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+
+        let _ = runtime.trigger(tid_1.clone(), 1);
+        let _ = runtime
+            .run(&mut heap, &mut stdio, &mut engine)
+            .expect("Execution should have succeeded");
+        assert!(runtime.context_of(&tid_1).is_err());
+        assert!(runtime.event_queue.current_events.is_empty());
+        assert_eq!(heap.allocated_size(), 0);
+    }
 }
@@ -77,17 +77,26 @@ impl ResolveCore for VectorFn {
         scope_id: Option<u128>,
         context: Option<&EType>,
         parameters: &mut Vec<Expression>,
+        resolved_receiver: bool,
     ) -> Result<EType, SemanticError> {
         fn vec_param<E: crate::vm::external::Engine>(
             param: &mut Expression,
+            resolved: bool,
             scope_manager: &mut crate::semantic::scope::scope::ScopeManager,
             scope_id: Option<u128>,
         ) -> Result<VecType, SemanticError> {
-            let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
-            let EType::Static(StaticType::Vec(vector_type)) =
-                param.type_of(scope_manager, scope_id)?
-            else {
-                return Err(SemanticError::IncorrectArguments);
+            if !resolved {
+                let _ = param.resolve::<E>(scope_manager, scope_id, &None, &mut None)?;
+            }
+            let param_type = param.type_of(scope_manager, scope_id)?;
+            let EType::Static(StaticType::Vec(vector_type)) = param_type.clone() else {
+                return Err(SemanticError::mismatched_kind(
+                    "a Vec",
+                    &param_type,
+                    scope_manager,
+                    scope_id,
+                )
+                .at(param.span()));
             };
             Ok(vector_type)
         }
@@ -103,7 +112,11 @@ impl ResolveCore for VectorFn {
                     )));
                 };
                 if parameters.len() == 0 || parameters.len() > 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    // the length of the vector, then its capacity if any
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: parameters.len().clamp(1, 2),
+                        found: parameters.len(),
+                    });
                 }
 
                 for param in parameters.iter_mut() {
@@ -113,8 +126,15 @@ impl ResolveCore for VectorFn {
                         &Some(crate::p_num!(U64)),
                         &mut None,
                     )?;
-                    let crate::p_num!(U64) = param.type_of(&scope_manager, scope_id)? else {
-                        return Err(SemanticError::IncorrectArguments);
+                    let param_type = param.type_of(&scope_manager, scope_id)?;
+                    let crate::p_num!(U64) = param_type else {
+                        return Err(SemanticError::mismatched(
+                            &crate::p_num!(U64),
+                            &param_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(param.span()));
                     };
                 }
 
@@ -125,12 +145,15 @@ impl ResolveCore for VectorFn {
             }
             VectorFn::Push { item_size } => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let vector = &mut first_part[0];
                 let item = &mut second_part[0];
-                let vector_type = vec_param::<E>(vector, scope_manager, scope_id)?;
+                let vector_type = vec_param::<E>(vector, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = item.resolve::<E>(
                     scope_manager,
@@ -148,10 +171,13 @@ impl ResolveCore for VectorFn {
             }
             VectorFn::Pop { item_size } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let vector = &mut parameters[0];
-                let vector_type = vec_param::<E>(vector, scope_manager, scope_id)?;
+                let vector_type = vec_param::<E>(vector, resolved_receiver, scope_manager, scope_id)?;
 
                 *item_size = vector_type.0.size_of();
 
@@ -159,12 +185,15 @@ impl ResolveCore for VectorFn {
             }
             VectorFn::Delete { item_size } => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let vector = &mut first_part[0];
                 let index = &mut second_part[0];
-                let vector_type = vec_param::<E>(vector, scope_manager, scope_id)?;
+                let vector_type = vec_param::<E>(vector, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = index.resolve::<E>(
                     scope_manager,
@@ -172,8 +201,15 @@ impl ResolveCore for VectorFn {
                     &Some(crate::p_num!(U64)),
                     &mut None,
                 )?;
-                let crate::p_num!(U64) = index.type_of(&scope_manager, scope_id)? else {
-                    return Err(SemanticError::IncorrectArguments);
+                let index_type = index.type_of(&scope_manager, scope_id)?;
+                let crate::p_num!(U64) = index_type else {
+                    return Err(SemanticError::mismatched(
+                        &crate::p_num!(U64),
+                        &index_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(index.span()));
                 };
 
                 *item_size = vector_type.0.size_of();
@@ -182,12 +218,15 @@ impl ResolveCore for VectorFn {
             }
             VectorFn::Extend { item_size, len } => {
                 if parameters.len() != 2 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: parameters.len(),
+                    });
                 }
                 let (first_part, second_part) = parameters.split_at_mut(1);
                 let vector = &mut first_part[0];
                 let array = &mut second_part[0];
-                let vector_type = vec_param::<E>(vector, scope_manager, scope_id)?;
+                let vector_type = vec_param::<E>(vector, resolved_receiver, scope_manager, scope_id)?;
 
                 let _ = array.resolve::<E>(
                     scope_manager,
@@ -195,10 +234,17 @@ impl ResolveCore for VectorFn {
                     &None,
                     &mut Some(vector_type.0.as_ref().clone()),
                 )?;
+                let array_type = array.type_of(scope_manager, scope_id)?;
                 let EType::Static(StaticType::Slice(SliceType { size, item_type })) =
-                    array.type_of(scope_manager, scope_id)?
+                    array_type.clone()
                 else {
-                    return Err(SemanticError::IncompatibleTypes);
+                    return Err(SemanticError::mismatched_kind(
+                        "a slice",
+                        &array_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(array.span()));
                 };
 
                 *len = size;
@@ -209,10 +255,13 @@ impl ResolveCore for VectorFn {
             }
             VectorFn::ClearVec { item_size } => {
                 if parameters.len() != 1 {
-                    return Err(SemanticError::IncorrectArguments);
+                    return Err(SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: parameters.len(),
+                    });
                 }
                 let vector = &mut parameters[0];
-                let vector_type = vec_param::<E>(vector, scope_manager, scope_id)?;
+                let vector_type = vec_param::<E>(vector, resolved_receiver, scope_manager, scope_id)?;
 
                 *item_size = vector_type.0.size_of();
 
@@ -247,6 +296,20 @@ impl<E: crate::vm::external::Engine> crate::vm::AsmName<E> for VectorAsm {
     }
 }
 
+impl crate::vm::AsmListing for VectorAsm {
+    fn listing(&self, labels: &crate::vm::listing::LabelNames) -> String {
+        match self {
+            VectorAsm::Vec { item_size } => format!("vec.new {item_size}"),
+            VectorAsm::VecWithCapacity { item_size } => format!("vec.with_capacity {item_size}"),
+            VectorAsm::Push { item_size } => format!("vec.push {item_size}"),
+            VectorAsm::Pop { item_size } => format!("vec.pop {item_size}"),
+            VectorAsm::Extend { item_size, len } => format!("vec.extend {item_size} {len}"),
+            VectorAsm::Delete { item_size } => format!("vec.delete {item_size}"),
+            VectorAsm::Clear { item_size } => format!("vec.clear {item_size}"),
+        }
+    }
+}
+
 impl crate::vm::AsmWeight for VectorAsm {
     fn weight(&self) -> crate::vm::Weight {
         match self {
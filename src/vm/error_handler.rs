@@ -1,37 +1,81 @@
 use ulid::Ulid;
 
-use super::{program::Program, runtime::RuntimeError};
+use super::{
+    allocator::stack::{Stack, StackState},
+    program::Program,
+    runtime::RuntimeError,
+};
 
 pub struct ErrorHandler {
-    stacktrace: Vec<Ulid>,
+    stacktrace: Vec<(Ulid, StackState)>,
+    // number of tries opened before the running event, an event does not unwind to them
+    event_floor: Option<usize>,
+    // message of the error caught by the innermost try
+    caught: Option<String>,
 }
 
 impl Default for ErrorHandler {
     fn default() -> Self {
         Self {
             stacktrace: Vec::default(),
+            event_floor: None,
+            caught: None,
         }
     }
 }
 
 impl ErrorHandler {
-    pub fn push_catch(&mut self, label: &Ulid) {
-        self.stacktrace.push(*label);
+    pub fn push_catch(&mut self, label: &Ulid, state: StackState) {
+        self.stacktrace.push((*label, state));
     }
 
     pub fn pop_catch(&mut self) {
         self.stacktrace.pop();
+        self.caught = None;
     }
 
+    /*
+     * @desc Message of the error caught by the innermost try
+     */
+    pub fn take_caught(&mut self) -> Option<String> {
+        self.caught.take()
+    }
+
+    pub fn enter_event(&mut self) {
+        self.event_floor = Some(self.stacktrace.len());
+    }
+
+    /*
+     * @desc Drop the tries left open by an event
+     */
+    pub fn leave_event(&mut self) {
+        if let Some(floor) = self.event_floor.take() {
+            self.stacktrace.truncate(floor);
+        }
+    }
+
+    /*
+     * @desc Cursor of the catch label of the innermost try
+     * The stack is restored as it was when entering the try
+     * and the code of the error is pushed on it
+     */
     pub fn catch<E: crate::vm::external::Engine>(
-        &self,
+        &mut self,
         error: RuntimeError,
         program: &Program<E>,
+        stack: &mut Stack,
     ) -> Result<usize, RuntimeError> {
-        if let Some(label) = self.stacktrace.last() {
+        let floor = self.event_floor.unwrap_or(0);
+        if let Some((label, state)) = self.stacktrace[floor..].last() {
             let Some(idx) = program.get_cursor_from_label(&label) else {
                 return Err(RuntimeError::CodeSegmentation);
             };
+            stack.restore(*state);
+            let _ = stack.push_with(&error.code().to_le_bytes())?;
+            self.caught = Some(match error {
+                RuntimeError::Raised(message) => message,
+                error => error.to_string(),
+            });
             Ok(idx)
         } else {
             Err(error)
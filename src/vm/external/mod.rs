@@ -1,4 +1,4 @@
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
 
 use super::{allocator::MemoryAddress, runtime::RuntimeError};
 
@@ -122,6 +122,33 @@ pub trait ExternThreadHandler {
     fn close(&mut self, pid: &Self::PID, tid: &Self::TID) -> Result<(), RuntimeError>;
 }
 
+/*
+ * @desc Source of the modules imported with : import "path";
+ * The loader is called at most once per module path and per process
+ */
+pub trait ModuleLoader<PID: ExternProcessIdentifier> {
+    fn load(&mut self, pid: PID, path: &str) -> Option<String>;
+}
+
+impl<PID: ExternProcessIdentifier> ModuleLoader<PID> for HashMap<String, String> {
+    fn load(&mut self, _pid: PID, path: &str) -> Option<String> {
+        self.get(path).cloned()
+    }
+}
+
+/*
+ * @desc Binary encoding of the extern functions of an engine, used by the bytecode of the programs
+ * A function is encoded alone so the decoder receives exactly the bytes of its encoding
+ */
+pub trait ExternCodec {
+    fn encode_function(function: &Self::Function) -> Vec<u8>
+    where
+        Self: Engine;
+    fn decode_function(bytes: &[u8]) -> Option<Self::Function>
+    where
+        Self: Engine;
+}
+
 pub trait Engine:
     ExternIO<Self::PID, Self::TID>
     + ExternPathFinder
@@ -9,9 +9,9 @@ use crate::{
 };
 
 use super::{
-    Engine, ExternEnergyDispenser, ExternEventManager, ExternExecutionContext, ExternFunction,
-    ExternIO, ExternPathFinder, ExternProcessIdentifier, ExternResolve, ExternThreadHandler,
-    ExternThreadIdentifier,
+    Engine, ExternCodec, ExternEnergyDispenser, ExternEventManager, ExternExecutionContext,
+    ExternFunction, ExternIO, ExternPathFinder, ExternProcessIdentifier, ExternResolve,
+    ExternThreadHandler, ExternThreadIdentifier,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
@@ -41,6 +41,18 @@ impl Default for DefaultProcessID {
     }
 }
 
+impl std::fmt::Display for DefaultThreadID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "thread {}", self.0)
+    }
+}
+
+impl std::fmt::Display for DefaultProcessID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "process {}", self.0)
+    }
+}
+
 impl ExternProcessIdentifier for DefaultProcessID {}
 
 impl ExternThreadIdentifier<DefaultProcessID> for DefaultThreadID {
@@ -174,6 +186,22 @@ impl ExternPathFinder for NoopEngine {
     }
 }
 
+impl ExternCodec for NoopEngine {
+    fn encode_function(function: &<Self as Engine>::Function) -> Vec<u8>
+    where
+        Self: super::Engine,
+    {
+        Vec::default()
+    }
+
+    fn decode_function(bytes: &[u8]) -> Option<<Self as Engine>::Function>
+    where
+        Self: super::Engine,
+    {
+        bytes.is_empty().then_some(DefaultExternFunctionNoopEngine)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct DefaultExternFunctionStdoutTestEngine;
 
@@ -238,7 +266,9 @@ impl<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> ExternIO<PI
         self.out = format!("{}\n", content);
     }
 
-    fn stderr_print(&mut self, pid: PID, content: String) {}
+    fn stderr_print(&mut self, pid: PID, content: String) {
+        self.out = content;
+    }
 
     fn stdin_scan(&mut self, tid: TID) -> Option<String> {
         None
@@ -282,7 +312,7 @@ impl ExternThreadHandler for StdoutTestEngine {
         pid: &Self::PID,
         tid: &Self::TID,
     ) -> Result<(), crate::vm::runtime::RuntimeError> {
-        unimplemented!()
+        Ok(())
     }
 }
 
@@ -691,6 +721,7 @@ impl<PID: ExternProcessIdentifier, TID: ExternThreadIdentifier<PID>> ExternIO<PI
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ExternFuncTest {
     TEST_ADDER,
+    TEST_ANNOTATED, // only callable from a statement annotated with @test
 }
 
 impl<E: Engine> AsmName<E> for ExternFuncTest {
@@ -703,6 +734,7 @@ impl<E: Engine> AsmName<E> for ExternFuncTest {
     ) {
         match self {
             ExternFuncTest::TEST_ADDER => stdio.push_asm_lib(engine, pid, "test_adder"),
+            ExternFuncTest::TEST_ANNOTATED => stdio.push_asm_lib(engine, pid, "test_annotated"),
         }
     }
 }
@@ -733,6 +765,12 @@ impl<E: Engine> Executable<E> for ExternFuncTest {
 
                 let _ = stack.push_with(&(x + y).to_le_bytes())?;
 
+                scheduler.next();
+                Ok(())
+            }
+            ExternFuncTest::TEST_ANNOTATED => {
+                let _ = stack.push_with(&1u64.to_le_bytes())?;
+
                 scheduler.next();
                 Ok(())
             }
@@ -750,7 +788,10 @@ impl ExternResolve for ExternFuncTest {
         match self {
             ExternFuncTest::TEST_ADDER => {
                 if params.len() != 2 {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                    return Err(crate::semantic::SemanticError::IncorrectArgumentCount {
+                        expected: 2,
+                        found: params.len(),
+                    });
                 }
                 let (first_part, second_part) = params.split_at_mut(1);
                 let x = &mut first_part[0];
@@ -771,15 +812,25 @@ impl ExternResolve for ExternFuncTest {
                     &mut None,
                 )?;
 
-                let crate::p_num!(U64) =
-                    crate::semantic::TypeOf::type_of(x, &scope_manager, scope_id)?
-                else {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                let x_type = crate::semantic::TypeOf::type_of(x, &scope_manager, scope_id)?;
+                let crate::p_num!(U64) = x_type else {
+                    return Err(crate::semantic::SemanticError::mismatched(
+                        &crate::p_num!(U64),
+                        &x_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(x.span()));
                 };
-                let crate::p_num!(U64) =
-                    crate::semantic::TypeOf::type_of(y, &scope_manager, scope_id)?
-                else {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                let y_type = crate::semantic::TypeOf::type_of(y, &scope_manager, scope_id)?;
+                let crate::p_num!(U64) = y_type else {
+                    return Err(crate::semantic::SemanticError::mismatched(
+                        &crate::p_num!(U64),
+                        &y_type,
+                        scope_manager,
+                        scope_id,
+                    )
+                    .at(y.span()));
                 };
 
                 Ok(crate::semantic::EType::Static(
@@ -790,6 +841,26 @@ impl ExternResolve for ExternFuncTest {
                     ),
                 ))
             }
+            ExternFuncTest::TEST_ANNOTATED => {
+                if params.len() != 0 {
+                    return Err(crate::semantic::SemanticError::IncorrectArgumentCount {
+                        expected: 0,
+                        found: params.len(),
+                    });
+                }
+                if scope_manager.annotation("test").is_none() {
+                    return Err(crate::semantic::SemanticError::UnknownItem(
+                        "test_annotated".to_string(),
+                    ));
+                }
+                Ok(crate::semantic::EType::Static(
+                    crate::semantic::scope::static_types::StaticType::Primitive(
+                        crate::semantic::scope::static_types::PrimitiveType::Number(
+                            crate::semantic::scope::static_types::NumberType::U64,
+                        ),
+                    ),
+                ))
+            }
         }
     }
 }
@@ -839,10 +910,33 @@ impl ExternPathFinder for ExternFuncTestEngine {
     where
         Self: super::Engine,
     {
-        if name == "test_adder" {
-            Some(ExternFuncTest::TEST_ADDER)
-        } else {
-            None
+        match name {
+            "test_adder" => Some(ExternFuncTest::TEST_ADDER),
+            "test_annotated" => Some(ExternFuncTest::TEST_ANNOTATED),
+            _ => None,
+        }
+    }
+}
+
+impl ExternCodec for ExternFuncTestEngine {
+    fn encode_function(function: &<Self as Engine>::Function) -> Vec<u8>
+    where
+        Self: super::Engine,
+    {
+        match function {
+            ExternFuncTest::TEST_ADDER => vec![0],
+            ExternFuncTest::TEST_ANNOTATED => vec![1],
+        }
+    }
+
+    fn decode_function(bytes: &[u8]) -> Option<<Self as Engine>::Function>
+    where
+        Self: super::Engine,
+    {
+        match bytes {
+            [0] => Some(ExternFuncTest::TEST_ADDER),
+            [1] => Some(ExternFuncTest::TEST_ANNOTATED),
+            _ => None,
         }
     }
 }
@@ -1043,7 +1137,7 @@ impl<
                         trigger: 1,
                         callback: crate::vm::scheduler::EventCallback {
                             callback,
-                            manager: *self,
+                            manager: crate::vm::scheduler::EventManager::Extern(*self),
                             _phantom: std::marker::PhantomData::default(),
                         },
                         conf: crate::vm::scheduler::EventConf {
@@ -1075,7 +1169,7 @@ impl<
                         trigger: 1,
                         callback: crate::vm::scheduler::EventCallback {
                             callback,
-                            manager: *self,
+                            manager: crate::vm::scheduler::EventManager::Extern(*self),
                             _phantom: std::marker::PhantomData::default(),
                         },
                         conf: crate::vm::scheduler::EventConf {
@@ -1105,32 +1199,36 @@ impl ExternResolve for ExternFuncEventTest {
         match self {
             ExternFuncEventTest::TEST_EVENT | ExternFuncEventTest::TEST_EVENT_REPETABLE => {
                 if params.len() != 1 {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                    return Err(crate::semantic::SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: params.len(),
+                    });
                 }
                 let callback = &mut params[0];
 
+                let expected = crate::semantic::EType::Static(
+                    crate::semantic::scope::static_types::StaticType::Closure(
+                        crate::semantic::scope::static_types::ClosureType {
+                            params: Vec::default(),
+                            ret: crate::semantic::EType::Static(
+                                crate::semantic::scope::static_types::StaticType::Unit,
+                            )
+                            .into(),
+                        },
+                    ),
+                );
                 let _ = crate::semantic::Resolve::resolve::<E>(
                     callback,
                     scope_manager,
                     scope_id,
-                    &Some(crate::semantic::EType::Static(
-                        crate::semantic::scope::static_types::StaticType::Closure(
-                            crate::semantic::scope::static_types::ClosureType {
-                                params: Vec::default(),
-                                ret: crate::semantic::EType::Static(
-                                    crate::semantic::scope::static_types::StaticType::Unit,
-                                )
-                                .into(),
-                            },
-                        ),
-                    )),
+                    &Some(expected.clone()),
                     &mut None,
                 )?;
 
                 let callback_type =
                     crate::semantic::TypeOf::type_of(callback, &scope_manager, scope_id)?;
 
-                match callback_type {
+                match &callback_type {
                     crate::semantic::EType::Static(
                         crate::semantic::scope::static_types::StaticType::Closure(
                             crate::semantic::scope::static_types::ClosureType { params, ret },
@@ -1139,12 +1237,26 @@ impl ExternResolve for ExternFuncEventTest {
                         if params.len() != 0
                             || crate::semantic::EType::Static(
                                 crate::semantic::scope::static_types::StaticType::Unit,
-                            ) != *ret
+                            ) != **ret
                         {
-                            return Err(crate::semantic::SemanticError::IncorrectArguments);
+                            return Err(crate::semantic::SemanticError::mismatched(
+                                &expected,
+                                &callback_type,
+                                scope_manager,
+                                scope_id,
+                            )
+                            .at(callback.span()));
                         }
                     }
-                    _ => return Err(crate::semantic::SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(crate::semantic::SemanticError::mismatched(
+                            &expected,
+                            &callback_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(callback.span()))
+                    }
                 }
 
                 Ok(crate::semantic::EType::Static(
@@ -1153,32 +1265,36 @@ impl ExternResolve for ExternFuncEventTest {
             }
             ExternFuncEventTest::TEST_EVENT_WITH_ARG => {
                 if params.len() != 1 {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                    return Err(crate::semantic::SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: params.len(),
+                    });
                 }
                 let callback = &mut params[0];
 
+                let expected = crate::semantic::EType::Static(
+                    crate::semantic::scope::static_types::StaticType::Closure(
+                        crate::semantic::scope::static_types::ClosureType {
+                            params: vec![p_num!(I64)],
+                            ret: crate::semantic::EType::Static(
+                                crate::semantic::scope::static_types::StaticType::Unit,
+                            )
+                            .into(),
+                        },
+                    ),
+                );
                 let _ = crate::semantic::Resolve::resolve::<E>(
                     callback,
                     scope_manager,
                     scope_id,
-                    &Some(crate::semantic::EType::Static(
-                        crate::semantic::scope::static_types::StaticType::Closure(
-                            crate::semantic::scope::static_types::ClosureType {
-                                params: vec![p_num!(I64)],
-                                ret: crate::semantic::EType::Static(
-                                    crate::semantic::scope::static_types::StaticType::Unit,
-                                )
-                                .into(),
-                            },
-                        ),
-                    )),
+                    &Some(expected.clone()),
                     &mut None,
                 )?;
 
                 let callback_type =
                     crate::semantic::TypeOf::type_of(callback, &scope_manager, scope_id)?;
 
-                match callback_type {
+                match &callback_type {
                     crate::semantic::EType::Static(
                         crate::semantic::scope::static_types::StaticType::Closure(
                             crate::semantic::scope::static_types::ClosureType { params, ret },
@@ -1187,12 +1303,26 @@ impl ExternResolve for ExternFuncEventTest {
                         if params.len() != 1
                             || crate::semantic::EType::Static(
                                 crate::semantic::scope::static_types::StaticType::Unit,
-                            ) != *ret
+                            ) != **ret
                         {
-                            return Err(crate::semantic::SemanticError::IncorrectArguments);
+                            return Err(crate::semantic::SemanticError::mismatched(
+                                &expected,
+                                &callback_type,
+                                scope_manager,
+                                scope_id,
+                            )
+                            .at(callback.span()));
                         }
                     }
-                    _ => return Err(crate::semantic::SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(crate::semantic::SemanticError::mismatched(
+                            &expected,
+                            &callback_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(callback.span()))
+                    }
                 }
 
                 Ok(crate::semantic::EType::Static(
@@ -1201,39 +1331,57 @@ impl ExternResolve for ExternFuncEventTest {
             }
             ExternFuncEventTest::TEST_EVENT_WITH_RETURN => {
                 if params.len() != 1 {
-                    return Err(crate::semantic::SemanticError::IncorrectArguments);
+                    return Err(crate::semantic::SemanticError::IncorrectArgumentCount {
+                        expected: 1,
+                        found: params.len(),
+                    });
                 }
                 let callback = &mut params[0];
 
+                let expected = crate::semantic::EType::Static(
+                    crate::semantic::scope::static_types::StaticType::Closure(
+                        crate::semantic::scope::static_types::ClosureType {
+                            params: Vec::default(),
+                            ret: p_num!(I64).into(),
+                        },
+                    ),
+                );
                 let _ = crate::semantic::Resolve::resolve::<E>(
                     callback,
                     scope_manager,
                     scope_id,
-                    &Some(crate::semantic::EType::Static(
-                        crate::semantic::scope::static_types::StaticType::Closure(
-                            crate::semantic::scope::static_types::ClosureType {
-                                params: Vec::default(),
-                                ret: p_num!(I64).into(),
-                            },
-                        ),
-                    )),
+                    &Some(expected.clone()),
                     &mut None,
                 )?;
 
                 let callback_type =
                     crate::semantic::TypeOf::type_of(callback, &scope_manager, scope_id)?;
 
-                match callback_type {
+                match &callback_type {
                     crate::semantic::EType::Static(
                         crate::semantic::scope::static_types::StaticType::Closure(
                             crate::semantic::scope::static_types::ClosureType { params, ret },
                         ),
                     ) => {
-                        if params.len() != 0 || p_num!(I64) != *ret {
-                            return Err(crate::semantic::SemanticError::IncorrectArguments);
+                        if params.len() != 0 || p_num!(I64) != **ret {
+                            return Err(crate::semantic::SemanticError::mismatched(
+                                &expected,
+                                &callback_type,
+                                scope_manager,
+                                scope_id,
+                            )
+                            .at(callback.span()));
                         }
                     }
-                    _ => return Err(crate::semantic::SemanticError::IncorrectArguments),
+                    _ => {
+                        return Err(crate::semantic::SemanticError::mismatched(
+                            &expected,
+                            &callback_type,
+                            scope_manager,
+                            scope_id,
+                        )
+                        .at(callback.span()))
+                    }
                 }
 
                 Ok(crate::semantic::EType::Static(
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use ulid::Ulid;
+
+use super::{
+    asm::data::HexSlice,
+    external::Engine,
+    program::{Instruction, Program},
+    AsmListing, AsmWeight, Weight,
+};
+
+// column of the comment holding the index and the weight of an instruction
+const COMMENT_COLUMN: usize = 40;
+
+/*
+ * @desc Names of the labels of a program as they appear in its listing
+ * A name shared by several labels is suffixed with the index of the label
+ */
+#[derive(Debug, Clone, Default)]
+pub struct LabelNames {
+    names: HashMap<Ulid, String>,
+}
+
+impl LabelNames {
+    pub fn of<E: Engine>(program: &Program<E>) -> Self {
+        let mut counts: HashMap<String, usize> = HashMap::default();
+        for (_, name) in program.labels.values() {
+            *counts.entry(label_token(name)).or_default() += 1;
+        }
+        let names = program
+            .labels
+            .iter()
+            .map(|(id, (index, name))| {
+                let token = label_token(name);
+                let name = match counts.get(&token) {
+                    Some(1) => token,
+                    _ => format!("{token}#{index}"),
+                };
+                (*id, name)
+            })
+            .collect();
+        Self { names }
+    }
+
+    /*
+     * @desc Name of a label, a label missing from the program is shown by its id
+     */
+    pub fn name(&self, id: &Ulid) -> String {
+        self.names
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| format!("?{id}"))
+    }
+}
+
+/*
+ * @desc A label name is a single token of the listing
+ */
+fn label_token(name: &str) -> String {
+    let token: String = name
+        .chars()
+        .map(|c| match c {
+            c if c.is_whitespace() => '_',
+            ';' | '#' | '?' => '_',
+            c => c,
+        })
+        .collect();
+    if token.is_empty() {
+        "_label".to_string()
+    } else {
+        token
+    }
+}
+
+pub fn weight_name(weight: Weight) -> String {
+    match weight {
+        Weight::ZERO => "zero".to_string(),
+        Weight::MAX => "max".to_string(),
+        Weight::END => "end".to_string(),
+        Weight::CUSTOM(weight) => format!("custom({weight})"),
+        Weight::LOW => "low".to_string(),
+        Weight::MEDIUM => "medium".to_string(),
+        Weight::HIGH => "high".to_string(),
+        Weight::EXTREME => "extreme".to_string(),
+    }
+}
+
+pub fn hex(data: &[u8]) -> String {
+    format!("0x{}", HexSlice(data))
+}
+
+pub fn option<T: ToString>(value: Option<T>) -> String {
+    value.map_or("_".to_string(), |value| value.to_string())
+}
+
+/*
+ * @desc Casm of an instruction, the extern functions are shown as given by the engine
+ */
+pub fn instruction_listing<E: Engine>(instruction: &Instruction<E>, labels: &LabelNames) -> String {
+    match instruction {
+        Instruction::Extern(function) => format!("    extern {:?}", function),
+        Instruction::Asm(super::asm::Asm::Label(label)) => label.listing(labels),
+        Instruction::Asm(asm) => format!("    {}", asm.listing(labels)),
+    }
+}
+
+/*
+ * @desc Listing of a program without running it
+ * Each line holds the casm of an instruction followed by a comment with its index and its weight,
+ * the vtables of the program come last
+ */
+pub fn listing<E: Engine>(program: &Program<E>) -> String {
+    let labels = LabelNames::of(program);
+    let mut text = String::new();
+    for (index, instruction) in program.instructions.iter().enumerate() {
+        let casm = instruction_listing(instruction, &labels);
+        let weight = weight_name(instruction.weight());
+        text.push_str(&format!("{casm:<COMMENT_COLUMN$} ; {index:04} {weight}\n"));
+    }
+    for (index, interface_id, type_id, functions) in program.vtable.entries() {
+        let functions = match functions {
+            Some(functions) => functions
+                .iter()
+                .map(|label| labels.name(label))
+                .collect::<Vec<String>>()
+                .join(" "),
+            None => "_".to_string(),
+        };
+        text.push_str(&format!(
+            ".vtable {index} {interface_id} {type_id} {functions}\n"
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::statements::parse_statements,
+        semantic::{scope::scope::ScopeManager, Resolve},
+        vm::{
+            allocator::MemoryAddress,
+            asm::{
+                branch::{BranchIf, Goto},
+                data::Data,
+                mem::Mem,
+                Asm,
+            },
+            external::test::{ExternFuncTest, ExternFuncTestEngine, NoopEngine},
+            CodeGenerationContext, GenerateCode,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn valid_listing() {
+        let mut program = Program::<NoopEngine>::default();
+        let end = Ulid::new();
+        program.push(Asm::Data(Data::Serialized {
+            data: vec![1, 0, 0xff].into(),
+        }));
+        program.push(Asm::If(BranchIf { else_label: end }));
+        program.push(Asm::Mem(Mem::Store {
+            size: 8,
+            address: MemoryAddress::Global { offset: 16 },
+        }));
+        program.push_label_by_id(end, "end_if".to_string());
+        program.push(Asm::Pop(8));
+
+        assert_eq!(
+            listing(&program),
+            "    dmp 0x0100ff                         ; 0000 low\n\
+             \x20   else_goto end_if                     ; 0001 zero\n\
+             \x20   store GL[16] 8                       ; 0002 zero\n\
+             end_if :                                 ; 0003 zero\n\
+             \x20   pop 8                                ; 0004 zero\n"
+        );
+    }
+
+    #[test]
+    fn valid_listing_of_shared_label_names() {
+        let mut program = Program::<NoopEngine>::default();
+        let first = program.push_label("loop".to_string());
+        let second = program.push_label("loop".to_string());
+        program.push(Asm::Goto(Goto { label: Some(first) }));
+        program.push(Asm::Goto(Goto {
+            label: Some(Ulid::new()),
+        }));
+
+        let labels = LabelNames::of(&program);
+        assert_eq!(labels.name(&first), "loop#0");
+        assert_eq!(labels.name(&second), "loop#1");
+        let text = listing(&program);
+        assert!(text.contains("goto loop#0"));
+        assert!(text.contains("goto ?"));
+    }
+
+    #[test]
+    fn valid_listing_of_extern_function() {
+        let mut program = Program::<ExternFuncTestEngine>::default();
+        program.push_extern(ExternFuncTest::TEST_ADDER);
+        assert!(listing(&program).starts_with("    extern TEST_ADDER"));
+    }
+
+    #[test]
+    fn valid_listing_of_compiled_program() {
+        let mut statements = parse_statements::<
+            crate::vm::external::test::DefaultProcessID,
+            crate::vm::external::test::DefaultThreadID,
+        >(
+            r##"
+        fn add(x:u64, y:u64) -> u64 {
+            return x + y;
+        }
+        let res = add(1, 2);
+        "##
+            .into(),
+            0,
+        )
+        .expect("Parsing should have succeeded");
+        let mut scope_manager = ScopeManager::default();
+        let mut program = Program::<NoopEngine>::default();
+        for statement in statements.iter_mut() {
+            statement
+                .resolve::<NoopEngine>(&mut scope_manager, None, &None, &mut ())
+                .expect("Resolution should have succeeded");
+        }
+        for statement in statements {
+            statement
+                .gencode::<NoopEngine>(
+                    &mut scope_manager,
+                    None,
+                    &mut program,
+                    &CodeGenerationContext::default(),
+                )
+                .expect("Code generation should have succeeded");
+        }
+
+        assert_eq!(
+            listing(&program),
+            "    goto store_fn_add                    ; 0000 zero\n\
+             fn_add :                                 ; 0001 zero\n\
+             \x20   salloc 0                             ; 0002 medium\n\
+             \x20   load FP[8] 8                         ; 0003 low\n\
+             \x20   load FP[16] 8                        ; 0004 low\n\
+             \x20   add_u64_u64                          ; 0005 low\n\
+             \x20   goto epilog_Function                 ; 0006 zero\n\
+             epilog_Function :                        ; 0007 zero\n\
+             \x20   return 8                             ; 0008 medium\n\
+             store_fn_add :                           ; 0009 zero\n\
+             \x20   dmp_label fn_add                     ; 0010 low\n\
+             \x20   store GL[8] 8                        ; 0011 zero\n\
+             \x20   dmp 0x0100000000000000               ; 0012 low\n\
+             \x20   dmp 0x0200000000000000               ; 0013 low\n\
+             \x20   load GL[8] 8                         ; 0014 low\n\
+             \x20   call_fn 16                           ; 0015 zero\n\
+             \x20   store GL[16] 8                       ; 0016 zero\n"
+        );
+    }
+}
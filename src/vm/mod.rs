@@ -2,14 +2,18 @@ use thiserror::Error;
 use ulid::Ulid;
 
 pub mod allocator;
+pub mod assembler;
+pub mod bytecode;
 pub mod asm;
 pub mod core;
 pub mod error_handler;
 pub mod external;
+pub mod listing;
 pub mod program;
 pub mod runtime;
 pub mod scheduler;
 pub mod signal;
+pub mod source_map;
 pub mod stdio;
 
 #[derive(Debug, Clone)]
@@ -70,6 +74,13 @@ pub trait AsmName<E: crate::vm::external::Engine> {
     fn name(&self, stdio: &mut stdio::StdIO, program: &program::Program<E>, engine: &mut E, pid : E::PID);
 }
 
+/*
+ * @desc Casm of an instruction as it appears in the static listing of a program
+ */
+pub trait AsmListing {
+    fn listing(&self, labels: &listing::LabelNames) -> String;
+}
+
 pub trait AsmWeight {
     fn weight(&self) -> Weight {
         Weight::LOW
@@ -1,13 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ulid::Ulid;
 
 use super::{
+    allocator::vtable::VTable,
     asm::{branch::Label, Asm},
     external::Engine,
     scheduler::Executable,
+    source_map::{ErrorLocation, SourceMap, StackTrace},
     AsmName, AsmWeight,
 };
+use crate::ast::utils::io::SourceSpan;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransactionState {
@@ -80,6 +83,8 @@ impl<E: crate::vm::external::Engine> Executable<E> for Instruction<E> {
 pub struct Program<E: Engine> {
     pub instructions: Vec<Instruction<E>>,
     pub labels: HashMap<Ulid, (usize, Box<str>)>,
+    pub vtable: VTable,
+    pub source_map: SourceMap,
 }
 
 impl<E: Engine> Default for Program<E> {
@@ -87,6 +92,8 @@ impl<E: Engine> Default for Program<E> {
         Self {
             instructions: Default::default(),
             labels: Default::default(),
+            vtable: Default::default(),
+            source_map: Default::default(),
         }
     }
 }
@@ -114,6 +121,20 @@ impl<E: Engine> Program<E> {
             .push(Instruction::Asm(Asm::Label(Label { id, name: label })));
         id
     }
+    /*
+     * @desc Rename a pushed label, in the table of labels and in its instruction
+     */
+    pub fn rename_label(&mut self, id: &Ulid, label: String) {
+        let Some((index, name)) = self.labels.get_mut(id) else {
+            return;
+        };
+        *name = label.clone().into();
+        if let Some(Instruction::Asm(Asm::Label(Label { name, .. }))) =
+            self.instructions.get_mut(*index)
+        {
+            *name = label;
+        }
+    }
     pub fn get_cursor_from_label(&self, label: &Ulid) -> Option<usize> {
         self.labels.get(label).map(|(i, _)| i).cloned()
     }
@@ -137,4 +158,79 @@ impl<E: Engine> Program<E> {
     pub fn len(&self) -> usize {
         self.instructions.len()
     }
+
+    /*
+     * @desc Length and labels of the program, the state a rejected compilation rolls back to
+     */
+    pub fn checkpoint(&self) -> (usize, HashSet<Ulid>) {
+        (self.len(), self.labels.keys().cloned().collect())
+    }
+
+    /*
+     * @desc Drop the instructions pushed since a checkpoint, with the labels and locations they added
+     */
+    pub fn rollback(&mut self, (len, labels): (usize, HashSet<Ulid>)) {
+        self.instructions.truncate(len);
+        self.labels.retain(|id, _| labels.contains(id));
+        self.source_map.truncate(len);
+    }
+
+    /*
+     * @desc Generate the code of a statement at its location
+     * The instructions it pushes are mapped to it in the source map
+     */
+    pub fn at<T>(&mut self, span: SourceSpan, gencode: impl FnOnce(&mut Self) -> T) -> T {
+        let enclosing = self.source_map.enter_statement(self.len(), span);
+        let res = gencode(self);
+        self.source_map.exit_statement(self.len(), enclosing);
+        res
+    }
+
+    /*
+     * @desc Generate the body of the function starting at a label
+     */
+    pub fn in_function<T>(&mut self, label: Ulid, gencode: impl FnOnce(&mut Self) -> T) -> T {
+        let enclosing = self.source_map.enter_function(self.len(), label);
+        let res = gencode(self);
+        self.source_map.exit_function(self.len(), enclosing);
+        res
+    }
+
+    /*
+     * @desc Location of an instruction in the source with the name of its function
+     */
+    pub fn error_location(&self, cursor: usize) -> ErrorLocation {
+        let location = self.source_map.locate(cursor);
+        ErrorLocation {
+            cursor,
+            line: location.map_or(0, |location| location.line),
+            column: location.map_or(0, |location| location.column),
+            function: location
+                .and_then(|location| location.function)
+                .and_then(|label| self.get_label_name(&label))
+                .map(|name| {
+                    if let Some(name) = name.strip_prefix("fn_") {
+                        format!("fn {name}")
+                    } else if let Some(name) = name.strip_prefix("event_") {
+                        format!("event {name}")
+                    } else {
+                        name.to_string()
+                    }
+                }),
+        }
+    }
+
+    /*
+     * @desc Calls of the opened frames of a stack
+     * A frame is located at the instruction before its return pointer :
+     * the call opening it, or the last instruction run before an event callback
+     */
+    pub fn stack_trace(&self, return_pointers: &[usize]) -> StackTrace {
+        StackTrace {
+            frames: return_pointers
+                .iter()
+                .map(|return_pointer| self.error_location(return_pointer.saturating_sub(1)))
+                .collect(),
+        }
+    }
 }
@@ -8,11 +8,13 @@ use thiserror::Error;
 use super::{
     allocator::{
         heap::HeapError,
-        stack::{Stack, StackError},
+        stack::{Stack, StackError, StackState},
+        vtable::VTableError,
         MemoryAddress,
     },
     external::{ExternProcessIdentifier, ExternThreadIdentifier},
     program::Program,
+    source_map::{ErrorLocation, StackTrace},
     scheduler::{
         Event, EventCallback, EventConf, EventExclusivity, EventKind, EventQueue, EventState,
         Scheduler, SchedulingPolicy,
@@ -24,6 +26,7 @@ use crate::{
     ast::modules::Module,
     semantic::{scope::scope::ScopeManager, Resolve},
     vm::signal::SignalHandler,
+    CompilationError,
 };
 
 #[derive(Debug, Clone, Error)]
@@ -32,6 +35,8 @@ pub enum RuntimeError {
     StackError(#[from] StackError),
     #[error("HeapError : {0}")]
     HeapError(#[from] HeapError),
+    #[error("VTableError : {0}")]
+    VTableError(#[from] VTableError),
 
     #[error("MEMORY VIOLATION")]
     MemoryViolation,
@@ -60,8 +65,87 @@ pub enum RuntimeError {
     #[error("Context Error")]
     ContextError,
 
+    #[error("Raised : {0}")]
+    Raised(String),
+
     #[error("Default")]
     Default,
+
+    #[error("{error} {location}{trace}")]
+    Located {
+        error: Box<RuntimeError>,
+        location: ErrorLocation,
+        trace: StackTrace,
+    },
+}
+
+/*
+ * @desc Variants of the language level RuntimeError enum
+ * A caught error is bound as the index of its variant
+ * Raised covers the raise statements and the error values (Err) caught by a try expression
+ */
+pub const RUNTIME_ERROR_VARIANTS: [&str; 16] = [
+    "StackError",
+    "HeapError",
+    "VTableError",
+    "MemoryViolation",
+    "Deserialization",
+    "UnsupportedOperation",
+    "MathError",
+    "CodeSegmentation",
+    "IndexOutOfBound",
+    "SignalError",
+    "AssertError",
+    "ConcurrencyError",
+    "NotEnoughEnergy",
+    "ContextError",
+    "Default",
+    "Raised",
+];
+pub const RAISED_ERROR_CODE: u64 = 15;
+
+impl RuntimeError {
+    pub fn code(&self) -> u64 {
+        match self {
+            RuntimeError::StackError(_) => 0,
+            RuntimeError::HeapError(_) => 1,
+            RuntimeError::VTableError(_) => 2,
+            RuntimeError::MemoryViolation => 3,
+            RuntimeError::Deserialization => 4,
+            RuntimeError::UnsupportedOperation => 5,
+            RuntimeError::MathError => 6,
+            RuntimeError::CodeSegmentation => 7,
+            RuntimeError::IndexOutOfBound => 8,
+            RuntimeError::SignalError => 9,
+            RuntimeError::AssertError => 10,
+            RuntimeError::ConcurrencyError => 11,
+            RuntimeError::NotEnoughEnergy => 12,
+            RuntimeError::ContextError => 13,
+            RuntimeError::Default => 14,
+            RuntimeError::Raised(_) => RAISED_ERROR_CODE,
+            RuntimeError::Located { error, .. } => error.code(),
+        }
+    }
+
+    /*
+     * @desc Location of the instruction raising an uncaught error
+     */
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            RuntimeError::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /*
+     * @desc Calls of the functions opened when an uncaught error is raised
+     */
+    pub fn trace(&self) -> Option<&StackTrace> {
+        match self {
+            RuntimeError::Located { trace, .. } => Some(trace),
+            _ => None,
+        }
+    }
 }
 
 pub struct ThreadContext<E: crate::vm::external::Engine> {
@@ -183,27 +267,8 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Runtime<E, P> {
     pub fn spawn(&mut self, pid: E::PID, engine: &mut E) -> Result<E::TID, RuntimeError> {
         let tid = engine.spawn(&pid)?;
 
-        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
-        let mut program = crate::vm::program::Program::default();
-
-        // re-import all modules
-        if let Some(modules) = self.modules.get_mut(&pid) {
-            for module in modules.iter_mut() {
-                module
-                    .resolve::<E>(&mut scope_manager, None, &(), &mut ())
-                    .map_err(|err| RuntimeError::Default)?;
-                module
-                    .gencode::<E>(
-                        &mut scope_manager,
-                        None,
-                        &mut program,
-                        &crate::vm::CodeGenerationContext::default(),
-                    )
-                    .map_err(|err| RuntimeError::Default)?;
-                scope_manager.modules.push(module.clone());
-            }
-        }
-
+        let scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let program = crate::vm::program::Program::default();
         let scheduler = Scheduler::default();
         let stack = Stack::default();
 
@@ -217,33 +282,52 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Runtime<E, P> {
         );
         self.threads
             .insert(tid.clone(), Thread { scheduler, stack });
+
+        // re-import all modules
+        if self.link_modules(&tid).is_err() {
+            self.close(tid);
+            return Err(RuntimeError::Default);
+        }
+        Ok(tid)
+    }
+
+    /*
+     * @desc Spawn a thread running a program decoded from its bytecode
+     * The modules of the process are not generated again, the program is expected to hold them
+     */
+    pub fn spawn_with_program(
+        &mut self,
+        pid: E::PID,
+        program: crate::vm::program::Program<E>,
+        engine: &mut E,
+    ) -> Result<E::TID, RuntimeError> {
+        let tid = engine.spawn(&pid)?;
+
+        self.contexts.insert(
+            tid.clone(),
+            ThreadContext {
+                scope_manager: crate::semantic::scope::scope::ScopeManager::default(),
+                program,
+                state: ThreadState::default(),
+            },
+        );
+        self.threads.insert(
+            tid.clone(),
+            Thread {
+                scheduler: Scheduler::default(),
+                stack: Stack::default(),
+            },
+        );
         Ok(tid)
     }
 
     pub fn spawn_with_id(&mut self, tid: E::TID) -> Result<(), RuntimeError> {
-        let mut scope_manager = crate::semantic::scope::scope::ScopeManager::default();
-        let mut program = crate::vm::program::Program::default();
+        let scope_manager = crate::semantic::scope::scope::ScopeManager::default();
+        let program = crate::vm::program::Program::default();
         let scheduler = Scheduler::default();
         let stack = Stack::default();
         let state = ThreadState::default();
 
-        // re-import all modules
-        if let Some(modules) = self.modules.get_mut(&tid.pid()) {
-            for module in modules.iter_mut() {
-                module
-                    .resolve::<E>(&mut scope_manager, None, &(), &mut ())
-                    .map_err(|err| RuntimeError::Default)?;
-                module
-                    .gencode::<E>(
-                        &mut scope_manager,
-                        None,
-                        &mut program,
-                        &crate::vm::CodeGenerationContext::default(),
-                    )
-                    .map_err(|err| RuntimeError::Default)?;
-                scope_manager.modules.push(module.clone());
-            }
-        }
         self.contexts.insert(
             tid.clone(),
             ThreadContext {
@@ -254,12 +338,97 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Runtime<E, P> {
         );
         self.threads
             .insert(tid.clone(), Thread { scheduler, stack });
+
+        // re-import all modules
+        if self.link_modules(&tid).is_err() {
+            self.close(tid);
+            return Err(RuntimeError::Default);
+        }
+        Ok(())
+    }
+
+    /*
+     * @desc Resolve and generate the modules of the process that the thread does not know yet
+     * The modules of a process are only appended so the thread knows a prefix of them
+     */
+    pub fn link_modules(&mut self, tid: &E::TID) -> Result<(), CompilationError<E::PID, E::TID>> {
+        let linked = self.resolve_modules(tid)?;
+        self.gencode_modules(tid, linked)
+    }
+
+    /*
+     * @desc Resolve the modules of the process that the thread does not know yet
+     * Returns the number of modules the thread knew before, the code of the others is generated by gencode_modules
+     */
+    pub fn resolve_modules(
+        &mut self,
+        tid: &E::TID,
+    ) -> Result<usize, CompilationError<E::PID, E::TID>> {
+        let Some(ThreadContext { scope_manager, .. }) = self.contexts.get_mut(tid) else {
+            return Err(CompilationError::InvalidTID(tid.clone()));
+        };
+        let linked = scope_manager.modules.len();
+        let Some(modules) = self.modules.get(&tid.pid()) else {
+            return Ok(linked);
+        };
+        for module in modules.iter().skip(linked) {
+            let mut module = module.clone();
+            module
+                .resolve::<E>(scope_manager, None, &(), &mut ())
+                .map_err(|err| {
+                    let line = err.span().map_or(0, |span| span.line);
+                    let err = CompilationError::semantic("", 0, line, err);
+                    match module.path() {
+                        Some(path) => {
+                            CompilationError::ModuleError(0, path.to_string(), Box::new(err))
+                        }
+                        None => err,
+                    }
+                })?;
+            scope_manager.modules.push(module);
+        }
+        Ok(linked)
+    }
+
+    /*
+     * @desc Generate the code of the resolved modules of the thread, starting from the given one
+     */
+    pub fn gencode_modules(
+        &mut self,
+        tid: &E::TID,
+        from: usize,
+    ) -> Result<(), CompilationError<E::PID, E::TID>> {
+        let Some(ThreadContext {
+            scope_manager,
+            program,
+            ..
+        }) = self.contexts.get_mut(tid)
+        else {
+            return Err(CompilationError::InvalidTID(tid.clone()));
+        };
+        // the lines of a module are the lines of its own source
+        program.source_map.set_line_offset(0);
+        let modules = scope_manager.modules.get(from..).unwrap_or_default().to_vec();
+        for module in modules {
+            module
+                .gencode::<E>(
+                    scope_manager,
+                    None,
+                    program,
+                    &crate::vm::CodeGenerationContext::default(),
+                )
+                .map_err(|err| CompilationError::CodeGen(0, err))?;
+        }
         Ok(())
     }
 
     pub fn close(&mut self, tid: E::TID) {
         self.contexts.remove(&tid);
         self.threads.remove(&tid);
+        // the events of a closed thread can no longer run
+        self.event_queue.current_events.remove(&tid);
+        self.event_queue.running_events.remove(&tid);
+        self.event_queue.events.remove(&tid);
     }
 
     pub fn put_to_sleep_for(&mut self, tid: E::TID, time: usize) -> Result<(), RuntimeError> {
@@ -388,6 +557,44 @@ impl<E: crate::vm::external::Engine, P: SchedulingPolicy> Runtime<E, P> {
         ))
     }
 
+    /*
+     * @desc The runtime is idle when no MAF can make a thread progress anymore
+     * A waiting thread only progresses when another thread wakes it
+     */
+    pub fn is_idle(&self) -> bool {
+        self.threads.iter().all(|(tid, Thread { scheduler, .. })| {
+            let Some(ThreadContext { program, state, .. }) = self.contexts.get(tid) else {
+                return true;
+            };
+            match state {
+                ThreadState::IDLE | ThreadState::RUNNING => {
+                    scheduler.cursor.get() >= program.len()
+                        && !self.event_queue.current_events.contains_key(tid)
+                }
+                ThreadState::WAITING => true,
+                ThreadState::SLEEPING(_)
+                | ThreadState::JOINING { .. }
+                | ThreadState::WAITING_STDIN => false,
+            }
+        })
+    }
+
+    /*
+     * @desc Drop the instructions a thread has left after an uncaught error
+     * The thread keeps its globals and runs the instructions compiled afterwards
+     */
+    pub fn abort(&mut self, tid: &E::TID) -> Result<(), RuntimeError> {
+        let (Some(Thread { scheduler, stack }), Some(ThreadContext { program, state, .. })) =
+            (self.threads.get_mut(tid), self.contexts.get_mut(tid))
+        else {
+            return Err(RuntimeError::Default);
+        };
+        stack.restore(StackState::default());
+        scheduler.jump(program.len());
+        scheduler.cursor.update(program, state);
+        Ok(())
+    }
+
     pub fn run(
         &mut self,
         heap: &mut crate::vm::allocator::heap::Heap,
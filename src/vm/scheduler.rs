@@ -10,7 +10,7 @@ use ulid::Ulid;
 use crate::vm::asm::operation::{GetNumFrom, OpPrimitive};
 
 use super::{
-    allocator::MemoryAddress,
+    allocator::{stack::StackState, MemoryAddress},
     error_handler::ErrorHandler,
     external::{
         ExternEventManager, ExternExecutionContext, ExternProcessIdentifier, ExternThreadIdentifier,
@@ -174,6 +174,102 @@ pub struct EventConf {
     pub kind: EventKind,
     pub exclu: EventExclusivity,
 }
+
+/// Manager of an event : either provided by the host through an extern function
+/// or compiled from an `event` definition of the language.
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum EventManager<EM> {
+    Extern(EM),
+    Native,
+}
+
+impl<
+        EC: ExternExecutionContext,
+        PID: ExternProcessIdentifier,
+        TID: ExternThreadIdentifier<PID>,
+        EM: super::external::ExternEventManager<EC, PID, TID>,
+    > super::external::ExternEventManager<EC, PID, TID> for EventManager<EM>
+{
+    type E = EM::E;
+
+    fn event_setup(
+        &self,
+        callback_address: MemoryAddress,
+        stack: &mut crate::vm::allocator::stack::Stack,
+        heap: &mut crate::vm::allocator::heap::Heap,
+        stdio: &mut crate::vm::stdio::StdIO,
+        engine: &mut Self::E,
+        context: &ExecutionContext<EC, PID, TID>,
+    ) -> Result<super::external::EventSetupResult, RuntimeError> {
+        match self {
+            EventManager::Extern(manager) => {
+                manager.event_setup(callback_address, stack, heap, stdio, engine, context)
+            }
+            EventManager::Native => {
+                // the callback is a closure without parameters : its first field is the function offset
+                let function_offset =
+                    OpPrimitive::get_num_from::<u64>(callback_address, stack, heap)? as usize;
+                let callback: u64 = (callback_address).into(stack);
+                Ok(super::external::EventSetupResult {
+                    parameters_size: 0,
+                    function_offset,
+                    callback,
+                })
+            }
+        }
+    }
+
+    fn event_conclusion(
+        &self,
+        stack: &mut crate::vm::allocator::stack::Stack,
+        heap: &mut crate::vm::allocator::heap::Heap,
+        stdio: &mut crate::vm::stdio::StdIO,
+        engine: &mut Self::E,
+        context: &ExecutionContext<EC, PID, TID>,
+    ) -> Result<(), RuntimeError> {
+        match self {
+            EventManager::Extern(manager) => {
+                manager.event_conclusion(stack, heap, stdio, engine, context)
+            }
+            EventManager::Native => Ok(()),
+        }
+    }
+
+    fn event_cleanup(
+        &self,
+        callback_address: MemoryAddress,
+        event_state: EventState,
+        stack: &mut crate::vm::allocator::stack::Stack,
+        heap: &mut crate::vm::allocator::heap::Heap,
+        stdio: &mut crate::vm::stdio::StdIO,
+        engine: &mut Self::E,
+        context: &ExecutionContext<EC, PID, TID>,
+    ) -> Result<(), RuntimeError> {
+        match self {
+            EventManager::Extern(manager) => manager.event_cleanup(
+                callback_address,
+                event_state,
+                stack,
+                heap,
+                stdio,
+                engine,
+                context,
+            ),
+            EventManager::Native => {
+                let _ = heap.free(callback_address)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn event_trigger(&self, signal: u64, trigger: u64) -> bool {
+        match self {
+            EventManager::Extern(manager) => manager.event_trigger(signal, trigger),
+            EventManager::Native => signal & trigger != 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub struct EventCallback<
     EC: ExternExecutionContext,
@@ -182,7 +278,7 @@ pub struct EventCallback<
     EM: super::external::ExternEventManager<EC, PID, TID>,
 > {
     pub callback: MemoryAddress,
-    pub manager: EM,
+    pub manager: EventManager<EM>,
     pub _phantom: PhantomData<(EC, PID, TID)>,
 }
 
@@ -317,14 +413,18 @@ impl<P: SchedulingPolicy> Scheduler<P> {
             self.return_signal = true;
         }
     }
-    pub fn push_catch(&mut self, label: &Ulid) {
-        self.error_handler.push_catch(label);
+    pub fn push_catch(&mut self, label: &Ulid, state: StackState) {
+        self.error_handler.push_catch(label, state);
     }
 
     pub fn pop_catch(&mut self) {
         self.error_handler.pop_catch();
     }
 
+    pub fn take_caught(&mut self) -> Option<String> {
+        self.error_handler.take_caught()
+    }
+
     pub fn prepare(&mut self) {}
 
     fn select<'a, E: crate::vm::external::Engine>(
@@ -340,6 +440,16 @@ impl<P: SchedulingPolicy> Scheduler<P> {
         Ok(Some(instruction))
     }
 
+    /*
+     * @desc Resume the paused program once the running event is over
+     */
+    fn resume_program(&mut self) {
+        self.in_event = false;
+        self.cursor = self.saved_cursor.unwrap_or_default();
+        self.saved_cursor = None;
+        self.error_handler.leave_event();
+    }
+
     pub fn run<E: crate::vm::external::Engine>(
         &mut self,
         tid: E::TID,
@@ -367,6 +477,7 @@ impl<P: SchedulingPolicy> Scheduler<P> {
                 stdio.push_asm_info(engine, E::PID::default(), "START EVENT");
                 self.in_event = true;
                 let _ = self.saved_cursor.insert(self.cursor.clone());
+                self.error_handler.enter_event();
 
                 let setup_res =
                     manager.event_setup(*callback, stack, heap, stdio, engine, context)?;
@@ -397,6 +508,7 @@ impl<P: SchedulingPolicy> Scheduler<P> {
                 .defer(acceptance_weight, energy, pid.clone(), engine)?;
 
             instruction.name(stdio, program, engine, pid);
+            let cursor = self.cursor.get();
             self.return_signal = false;
             self.sleeping_signal = false;
             match instruction.execute(
@@ -414,12 +526,58 @@ impl<P: SchedulingPolicy> Scheduler<P> {
                 },
             ) {
                 Ok(_) => {}
-                Err(error) => self.jump(self.error_handler.catch(error, program)?),
+                Err(error) => match self.error_handler.catch(error, program, stack) {
+                    Ok(cursor) => self.jump(cursor),
+                    Err(RuntimeError::Raised(message)) => {
+                        // uncaught raised error : the thread is closed
+                        let report = format!(
+                            "uncaught raise : {message} {}{}",
+                            program.error_location(cursor),
+                            program.stack_trace(&stack.return_pointers())
+                        );
+                        stdio.print_stderr(engine, pid.clone(), &report);
+                        if self.in_event {
+                            // the event has no return value to conclude with
+                            self.resume_program();
+                            if let Some(Event {
+                                state,
+                                callback:
+                                    EventCallback {
+                                        callback, manager, ..
+                                    },
+                                ..
+                            }) = current_event
+                            {
+                                *state = EventState::Completed;
+                                let _ = manager.event_cleanup(
+                                    *callback, *state, stack, heap, stdio, engine, context,
+                                )?;
+                            }
+                            stdio.push_asm_info(engine, E::PID::default(), "END EVENT");
+                        }
+                        // drop the frames opened by the thread
+                        stack.restore(StackState::default());
+                        self.jump(program.instructions.len());
+                        signal_handler.notify(
+                            crate::vm::signal::Signal::Exit,
+                            stack,
+                            engine,
+                            tid,
+                            exit_callback::<E>,
+                        )?;
+                        return Ok(ControlFlow::Break(()));
+                    }
+                    Err(error) => {
+                        return Err(RuntimeError::Located {
+                            error: Box::new(error),
+                            location: program.error_location(cursor),
+                            trace: program.stack_trace(&stack.return_pointers()),
+                        })
+                    }
+                },
             }
             if self.return_signal {
-                self.in_event = false;
-                self.cursor = self.saved_cursor.unwrap_or_default();
-                self.saved_cursor = None;
+                self.resume_program();
 
                 if let Some(Event {
                     state,
@@ -460,6 +618,16 @@ impl<P: SchedulingPolicy> Scheduler<P> {
     }
 }
 
+fn exit_callback<E: crate::vm::external::Engine>(
+    response: crate::vm::signal::SignalResult<E>,
+    _stack: &mut crate::vm::allocator::stack::Stack,
+) -> Result<(), RuntimeError> {
+    match response {
+        crate::vm::signal::SignalResult::Error => Err(RuntimeError::SignalError),
+        crate::vm::signal::SignalResult::Ok(_) => Ok(()),
+    }
+}
+
 pub struct ToCompletion;
 
 impl Default for ToCompletion {
@@ -612,3 +780,85 @@ impl SchedulingPolicy for QueuePolicy {
         self.balance = Self::MAX_BALANCE;
     }
 }
+
+/*
+ * @desc Policy charging the instructions to the energy of the process given by the engine
+ * A thread stops for the MAF when its balance is spent or its process cannot pay for the next instruction
+ */
+pub struct EnergyPolicy {
+    balance: usize,
+}
+
+impl Default for EnergyPolicy {
+    fn default() -> Self {
+        Self {
+            balance: QueuePolicy::MAX_BALANCE,
+        }
+    }
+}
+
+impl SchedulingPolicy for EnergyPolicy {
+    fn weight_to_energy(&self, weight: Weight) -> usize {
+        match weight {
+            // ending the MAF does not cost more than the cheapest instruction
+            Weight::END => 1,
+            weight => self.weight_of(weight),
+        }
+    }
+    fn weight_of(&self, weight: Weight) -> usize {
+        match weight {
+            Weight::ZERO => 0,
+            Weight::MAX => QueuePolicy::MAX_BALANCE,
+            Weight::CUSTOM(w) => w,
+            Weight::LOW => 1,
+            Weight::MEDIUM => 2,
+            Weight::HIGH => 4,
+            Weight::EXTREME => 8,
+            Weight::END => QueuePolicy::MAX_BALANCE,
+        }
+    }
+    fn accept<E: crate::vm::external::Engine>(
+        &self,
+        weight: usize,
+        energy: usize,
+        pid: E::PID,
+        engine: &E,
+    ) -> bool {
+        self.balance > 0 && engine.get_energy(pid) >= energy
+    }
+
+    fn defer<E: crate::vm::external::Engine>(
+        &mut self,
+        weight: usize,
+        energy: usize,
+        pid: E::PID,
+        engine: &mut E,
+    ) -> Result<(), RuntimeError> {
+        self.balance = self.balance.saturating_sub(weight);
+        engine.consume_energy(energy, pid)
+    }
+
+    fn init_watchdog(&mut self) {}
+
+    fn watchdog(&mut self) -> ControlFlow<(), ()> {
+        ControlFlow::Continue(())
+    }
+
+    fn schedule<'a, E: crate::vm::external::Engine>(
+        input: impl Iterator<Item = (&'a E::TID, &'a mut super::runtime::Thread<Self>)>,
+    ) -> impl Iterator<Item = (&'a E::TID, &'a mut super::runtime::Thread<Self>)>
+    where
+        Self: 'a,
+        <E as super::external::ExternThreadHandler>::TID: 'a,
+    {
+        input
+    }
+
+    fn init_maf<E: crate::vm::external::Engine>(
+        &mut self,
+        tid: &E::TID,
+        state: &super::runtime::ThreadState<E::PID, E::TID>,
+    ) {
+        self.balance = QueuePolicy::MAX_BALANCE;
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt;
+
+use ulid::Ulid;
+
+use crate::ast::utils::io::SourceSpan;
+
+/*
+ * @desc Location in the source of the statement generating an instruction
+ * The function is the label of the function, closure or lambda holding the statement
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+    pub function: Option<Ulid>,
+}
+
+/*
+ * @desc Map from the index of an instruction to the location of the statement generating it
+ * Each entry holds the location of the instructions from its index up to the next entry
+ */
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    entries: Vec<(usize, Option<SourceLocation>)>,
+    location: Option<SourceLocation>, // location of the statement being generated
+    function: Option<Ulid>,
+    line_offset: usize,
+}
+
+impl SourceMap {
+    /*
+     * @desc Lines of the source generated from now on start after line_offset lines
+     */
+    pub fn set_line_offset(&mut self, line_offset: usize) {
+        self.line_offset = line_offset;
+    }
+
+    pub fn locate(&self, cursor: usize) -> Option<SourceLocation> {
+        let next = self.entries.partition_point(|(index, _)| *index <= cursor);
+        next.checked_sub(1).and_then(|entry| self.entries[entry].1)
+    }
+
+    pub fn entries(&self) -> &[(usize, Option<SourceLocation>)] {
+        &self.entries
+    }
+
+    /*
+     * @desc Add an entry of a decoded program, the entries are pushed by index
+     */
+    pub fn push(&mut self, index: usize, location: Option<SourceLocation>) {
+        self.entries.push((index, location));
+    }
+
+    /*
+     * @desc Drop the locations of the instructions from the index len
+     */
+    pub fn truncate(&mut self, len: usize) {
+        let kept = self.entries.partition_point(|(index, _)| *index < len);
+        self.entries.truncate(kept);
+    }
+
+    /*
+     * @desc Map the instructions pushed from index to the statement at span
+     * An unknown span keeps the location of the enclosing statement, which is returned
+     */
+    pub fn enter_statement(&mut self, index: usize, span: SourceSpan) -> Option<SourceLocation> {
+        let enclosing = self.location;
+        if span.is_known() {
+            let location = SourceLocation {
+                line: span.line + self.line_offset,
+                column: span.column,
+                function: self.function,
+            };
+            self.enter(index, Some(location));
+        }
+        enclosing
+    }
+
+    pub fn exit_statement(&mut self, index: usize, enclosing: Option<SourceLocation>) {
+        self.enter(index, enclosing);
+    }
+
+    /*
+     * @desc Map the instructions pushed from index to the function at label
+     * The enclosing function is returned
+     */
+    pub fn enter_function(&mut self, index: usize, label: Ulid) -> Option<Ulid> {
+        let enclosing = self.function;
+        self.set_function(index, Some(label));
+        enclosing
+    }
+
+    pub fn exit_function(&mut self, index: usize, enclosing: Option<Ulid>) {
+        self.set_function(index, enclosing);
+    }
+
+    fn set_function(&mut self, index: usize, function: Option<Ulid>) {
+        self.function = function;
+        if let Some(location) = self.location {
+            self.enter(
+                index,
+                Some(SourceLocation {
+                    function,
+                    ..location
+                }),
+            );
+        }
+    }
+
+    fn enter(&mut self, index: usize, location: Option<SourceLocation>) {
+        self.location = location;
+        match self.entries.last_mut() {
+            Some((last, last_location)) if *last == index => *last_location = location,
+            Some((_, last_location)) if *last_location == location => {}
+            None if location.is_none() => {}
+            _ => self.entries.push((index, location)),
+        }
+    }
+}
+
+/*
+ * @desc Instruction raising an uncaught runtime error, a line 0 is an unknown location
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorLocation {
+    pub cursor: usize,
+    pub line: usize,
+    pub column: usize,
+    pub function: Option<String>,
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "at instruction {}", self.cursor)?;
+        } else {
+            write!(f, "at line {}", self.line)?;
+        }
+        match &self.function {
+            Some(function) => write!(f, " in {function}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/*
+ * @desc Calls of the functions opened when an uncaught runtime error is raised
+ * The innermost call comes first
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StackTrace {
+    pub frames: Vec<ErrorLocation>,
+}
+
+impl fmt::Display for StackTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in &self.frames {
+            write!(f, "\n  called {frame}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        vm::{
+            asm::Asm,
+            external::test::{DefaultProcessID, NoopEngine},
+            program::Program,
+            runtime::RuntimeError,
+            scheduler::ToCompletion,
+        },
+        Ciphel,
+    };
+
+    use super::*;
+
+    fn span(line: usize, column: usize) -> SourceSpan {
+        SourceSpan {
+            offset: 0,
+            length: 1,
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn valid_locate_nested_statements() {
+        let mut program = Program::<NoopEngine>::default();
+        program.push(Asm::Pop(8));
+        let function = program.push_label("fn_foo".to_string());
+        program.at(span(2, 1), |program| {
+            program.push(Asm::Pop(8));
+            program.in_function(function, |program| {
+                program.at(span(3, 5), |program| program.push(Asm::Pop(8)));
+                program.at(SourceSpan::default(), |program| program.push(Asm::Pop(8)));
+            });
+            program.push(Asm::Pop(8));
+        });
+        program.push(Asm::Pop(8));
+
+        let map = &program.source_map;
+        assert_eq!(map.locate(0), None);
+        let statement = SourceLocation {
+            line: 2,
+            column: 1,
+            function: None,
+        };
+        let body = SourceLocation {
+            line: 3,
+            column: 5,
+            function: Some(function),
+        };
+        assert_eq!(map.locate(2), Some(statement));
+        assert_eq!(map.locate(3), Some(body));
+        assert_eq!(
+            map.locate(4),
+            Some(SourceLocation {
+                function: Some(function),
+                ..statement
+            })
+        );
+        assert_eq!(map.locate(5), Some(statement));
+        assert_eq!(map.locate(6), None);
+
+        assert_eq!(program.error_location(3).to_string(), "at line 3 in fn foo");
+        assert_eq!(program.error_location(6).to_string(), "at instruction 6");
+    }
+
+    #[test]
+    fn valid_rollback() {
+        let mut program = Program::<NoopEngine>::default();
+        let kept = program.push_label("kept".to_string());
+        program.at(span(1, 1), |program| program.push(Asm::Pop(8)));
+
+        let checkpoint = program.checkpoint();
+        // the label pushed first after the checkpoint is at the length of the checkpoint
+        let dropped = program.push_label("dropped".to_string());
+        program.at(span(2, 1), |program| program.push(Asm::Pop(8)));
+        program.rollback(checkpoint);
+
+        assert_eq!(program.len(), 2);
+        assert_eq!(program.get_cursor_from_label(&kept), Some(0));
+        assert_eq!(program.get_cursor_from_label(&dropped), None);
+        assert!(program
+            .source_map
+            .entries()
+            .iter()
+            .all(|(index, _)| *index < 2));
+    }
+
+    #[test]
+    fn valid_location_of_runtime_error() {
+        let mut engine = NoopEngine {};
+        let mut ciphel = Ciphel::<NoopEngine, ToCompletion>::default();
+        let tid = ciphel
+            .runtime
+            .spawn(DefaultProcessID::default(), &mut engine)
+            .expect("Spawning should have succeeded");
+        ciphel
+            .compile(tid, "let x = 1;", 0)
+            .expect("the compilation should have succeeded");
+        ciphel
+            .compile(
+                tid,
+                r##"fn divide(a:i64, b:i64) -> i64 {
+            return a / b;
+        }
+        let y = divide(x, 0);"##,
+                1,
+            )
+            .expect("the compilation should have succeeded");
+
+        let Err((_, error)) = ciphel.run(&mut engine) else {
+            panic!("the execution should have failed");
+        };
+        assert!(matches!(
+            error,
+            RuntimeError::Located { ref error, .. } if matches!(**error, RuntimeError::MathError)
+        ));
+        let location = error
+            .location()
+            .expect("the error should have been located");
+        assert_eq!((location.line, location.column), (3, 13));
+        assert_eq!(location.function.as_deref(), Some("fn divide"));
+        let trace = error.trace().expect("the error should have been traced");
+        assert_eq!(trace.frames.len(), 1);
+        assert_eq!(trace.frames[0].line, 5);
+        assert_eq!(trace.frames[0].function, None);
+        assert_eq!(
+            error.to_string(),
+            "MathError at line 3 in fn divide\n  called at line 5"
+        );
+    }
+}